@@ -0,0 +1,282 @@
+//! `easy-wager`: create, join, resolve and inspect games from the command line.
+
+pub mod rpc;
+
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anchor_lang::Discriminator;
+use anyhow::{anyhow, bail, Result};
+use clap::{Parser, Subcommand, ValueEnum};
+use easy_wager_client::instructions::{self, CreateGameArgs, JoinGameArgs};
+use easy_wager_client::state::{Game, GameState};
+use easy_wager_client::{decode_game, find_sol_vault_pda, find_vault_pda, GAME_CREATOR_OFFSET, GAME_STATE_OFFSET, ID};
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signature, Signer};
+use solana_sdk::transaction::Transaction;
+
+use rpc::{Filter, Rpc};
+
+#[derive(Parser)]
+#[command(name = "easy-wager", version, about = "Create, resolve and inspect easy_wager_on_sol games")]
+pub struct Cli {
+    /// JSON-RPC endpoint
+    #[arg(long, short, global = true, env = "EASY_WAGER_RPC_URL", default_value = "http://127.0.0.1:8899")]
+    pub url: String,
+
+    /// Keypair file that signs and pays [default: ~/.config/solana/id.json]
+    #[arg(long, short, global = true, env = "EASY_WAGER_KEYPAIR")]
+    pub keypair: Option<PathBuf>,
+
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Create a game, staking the wager as player1
+    Create {
+        /// Wager per player, in lamports or the mint's smallest unit
+        wager: u64,
+        /// Share of the pot paid to the winner, in basis points (8500 = 85%)
+        #[arg(long)]
+        payout_bps: u16,
+        /// Token mint; native SOL if omitted
+        #[arg(long)]
+        mint: Option<Pubkey>,
+        /// Wallet receiving the fee
+        #[arg(long, env = "EASY_WAGER_DEV_WALLET")]
+        dev_wallet: Pubkey,
+        /// Seconds until the game expires
+        #[arg(long, default_value_t = 86_400)]
+        expires_in: i64,
+        /// Resolver; the creator if omitted
+        #[arg(long)]
+        resolver: Option<Pubkey>,
+        /// Game nonce; the current time in milliseconds if omitted
+        #[arg(long)]
+        nonce: Option<u64>,
+        /// Rounds in the series
+        #[arg(long, default_value_t = 1)]
+        best_of: u8,
+        /// Ranked ladder id, 0 for unranked
+        #[arg(long, default_value_t = 0)]
+        game_type: u32,
+        /// Share of the game's rent paid to whoever cranks it once expired
+        #[arg(long, default_value_t = Game::DEFAULT_CRANK_BOUNTY_BPS)]
+        crank_bounty_bps: u16,
+    },
+    /// Join a game as player2
+    Join { game: Pubkey },
+    /// Resolve a game (or report a round of a series) as its resolver
+    Resolve { game: Pubkey, winner: Pubkey },
+    /// Cancel an expired game and record the refunds
+    Cancel { game: Pubkey },
+    /// Hand a game's resolver role to another key
+    UpdateResolver { game: Pubkey, new_resolver: Pubkey },
+    /// Print a game's decoded state
+    Show { game: Pubkey },
+    /// List games, optionally by creator and state
+    List {
+        #[arg(long)]
+        creator: Option<Pubkey>,
+        #[arg(long, value_enum)]
+        state: Option<StateArg>,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum StateArg {
+    Open,
+    Ready,
+    Paid,
+    Canceled,
+    Expired,
+}
+
+impl From<StateArg> for GameState {
+    fn from(state: StateArg) -> Self {
+        match state {
+            StateArg::Open => GameState::Open,
+            StateArg::Ready => GameState::Ready,
+            StateArg::Paid => GameState::Paid,
+            StateArg::Canceled => GameState::Canceled,
+            StateArg::Expired => GameState::Expired,
+        }
+    }
+}
+
+/// Runs `command`. `signer` is only loaded by commands that send a
+/// transaction.
+pub fn run(rpc: &impl Rpc, command: Command, signer: impl FnOnce() -> Result<Keypair>) -> Result<()> {
+    match command {
+        Command::Create { wager, payout_bps, mint, dev_wallet, expires_in, resolver, nonce, best_of, game_type, crank_bounty_bps } => {
+            let signer = signer()?;
+            let now = SystemTime::now().duration_since(UNIX_EPOCH)?;
+            let mut args = CreateGameArgs::new(
+                signer.pubkey(),
+                mint.unwrap_or_default(),
+                wager,
+                payout_bps,
+                now.as_secs() as i64 + expires_in,
+                nonce.unwrap_or(now.as_millis() as u64),
+                dev_wallet,
+            );
+            args.resolver = resolver;
+            args.best_of = best_of;
+            args.game_type = game_type;
+            args.crank_bounty_bps = crank_bounty_bps;
+
+            let game = args.game();
+            let signature = send(rpc, &signer, &[instructions::create_game(&args)])?;
+            println!("Signature     {signature}");
+            print_game(&game, &fetch_game(rpc, &game)?);
+        },
+        Command::Join { game } => {
+            let signer = signer()?;
+            let state = fetch_game(rpc, &game)?;
+            let ix = instructions::join_game(&game, &state, &JoinGameArgs::new(signer.pubkey()));
+            let signature = send(rpc, &signer, &[ix])?;
+            println!("Signature     {signature}");
+            print_game(&game, &fetch_game(rpc, &game)?);
+        },
+        Command::Resolve { game, winner } => {
+            let signer = signer()?;
+            let state = fetch_game(rpc, &game)?;
+            let ix = if state.best_of > 1 {
+                instructions::report_round(&signer.pubkey(), &game, &state, &winner)
+            } else {
+                instructions::resolve_game(&signer.pubkey(), &game, &state, &winner)
+            };
+            let signature = send(rpc, &signer, &[ix])?;
+            println!("Signature     {signature}");
+            print_game(&game, &fetch_game(rpc, &game)?);
+        },
+        Command::Cancel { game } => {
+            let signer = signer()?;
+            let state = fetch_game(rpc, &game)?;
+            let signature = send(rpc, &signer, &[instructions::cancel_if_expired(&signer.pubkey(), &game, &state)])?;
+            println!("Signature     {signature}");
+            print_game(&game, &fetch_game(rpc, &game)?);
+        },
+        Command::UpdateResolver { game, new_resolver } => {
+            let signer = signer()?;
+            let signature = send(rpc, &signer, &[instructions::update_resolver(&signer.pubkey(), &game, &new_resolver)])?;
+            println!("Signature     {signature}");
+            print_game(&game, &fetch_game(rpc, &game)?);
+        },
+        Command::Show { game } => {
+            print_game(&game, &fetch_game(rpc, &game)?);
+        },
+        Command::List { creator, state } => {
+            let mut filters = vec![
+                Filter::DataSize(Game::LEN),
+                Filter::Memcmp { offset: 0, bytes: Game::DISCRIMINATOR.to_vec() },
+            ];
+            if let Some(creator) = creator {
+                filters.push(Filter::Memcmp { offset: GAME_CREATOR_OFFSET, bytes: creator.to_bytes().to_vec() });
+            }
+            if let Some(state) = state {
+                filters.push(Filter::Memcmp { offset: GAME_STATE_OFFSET, bytes: vec![GameState::from(state) as u8] });
+            }
+
+            let mut games = rpc
+                .get_program_accounts(&ID, &filters)?
+                .into_iter()
+                .filter_map(|(address, data)| decode_game(&data).ok().map(|game| (address, game)))
+                .collect::<Vec<_>>();
+            games.sort_by_key(|(_, game)| game.expiry_ts);
+
+            println!("{:<44}  {:<8}  {:>20}  {:<44}  {:<44}", "GAME", "STATE", "WAGER", "MINT", "CREATOR");
+            for (address, game) in &games {
+                println!(
+                    "{:<44}  {:<8}  {:>20}  {:<44}  {:<44}",
+                    address.to_string(),
+                    game.state().map_or("unknown", state_name),
+                    game.wager,
+                    mint_name(&game.mint),
+                    game.creator.to_string(),
+                );
+            }
+            println!("{} game(s)", games.len());
+        },
+    }
+    Ok(())
+}
+
+fn send(rpc: &impl Rpc, signer: &Keypair, instructions: &[Instruction]) -> Result<Signature> {
+    let blockhash = rpc.get_latest_blockhash()?;
+    let transaction = Transaction::new_signed_with_payer(instructions, Some(&signer.pubkey()), &[signer], blockhash);
+    rpc.send_and_confirm(&transaction)
+}
+
+pub fn fetch_game(rpc: &impl Rpc, address: &Pubkey) -> Result<Game> {
+    let Some(data) = rpc.get_account_data(address)? else {
+        bail!("game {address} not found");
+    };
+    decode_game(&data).map_err(|err| anyhow!("{address} is not a current Game account: {err}"))
+}
+
+fn state_name(state: GameState) -> &'static str {
+    match state {
+        GameState::Open => "open",
+        GameState::Ready => "ready",
+        GameState::Paid => "paid",
+        GameState::Canceled => "canceled",
+        GameState::Expired => "expired",
+    }
+}
+
+fn mint_name(mint: &Pubkey) -> String {
+    if *mint == Pubkey::default() {
+        "SOL".to_string()
+    } else {
+        mint.to_string()
+    }
+}
+
+fn or_none(key: &Pubkey) -> String {
+    if *key == Pubkey::default() {
+        "-".to_string()
+    } else {
+        key.to_string()
+    }
+}
+
+fn print_game(address: &Pubkey, game: &Game) {
+    println!("Game          {address}");
+    println!("State         {}", game.state().map_or("unknown", state_name));
+    println!("Creator       {}", game.creator);
+    println!("Nonce         {}", game.nonce);
+    println!("Player 1      {}", or_none(&game.player1));
+    println!("Player 2      {}", or_none(&game.player2));
+    println!("Resolver      {}", or_none(&game.resolver));
+    println!("Winner        {}", or_none(&game.winner));
+    println!("Mint          {}", mint_name(&game.mint));
+    println!("Wager         {}", game.wager);
+    println!("Payout        {}.{:02}% to the winner", game.payout_bps / 100, game.payout_bps % 100);
+    if game.best_of > 1 {
+        println!("Series        best of {} ({}-{})", game.best_of, game.player1_wins, game.player2_wins);
+    }
+    if game.game_type != 0 {
+        println!("Ladder        {} (rating {}-{})", game.game_type, game.min_rating, game.max_rating);
+    }
+    println!("Expires       {}", game.expiry_ts);
+    println!(
+        "Claimable     player 1 {}, player 2 {}, fee {}",
+        game.player1_claimable, game.player2_claimable, game.fee_claimable,
+    );
+    println!("Dev wallet    {}", game.dev_wallet);
+    println!("SOL vault     {}", find_sol_vault_pda(address).0);
+    if !game.is_native_sol() {
+        println!("Token vault   {}", find_vault_pda(address, &game.mint).0);
+    }
+    if game.side_pool != Pubkey::default() {
+        println!("Side pool     {}", game.side_pool);
+    }
+    println!("Rent payer    {}", game.rent_receiver());
+    println!("Crank bounty  {}.{:02}% of the rent", game.crank_bounty_bps / 100, game.crank_bounty_bps % 100);
+    println!("Version       {}", game.version);
+}
+
@@ -0,0 +1,22 @@
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use easy_wager_cli::rpc::HttpRpc;
+use easy_wager_cli::{run, Cli};
+use solana_sdk::signature::read_keypair_file;
+
+fn default_keypair() -> PathBuf {
+    let home = std::env::var_os("HOME").unwrap_or_default();
+    PathBuf::from(home).join(".config/solana/id.json")
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let keypair = cli.keypair.unwrap_or_else(default_keypair);
+    let rpc = HttpRpc::new(cli.url);
+
+    run(&rpc, cli.command, || {
+        read_keypair_file(&keypair).map_err(|err| anyhow!("reading keypair {}: {err}", keypair.display()))
+    })
+}
@@ -0,0 +1,150 @@
+//! The handful of RPC calls the CLI needs. Commands go through the `Rpc`
+//! trait so they can run against a local validator, a remote cluster or an
+//! in-process bank such as LiteSVM.
+
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, bail, Context, Result};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde_json::{json, Value};
+use solana_sdk::hash::Hash;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_sdk::transaction::Transaction;
+
+/// A `getProgramAccounts` filter.
+pub enum Filter {
+    DataSize(usize),
+    Memcmp { offset: usize, bytes: Vec<u8> },
+}
+
+pub trait Rpc {
+    /// Data of `address`, or `None` if the account doesn't exist.
+    fn get_account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>>;
+
+    /// Accounts owned by `program_id` matching every filter.
+    fn get_program_accounts(&self, program_id: &Pubkey, filters: &[Filter]) -> Result<Vec<(Pubkey, Vec<u8>)>>;
+
+    fn get_latest_blockhash(&self) -> Result<Hash>;
+
+    /// Sends a signed transaction and waits until it is confirmed.
+    fn send_and_confirm(&self, transaction: &Transaction) -> Result<Signature>;
+}
+
+/// JSON-RPC over HTTP, as served by `solana-test-validator` and RPC nodes.
+pub struct HttpRpc {
+    url: String,
+    agent: ureq::Agent,
+}
+
+const COMMITMENT: &str = "confirmed";
+const CONFIRM_TIMEOUT: Duration = Duration::from_secs(60);
+
+impl HttpRpc {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            agent: ureq::AgentBuilder::new().timeout(Duration::from_secs(30)).build(),
+        }
+    }
+
+    fn call(&self, method: &str, params: Value) -> Result<Value> {
+        let request = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let mut response: Value = self
+            .agent
+            .post(&self.url)
+            .send_json(request)
+            .with_context(|| format!("{method} request to {} failed", self.url))?
+            .into_json()?;
+
+        if let Some(error) = response.get("error") {
+            let message = error["message"].as_str().unwrap_or("unknown error");
+            // Preflight failures carry the simulated program logs
+            let logs = error["data"]["logs"]
+                .as_array()
+                .map(|logs| logs.iter().filter_map(Value::as_str).collect::<Vec<_>>().join("\n  "))
+                .unwrap_or_default();
+            if logs.is_empty() {
+                bail!("{method}: {message}");
+            }
+            bail!("{method}: {message}\n  {logs}");
+        }
+        Ok(response["result"].take())
+    }
+}
+
+fn decode_data(account: &Value) -> Result<Vec<u8>> {
+    let data = account["data"][0].as_str().ok_or_else(|| anyhow!("account data is not base64"))?;
+    Ok(BASE64.decode(data)?)
+}
+
+impl Rpc for HttpRpc {
+    fn get_account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>> {
+        let result = self.call(
+            "getAccountInfo",
+            json!([address.to_string(), { "encoding": "base64", "commitment": COMMITMENT }]),
+        )?;
+        match &result["value"] {
+            Value::Null => Ok(None),
+            account => decode_data(account).map(Some),
+        }
+    }
+
+    fn get_program_accounts(&self, program_id: &Pubkey, filters: &[Filter]) -> Result<Vec<(Pubkey, Vec<u8>)>> {
+        let filters: Vec<Value> = filters
+            .iter()
+            .map(|filter| match filter {
+                Filter::DataSize(size) => json!({ "dataSize": size }),
+                Filter::Memcmp { offset, bytes } => json!({
+                    "memcmp": { "offset": offset, "bytes": BASE64.encode(bytes), "encoding": "base64" }
+                }),
+            })
+            .collect();
+        let result = self.call(
+            "getProgramAccounts",
+            json!([program_id.to_string(), { "encoding": "base64", "commitment": COMMITMENT, "filters": filters }]),
+        )?;
+
+        let accounts = result.as_array().ok_or_else(|| anyhow!("getProgramAccounts: unexpected response"))?;
+        accounts
+            .iter()
+            .map(|entry| {
+                let pubkey = entry["pubkey"].as_str().unwrap_or_default().parse()?;
+                Ok((pubkey, decode_data(&entry["account"])?))
+            })
+            .collect()
+    }
+
+    fn get_latest_blockhash(&self) -> Result<Hash> {
+        let result = self.call("getLatestBlockhash", json!([{ "commitment": COMMITMENT }]))?;
+        let blockhash = result["value"]["blockhash"].as_str().ok_or_else(|| anyhow!("getLatestBlockhash: no blockhash"))?;
+        Ok(blockhash.parse()?)
+    }
+
+    fn send_and_confirm(&self, transaction: &Transaction) -> Result<Signature> {
+        let wire = BASE64.encode(bincode::serialize(transaction)?);
+        let result = self.call(
+            "sendTransaction",
+            json!([wire, { "encoding": "base64", "preflightCommitment": COMMITMENT }]),
+        )?;
+        let signature: Signature = result.as_str().ok_or_else(|| anyhow!("sendTransaction: no signature"))?.parse()?;
+
+        let started = Instant::now();
+        while started.elapsed() < CONFIRM_TIMEOUT {
+            let result = self.call("getSignatureStatuses", json!([[signature.to_string()]]))?;
+            let status = &result["value"][0];
+            if !status.is_null() {
+                if !status["err"].is_null() {
+                    bail!("transaction {signature} failed: {}", status["err"]);
+                }
+                if matches!(status["confirmationStatus"].as_str(), Some("confirmed" | "finalized")) {
+                    return Ok(signature);
+                }
+            }
+            sleep(Duration::from_millis(500));
+        }
+        bail!("transaction {signature} was not confirmed within {}s", CONFIRM_TIMEOUT.as_secs())
+    }
+}
@@ -0,0 +1,183 @@
+//! Runs the commands end to end against the program in LiteSVM: creating,
+//! joining, resolving, canceling and handing over the resolver role.
+
+use std::cell::RefCell;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+use anyhow::{bail, Result};
+use easy_wager_cli::rpc::{Filter, Rpc};
+use easy_wager_cli::{fetch_game, run, Command, StateArg};
+use easy_wager_client::find_game_pda;
+use easy_wager_client::state::{Game, GameState};
+use easy_wager_harness::litesvm::LiteSVM;
+use easy_wager_harness::{funded, svm, warp_to};
+use solana_sdk::account::ReadableAccount;
+use solana_sdk::hash::Hash;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signature, Signer};
+use solana_sdk::transaction::Transaction;
+
+const WAGER: u64 = LAMPORTS_PER_SOL;
+
+/// The program in LiteSVM behind the CLI's RPC calls. Transactions are
+/// confirmed as soon as they're processed.
+struct SvmRpc {
+    svm: RefCell<LiteSVM>,
+}
+
+impl Rpc for SvmRpc {
+    fn get_account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>> {
+        // Closed accounts linger with no lamports until the bank drops them
+        let account = self.svm.borrow().get_account(address).filter(|account| account.lamports > 0);
+        Ok(account.map(|account| account.data))
+    }
+
+    fn get_program_accounts(&self, program_id: &Pubkey, filters: &[Filter]) -> Result<Vec<(Pubkey, Vec<u8>)>> {
+        let svm = self.svm.borrow();
+        let accounts = svm
+            .accounts_db()
+            .inner
+            .iter()
+            .filter(|(_, account)| account.owner() == program_id && account.lamports() > 0)
+            .filter(|(_, account)| {
+                filters.iter().all(|filter| match filter {
+                    Filter::DataSize(size) => account.data().len() == *size,
+                    Filter::Memcmp { offset, bytes } => account.data().get(*offset..*offset + bytes.len()) == Some(&bytes[..]),
+                })
+            })
+            .map(|(address, account)| (*address, account.data().to_vec()))
+            .collect();
+        Ok(accounts)
+    }
+
+    fn get_latest_blockhash(&self) -> Result<Hash> {
+        Ok(self.svm.borrow().latest_blockhash())
+    }
+
+    fn send_and_confirm(&self, transaction: &Transaction) -> Result<Signature> {
+        let mut svm = self.svm.borrow_mut();
+        let result = svm.send_transaction(transaction.clone());
+        // A new blockhash per transaction, so identical commands can be rerun
+        svm.expire_blockhash();
+        match result {
+            Ok(_) => Ok(transaction.signatures[0]),
+            Err(failed) => bail!("transaction failed: {}\n  {}", failed.err, failed.meta.logs.join("\n  ")),
+        }
+    }
+}
+
+struct Setup {
+    rpc: SvmRpc,
+    creator: Keypair,
+    player2: Keypair,
+    resolver: Keypair,
+}
+
+/// The CLI stamps expiries from the wall clock, so the bank's clock starts there.
+fn setup() -> Setup {
+    let mut svm = svm();
+    warp_to(&mut svm, unix_now());
+    let creator = funded(&mut svm, 10 * LAMPORTS_PER_SOL);
+    let player2 = funded(&mut svm, 10 * LAMPORTS_PER_SOL);
+    let resolver = funded(&mut svm, LAMPORTS_PER_SOL);
+    Setup { rpc: SvmRpc { svm: RefCell::new(svm) }, creator, player2, resolver }
+}
+
+fn unix_now() -> i64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() as i64
+}
+
+fn create(nonce: u64, resolver: Pubkey, expires_in: i64) -> Command {
+    Command::Create {
+        wager: WAGER,
+        payout_bps: 9_000,
+        mint: None,
+        dev_wallet: Pubkey::new_unique(),
+        expires_in,
+        resolver: Some(resolver),
+        nonce: Some(nonce),
+        best_of: 1,
+        game_type: 0,
+        crank_bounty_bps: Game::DEFAULT_CRANK_BOUNTY_BPS,
+    }
+}
+
+fn as_signer(keypair: &Keypair) -> impl FnOnce() -> Result<Keypair> + '_ {
+    || Ok(keypair.insecure_clone())
+}
+
+fn state(setup: &Setup, game: &Pubkey) -> GameState {
+    fetch_game(&setup.rpc, game).unwrap().state().unwrap()
+}
+
+#[test]
+fn game_is_created_joined_and_resolved() {
+    let setup = setup();
+    let game = find_game_pda(&setup.creator.pubkey(), 1).0;
+
+    run(&setup.rpc, create(1, setup.resolver.pubkey(), 600), as_signer(&setup.creator)).unwrap();
+    assert!(state(&setup, &game) == GameState::Open);
+    run(&setup.rpc, Command::List { creator: Some(setup.creator.pubkey()), state: Some(StateArg::Open) }, as_signer(&setup.creator)).unwrap();
+
+    run(&setup.rpc, Command::Join { game }, as_signer(&setup.player2)).unwrap();
+    assert!(state(&setup, &game) == GameState::Ready);
+
+    let winner = setup.player2.pubkey();
+    run(&setup.rpc, Command::Resolve { game, winner }, as_signer(&setup.resolver)).unwrap();
+    let resolved = fetch_game(&setup.rpc, &game).unwrap();
+    assert!(resolved.state().unwrap() == GameState::Paid);
+    assert_eq!(resolved.winner, winner);
+    assert_eq!(resolved.claimable_by(&winner).unwrap(), 2 * WAGER * 9_000 / 10_000);
+}
+
+#[test]
+fn failed_transactions_surface_the_program_error() {
+    let setup = setup();
+    let game = find_game_pda(&setup.creator.pubkey(), 1).0;
+    run(&setup.rpc, create(1, setup.resolver.pubkey(), 600), as_signer(&setup.creator)).unwrap();
+    run(&setup.rpc, Command::Join { game }, as_signer(&setup.player2)).unwrap();
+
+    // Only the resolver may resolve
+    let err = run(&setup.rpc, Command::Resolve { game, winner: setup.player2.pubkey() }, as_signer(&setup.player2)).unwrap_err();
+    assert!(err.to_string().starts_with("transaction failed"), "{err}");
+    assert!(state(&setup, &game) == GameState::Ready);
+}
+
+#[test]
+fn expired_game_is_canceled_and_refunded() {
+    let setup = setup();
+    let game = find_game_pda(&setup.creator.pubkey(), 1).0;
+    run(&setup.rpc, create(1, setup.resolver.pubkey(), 60), as_signer(&setup.creator)).unwrap();
+
+    // Not before it expires
+    assert!(run(&setup.rpc, Command::Cancel { game }, as_signer(&setup.creator)).is_err());
+
+    let expiry_ts = fetch_game(&setup.rpc, &game).unwrap().expiry_ts;
+    warp_to(&mut setup.rpc.svm.borrow_mut(), expiry_ts + 1);
+    run(&setup.rpc, Command::Cancel { game }, as_signer(&setup.creator)).unwrap();
+    let canceled = fetch_game(&setup.rpc, &game).unwrap();
+    assert!(canceled.state().unwrap() == GameState::Canceled);
+    assert_eq!(canceled.claimable_by(&setup.creator.pubkey()).unwrap(), WAGER);
+}
+
+#[test]
+fn resolver_role_is_handed_over_by_the_creator() {
+    let setup = setup();
+    let game = find_game_pda(&setup.creator.pubkey(), 1).0;
+    run(&setup.rpc, create(1, setup.creator.pubkey(), 600), as_signer(&setup.creator)).unwrap();
+    let new_resolver = setup.resolver.pubkey();
+
+    // Only the creator may hand it over
+    let update = || Command::UpdateResolver { game, new_resolver };
+    assert!(run(&setup.rpc, update(), as_signer(&setup.player2)).is_err());
+    run(&setup.rpc, update(), as_signer(&setup.creator)).unwrap();
+    assert_eq!(fetch_game(&setup.rpc, &game).unwrap().resolver, new_resolver);
+
+    // ... after which the new resolver settles the game
+    run(&setup.rpc, Command::Join { game }, as_signer(&setup.player2)).unwrap();
+    let winner = setup.creator.pubkey();
+    assert!(run(&setup.rpc, Command::Resolve { game, winner }, as_signer(&setup.creator)).is_err());
+    run(&setup.rpc, Command::Resolve { game, winner }, as_signer(&setup.resolver)).unwrap();
+    assert!(state(&setup, &game) == GameState::Paid);
+}
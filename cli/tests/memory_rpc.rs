@@ -0,0 +1,113 @@
+//! Runs the read-only commands against an in-memory account store, checking
+//! the `list` filters select the right bytes of the `Game` layout.
+
+use std::cell::RefCell;
+
+use anchor_lang::Discriminator;
+use anyhow::{bail, Result};
+use easy_wager_cli::rpc::{Filter, Rpc};
+use easy_wager_cli::{fetch_game, run, Command, StateArg};
+use easy_wager_client::state::{Game, GameState};
+use solana_sdk::hash::Hash;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_sdk::transaction::Transaction;
+
+#[derive(Default)]
+struct MemoryRpc {
+    accounts: Vec<(Pubkey, Vec<u8>)>,
+    // Every getProgramAccounts result, for assertions
+    listed: RefCell<Vec<Vec<Pubkey>>>,
+}
+
+impl Rpc for MemoryRpc {
+    fn get_account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>> {
+        Ok(self.accounts.iter().find(|(key, _)| key == address).map(|(_, data)| data.clone()))
+    }
+
+    fn get_program_accounts(&self, _program_id: &Pubkey, filters: &[Filter]) -> Result<Vec<(Pubkey, Vec<u8>)>> {
+        let matches: Vec<_> = self
+            .accounts
+            .iter()
+            .filter(|(_, data)| {
+                filters.iter().all(|filter| match filter {
+                    Filter::DataSize(size) => data.len() == *size,
+                    Filter::Memcmp { offset, bytes } => data.get(*offset..*offset + bytes.len()) == Some(&bytes[..]),
+                })
+            })
+            .cloned()
+            .collect();
+        self.listed.borrow_mut().push(matches.iter().map(|(key, _)| *key).collect());
+        Ok(matches)
+    }
+
+    fn get_latest_blockhash(&self) -> Result<Hash> {
+        bail!("read-only")
+    }
+
+    fn send_and_confirm(&self, _transaction: &Transaction) -> Result<Signature> {
+        bail!("read-only")
+    }
+}
+
+fn game_data(creator: Pubkey, state: GameState, wager: u64) -> Vec<u8> {
+    let mut game: Game = bytemuck::Zeroable::zeroed();
+    game.creator = creator;
+    game.player1 = creator;
+    game.resolver = creator;
+    game.wager = wager;
+    game.payout_bps = 8_500;
+    game.best_of = 1;
+    game.version = Game::VERSION;
+    game.set_state(state);
+    [Game::DISCRIMINATOR, bytemuck::bytes_of(&game)].concat()
+}
+
+fn no_signer() -> Result<solana_sdk::signature::Keypair> {
+    bail!("read-only commands must not load the keypair")
+}
+
+#[test]
+fn list_filters_by_creator_and_state() {
+    let (alice, bob) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let (open, ready, other) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+    let rpc = MemoryRpc {
+        accounts: vec![
+            (open, game_data(alice, GameState::Open, 100)),
+            (ready, game_data(alice, GameState::Ready, 200)),
+            (other, game_data(bob, GameState::Open, 300)),
+            // Not a game: wrong size
+            (Pubkey::new_unique(), vec![0; 64]),
+        ],
+        ..MemoryRpc::default()
+    };
+
+    run(&rpc, Command::List { creator: None, state: None }, no_signer).unwrap();
+    run(&rpc, Command::List { creator: Some(alice), state: None }, no_signer).unwrap();
+    run(&rpc, Command::List { creator: None, state: Some(StateArg::Open) }, no_signer).unwrap();
+    run(&rpc, Command::List { creator: Some(alice), state: Some(StateArg::Ready) }, no_signer).unwrap();
+
+    let listed = rpc.listed.borrow();
+    assert_eq!(listed[0], vec![open, ready, other]);
+    assert_eq!(listed[1], vec![open, ready]);
+    assert_eq!(listed[2], vec![open, other]);
+    assert_eq!(listed[3], vec![ready]);
+}
+
+#[test]
+fn show_decodes_the_game() {
+    let creator = Pubkey::new_unique();
+    let address = Pubkey::new_unique();
+    let rpc = MemoryRpc {
+        accounts: vec![(address, game_data(creator, GameState::Ready, 500))],
+        ..MemoryRpc::default()
+    };
+
+    run(&rpc, Command::Show { game: address }, no_signer).unwrap();
+    let game = fetch_game(&rpc, &address).unwrap();
+    assert_eq!(game.creator, creator);
+    assert_eq!(game.wager, 500);
+    assert!(game.state().unwrap() == GameState::Ready);
+
+    assert!(run(&rpc, Command::Show { game: Pubkey::new_unique() }, no_signer).is_err());
+}
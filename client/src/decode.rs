@@ -0,0 +1,144 @@
+//! Decoders for the program's accounts and events.
+
+use anchor_lang::error::ErrorCode;
+use anchor_lang::{AccountDeserialize, AnchorDeserialize, Discriminator, Result};
+use anchor_lang::event::EVENT_IX_TAG_LE;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use easy_wager_on_sol::state::*;
+use easy_wager_on_sol::ID;
+
+/// Offsets into a `Game` account's data, for `getProgramAccounts` memcmp
+/// filters.
+pub const GAME_CREATOR_OFFSET: usize = 8 + std::mem::offset_of!(Game, creator);
+pub const GAME_STATE_OFFSET: usize = 8 + std::mem::offset_of!(Game, state);
+
+/// Decodes a `Game` account. Games still on a Borsh layout (see
+/// `migrate_game`) have the wrong size and are rejected.
+pub fn decode_game(data: &[u8]) -> Result<Game> {
+    if !data.starts_with(Game::DISCRIMINATOR) {
+        return Err(ErrorCode::AccountDiscriminatorMismatch.into());
+    }
+    if data.len() != Game::LEN {
+        return Err(ErrorCode::AccountDidNotDeserialize.into());
+    }
+    // RPC buffers carry no alignment guarantee, so copy rather than cast
+    Ok(bytemuck::pod_read_unaligned(&data[8..]))
+}
+
+/// Decodes any of the program's Borsh accounts (`Tournament`, `SidePool`,
+/// `PlayerStats`, ...), checking the discriminator.
+pub fn decode_account<T: AccountDeserialize>(data: &[u8]) -> Result<T> {
+    T::try_deserialize(&mut &data[..])
+}
+
+macro_rules! wager_events {
+    ($($event:ident),* $(,)?) => {
+        /// Every event the program emits.
+        pub enum WagerEvent {
+            $($event($event),)*
+        }
+
+        impl WagerEvent {
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(WagerEvent::$event(_) => stringify!($event),)*
+                }
+            }
+        }
+
+        /// Decodes an event from its discriminator and Borsh payload, as found
+        /// in a `Program data:` log line. Returns `None` for other data.
+        pub fn decode_event(data: &[u8]) -> Option<WagerEvent> {
+            $(
+                if let Some(mut payload) = data.strip_prefix($event::DISCRIMINATOR) {
+                    return $event::deserialize(&mut payload).ok().map(WagerEvent::$event);
+                }
+            )*
+            None
+        }
+    };
+}
+
+wager_events!(
+    GameCreated,
+    GameJoined,
+    RoundReported,
+    GameResolved,
+    GameResolutionSkipped,
+    BatchResolved,
+    ExpiredGamesCranked,
+    EscrowSwept,
+    GameClaimed,
+    RatingsUpdated,
+    BasketLegClaimed,
+    NftClaimed,
+    BalanceDeposited,
+    BalanceWithdrawn,
+    SessionApproved,
+    SessionRevoked,
+    QueueCreated,
+    PlayerQueued,
+    PlayerDequeued,
+    RelayerReimbursed,
+    GameMigrated,
+    GameCanceled,
+    ResolverUpdated,
+    TournamentCreated,
+    TournamentRegistered,
+    TournamentStarted,
+    MatchReported,
+    TournamentCompleted,
+    TournamentCanceled,
+    TournamentPrizeClaimed,
+    SidePoolCreated,
+    SideBetPlaced,
+    SidePoolSettled,
+    SideBetClaimed,
+    SidePoolClosed,
+);
+
+/// Decodes an event emitted with `emit_cpi!`, from the data of the program's
+/// self-invocation found among a transaction's inner instructions.
+pub fn decode_cpi_event(ix_data: &[u8]) -> Option<WagerEvent> {
+    decode_event(ix_data.strip_prefix(EVENT_IX_TAG_LE)?)
+}
+
+/// Raw event data (discriminator and Borsh payload) from a transaction's log
+/// messages. Only `Program data:` lines written while this program is
+/// executing count, so other programs can't forge events by logging the same
+/// bytes.
+pub fn log_event_data<S: AsRef<str>>(logs: &[S]) -> Vec<Vec<u8>> {
+    log_event_lines(logs).into_iter().map(|(_, data)| data).collect()
+}
+
+/// Like `log_event_data`, along with the index of the log line each event
+/// was read from.
+pub fn log_event_lines<S: AsRef<str>>(logs: &[S]) -> Vec<(usize, Vec<u8>)> {
+    let program = ID.to_string();
+    let mut stack: Vec<&str> = Vec::new();
+    let mut events = Vec::new();
+
+    for (index, line) in logs.iter().enumerate() {
+        let line = line.as_ref();
+        if let Some(data) = line.strip_prefix("Program data: ") {
+            if stack.last() == Some(&program.as_str()) {
+                events.extend(BASE64.decode(data).ok().map(|data| (index, data)));
+            }
+        } else if let Some(invoke) = line.strip_prefix("Program ").and_then(|rest| rest.split_once(" invoke [")) {
+            stack.push(invoke.0);
+        } else if let Some(top) = stack.last() {
+            // Only the executing program's own completion line ends its frame
+            let status = line.strip_prefix("Program ").and_then(|rest| rest.strip_prefix(*top));
+            if matches!(status, Some(status) if status == " success" || status.starts_with(" failed: ")) {
+                stack.pop();
+            }
+        }
+    }
+    events
+}
+
+/// Collects the program's events from a transaction's log messages.
+pub fn parse_logs<S: AsRef<str>>(logs: &[S]) -> Vec<WagerEvent> {
+    log_event_data(logs).iter().filter_map(|data| decode_event(data)).collect()
+}
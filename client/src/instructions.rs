@@ -0,0 +1,898 @@
+//! Instruction builders. Each fills in the optional accounts the program
+//! expects for the game's mint: SOL vaults for native SOL, token vaults and
+//! associated token accounts for SPL tokens, and unwrap accounts for wrapped
+//! SOL payouts.
+
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::{system_program, InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::{self, get_associated_token_address};
+use anchor_spl::metadata::mpl_token_metadata::accounts::Metadata;
+use anchor_spl::token;
+use easy_wager_on_sol::state::*;
+use easy_wager_on_sol::{accounts, instruction, ID};
+
+use crate::pda::*;
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+fn is_spl(mint: &Pubkey) -> bool {
+    *mint != Pubkey::default()
+}
+
+fn is_wrapped_sol(mint: &Pubkey) -> bool {
+    *mint == token::spl_token::native_mint::ID
+}
+
+/// `f(mint)` for SPL games, `None` (the program id placeholder) for native SOL.
+fn spl<T>(mint: &Pubkey, f: impl FnOnce(&Pubkey) -> T) -> Option<T> {
+    is_spl(mint).then(|| f(mint))
+}
+
+fn joined(player: &Pubkey) -> bool {
+    *player != Pubkey::default()
+}
+
+/// Metaplex metadata, needed when the game enforces a collection.
+fn nft_metadata(asset: &StakeAsset, nft_mint: &Pubkey) -> Option<Pubkey> {
+    (asset.collection != Pubkey::default()).then(|| Metadata::find_pda(nft_mint).0)
+}
+
+/// For each used leg of `basket`: its mint, the depositor's token account
+/// and the game's basket vault.
+fn basket_deposit_metas(game: &Pubkey, depositor: &Pubkey, basket: &Basket) -> Vec<AccountMeta> {
+    basket
+        .used_legs()
+        .flat_map(|(_, leg)| {
+            [
+                AccountMeta::new_readonly(leg.mint, false),
+                AccountMeta::new(get_associated_token_address(depositor, &leg.mint), false),
+                AccountMeta::new(find_basket_vault_pda(game, &leg.mint).0, false),
+            ]
+        })
+        .collect()
+}
+
+/// Arguments of `create_game`, plus how the creator funds its stake.
+#[derive(Clone)]
+pub struct CreateGameArgs {
+    pub creator: Pubkey,
+    // Pays rent and fees; `creator` unless a relayer sponsors the transaction
+    pub payer: Pubkey,
+    pub mint: Pubkey,
+    pub wager: u64,
+    pub payout_bps: u16,
+    pub expiry_ts: i64,
+    pub resolver: Option<Pubkey>,
+    pub nonce: u64,
+    pub best_of: u8,
+    pub player1_asset: StakeAsset,
+    pub player2_asset: StakeAsset,
+    pub asset_fee: u64,
+    pub player1_basket: Basket,
+    pub player2_basket: Basket,
+    pub game_type: u32,
+    pub min_rating: u32,
+    pub max_rating: u32,
+    pub relayer_fee: u64,
+    // Share of the game's rent paid to whoever cranks it once expired
+    pub crank_bounty_bps: u16,
+    pub dev_wallet: Pubkey,
+    // NFT the creator stakes, when player1's asset is an NFT
+    pub nft_mint: Option<Pubkey>,
+    // Wallet `creator` signs for as a session key
+    pub session_owner: Option<Pubkey>,
+    // Stake from the player balance instead of the wallet
+    pub use_balance: bool,
+}
+
+impl CreateGameArgs {
+    /// A plain single-game wager staked from the creator's wallet.
+    pub fn new(creator: Pubkey, mint: Pubkey, wager: u64, payout_bps: u16, expiry_ts: i64, nonce: u64, dev_wallet: Pubkey) -> Self {
+        Self {
+            creator,
+            payer: creator,
+            mint,
+            wager,
+            payout_bps,
+            expiry_ts,
+            resolver: None,
+            nonce,
+            best_of: 1,
+            player1_asset: StakeAsset::default(),
+            player2_asset: StakeAsset::default(),
+            asset_fee: 0,
+            player1_basket: Basket::default(),
+            player2_basket: Basket::default(),
+            game_type: 0,
+            min_rating: 0,
+            max_rating: u32::MAX,
+            relayer_fee: 0,
+            crank_bounty_bps: Game::DEFAULT_CRANK_BOUNTY_BPS,
+            dev_wallet,
+            nft_mint: None,
+            session_owner: None,
+            use_balance: false,
+        }
+    }
+
+    /// Wallet the game is created for.
+    pub fn wallet(&self) -> Pubkey {
+        self.session_owner.unwrap_or(self.creator)
+    }
+
+    pub fn game(&self) -> Pubkey {
+        find_game_pda(&self.wallet(), self.nonce).0
+    }
+
+    /// The instruction argument: the game's terms without the accounts.
+    pub fn terms(&self) -> easy_wager_on_sol::state::CreateGameArgs {
+        easy_wager_on_sol::state::CreateGameArgs {
+            mint: self.mint,
+            wager: self.wager,
+            payout_bps: self.payout_bps,
+            expiry_ts: self.expiry_ts,
+            resolver: self.resolver,
+            nonce: self.nonce,
+            best_of: self.best_of,
+            player1_asset: self.player1_asset,
+            player2_asset: self.player2_asset,
+            asset_fee: self.asset_fee,
+            player1_basket: self.player1_basket,
+            player2_basket: self.player2_basket,
+            game_type: self.game_type,
+            min_rating: self.min_rating,
+            max_rating: self.max_rating,
+            relayer_fee: self.relayer_fee,
+            crank_bounty_bps: self.crank_bounty_bps,
+        }
+    }
+}
+
+pub fn create_game(args: &CreateGameArgs) -> Instruction {
+    let wallet = args.wallet();
+    let game = args.game();
+    let mint = &args.mint;
+    let nft_mint = args.nft_mint.filter(|_| args.player1_asset.is_nft());
+
+    let mut ix = build(
+        accounts::CreateGame {
+            creator: args.creator,
+            payer: args.payer,
+            relayer_token_account: spl(mint, |mint| get_associated_token_address(&args.payer, mint))
+                .filter(|_| args.relayer_fee > 0),
+            game,
+            vault: spl(mint, |mint| find_vault_pda(&game, mint).0),
+            creator_stats: find_player_stats_pda(&wallet).0,
+            creator_rating: (args.game_type != 0).then(|| find_rating_pda(&wallet, args.game_type).0),
+            sol_vault: find_sol_vault_pda(&game).0,
+            token_mint: spl(mint, |mint| *mint),
+            // Wrapped SOL games are funded with plain SOL when no token account is passed
+            creator_token_account: spl(mint, |mint| get_associated_token_address(&args.creator, mint))
+                .filter(|_| !args.use_balance && !is_wrapped_sol(mint)),
+            creator_balance: args.use_balance.then(|| find_balance_pda(&wallet, mint).0),
+            creator_balance_vault: spl(mint, |mint| find_balance_vault_pda(&wallet, mint).0)
+                .filter(|_| args.use_balance),
+            nft_mint,
+            creator_nft_account: nft_mint.map(|nft| get_associated_token_address(&args.creator, &nft)),
+            nft_vault: nft_mint.map(|_| find_nft_vault_pda(&game, &args.creator).0),
+            nft_metadata: nft_mint.and_then(|nft| nft_metadata(&args.player1_asset, &nft)),
+            dev_wallet: args.dev_wallet,
+            session: args.session_owner.map(|owner| find_session_pda(&owner, &args.creator).0),
+            token_program: token::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+            event_authority: find_event_authority_pda().0,
+            program: ID,
+        },
+        instruction::CreateGame { args: args.terms() },
+    );
+    ix.accounts.extend(basket_deposit_metas(&game, &args.creator, &args.player1_basket));
+    ix
+}
+
+/// How player2 joins a game.
+#[derive(Clone, Default)]
+pub struct JoinGameArgs {
+    pub player2: Pubkey,
+    // Pays rent and fees; `player2` unless a relayer sponsors the transaction
+    pub payer: Pubkey,
+    pub relayer_fee: u64,
+    // NFT player2 stakes, when player2's asset is an NFT
+    pub nft_mint: Option<Pubkey>,
+    // Wallet `player2` signs for as a session key
+    pub session_owner: Option<Pubkey>,
+    // Stake from the player balance instead of the wallet
+    pub use_balance: bool,
+}
+
+impl JoinGameArgs {
+    pub fn new(player2: Pubkey) -> Self {
+        Self { player2, payer: player2, ..Self::default() }
+    }
+}
+
+pub fn join_game(game: &Pubkey, state: &Game, args: &JoinGameArgs) -> Instruction {
+    let wallet = args.session_owner.unwrap_or(args.player2);
+    let mint = &state.mint;
+    let nft_mint = args.nft_mint.filter(|_| state.player2_asset.is_nft());
+
+    let mut ix = build(
+        accounts::JoinGame {
+            player2: args.player2,
+            payer: args.payer,
+            relayer_token_account: spl(mint, |mint| get_associated_token_address(&args.payer, mint))
+                .filter(|_| args.relayer_fee > 0),
+            game: *game,
+            player2_token_account: spl(mint, |mint| get_associated_token_address(&args.player2, mint))
+                .filter(|_| !args.use_balance && !is_wrapped_sol(mint)),
+            player2_balance: args.use_balance.then(|| find_balance_pda(&wallet, mint).0),
+            player2_balance_vault: spl(mint, |mint| find_balance_vault_pda(&wallet, mint).0)
+                .filter(|_| args.use_balance),
+            vault: spl(mint, |mint| find_vault_pda(game, mint).0),
+            nft_mint,
+            player2_nft_account: nft_mint.map(|nft| get_associated_token_address(&args.player2, &nft)),
+            nft_vault: nft_mint.map(|_| find_nft_vault_pda(game, &args.player2).0),
+            nft_metadata: nft_mint.and_then(|nft| nft_metadata(&state.player2_asset, &nft)),
+            player2_stats: find_player_stats_pda(&wallet).0,
+            player2_rating: state.is_ranked().then(|| find_rating_pda(&wallet, state.game_type).0),
+            sol_vault: find_sol_vault_pda(game).0,
+            session: args.session_owner.map(|owner| find_session_pda(&owner, &args.player2).0),
+            token_program: token::ID,
+            system_program: system_program::ID,
+            event_authority: find_event_authority_pda().0,
+            program: ID,
+        },
+        instruction::JoinGame { relayer_fee: args.relayer_fee },
+    );
+    ix.accounts.extend(basket_deposit_metas(game, &args.player2, &state.player2_basket));
+    ix
+}
+
+fn side_pool(state: &Game) -> Option<Pubkey> {
+    (state.side_pool != Pubkey::default()).then_some(state.side_pool)
+}
+
+fn rating(state: &Game, player: &Pubkey) -> Option<Pubkey> {
+    state.is_ranked().then(|| find_rating_pda(player, state.game_type).0)
+}
+
+pub fn resolve_game(resolver: &Pubkey, game: &Pubkey, state: &Game, winner: &Pubkey) -> Instruction {
+    build(
+        accounts::ResolveGame {
+            resolver: *resolver,
+            game: *game,
+            side_pool: side_pool(state),
+            player1_stats: find_player_stats_pda(&state.player1).0,
+            player2_stats: find_player_stats_pda(&state.player2).0,
+            player1_rating: rating(state, &state.player1),
+            player2_rating: rating(state, &state.player2),
+            event_authority: find_event_authority_pda().0,
+            program: ID,
+        },
+        instruction::ResolveGame { winner: *winner },
+    )
+}
+
+/// The program instruction only; the transaction must also carry an Ed25519
+/// precompile instruction verifying the resolver's signature.
+pub fn resolve_with_attestation(submitter: &Pubkey, game: &Pubkey, state: &Game, winner: &Pubkey) -> Instruction {
+    build(
+        accounts::ResolveWithAttestation {
+            submitter: *submitter,
+            game: *game,
+            side_pool: side_pool(state),
+            player1_stats: find_player_stats_pda(&state.player1).0,
+            player2_stats: find_player_stats_pda(&state.player2).0,
+            player1_rating: rating(state, &state.player1),
+            player2_rating: rating(state, &state.player2),
+            instructions: sysvar::instructions::ID,
+            event_authority: find_event_authority_pda().0,
+            program: ID,
+        },
+        instruction::ResolveWithAttestation { winner: *winner },
+    )
+}
+
+pub fn report_round(resolver: &Pubkey, game: &Pubkey, state: &Game, winner: &Pubkey) -> Instruction {
+    build(
+        accounts::ReportRound {
+            resolver: *resolver,
+            game: *game,
+            side_pool: side_pool(state),
+            player1_stats: find_player_stats_pda(&state.player1).0,
+            player2_stats: find_player_stats_pda(&state.player2).0,
+            player1_rating: rating(state, &state.player1),
+            player2_rating: rating(state, &state.player2),
+            event_authority: find_event_authority_pda().0,
+            program: ID,
+        },
+        instruction::ReportRound { winner: *winner },
+    )
+}
+
+pub fn cancel_if_expired(authority: &Pubkey, game: &Pubkey, state: &Game) -> Instruction {
+    // Stats are only touched once both players have staked
+    let both_staked = joined(&state.player2);
+    build(
+        accounts::CancelGame {
+            authority: *authority,
+            game: *game,
+            side_pool: side_pool(state),
+            player1_stats: both_staked.then(|| find_player_stats_pda(&state.player1).0),
+            player2_stats: both_staked.then(|| find_player_stats_pda(&state.player2).0),
+            event_authority: find_event_authority_pda().0,
+            program: ID,
+        },
+        instruction::CancelIfExpired {},
+    )
+}
+
+pub fn update_resolver(creator: &Pubkey, game: &Pubkey, new_resolver: &Pubkey) -> Instruction {
+    build(
+        accounts::UpdateResolver {
+            creator: *creator,
+            game: *game,
+            event_authority: find_event_authority_pda().0,
+            program: ID,
+        },
+        instruction::UpdateResolver { new_resolver: *new_resolver },
+    )
+}
+
+/// Where a claimed payout goes.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ClaimTo {
+    // The recipient's wallet (SOL) or associated token account (SPL);
+    // wrapped SOL is unwrapped to plain SOL
+    Wallet,
+    // The recipient's associated wSOL account, for wrapped SOL games
+    TokenAccount,
+    // The recipient's player balance
+    Balance,
+}
+
+pub fn claim(claimer: &Pubkey, game: &Pubkey, state: &Game, recipient: &Pubkey, to: ClaimTo) -> Instruction {
+    let mint = &state.mint;
+    let unwrap = is_wrapped_sol(mint) && to == ClaimTo::Wallet;
+    let to_token_account = is_spl(mint) && !unwrap && to != ClaimTo::Balance;
+    let to_balance = to == ClaimTo::Balance;
+
+    build(
+        accounts::Claim {
+            claimer: *claimer,
+            game: *game,
+            recipient_token_account: to_token_account.then(|| get_associated_token_address(recipient, mint)),
+            token_mint: spl(mint, |mint| *mint),
+            vault: spl(mint, |mint| find_vault_pda(game, mint).0),
+            sol_vault: find_sol_vault_pda(game).0,
+            unwrap_account: unwrap.then(|| find_unwrap_pda(game).0),
+            recipient_account: *recipient,
+            recipient_balance: to_balance.then(|| find_balance_pda(recipient, mint).0),
+            recipient_balance_vault: spl(mint, |mint| find_balance_vault_pda(recipient, mint).0)
+                .filter(|_| to_balance),
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            event_authority: find_event_authority_pda().0,
+            program: ID,
+        },
+        instruction::Claim { recipient: *recipient },
+    )
+}
+
+pub fn claim_nft(claimer: &Pubkey, game: &Pubkey, state: &Game, side: Side, recipient: &Pubkey) -> Instruction {
+    let nft_mint = state.asset(side).mint;
+    let staker = state.player(side);
+    build(
+        accounts::ClaimNft {
+            claimer: *claimer,
+            game: *game,
+            nft_mint,
+            nft_vault: find_nft_vault_pda(game, &staker).0,
+            staker,
+            recipient_token_account: get_associated_token_address(recipient, &nft_mint),
+            recipient_account: *recipient,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            event_authority: find_event_authority_pda().0,
+            program: ID,
+        },
+        instruction::ClaimNft { side },
+    )
+}
+
+/// Passes a basket vault and the recipient's token account for every leg
+/// still owed to `recipient`, in the order the program pays them.
+pub fn claim_basket(claimer: &Pubkey, game: &Pubkey, state: &Game, recipient: &Pubkey) -> Instruction {
+    let mut ix = build(
+        accounts::ClaimBasket {
+            claimer: *claimer,
+            game: *game,
+            token_program: token::ID,
+            event_authority: find_event_authority_pda().0,
+            program: ID,
+        },
+        instruction::ClaimBasket { recipient: *recipient },
+    );
+
+    let paid = matches!(state.state(), Ok(GameState::Paid));
+    for side in [Side::Player1, Side::Player2] {
+        for (index, leg) in state.basket(side).used_legs() {
+            let bit = Game::basket_leg_bit(side, index);
+            let owed = if paid {
+                (*recipient == state.winner && state.basket_paid & bit == 0)
+                    || (*recipient == state.dev_wallet && state.basket_fee_paid & bit == 0)
+            } else {
+                *recipient == state.player(side) && state.basket_paid & bit == 0
+            };
+            if owed {
+                ix.accounts.push(AccountMeta::new(find_basket_vault_pda(game, &leg.mint).0, false));
+                ix.accounts.push(AccountMeta::new(get_associated_token_address(recipient, &leg.mint), false));
+            }
+        }
+    }
+    ix
+}
+
+/// Migrates a legacy `game` in `mint`; `decode_game` can't read legacy
+/// accounts, so the caller supplies the mint.
+pub fn migrate_game(payer: &Pubkey, game: &Pubkey, mint: &Pubkey) -> Instruction {
+    build(
+        accounts::MigrateGame {
+            payer: *payer,
+            game: *game,
+            sol_vault: find_sol_vault_pda(game).0,
+            vault: spl(mint, |mint| find_vault_pda(game, mint).0),
+            system_program: system_program::ID,
+        },
+        instruction::MigrateGame {},
+    )
+}
+
+/// Resolves each `(game, state, winner)` in one instruction.
+pub fn resolve_many(resolver: &Pubkey, games: &[(Pubkey, &Game, Pubkey)], policy: BatchPolicy) -> Instruction {
+    let resolutions = games
+        .iter()
+        .enumerate()
+        .map(|(index, (_, _, winner))| BatchResolution { game_index: index as u16, winner: *winner })
+        .collect();
+    let mut ix = build(
+        accounts::ResolveMany {
+            resolver: *resolver,
+            event_authority: find_event_authority_pda().0,
+            program: ID,
+        },
+        instruction::ResolveMany { resolutions, policy },
+    );
+
+    let optional = |key: Option<Pubkey>| match key {
+        Some(key) => AccountMeta::new(key, false),
+        None => AccountMeta::new_readonly(ID, false),
+    };
+    for (game, state, _) in games {
+        ix.accounts.extend([
+            AccountMeta::new(*game, false),
+            AccountMeta::new(find_player_stats_pda(&state.player1).0, false),
+            AccountMeta::new(find_player_stats_pda(&state.player2).0, false),
+            optional(rating(state, &state.player1)),
+            optional(rating(state, &state.player2)),
+            optional(side_pool(state)),
+        ]);
+    }
+    ix
+}
+
+/// Refunds and closes each expired `(game, state)`.
+pub fn crank_expired(cranker: &Pubkey, games: &[(Pubkey, &Game)]) -> Instruction {
+    let mut ix = build(
+        accounts::CrankExpired {
+            cranker: *cranker,
+            token_program: token::ID,
+            system_program: system_program::ID,
+            event_authority: find_event_authority_pda().0,
+            program: ID,
+        },
+        instruction::CrankExpired {},
+    );
+
+    let placeholder = AccountMeta::new_readonly(ID, false);
+    for (game, state) in games {
+        let mint = &state.mint;
+        // Refunds go to wallets for native SOL, token accounts otherwise
+        let player_metas = |player: &Pubkey| {
+            if !joined(player) {
+                return [placeholder.clone(), placeholder.clone()];
+            }
+            let destination = if is_spl(mint) { get_associated_token_address(player, mint) } else { *player };
+            [
+                AccountMeta::new(destination, false),
+                AccountMeta::new(find_player_stats_pda(player).0, false),
+            ]
+        };
+        let [player1_destination, player1_stats] = player_metas(&state.player1);
+        let [player2_destination, player2_stats] = player_metas(&state.player2);
+
+        ix.accounts.extend([
+            AccountMeta::new(*game, false),
+            AccountMeta::new(state.creator, false),
+            AccountMeta::new(state.rent_receiver(), false),
+            AccountMeta::new(find_sol_vault_pda(game).0, false),
+            spl(mint, |mint| AccountMeta::new(find_vault_pda(game, mint).0, false)).unwrap_or(placeholder.clone()),
+            // Receives whatever the vault holds beyond the refunds
+            spl(mint, |mint| AccountMeta::new(get_associated_token_address(&state.creator, mint), false))
+                .unwrap_or(placeholder.clone()),
+            player1_destination,
+            player2_destination,
+            player1_stats,
+            player2_stats,
+        ]);
+    }
+    ix
+}
+
+/// Arguments of `create_tournament`.
+#[derive(Clone)]
+pub struct CreateTournamentArgs {
+    pub organizer: Pubkey,
+    pub mint: Pubkey,
+    pub entry_fee: u64,
+    pub fee_bps: u16,
+    pub max_players: u8,
+    pub registration_end_ts: i64,
+    pub payout_table: Vec<u16>,
+    pub resolver: Option<Pubkey>,
+    pub nonce: u64,
+    pub dev_wallet: Pubkey,
+}
+
+pub fn create_tournament(args: &CreateTournamentArgs) -> Instruction {
+    let tournament = find_tournament_pda(&args.organizer, args.nonce).0;
+    let mint = &args.mint;
+    build(
+        accounts::CreateTournament {
+            organizer: args.organizer,
+            tournament,
+            vault: spl(mint, |mint| find_vault_pda(&tournament, mint).0),
+            sol_vault: find_sol_vault_pda(&tournament).0,
+            token_mint: spl(mint, |mint| *mint),
+            dev_wallet: args.dev_wallet,
+            token_program: token::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        instruction::CreateTournament {
+            mint: args.mint,
+            entry_fee: args.entry_fee,
+            fee_bps: args.fee_bps,
+            max_players: args.max_players,
+            registration_end_ts: args.registration_end_ts,
+            payout_table: args.payout_table.clone(),
+            resolver_pubkey: args.resolver,
+            nonce: args.nonce,
+        },
+    )
+}
+
+pub fn register_tournament(player: &Pubkey, tournament: &Pubkey, state: &Tournament) -> Instruction {
+    let mint = &state.mint;
+    build(
+        accounts::RegisterTournament {
+            player: *player,
+            tournament: *tournament,
+            player_token_account: spl(mint, |mint| get_associated_token_address(player, mint)),
+            vault: spl(mint, |mint| find_vault_pda(tournament, mint).0),
+            sol_vault: find_sol_vault_pda(tournament).0,
+            token_program: token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::RegisterTournament {},
+    )
+}
+
+pub fn start_tournament(authority: &Pubkey, tournament: &Pubkey, seeding: Vec<u8>) -> Instruction {
+    build(
+        accounts::StartTournament { authority: *authority, tournament: *tournament },
+        instruction::StartTournament { seeding },
+    )
+}
+
+pub fn report_match(resolver: &Pubkey, tournament: &Pubkey, match_index: u8, winner: &Pubkey) -> Instruction {
+    build(
+        accounts::ReportMatch { resolver: *resolver, tournament: *tournament },
+        instruction::ReportMatch { match_index, winner: *winner },
+    )
+}
+
+pub fn cancel_tournament(authority: &Pubkey, tournament: &Pubkey) -> Instruction {
+    build(
+        accounts::CancelTournament { authority: *authority, tournament: *tournament },
+        instruction::CancelTournament {},
+    )
+}
+
+pub fn claim_tournament_prize(claimer: &Pubkey, tournament: &Pubkey, state: &Tournament, recipient: &Pubkey) -> Instruction {
+    let mint = &state.mint;
+    build(
+        accounts::ClaimTournamentPrize {
+            claimer: *claimer,
+            tournament: *tournament,
+            recipient_token_account: spl(mint, |mint| get_associated_token_address(recipient, mint)),
+            token_mint: spl(mint, |mint| *mint),
+            vault: spl(mint, |mint| find_vault_pda(tournament, mint).0),
+            sol_vault: find_sol_vault_pda(tournament).0,
+            recipient_account: *recipient,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::ClaimTournamentPrize { recipient: *recipient },
+    )
+}
+
+pub fn create_side_pool(creator: &Pubkey, game: &Pubkey, state: &Game, fee_bps: u16, cutoff_ts: i64) -> Instruction {
+    let side_pool = find_side_pool_pda(game).0;
+    let mint = &state.mint;
+    build(
+        accounts::CreateSidePool {
+            creator: *creator,
+            game: *game,
+            side_pool,
+            vault: spl(mint, |mint| find_vault_pda(&side_pool, mint).0),
+            sol_vault: find_sol_vault_pda(&side_pool).0,
+            token_mint: spl(mint, |mint| *mint),
+            token_program: token::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        instruction::CreateSidePool { fee_bps, cutoff_ts },
+    )
+}
+
+pub fn place_side_bet(bettor: &Pubkey, game: &Pubkey, pool: &SidePool, side: Side, amount: u64) -> Instruction {
+    let side_pool = find_side_pool_pda(game).0;
+    let mint = &pool.mint;
+    build(
+        accounts::PlaceSideBet {
+            bettor: *bettor,
+            game: *game,
+            side_pool,
+            side_bet: find_side_bet_pda(&side_pool, bettor).0,
+            bettor_token_account: spl(mint, |mint| get_associated_token_address(bettor, mint)),
+            vault: spl(mint, |mint| find_vault_pda(&side_pool, mint).0),
+            sol_vault: find_sol_vault_pda(&side_pool).0,
+            token_program: token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::PlaceSideBet { side, amount },
+    )
+}
+
+pub fn claim_side_bet(claimer: &Pubkey, pool: &SidePool, bettor: &Pubkey) -> Instruction {
+    let side_pool = find_side_pool_pda(&pool.game).0;
+    let mint = &pool.mint;
+    build(
+        accounts::ClaimSideBet {
+            claimer: *claimer,
+            side_pool,
+            side_bet: find_side_bet_pda(&side_pool, bettor).0,
+            bettor_token_account: spl(mint, |mint| get_associated_token_address(bettor, mint)),
+            token_mint: spl(mint, |mint| *mint),
+            vault: spl(mint, |mint| find_vault_pda(&side_pool, mint).0),
+            sol_vault: find_sol_vault_pda(&side_pool).0,
+            bettor_account: *bettor,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::ClaimSideBet {},
+    )
+}
+
+pub fn claim_side_pool_fee(claimer: &Pubkey, game: &Pubkey, state: &Game) -> Instruction {
+    let side_pool = find_side_pool_pda(game).0;
+    let mint = &state.mint;
+    build(
+        accounts::ClaimSidePoolFee {
+            claimer: *claimer,
+            game: *game,
+            side_pool,
+            dev_token_account: spl(mint, |mint| get_associated_token_address(&state.dev_wallet, mint)),
+            token_mint: spl(mint, |mint| *mint),
+            vault: spl(mint, |mint| find_vault_pda(&side_pool, mint).0),
+            sol_vault: find_sol_vault_pda(&side_pool).0,
+            dev_wallet_account: state.dev_wallet,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::ClaimSidePoolFee {},
+    )
+}
+
+/// Closes a fully claimed side pool; the dust and rent go to the creator.
+pub fn close_side_pool(closer: &Pubkey, game: &Pubkey, state: &Game) -> Instruction {
+    let side_pool = find_side_pool_pda(game).0;
+    let mint = &state.mint;
+    build(
+        accounts::CloseSidePool {
+            closer: *closer,
+            game: *game,
+            side_pool,
+            creator_token_account: spl(mint, |mint| get_associated_token_address(&state.creator, mint)),
+            token_mint: spl(mint, |mint| *mint),
+            vault: spl(mint, |mint| find_vault_pda(&side_pool, mint).0),
+            sol_vault: find_sol_vault_pda(&side_pool).0,
+            creator_account: state.creator,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            event_authority: find_event_authority_pda().0,
+            program: ID,
+        },
+        instruction::CloseSidePool {},
+    )
+}
+
+pub fn create_queue(
+    resolver: &Pubkey,
+    mint: &Pubkey,
+    wager: u64,
+    game_type: u32,
+    payout_bps: u16,
+    game_duration: i64,
+    dev_wallet: &Pubkey,
+) -> Instruction {
+    let queue = find_queue_pda(resolver, mint, wager, game_type).0;
+    build(
+        accounts::CreateQueue {
+            resolver: *resolver,
+            queue,
+            vault: spl(mint, |mint| find_vault_pda(&queue, mint).0),
+            sol_vault: find_sol_vault_pda(&queue).0,
+            token_mint: spl(mint, |mint| *mint),
+            dev_wallet: *dev_wallet,
+            token_program: token::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        instruction::CreateQueue { mint: *mint, wager, game_type, payout_bps, game_duration },
+    )
+}
+
+pub fn enqueue(player: &Pubkey, queue: &Pubkey, state: &MatchQueue) -> Instruction {
+    let mint = &state.mint;
+    build(
+        accounts::Enqueue {
+            player: *player,
+            queue: *queue,
+            player_token_account: spl(mint, |mint| get_associated_token_address(player, mint)),
+            vault: spl(mint, |mint| find_vault_pda(queue, mint).0),
+            sol_vault: find_sol_vault_pda(queue).0,
+            player_stats: find_player_stats_pda(player).0,
+            player_rating: (state.game_type != 0).then(|| find_rating_pda(player, state.game_type).0),
+            token_program: token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::Enqueue {},
+    )
+}
+
+pub fn dequeue(player: &Pubkey, queue: &Pubkey, state: &MatchQueue) -> Instruction {
+    let mint = &state.mint;
+    build(
+        accounts::Dequeue {
+            player: *player,
+            queue: *queue,
+            player_token_account: spl(mint, |mint| get_associated_token_address(player, mint)),
+            vault: spl(mint, |mint| find_vault_pda(queue, mint).0),
+            sol_vault: find_sol_vault_pda(queue).0,
+            token_program: token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::Dequeue {},
+    )
+}
+
+pub fn match_players(cranker: &Pubkey, queue: &Pubkey, state: &MatchQueue) -> Instruction {
+    let game = find_matched_game_pda(queue, state.games_matched).0;
+    let mint = &state.mint;
+    build(
+        accounts::MatchPlayers {
+            cranker: *cranker,
+            queue: *queue,
+            game,
+            queue_vault: spl(mint, |mint| find_vault_pda(queue, mint).0),
+            queue_sol_vault: find_sol_vault_pda(queue).0,
+            vault: spl(mint, |mint| find_vault_pda(&game, mint).0),
+            sol_vault: find_sol_vault_pda(&game).0,
+            token_mint: spl(mint, |mint| *mint),
+            token_program: token::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+            event_authority: find_event_authority_pda().0,
+            program: ID,
+        },
+        instruction::MatchPlayers {},
+    )
+}
+
+pub fn deposit_balance(owner: &Pubkey, mint: &Pubkey, amount: u64) -> Instruction {
+    build(
+        accounts::DepositBalance {
+            owner: *owner,
+            balance: find_balance_pda(owner, mint).0,
+            balance_vault: spl(mint, |mint| find_balance_vault_pda(owner, mint).0),
+            token_mint: spl(mint, |mint| *mint),
+            owner_token_account: spl(mint, |mint| get_associated_token_address(owner, mint)),
+            token_program: token::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        instruction::DepositBalance { mint: *mint, amount },
+    )
+}
+
+pub fn withdraw(owner: &Pubkey, mint: &Pubkey, amount: u64) -> Instruction {
+    build(
+        accounts::Withdraw {
+            owner: *owner,
+            balance: find_balance_pda(owner, mint).0,
+            balance_vault: spl(mint, |mint| find_balance_vault_pda(owner, mint).0),
+            owner_token_account: spl(mint, |mint| get_associated_token_address(owner, mint)),
+            token_program: token::ID,
+        },
+        instruction::Withdraw { amount },
+    )
+}
+
+/// Arguments of `approve_session`: the key, its spending limits and the game
+/// terms every stake through it must match.
+#[derive(Clone)]
+pub struct ApproveSessionArgs {
+    pub owner: Pubkey,
+    pub session_key: Pubkey,
+    pub spend_cap: u64,
+    pub allowed_mints: Vec<Pubkey>,
+    pub expires_at: i64,
+    pub resolver: Pubkey,
+    pub dev_wallet: Pubkey,
+    pub min_payout_bps: u16,
+}
+
+pub fn approve_session(args: &ApproveSessionArgs) -> Instruction {
+    build(
+        accounts::ApproveSession {
+            owner: args.owner,
+            session: find_session_pda(&args.owner, &args.session_key).0,
+            system_program: system_program::ID,
+        },
+        instruction::ApproveSession {
+            session_key: args.session_key,
+            spend_cap: args.spend_cap,
+            allowed_mints: args.allowed_mints.clone(),
+            expires_at: args.expires_at,
+            resolver: args.resolver,
+            dev_wallet: args.dev_wallet,
+            min_payout_bps: args.min_payout_bps,
+        },
+    )
+}
+
+pub fn revoke_session(owner: &Pubkey, session_key: &Pubkey) -> Instruction {
+    build(
+        accounts::RevokeSession {
+            owner: *owner,
+            session: find_session_pda(owner, session_key).0,
+        },
+        instruction::RevokeSession {},
+    )
+}
@@ -0,0 +1,13 @@
+//! Rust client for the easy_wager_on_sol program: PDA helpers, typed
+//! instruction builders and decoders for the `Game` account and events.
+
+pub mod decode;
+pub mod instructions;
+pub mod pda;
+
+pub use decode::{
+    decode_account, decode_cpi_event, decode_event, decode_game, log_event_data, log_event_lines, parse_logs, WagerEvent,
+    GAME_CREATOR_OFFSET, GAME_STATE_OFFSET,
+};
+pub use easy_wager_on_sol::{error::WagerError, state, ID};
+pub use pda::*;
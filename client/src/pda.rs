@@ -0,0 +1,89 @@
+//! Program-derived addresses, with the same seeds as the program's account
+//! constraints. Each helper returns the address and its bump.
+
+use anchor_lang::prelude::Pubkey;
+use easy_wager_on_sol::ID;
+
+pub fn find_game_pda(creator: &Pubkey, nonce: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"game", creator.as_ref(), &nonce.to_le_bytes()], &ID)
+}
+
+/// Game created by a matchmaking queue: the queue stands in for the creator
+/// and its match count for the nonce.
+pub fn find_matched_game_pda(queue: &Pubkey, games_matched: u64) -> (Pubkey, u8) {
+    find_game_pda(queue, games_matched)
+}
+
+/// Token vault of a game, tournament, side pool or queue (`owner`) for `mint`.
+pub fn find_vault_pda(owner: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"vault", owner.as_ref(), mint.as_ref()], &ID)
+}
+
+/// System-owned vault escrowing native SOL for a game, tournament, side pool
+/// or queue.
+pub fn find_sol_vault_pda(owner: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"sol_vault", owner.as_ref()], &ID)
+}
+
+pub fn find_nft_vault_pda(game: &Pubkey, staker: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"nft_vault", game.as_ref(), staker.as_ref()], &ID)
+}
+
+pub fn find_basket_vault_pda(game: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"basket_vault", game.as_ref(), mint.as_ref()], &ID)
+}
+
+/// Temporary account wrapped SOL payouts are unwrapped through.
+pub fn find_unwrap_pda(game: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"unwrap", game.as_ref()], &ID)
+}
+
+pub fn find_player_stats_pda(wallet: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"player_stats", wallet.as_ref()], &ID)
+}
+
+pub fn find_rating_pda(wallet: &Pubkey, game_type: u32) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"rating", wallet.as_ref(), &game_type.to_le_bytes()], &ID)
+}
+
+pub fn find_side_pool_pda(game: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"side_pool", game.as_ref()], &ID)
+}
+
+pub fn find_side_bet_pda(side_pool: &Pubkey, bettor: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"side_bet", side_pool.as_ref(), bettor.as_ref()], &ID)
+}
+
+pub fn find_tournament_pda(organizer: &Pubkey, nonce: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"tournament", organizer.as_ref(), &nonce.to_le_bytes()], &ID)
+}
+
+pub fn find_queue_pda(resolver: &Pubkey, mint: &Pubkey, wager: u64, game_type: u32) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"queue",
+            resolver.as_ref(),
+            mint.as_ref(),
+            &wager.to_le_bytes(),
+            &game_type.to_le_bytes(),
+        ],
+        &ID,
+    )
+}
+
+pub fn find_balance_pda(owner: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"balance", owner.as_ref(), mint.as_ref()], &ID)
+}
+
+pub fn find_balance_vault_pda(owner: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"balance_vault", owner.as_ref(), mint.as_ref()], &ID)
+}
+
+pub fn find_session_pda(owner: &Pubkey, session_key: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"session", owner.as_ref(), session_key.as_ref()], &ID)
+}
+
+/// Signs the program's `emit_cpi!` self-invocations.
+pub fn find_event_authority_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"__event_authority"], &ID)
+}
@@ -0,0 +1,147 @@
+//! Decoding `Game` accounts and events out of transaction logs.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{Discriminator, Event};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use bytemuck::Zeroable;
+use easy_wager_client::state::{Game, GameState, ResolverUpdated};
+use easy_wager_client::{decode_cpi_event, decode_game, log_event_data, parse_logs, WagerEvent, GAME_CREATOR_OFFSET, GAME_STATE_OFFSET, ID};
+
+fn game() -> Game {
+    let mut game = Game::zeroed();
+    game.creator = Pubkey::new_unique();
+    game.player1 = game.creator;
+    game.player2 = Pubkey::new_unique();
+    game.mint = Pubkey::new_unique();
+    game.wager = 1_000;
+    game.payout_bps = 9_500;
+    game.expiry_ts = 1_700_000_000;
+    game.nonce = 42;
+    game.best_of = 3;
+    game.max_rating = u32::MAX;
+    game.rent_payer = Pubkey::new_unique();
+    game.crank_bounty_bps = 250;
+    game.version = Game::VERSION;
+    game.set_state(GameState::Ready);
+    game
+}
+
+fn account_data(game: &Game) -> Vec<u8> {
+    let mut data = Game::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(bytemuck::bytes_of(game));
+    data
+}
+
+fn event() -> ResolverUpdated {
+    ResolverUpdated {
+        game: Pubkey::new_unique(),
+        old_resolver: Pubkey::new_unique(),
+        new_resolver: Pubkey::new_unique(),
+        mint: Pubkey::default(),
+        timestamp: 1_700_000_000,
+    }
+}
+
+fn program_data(event: &ResolverUpdated) -> String {
+    format!("Program data: {}", BASE64.encode(event.data()))
+}
+
+#[test]
+fn game_round_trips_through_its_account_data() {
+    let game = game();
+    let data = account_data(&game);
+    assert_eq!(data.len(), Game::LEN);
+
+    let decoded = decode_game(&data).unwrap();
+    assert_eq!(bytemuck::bytes_of(&decoded), bytemuck::bytes_of(&game));
+    assert!(decoded.state().unwrap() == GameState::Ready);
+    assert_eq!((decoded.rent_payer, decoded.crank_bounty_bps), (game.rent_payer, 250));
+
+    // The memcmp filter offsets point at the fields they name
+    assert_eq!(&data[GAME_CREATOR_OFFSET..GAME_CREATOR_OFFSET + 32], game.creator.as_ref());
+    assert_eq!(data[GAME_STATE_OFFSET], GameState::Ready as u8);
+}
+
+#[test]
+fn game_decoding_rejects_other_accounts_and_borsh_layouts() {
+    let mut data = account_data(&game());
+    // Unaligned buffers still decode
+    let mut shifted = vec![0];
+    shifted.extend_from_slice(&data);
+    assert!(decode_game(&shifted[1..]).is_ok());
+
+    // Another account type
+    let mut other = data.clone();
+    other[0] ^= 1;
+    assert!(decode_game(&other).is_err());
+    // A game still on the last Borsh layout, awaiting `migrate_game`
+    data.truncate(859);
+    assert!(decode_game(&data).is_err());
+}
+
+#[test]
+fn events_are_read_from_the_programs_own_log_lines() {
+    let ours = event();
+    let forged = event();
+    let program = ID.to_string();
+    let other = Pubkey::new_unique().to_string();
+    let logs = vec![
+        format!("Program {program} invoke [1]"),
+        format!("Program log: Instruction: UpdateResolver"),
+        // Another program invoked by ours logs data that looks like an event
+        format!("Program {other} invoke [2]"),
+        program_data(&forged),
+        format!("Program {other} success"),
+        program_data(&ours),
+        format!("Program {program} success"),
+        // ... and so does another top-level instruction
+        format!("Program {other} invoke [1]"),
+        program_data(&forged),
+        format!("Program {other} success"),
+    ];
+
+    assert_eq!(log_event_data(&logs), vec![ours.data()]);
+    let events = parse_logs(&logs);
+    assert_eq!(events.len(), 1);
+    match &events[0] {
+        WagerEvent::ResolverUpdated(event) => assert_eq!(event.new_resolver, ours.new_resolver),
+        event => panic!("unexpected {}", event.name()),
+    }
+}
+
+#[test]
+fn events_of_the_program_invoked_by_another_are_read_until_it_returns() {
+    let ours = event();
+    let forged = event();
+    let program = ID.to_string();
+    let other = Pubkey::new_unique().to_string();
+    let logs = vec![
+        format!("Program {other} invoke [1]"),
+        program_data(&forged),
+        format!("Program {program} invoke [2]"),
+        program_data(&ours),
+        format!("Program {program} consumed 5000 of 200000 compute units"),
+        format!("Program {program} success"),
+        // Back in the caller's frame
+        program_data(&forged),
+        format!("Program {other} success"),
+    ];
+
+    assert_eq!(log_event_data(&logs), vec![ours.data()]);
+}
+
+#[test]
+fn cpi_events_decode_from_the_self_invocation_data() {
+    let event = event();
+    let mut ix_data = anchor_lang::event::EVENT_IX_TAG_LE.to_vec();
+    ix_data.extend(event.data());
+
+    match decode_cpi_event(&ix_data) {
+        Some(WagerEvent::ResolverUpdated(decoded)) => assert_eq!(decoded.game, event.game),
+        _ => panic!("event not decoded"),
+    }
+    // Without the tag it's some other instruction
+    assert!(decode_cpi_event(&event.data()).is_none());
+    assert!(decode_cpi_event(&ix_data[..12]).is_none());
+}
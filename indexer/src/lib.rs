@@ -0,0 +1,9 @@
+//! Indexes the program's events into SQLite: `games`, `players` and
+//! `payouts`, derived from every indexed transaction's events.
+
+pub mod rpc;
+pub mod store;
+pub mod transaction;
+
+pub use store::{Indexed, Store};
+pub use transaction::{parse_feed_line, parse_transaction, FeedItem, IndexedTransaction};
@@ -0,0 +1,106 @@
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::PathBuf;
+use std::thread::sleep;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use clap::{Parser, Subcommand};
+use easy_wager_indexer::rpc::RpcSource;
+use easy_wager_indexer::{parse_feed_line, parse_transaction, FeedItem, Indexed, Store};
+
+#[derive(Parser)]
+#[command(name = "easy-wager-indexer", version, about = "Index easy_wager_on_sol events into SQLite")]
+struct Cli {
+    /// SQLite database, created if missing
+    #[arg(long, env = "EASY_WAGER_INDEXER_DB", default_value = "easy-wager.db")]
+    db: PathBuf,
+
+    #[command(subcommand)]
+    source: Source,
+}
+
+#[derive(Subcommand)]
+enum Source {
+    /// Read JSON lines: `getTransaction` results and `{"rollback": <slot>}` notices
+    File {
+        /// Feed file, or `-` for stdin
+        path: PathBuf,
+    },
+    /// Fetch the program's transactions from an RPC node
+    Rpc {
+        #[arg(long, env = "EASY_WAGER_RPC_URL", default_value = "http://127.0.0.1:8899")]
+        url: String,
+        /// Keep polling for new transactions
+        #[arg(long)]
+        follow: bool,
+        /// Seconds between polls
+        #[arg(long, default_value_t = 5)]
+        interval: u64,
+    },
+}
+
+#[derive(Default)]
+struct Tally {
+    new: usize,
+    duplicate: usize,
+    moved: usize,
+    rolled_back: usize,
+}
+
+impl Tally {
+    fn record(&mut self, indexed: Indexed) {
+        match indexed {
+            Indexed::New => self.new += 1,
+            Indexed::Duplicate => self.duplicate += 1,
+            Indexed::Moved => self.moved += 1,
+        }
+    }
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let mut store = Store::open(&cli.db).with_context(|| format!("opening {}", cli.db.display()))?;
+    let mut tally = Tally::default();
+
+    match cli.source {
+        Source::File { path } => {
+            let reader: Box<dyn BufRead> = if path.as_os_str() == "-" {
+                Box::new(io::stdin().lock())
+            } else {
+                Box::new(BufReader::new(File::open(&path).with_context(|| format!("opening {}", path.display()))?))
+            };
+            for (number, line) in reader.lines().enumerate() {
+                let line = line?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                match parse_feed_line(&line).with_context(|| format!("line {}", number + 1))? {
+                    FeedItem::Transaction(transaction) => tally.record(store.index(&transaction)?),
+                    FeedItem::Rollback(slot) => tally.rolled_back += store.rollback(slot)?,
+                }
+            }
+        },
+        Source::Rpc { url, follow, interval } => {
+            let rpc = RpcSource::new(url);
+            loop {
+                let until = store.latest_signature()?;
+                for signature in rpc.signatures_since(until.as_deref())? {
+                    if let Some(transaction) = rpc.transaction(&signature)? {
+                        tally.record(store.index(&parse_transaction(&transaction)?)?);
+                    }
+                }
+                if !follow {
+                    break;
+                }
+                sleep(Duration::from_secs(interval));
+            }
+        },
+    }
+
+    println!(
+        "{} new, {} duplicate, {} moved, {} rolled back",
+        tally.new, tally.duplicate, tally.moved, tally.rolled_back,
+    );
+    Ok(())
+}
@@ -0,0 +1,76 @@
+//! Fetches the program's transactions over JSON-RPC.
+
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Context, Result};
+use easy_wager_client::ID;
+use serde_json::{json, Value};
+
+const COMMITMENT: &str = "confirmed";
+// getSignaturesForAddress page size
+const PAGE: usize = 1_000;
+
+pub struct RpcSource {
+    url: String,
+    agent: ureq::Agent,
+}
+
+impl RpcSource {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            agent: ureq::AgentBuilder::new().timeout(Duration::from_secs(30)).build(),
+        }
+    }
+
+    fn call(&self, method: &str, params: Value) -> Result<Value> {
+        let request = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let mut response: Value = self
+            .agent
+            .post(&self.url)
+            .send_json(request)
+            .with_context(|| format!("{method} request to {} failed", self.url))?
+            .into_json()?;
+        if let Some(error) = response.get("error") {
+            bail!("{method}: {}", error["message"].as_str().unwrap_or("unknown error"));
+        }
+        Ok(response["result"].take())
+    }
+
+    /// Signatures of the program's transactions newer than `until`, oldest
+    /// first so they index in slot order.
+    pub fn signatures_since(&self, until: Option<&str>) -> Result<Vec<String>> {
+        let mut signatures = Vec::new();
+        let mut before: Option<String> = None;
+        loop {
+            let mut config = json!({ "limit": PAGE, "commitment": COMMITMENT });
+            if let Some(until) = until {
+                config["until"] = json!(until);
+            }
+            if let Some(before) = &before {
+                config["before"] = json!(before);
+            }
+            let page = self.call("getSignaturesForAddress", json!([ID.to_string(), config]))?;
+            let page = page.as_array().ok_or_else(|| anyhow!("getSignaturesForAddress: unexpected response"))?;
+
+            let count = page.len();
+            signatures.extend(page.iter().filter_map(|entry| entry["signature"].as_str()).map(str::to_string));
+            if count < PAGE {
+                break;
+            }
+            before = signatures.last().cloned();
+        }
+        signatures.reverse();
+        Ok(signatures)
+    }
+
+    /// The transaction as `getTransaction` returns it, or `None` if the node
+    /// doesn't have it (yet).
+    pub fn transaction(&self, signature: &str) -> Result<Option<Value>> {
+        let result = self.call(
+            "getTransaction",
+            json!([signature, { "encoding": "json", "commitment": COMMITMENT, "maxSupportedTransactionVersion": 0 }]),
+        )?;
+        Ok((!result.is_null()).then_some(result))
+    }
+}
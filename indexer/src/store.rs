@@ -0,0 +1,298 @@
+//! SQLite storage. Every indexed transaction and its raw events are kept, and
+//! the `games`, `players` and `payouts` tables are derived from them, so a
+//! reorg or an out-of-order transaction is handled by replaying the events.
+
+use std::path::Path;
+
+use anyhow::Result;
+use easy_wager_client::state::CancelReason;
+use easy_wager_client::{decode_event, WagerEvent};
+use rusqlite::{params, Connection, OptionalExtension, Transaction};
+
+use crate::transaction::IndexedTransaction;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS transactions (
+    seq         INTEGER PRIMARY KEY AUTOINCREMENT,
+    signature   TEXT NOT NULL UNIQUE,
+    slot        INTEGER NOT NULL,
+    block_time  INTEGER,
+    failed      INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS events (
+    signature   TEXT NOT NULL REFERENCES transactions(signature) ON DELETE CASCADE,
+    idx         INTEGER NOT NULL,
+    name        TEXT NOT NULL,
+    data        BLOB NOT NULL,
+    PRIMARY KEY (signature, idx)
+);
+
+CREATE TABLE IF NOT EXISTS games (
+    address       TEXT PRIMARY KEY,
+    creator       TEXT NOT NULL,
+    player1       TEXT,
+    player2       TEXT,
+    mint          TEXT NOT NULL,
+    wager         INTEGER NOT NULL,
+    payout_bps    INTEGER NOT NULL,
+    expiry_ts     INTEGER NOT NULL,
+    state         TEXT NOT NULL,
+    winner        TEXT,
+    winner_amount INTEGER,
+    fee_amount    INTEGER,
+    cancel_reason TEXT,
+    player1_refund INTEGER,
+    player2_refund INTEGER,
+    created_slot  INTEGER NOT NULL,
+    updated_slot  INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS players (
+    address     TEXT PRIMARY KEY,
+    games       INTEGER NOT NULL DEFAULT 0,
+    wins        INTEGER NOT NULL DEFAULT 0,
+    losses      INTEGER NOT NULL DEFAULT 0
+);
+
+CREATE TABLE IF NOT EXISTS payouts (
+    signature   TEXT NOT NULL,
+    idx         INTEGER NOT NULL,
+    game        TEXT NOT NULL,
+    recipient   TEXT NOT NULL,
+    mint        TEXT,
+    amount      INTEGER NOT NULL,
+    slot        INTEGER NOT NULL,
+    PRIMARY KEY (signature, idx)
+);
+
+CREATE INDEX IF NOT EXISTS games_creator ON games(creator);
+CREATE INDEX IF NOT EXISTS games_state ON games(state);
+CREATE INDEX IF NOT EXISTS payouts_recipient ON payouts(recipient);
+";
+
+/// What `Store::index` did with a transaction.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Indexed {
+    New,
+    // Already indexed in the same slot; nothing changed
+    Duplicate,
+    // Already indexed in another slot, i.e. it landed again on a new fork
+    Moved,
+}
+
+pub struct Store {
+    conn: Connection,
+}
+
+impl Store {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Self::init(Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> Result<Self> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(conn: Connection) -> Result<Self> {
+        conn.execute_batch("PRAGMA foreign_keys = ON;")?;
+        conn.execute_batch(SCHEMA)?;
+        Ok(Self { conn })
+    }
+
+    /// For queries against the derived tables.
+    pub fn connection(&self) -> &Connection {
+        &self.conn
+    }
+
+    pub fn index(&mut self, transaction: &IndexedTransaction) -> Result<Indexed> {
+        let db = self.conn.transaction()?;
+        let existing: Option<u64> = db
+            .query_row(
+                "SELECT slot FROM transactions WHERE signature = ?1",
+                [&transaction.signature],
+                |row| row.get(0),
+            )
+            .optional()?;
+        if existing == Some(transaction.slot) {
+            return Ok(Indexed::Duplicate);
+        }
+        let latest_slot: Option<u64> = db.query_row("SELECT MAX(slot) FROM transactions", [], |row| row.get(0))?;
+
+        if existing.is_some() {
+            db.execute("DELETE FROM transactions WHERE signature = ?1", [&transaction.signature])?;
+        }
+        db.execute(
+            "INSERT INTO transactions (signature, slot, block_time, failed) VALUES (?1, ?2, ?3, ?4)",
+            params![transaction.signature, transaction.slot, transaction.block_time, transaction.failed],
+        )?;
+        for (idx, data) in transaction.events.iter().enumerate() {
+            let name = decode_event(data).map_or("Unknown", |event| event.name());
+            db.execute(
+                "INSERT INTO events (signature, idx, name, data) VALUES (?1, ?2, ?3, ?4)",
+                params![transaction.signature, idx, name, data],
+            )?;
+        }
+
+        // Events arriving in slot order apply on top of what's there; anything
+        // else means replaying from scratch
+        let in_order = existing.is_none() && latest_slot.is_none_or(|latest| transaction.slot >= latest);
+        if in_order {
+            for (idx, data) in transaction.events.iter().enumerate() {
+                apply(&db, &transaction.signature, idx, transaction.slot, data)?;
+            }
+        } else {
+            rebuild(&db)?;
+        }
+
+        db.commit()?;
+        Ok(if existing.is_some() { Indexed::Moved } else { Indexed::New })
+    }
+
+    /// Drops every transaction after `slot`, e.g. when those slots were
+    /// abandoned by a reorg. Returns how many transactions were dropped.
+    pub fn rollback(&mut self, slot: u64) -> Result<usize> {
+        let db = self.conn.transaction()?;
+        let dropped = db.execute("DELETE FROM transactions WHERE slot > ?1", [slot])?;
+        if dropped > 0 {
+            rebuild(&db)?;
+        }
+        db.commit()?;
+        Ok(dropped)
+    }
+
+    /// Newest indexed signature, where an RPC backfill resumes from.
+    pub fn latest_signature(&self) -> Result<Option<String>> {
+        Ok(self
+            .conn
+            .query_row(
+                "SELECT signature FROM transactions ORDER BY slot DESC, seq DESC LIMIT 1",
+                [],
+                |row| row.get(0),
+            )
+            .optional()?)
+    }
+}
+
+/// Recomputes the derived tables from the stored events.
+fn rebuild(db: &Transaction) -> Result<()> {
+    db.execute_batch("DELETE FROM games; DELETE FROM players; DELETE FROM payouts;")?;
+
+    let events = db
+        .prepare(
+            "SELECT e.signature, e.idx, t.slot, e.data FROM events e
+             JOIN transactions t ON t.signature = e.signature
+             ORDER BY t.slot, t.seq, e.idx",
+        )?
+        .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get(1)?, row.get(2)?, row.get::<_, Vec<u8>>(3)?)))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    for (signature, idx, slot, data) in events {
+        apply(db, &signature, idx, slot, &data)?;
+    }
+    Ok(())
+}
+
+fn record_player(db: &Transaction, player: &str, column: &str) -> Result<()> {
+    db.execute(
+        &format!(
+            "INSERT INTO players (address, {column}) VALUES (?1, 1)
+             ON CONFLICT(address) DO UPDATE SET {column} = {column} + 1"
+        ),
+        [player],
+    )?;
+    Ok(())
+}
+
+fn cancel_reason(reason: CancelReason) -> &'static str {
+    match reason {
+        CancelReason::ExpiredOpen => "expired_open",
+        CancelReason::ExpiredUnresolved => "expired_unresolved",
+        CancelReason::Draw => "draw",
+    }
+}
+
+fn apply(db: &Transaction, signature: &str, idx: usize, slot: u64, data: &[u8]) -> Result<()> {
+    let Some(event) = decode_event(data) else {
+        return Ok(());
+    };
+
+    match event {
+        WagerEvent::GameCreated(event) => {
+            // A crank-closed game's address can be reused by a new game
+            db.execute(
+                "INSERT OR REPLACE INTO games
+                 (address, creator, mint, wager, payout_bps, expiry_ts, state, created_slot, updated_slot)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, 'open', ?7, ?7)",
+                params![
+                    event.game.to_string(),
+                    event.creator.to_string(),
+                    event.mint.to_string(),
+                    event.wager,
+                    event.payout_bps,
+                    event.expiry_ts,
+                    slot,
+                ],
+            )?;
+        },
+        WagerEvent::GameJoined(event) => {
+            let (player1, player2) = (event.player1.to_string(), event.player2.to_string());
+            db.execute(
+                "UPDATE games SET player1 = ?2, player2 = ?3, state = 'ready', updated_slot = ?4 WHERE address = ?1",
+                params![event.game.to_string(), player1, player2, slot],
+            )?;
+            record_player(db, &player1, "games")?;
+            record_player(db, &player2, "games")?;
+        },
+        WagerEvent::GameResolved(event) => {
+            let game = event.game.to_string();
+            let winner = event.winner.to_string();
+            db.execute(
+                "UPDATE games SET state = 'paid', winner = ?2, winner_amount = ?3, fee_amount = ?4, updated_slot = ?5
+                 WHERE address = ?1",
+                params![game, winner, event.winner_amount, event.fee_amount, slot],
+            )?;
+
+            let players: Option<(Option<String>, Option<String>)> = db
+                .query_row("SELECT player1, player2 FROM games WHERE address = ?1", [&game], |row| {
+                    Ok((row.get(0)?, row.get(1)?))
+                })
+                .optional()?;
+            if let Some((Some(player1), Some(player2))) = players {
+                let loser = if winner == player1 { player2 } else { player1 };
+                record_player(db, &winner, "wins")?;
+                record_player(db, &loser, "losses")?;
+            }
+        },
+        WagerEvent::GameCanceled(event) => {
+            db.execute(
+                "UPDATE games SET state = 'canceled', cancel_reason = ?2, player1_refund = ?3, player2_refund = ?4,
+                 updated_slot = ?5 WHERE address = ?1",
+                params![
+                    event.game.to_string(),
+                    cancel_reason(event.reason),
+                    event.player1_refund,
+                    event.player2_refund,
+                    slot,
+                ],
+            )?;
+        },
+        WagerEvent::GameClaimed(event) => {
+            db.execute(
+                "INSERT OR REPLACE INTO payouts (signature, idx, game, recipient, mint, amount, slot)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    signature,
+                    idx,
+                    event.game.to_string(),
+                    event.recipient.to_string(),
+                    event.mint.to_string(),
+                    event.amount,
+                    slot,
+                ],
+            )?;
+        },
+        _ => {},
+    }
+    Ok(())
+}
@@ -0,0 +1,158 @@
+//! Extracts the program's events from transactions in the shape returned by
+//! `getTransaction` with `"encoding": "json"`.
+
+use anchor_lang::event::EVENT_IX_TAG_LE;
+use anyhow::{anyhow, Context, Result};
+use easy_wager_client::{decode_event, log_event_lines, ID};
+use serde_json::Value;
+
+pub struct IndexedTransaction {
+    pub signature: String,
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    // Failed transactions are recorded, but their events never happened
+    pub failed: bool,
+    // Discriminator and Borsh payload of each event, in emission order
+    pub events: Vec<Vec<u8>>,
+}
+
+/// One line of a transaction feed: a transaction, or a notice that every slot
+/// after `slot` was abandoned by a reorg.
+pub enum FeedItem {
+    Transaction(IndexedTransaction),
+    Rollback(u64),
+}
+
+pub fn parse_feed_line(line: &str) -> Result<FeedItem> {
+    let value: Value = serde_json::from_str(line)?;
+    if let Some(slot) = value.get("rollback") {
+        let slot = slot.as_u64().ok_or_else(|| anyhow!("rollback slot is not a number"))?;
+        return Ok(FeedItem::Rollback(slot));
+    }
+    parse_transaction(&value).map(FeedItem::Transaction)
+}
+
+pub fn parse_transaction(value: &Value) -> Result<IndexedTransaction> {
+    let signature = value["transaction"]["signatures"][0]
+        .as_str()
+        .ok_or_else(|| anyhow!("transaction has no signature"))?
+        .to_string();
+    let slot = value["slot"].as_u64().with_context(|| format!("{signature}: no slot"))?;
+    let meta = &value["meta"];
+    let failed = !meta["err"].is_null();
+
+    let events = if failed {
+        Vec::new()
+    } else {
+        let logs: Vec<&str> = meta["logMessages"]
+            .as_array()
+            .map(|logs| logs.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_default();
+        let cpi_events = cpi_event_data(value).with_context(|| format!("{signature}: bad inner instructions"))?;
+        merge_events(&logs, cpi_events)
+    };
+
+    Ok(IndexedTransaction {
+        signature,
+        slot,
+        block_time: value["blockTime"].as_i64(),
+        failed,
+        events: events.into_iter().filter(|data| decode_event(data).is_some()).collect(),
+    })
+}
+
+/// Position of an inner instruction: the index of the top-level instruction
+/// it ran under, then its index among that instruction's inner instructions.
+type InnerPosition = (usize, usize);
+
+/// Puts the events of both kinds in the order they were emitted. Lifecycle
+/// events go through `emit_cpi!` and survive log truncation; the rest are
+/// only in the logs. Each event is emitted one way, so taking both never
+/// double counts.
+///
+/// Inner instructions run in the order their `invoke [2+]` lines appear in
+/// the logs, so each CPI event goes where its self-invocation was logged and
+/// each log event where its `Program data:` line is. CPI events past the end
+/// of truncated logs follow everything logged.
+fn merge_events(logs: &[&str], cpi_events: Vec<(InnerPosition, Vec<u8>)>) -> Vec<Vec<u8>> {
+    let mut cpi_events = cpi_events.into_iter().peekable();
+    let mut log_events = log_event_lines(logs).into_iter().peekable();
+    let mut events = Vec::new();
+
+    // Top-level instruction being logged, and the inner instructions it has
+    // invoked so far
+    let mut instruction: Option<usize> = None;
+    let mut invoked = 0;
+    for (index, line) in logs.iter().enumerate() {
+        match invoke_depth(line) {
+            Some(1) => {
+                instruction = Some(instruction.map_or(0, |instruction| instruction + 1));
+                invoked = 0;
+            },
+            Some(_) => {
+                if let Some(instruction) = instruction {
+                    while let Some((_, data)) = cpi_events.next_if(|(position, _)| *position <= (instruction, invoked)) {
+                        events.push(data);
+                    }
+                }
+                invoked += 1;
+            },
+            None => {
+                while let Some((_, data)) = log_events.next_if(|(line, _)| *line == index) {
+                    events.push(data);
+                }
+            },
+        }
+    }
+    events.extend(cpi_events.map(|(_, data)| data));
+    events
+}
+
+/// Stack height of an `invoke` log line.
+fn invoke_depth(line: &str) -> Option<usize> {
+    let (_, depth) = line.strip_prefix("Program ")?.split_once(" invoke [")?;
+    depth.strip_suffix(']')?.parse().ok()
+}
+
+/// Event data from the program's self-invocations among the inner
+/// instructions, with the position of each, in execution order.
+fn cpi_event_data(value: &Value) -> Result<Vec<(InnerPosition, Vec<u8>)>> {
+    let meta = &value["meta"];
+    let Some(inner) = meta["innerInstructions"].as_array() else {
+        return Ok(Vec::new());
+    };
+
+    // Instructions index into the static keys followed by any keys loaded
+    // from address lookup tables
+    let keys: Vec<&str> = [
+        &value["transaction"]["message"]["accountKeys"],
+        &meta["loadedAddresses"]["writable"],
+        &meta["loadedAddresses"]["readonly"],
+    ]
+    .into_iter()
+    .filter_map(Value::as_array)
+    .flatten()
+    .filter_map(Value::as_str)
+    .collect();
+    let program = ID.to_string();
+
+    let mut events = Vec::new();
+    for set in inner {
+        let instruction = set["index"].as_u64().ok_or_else(|| anyhow!("no instruction index"))? as usize;
+        let Some(instructions) = set["instructions"].as_array() else {
+            continue;
+        };
+        for (position, inner_instruction) in instructions.iter().enumerate() {
+            let program_index = inner_instruction["programIdIndex"].as_u64().ok_or_else(|| anyhow!("no programIdIndex"))?;
+            if keys.get(program_index as usize) != Some(&program.as_str()) {
+                continue;
+            }
+            let data = bs58::decode(inner_instruction["data"].as_str().unwrap_or_default()).into_vec()?;
+            if let Some(event) = data.strip_prefix(EVENT_IX_TAG_LE) {
+                events.push(((instruction, position), event.to_vec()));
+            }
+        }
+    }
+    events.sort_by_key(|(position, _)| *position);
+    Ok(events)
+}
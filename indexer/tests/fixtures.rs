@@ -0,0 +1,205 @@
+//! Feeds the transactions in `fixtures/` (one `getTransaction` result per
+//! line, plus rollback notices) through the indexer. The fixtures are
+//! recorded against the program by `record_fixtures.rs`.
+
+use easy_wager_client::decode_event;
+use easy_wager_indexer::{parse_feed_line, FeedItem, Indexed, Store};
+
+const ALICE: &str = "AKnL4NNf3DGWZJS6cPknBuEGnVsV4A4m5tgebLHaRSZ9";
+const BOB: &str = "9hSR6S7WPtxmTojgo6GG3k4yDPecgJY292j7xrsUGWBu";
+const CAROL: &str = "GyGKxMyg1p9SsHfm15MkNUu1u9TN2JtTspcdmrtGUdse";
+const DEV: &str = "EdmxWPmx2WH6WgFfTdu9xfkYf3k1g5wD1zccTVySEEh1";
+// Resolved and claimed
+const GAME1: &str = "tHDvWGhADcVVsm4ExuySsF7mvbjWfuQF5WgDNE9hzSX";
+// Carol's two games, expired and closed by the crank in one transaction
+const GAME2: &str = "DpQFHvY84pCkBQXmsLyU821X2QkS25d9vyRo1dds7P7E";
+const GAME4: &str = "BpDizy387Erk5m673dD5RHuoBCFXretSunXfF3KWWiEw";
+// Left open
+const GAME3: &str = "EVNV1msF1KDCdUbnS7Jepn5m8yaWFB9Q6fgS7mc26Wqt";
+// The reorg fixture's game
+const REORG_GAME: &str = "2r9UxFZMKk6iXpvYotXDJCNsqYbM6eBbwqGnRcnEwvqp";
+
+fn fixture(name: &str) -> Vec<FeedItem> {
+    let path = format!("{}/tests/fixtures/{name}", env!("CARGO_MANIFEST_DIR"));
+    std::fs::read_to_string(path)
+        .unwrap()
+        .lines()
+        .map(|line| parse_feed_line(line).unwrap())
+        .collect()
+}
+
+fn feed(store: &mut Store, items: &[FeedItem]) -> Vec<Indexed> {
+    let mut results = Vec::new();
+    for item in items {
+        match item {
+            FeedItem::Transaction(transaction) => results.push(store.index(transaction).unwrap()),
+            FeedItem::Rollback(slot) => {
+                store.rollback(*slot).unwrap();
+            },
+        }
+    }
+    results
+}
+
+/// Every row of the derived tables, for comparing two stores.
+fn dump(store: &Store) -> Vec<String> {
+    let queries = [
+        "SELECT address, creator, player1, player2, mint, wager, state, winner, winner_amount, fee_amount, cancel_reason, player1_refund, player2_refund, created_slot, updated_slot FROM games ORDER BY address",
+        "SELECT address, games, wins, losses FROM players ORDER BY address",
+        "SELECT signature, idx, game, recipient, mint, amount, slot FROM payouts ORDER BY signature, idx",
+    ];
+    let mut rows = Vec::new();
+    for query in queries {
+        let conn = store.connection();
+        let mut statement = conn.prepare(query).unwrap();
+        let columns = statement.column_count();
+        let mut result = statement.query([]).unwrap();
+        while let Some(row) = result.next().unwrap() {
+            let values: Vec<String> = (0..columns)
+                .map(|i| format!("{:?}", row.get_ref(i).unwrap()))
+                .collect();
+            rows.push(values.join("|"));
+        }
+    }
+    rows
+}
+
+fn game_field(store: &Store, game: &str, column: &str) -> Option<String> {
+    store
+        .connection()
+        .query_row(&format!("SELECT CAST({column} AS TEXT) FROM games WHERE address = ?1"), [game], |row| row.get(0))
+        .unwrap()
+}
+
+fn player(store: &Store, address: &str) -> (u32, u32, u32) {
+    store
+        .connection()
+        .query_row("SELECT games, wins, losses FROM players WHERE address = ?1", [address], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+        })
+        .unwrap()
+}
+
+fn payouts(store: &Store, game: &str) -> Vec<(String, u64)> {
+    store
+        .connection()
+        .prepare("SELECT recipient, amount FROM payouts WHERE game = ?1 ORDER BY slot, recipient")
+        .unwrap()
+        .query_map([game], |row| Ok((row.get(0)?, row.get(1)?)))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap()
+}
+
+#[test]
+fn indexes_game_lifecycle() {
+    let mut store = Store::open_in_memory().unwrap();
+    let results = feed(&mut store, &fixture("game_lifecycle.jsonl"));
+
+    // The join is fed twice
+    assert_eq!(results.iter().filter(|result| **result == Indexed::Duplicate).count(), 1);
+
+    assert_eq!(game_field(&store, GAME1, "state").as_deref(), Some("paid"));
+    assert_eq!(game_field(&store, GAME1, "winner").as_deref(), Some(BOB));
+    assert_eq!(game_field(&store, GAME1, "player1").as_deref(), Some(ALICE));
+    assert_eq!(game_field(&store, GAME1, "fee_amount").as_deref(), Some("300000000"));
+    // The failed claim's event is not recorded
+    let mut game1_payouts = payouts(&store, GAME1);
+    game1_payouts.sort();
+    let mut expected = vec![(BOB.to_string(), 1_700_000_000), (DEV.to_string(), 300_000_000)];
+    expected.sort();
+    assert_eq!(game1_payouts, expected);
+
+    // The forged resolution logged by another program is ignored
+    assert_eq!(game_field(&store, GAME2, "state").as_deref(), Some("canceled"));
+    assert_eq!(game_field(&store, GAME2, "winner"), None);
+    assert_eq!(game_field(&store, GAME2, "cancel_reason").as_deref(), Some("expired_open"));
+    assert_eq!(game_field(&store, GAME2, "player1_refund").as_deref(), Some("500000000"));
+    assert_eq!(game_field(&store, GAME2, "player2_refund").as_deref(), Some("0"));
+    assert_eq!(payouts(&store, GAME2), vec![(CAROL.to_string(), 500_000_000)]);
+    assert_eq!(game_field(&store, GAME4, "state").as_deref(), Some("canceled"));
+    assert_eq!(payouts(&store, GAME4), vec![(CAROL.to_string(), 250_000_000)]);
+
+    assert_eq!(game_field(&store, GAME3, "state").as_deref(), Some("open"));
+    assert_eq!(game_field(&store, GAME3, "creator").as_deref(), Some(BOB));
+    assert_eq!(game_field(&store, GAME3, "wager").as_deref(), Some("2000000000"));
+
+    assert_eq!(player(&store, ALICE), (1, 0, 1));
+    assert_eq!(player(&store, BOB), (1, 1, 0));
+}
+
+#[test]
+fn crank_events_come_out_in_emission_order() {
+    let items = fixture("game_lifecycle.jsonl");
+    let crank = items
+        .iter()
+        .find_map(|item| match item {
+            FeedItem::Transaction(transaction) if transaction.slot == 109 => Some(transaction),
+            _ => None,
+        })
+        .unwrap();
+
+    // Each game's cancellation, refund and sweep, then the batch summary
+    let names: Vec<&str> = crank.events.iter().map(|data| decode_event(data).unwrap().name()).collect();
+    assert_eq!(
+        names,
+        ["GameCanceled", "GameClaimed", "EscrowSwept", "GameCanceled", "GameClaimed", "ExpiredGamesCranked"]
+    );
+}
+
+#[test]
+fn reindexing_is_idempotent() {
+    let items = fixture("game_lifecycle.jsonl");
+    let mut store = Store::open_in_memory().unwrap();
+    feed(&mut store, &items);
+    let first = dump(&store);
+
+    let results = feed(&mut store, &items);
+    assert!(results.iter().all(|result| *result == Indexed::Duplicate));
+    assert_eq!(dump(&store), first);
+}
+
+#[test]
+fn out_of_order_feed_matches_in_order() {
+    let items = fixture("game_lifecycle.jsonl");
+    let mut in_order = Store::open_in_memory().unwrap();
+    feed(&mut in_order, &items);
+
+    let mut reversed = Store::open_in_memory().unwrap();
+    let mut items = items;
+    items.reverse();
+    feed(&mut reversed, &items);
+
+    assert_eq!(dump(&reversed), dump(&in_order));
+}
+
+#[test]
+fn rollback_discards_abandoned_slots() {
+    let mut store = Store::open_in_memory().unwrap();
+    feed(&mut store, &fixture("reorg.jsonl"));
+
+    // Bob's win at slot 202 was rolled back; Alice won on the surviving fork
+    assert_eq!(game_field(&store, REORG_GAME, "state").as_deref(), Some("paid"));
+    assert_eq!(game_field(&store, REORG_GAME, "winner").as_deref(), Some(ALICE));
+    assert_eq!(game_field(&store, REORG_GAME, "updated_slot").as_deref(), Some("204"));
+    assert_eq!(player(&store, ALICE), (1, 1, 0));
+    assert_eq!(player(&store, BOB), (1, 0, 1));
+    let transactions: u32 = store.connection().query_row("SELECT COUNT(*) FROM transactions", [], |row| row.get(0)).unwrap();
+    assert_eq!(transactions, 3);
+}
+
+#[test]
+fn transaction_landing_in_another_slot_moves() {
+    let items = fixture("reorg.jsonl");
+    let mut store = Store::open_in_memory().unwrap();
+    // Create, join at slot 201, then the same join at slot 203 without a
+    // rollback notice
+    let FeedItem::Transaction(join_at_203) = &items[4] else { panic!("expected a transaction") };
+    feed(&mut store, &items[..2]);
+    assert_eq!(store.index(join_at_203).unwrap(), Indexed::Moved);
+
+    assert_eq!(game_field(&store, REORG_GAME, "state").as_deref(), Some("ready"));
+    assert_eq!(game_field(&store, REORG_GAME, "updated_slot").as_deref(), Some("203"));
+    assert_eq!(player(&store, BOB), (1, 0, 0));
+    assert_eq!(store.latest_signature().unwrap().as_deref(), Some(join_at_203.signature.as_str()));
+}
@@ -0,0 +1,250 @@
+//! Records the transactions in `fixtures/` by playing their scenarios against
+//! the program in LiteSVM and writing each one out the way `getTransaction`
+//! returns it. Ignored by default; rerun it whenever an event changes:
+//!
+//! ```text
+//! cargo test -p easy_wager_indexer --test record_fixtures -- --ignored
+//! ```
+//!
+//! Every keypair comes from a fixed seed, so the addresses `fixtures.rs`
+//! checks stay the same from one recording to the next.
+
+use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+use anchor_lang::Event;
+use easy_wager_client::instructions::*;
+use easy_wager_client::state::{Game, GameResolved};
+use easy_wager_client::{decode_game, find_game_pda, find_sol_vault_pda};
+use easy_wager_harness::litesvm::types::{FailedTransactionMetadata, TransactionMetadata};
+use easy_wager_harness::litesvm::LiteSVM;
+use easy_wager_harness::{svm, warp_to};
+use serde_json::{json, Value};
+use solana_program_runtime::{declare_process_instruction, stable_log};
+use solana_sdk::instruction::{Instruction, InstructionError};
+use solana_sdk::native_loader;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{keypair_from_seed, Keypair, Signer};
+use solana_sdk::transaction::{Transaction, TransactionError};
+
+// Clock time at slot 0; each slot is a second
+const GENESIS_TIME: i64 = 1_760_000_000;
+// Another program, logging whatever it's given as `Program data:`
+const FORGER: Pubkey = Pubkey::new_from_array([7; 32]);
+
+declare_process_instruction!(Forger, 1, |invoke_context| {
+    let instruction_context = invoke_context.transaction_context.get_current_instruction_context()?;
+    let data = instruction_context.get_instruction_data().to_vec();
+    stable_log::program_data(&invoke_context.get_log_collector(), &[&data]);
+    Ok(())
+});
+
+/// Alice, Bob, Carol, the dev wallet and the cranker.
+fn keypair(seed: u8) -> Keypair {
+    keypair_from_seed(&[seed; 32]).unwrap()
+}
+
+struct Recorder {
+    svm: LiteSVM,
+    lines: Vec<Value>,
+}
+
+impl Recorder {
+    fn new() -> Self {
+        let mut svm = svm();
+        svm.add_builtin(FORGER, Forger::vm);
+        let mut forger = svm.get_account(&FORGER).unwrap();
+        forger.owner = native_loader::ID;
+        svm.set_account(FORGER, forger).unwrap();
+        for seed in 1..=5 {
+            svm.airdrop(&keypair(seed).pubkey(), 10 * LAMPORTS_PER_SOL).unwrap();
+        }
+        Self { svm, lines: Vec::new() }
+    }
+
+    /// Sends `instructions` at `slot`, recording the transaction whether it
+    /// lands or fails.
+    fn send(&mut self, slot: u64, instructions: &[Instruction], signers: &[&Keypair]) -> Value {
+        self.svm.warp_to_slot(slot);
+        let block_time = GENESIS_TIME + slot as i64;
+        warp_to(&mut self.svm, block_time);
+
+        let tx = Transaction::new_signed_with_payer(instructions, Some(&signers[0].pubkey()), signers, self.svm.latest_blockhash());
+        let result = self.svm.send_transaction(tx.clone());
+        self.svm.expire_blockhash();
+        let value = match &result {
+            Ok(meta) => transaction_json(&tx, slot, block_time, meta, None),
+            Err(FailedTransactionMetadata { err, meta }) => transaction_json(&tx, slot, block_time, meta, Some(err)),
+        };
+        self.lines.push(value.clone());
+        value
+    }
+
+    fn game(&self, game: &Pubkey) -> Game {
+        decode_game(&self.svm.get_account(game).unwrap().data).unwrap()
+    }
+
+    fn write(self, name: &str) {
+        let path = format!("{}/tests/fixtures/{name}", env!("CARGO_MANIFEST_DIR"));
+        let lines: Vec<String> = self.lines.iter().map(Value::to_string).collect();
+        std::fs::write(path, lines.join("\n") + "\n").unwrap();
+    }
+}
+
+/// A processed transaction as `getTransaction` returns it with
+/// `"encoding": "json"`.
+fn transaction_json(tx: &Transaction, slot: u64, block_time: i64, meta: &TransactionMetadata, err: Option<&TransactionError>) -> Value {
+    let message = &tx.message;
+    let inner_instructions: Vec<Value> = meta
+        .inner_instructions
+        .iter()
+        .enumerate()
+        .filter(|(_, instructions)| !instructions.is_empty())
+        .map(|(index, instructions)| {
+            let instructions: Vec<Value> = instructions
+                .iter()
+                .map(|inner| {
+                    json!({
+                        "programIdIndex": inner.instruction.program_id_index,
+                        "accounts": inner.instruction.accounts,
+                        "data": bs58::encode(&inner.instruction.data).into_string(),
+                        "stackHeight": inner.stack_height,
+                    })
+                })
+                .collect();
+            json!({ "index": index, "instructions": instructions })
+        })
+        .collect();
+    let err = err.map(error_json);
+
+    json!({
+        "slot": slot,
+        "blockTime": block_time,
+        "transaction": {
+            "signatures": tx.signatures.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            "message": {
+                "accountKeys": message.account_keys.iter().map(ToString::to_string).collect::<Vec<_>>(),
+                "header": {
+                    "numRequiredSignatures": message.header.num_required_signatures,
+                    "numReadonlySignedAccounts": message.header.num_readonly_signed_accounts,
+                    "numReadonlyUnsignedAccounts": message.header.num_readonly_unsigned_accounts,
+                },
+                "instructions": message.instructions.iter().map(|instruction| json!({
+                    "programIdIndex": instruction.program_id_index,
+                    "accounts": instruction.accounts,
+                    "data": bs58::encode(&instruction.data).into_string(),
+                })).collect::<Vec<_>>(),
+                "recentBlockhash": message.recent_blockhash.to_string(),
+            },
+        },
+        "meta": {
+            "err": err,
+            "status": match &err {
+                None => json!({ "Ok": null }),
+                Some(err) => json!({ "Err": err }),
+            },
+            "fee": 5_000 * tx.signatures.len() as u64,
+            "preBalances": [],
+            "postBalances": [],
+            "innerInstructions": inner_instructions,
+            "logMessages": meta.logs,
+            "loadedAddresses": { "writable": [], "readonly": [] },
+            "computeUnitsConsumed": meta.compute_units_consumed,
+        },
+        "version": "legacy",
+    })
+}
+
+fn error_json(err: &TransactionError) -> Value {
+    match err {
+        TransactionError::InstructionError(index, InstructionError::Custom(code)) => {
+            json!({ "InstructionError": [index, { "Custom": code }] })
+        },
+        err => json!(format!("{err:?}")),
+    }
+}
+
+fn create(creator: &Keypair, nonce: u64, wager: u64, expires_in: i64, dev_wallet: Pubkey, now: i64) -> (Pubkey, Instruction) {
+    let args = CreateGameArgs::new(creator.pubkey(), Pubkey::default(), wager, 8_500, now + expires_in, nonce, dev_wallet);
+    (args.game(), create_game(&args))
+}
+
+#[test]
+#[ignore = "rewrites tests/fixtures"]
+fn record_game_lifecycle() {
+    let mut recorder = Recorder::new();
+    let [alice, bob, carol, dev, cranker] = [1, 2, 3, 4, 5].map(keypair);
+    let time = |slot: u64| GENESIS_TIME + slot as i64;
+
+    // Alice against Bob, resolved and claimed
+    let (game1, ix) = create(&alice, 0, LAMPORTS_PER_SOL, 600, dev.pubkey(), time(100));
+    recorder.send(100, &[ix], &[&alice]);
+    let state = recorder.game(&game1);
+    let join = recorder.send(101, &[join_game(&game1, &state, &JoinGameArgs::new(bob.pubkey()))], &[&bob]);
+
+    // Carol's two games, left to expire
+    let (game2, ix) = create(&carol, 0, LAMPORTS_PER_SOL / 2, 5, dev.pubkey(), time(102));
+    recorder.send(102, &[ix], &[&carol]);
+    let (game4, ix) = create(&carol, 1, LAMPORTS_PER_SOL / 4, 5, dev.pubkey(), time(103));
+    recorder.send(103, &[ix], &[&carol]);
+
+    let state = recorder.game(&game1);
+    recorder.send(105, &[resolve_game(&alice.pubkey(), &game1, &state, &bob.pubkey())], &[&alice]);
+    // Claiming twice fails as a whole, taking the first claim's event with it
+    let state = recorder.game(&game1);
+    let claim_bob = claim(&bob.pubkey(), &game1, &state, &bob.pubkey(), ClaimTo::Wallet);
+    recorder.send(106, &[claim_bob.clone(), claim_bob.clone()], &[&bob]);
+    recorder.send(107, &[claim_bob], &[&bob]);
+    recorder.send(107, &[claim(&dev.pubkey(), &game1, &state, &dev.pubkey(), ClaimTo::Wallet)], &[&dev]);
+
+    // Another program logs a resolution of Carol's game
+    let forged = GameResolved {
+        game: game2,
+        winner: carol.pubkey(),
+        mint: Pubkey::default(),
+        winner_amount: LAMPORTS_PER_SOL,
+        fee_amount: 0,
+        timestamp: time(108),
+    };
+    recorder.send(108, &[Instruction::new_with_bytes(FORGER, &forged.data(), vec![])], &[&carol]);
+
+    // Sent to a vault by mistake, and swept to Carol by the crank
+    recorder.svm.airdrop(&find_sol_vault_pda(&game2).0, 1_000).unwrap();
+    let states = [recorder.game(&game2), recorder.game(&game4)];
+    let crank = crank_expired(&cranker.pubkey(), &[(game2, &states[0]), (game4, &states[1])]);
+    recorder.send(109, &[crank], &[&cranker]);
+
+    let (_game3, ix) = create(&bob, 0, 2 * LAMPORTS_PER_SOL, 600, dev.pubkey(), time(110));
+    recorder.send(110, &[ix], &[&bob]);
+
+    // The join again, as a feed that replays it would
+    recorder.lines.push(join);
+    recorder.write("game_lifecycle.jsonl");
+}
+
+#[test]
+#[ignore = "rewrites tests/fixtures"]
+fn record_reorg() {
+    let mut recorder = Recorder::new();
+    let [alice, bob, dev] = [1, 2, 4].map(keypair);
+    let game = find_game_pda(&alice.pubkey(), 7).0;
+
+    let (_, ix) = create(&alice, 7, LAMPORTS_PER_SOL, 600, dev.pubkey(), GENESIS_TIME + 200);
+    recorder.send(200, &[ix], &[&alice]);
+    let state = recorder.game(&game);
+    let join = recorder.send(201, &[join_game(&game, &state, &JoinGameArgs::new(bob.pubkey()))], &[&bob]);
+    let fork = recorder.svm.clone();
+
+    // Bob wins at slot 202, on a fork that's abandoned
+    let state = recorder.game(&game);
+    recorder.send(202, &[resolve_game(&alice.pubkey(), &game, &state, &bob.pubkey())], &[&alice]);
+    recorder.lines.push(json!({ "rollback": 200 }));
+
+    // The same join lands again at 203, then Alice wins
+    let mut join = join;
+    join["slot"] = json!(203);
+    join["blockTime"] = json!(GENESIS_TIME + 203);
+    recorder.lines.push(join);
+    recorder.svm = fork;
+    recorder.send(204, &[resolve_game(&alice.pubkey(), &game, &state, &alice.pubkey())], &[&alice]);
+
+    recorder.write("reorg.jsonl");
+}
@@ -58,4 +58,163 @@ pub enum WagerError {
 
     #[msg("Game state transition not allowed")]
     InvalidStateTransition,
+
+    #[msg("No valid resolver attestation found in transaction")]
+    InvalidAttestation,
+
+    #[msg("Series length must be an odd number of rounds up to the maximum")]
+    InvalidSeriesLength,
+
+    #[msg("Series games are settled by reporting rounds")]
+    SeriesGame,
+
+    #[msg("Game is not a series")]
+    NotASeries,
+
+    #[msg("Only the organizer can perform this action")]
+    UnauthorizedOrganizer,
+
+    #[msg("Tournament is not accepting registrations")]
+    RegistrationClosed,
+
+    #[msg("Tournament registration is still open")]
+    RegistrationStillOpen,
+
+    #[msg("Tournament is full")]
+    TournamentFull,
+
+    #[msg("Player is already registered")]
+    AlreadyRegistered,
+
+    #[msg("Max players must be a power of two between 2 and 32")]
+    InvalidBracketSize,
+
+    #[msg("Payout table must have one entry per tier and pay out exactly 10000 bps")]
+    InvalidPayoutTable,
+
+    #[msg("Seeding must be a permutation of registered players")]
+    InvalidSeeding,
+
+    #[msg("Not enough players to start the tournament")]
+    NotEnoughPlayers,
+
+    #[msg("Tournament is not in progress")]
+    TournamentNotInProgress,
+
+    #[msg("Match does not exist or is not ready to be reported")]
+    InvalidMatch,
+
+    #[msg("Tournament prizes are not claimable yet")]
+    TournamentNotSettled,
+
+    #[msg("Prize has already been claimed")]
+    PrizeAlreadyClaimed,
+
+    #[msg("Nothing to claim for this recipient")]
+    NothingToClaim,
+
+    #[msg("Side pool is not accepting bets")]
+    SideBettingClosed,
+
+    #[msg("Side pool account does not match the game")]
+    SidePoolMismatch,
+
+    #[msg("Side pool has not been settled")]
+    SidePoolNotSettled,
+
+    #[msg("Escrow balance is lower than the amount owed")]
+    InsufficientEscrow,
+
+    #[msg("Staked asset is not an NFT or does not match the game's terms")]
+    InvalidNft,
+
+    #[msg("NFT is not a verified member of the required collection")]
+    CollectionMismatch,
+
+    #[msg("NFT has already been claimed")]
+    NftAlreadyClaimed,
+
+    #[msg("Basket legs must have both a mint and a non-zero amount")]
+    InvalidBasket,
+
+    #[msg("Remaining accounts do not match the basket legs")]
+    BasketAccountMismatch,
+
+    #[msg("Each side must stake a wager, an NFT or a basket")]
+    EmptyStake,
+
+    #[msg("Player stats accounts are required to settle this game")]
+    MissingPlayerStats,
+
+    #[msg("Rating band is invalid")]
+    InvalidRatingBand,
+
+    #[msg("Player's rating is outside the game's rating band")]
+    RatingOutOfBand,
+
+    #[msg("Rating accounts are required for ranked games")]
+    MissingRating,
+
+    #[msg("Matchmaking queue is full")]
+    QueueFull,
+
+    #[msg("Player is already waiting in this queue")]
+    AlreadyQueued,
+
+    #[msg("Player is not waiting in this queue")]
+    NotQueued,
+
+    #[msg("At least two players must be waiting to make a match")]
+    NotEnoughQueued,
+
+    #[msg("Player balance is too low")]
+    InsufficientBalance,
+
+    #[msg("Session key has expired")]
+    SessionExpired,
+
+    #[msg("Session key may not stake this mint")]
+    SessionMintNotAllowed,
+
+    #[msg("Session key spending cap exceeded")]
+    SessionCapExceeded,
+
+    #[msg("Session keys can only stake fungible wagers from the owner's balance")]
+    SessionStakeNotAllowed,
+
+    #[msg("Session approval is invalid")]
+    InvalidSession,
+
+    #[msg("Account is not a game of this program")]
+    InvalidGameAccount,
+
+    #[msg("Game is already on the current layout")]
+    GameAlreadyMigrated,
+
+    #[msg("Remaining accounts do not match the games in the batch")]
+    BatchAccountMismatch,
+
+    #[msg("Game can't be closed by the crank")]
+    GameNotCrankable,
+
+    #[msg("Only the recipient can claim into their player balance")]
+    UnauthorizedBalanceCredit,
+
+    #[msg("Session key may not stake on a game with this resolver, dev wallet or payout")]
+    SessionTermsNotAllowed,
+
+    #[msg("Relayer fee is only paid to a separate payer and never from a session key's stake")]
+    RelayerFeeNotAllowed,
+
+    #[msg("Game account holds an unknown state")]
+    InvalidGameState,
+
+    #[msg("Account does not match the address derived from the game")]
+    GameAccountMismatch,
+
+    #[msg("NFT fee is only charged when an NFT is staked, and up to the maximum")]
+    InvalidAssetFee,
+
+    #[msg("Side pool still has bets or its fee to claim")]
+    SidePoolNotEmpty,
 }
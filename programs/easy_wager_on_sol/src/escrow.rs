@@ -0,0 +1,661 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::metadata::mpl_token_metadata::accounts::MasterEdition;
+use anchor_spl::metadata::MetadataAccount;
+use anchor_spl::token::{
+    self, CloseAccount, InitializeAccount3, Mint, SyncNative, Token, TokenAccount, Transfer,
+};
+use crate::{state::*, error::WagerError};
+
+/// Accounts used to move a player's stake into a game's escrow.
+pub struct Deposit<'a, 'info> {
+    pub depositor: &'a Signer<'info>,
+    // Funds the SOL vault's rent reserve; may be a relayer rather than the player
+    pub payer: &'a Signer<'info>,
+    pub depositor_token_account: Option<&'a Account<'info, TokenAccount>>,
+    // Pre-funded balance to debit instead of the depositor's wallet, and its
+    // token vault for SPL games
+    pub balance: Option<&'a mut Account<'info, PlayerBalance>>,
+    pub balance_vault: Option<&'a Account<'info, TokenAccount>>,
+    pub game: &'a AccountLoader<'info, Game>,
+    pub vault: Option<&'a Account<'info, TokenAccount>>,
+    pub sol_vault: &'a SystemAccount<'info>,
+    pub token_program: &'a Program<'info, Token>,
+    pub system_program: &'a Program<'info, System>,
+}
+
+/// Moves `amount` from the depositor into escrow. Native SOL games hold
+/// lamports in the SOL vault, kept apart from the game account's rent. SPL
+/// games hold tokens in the vault; wrapped SOL games accept either wSOL from a
+/// token account or plain SOL, which is wrapped in the vault with `sync_native`.
+/// With a player balance the stake is debited from it instead.
+pub fn deposit(accounts: Deposit<'_, '_>, amount: u64) -> Result<()> {
+    let game = accounts.game.load()?;
+
+    // Sides staking only an NFT or a basket may owe nothing here
+    if amount == 0 {
+        return Ok(());
+    }
+
+    if game.is_native_sol() {
+        // The first deposit's payer also funds the vault's rent reserve, so
+        // partial payouts never leave it below rent exemption
+        let reserve = Rent::get()?.minimum_balance(0);
+        let top_up = reserve.saturating_sub(accounts.sol_vault.lamports());
+        if top_up > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: accounts.payer.to_account_info(),
+                        to: accounts.sol_vault.to_account_info(),
+                    }
+                ),
+                top_up
+            )?;
+        }
+
+        // The stake itself comes from the wallet unless a balance covers it
+        if accounts.balance.is_none() {
+            system_program::transfer(
+                CpiContext::new(
+                    accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: accounts.depositor.to_account_info(),
+                        to: accounts.sol_vault.to_account_info(),
+                    }
+                ),
+                amount
+            )?;
+        }
+    }
+
+    if let Some(balance) = accounts.balance {
+        let destination = if game.is_native_sol() {
+            accounts.sol_vault.to_account_info()
+        } else {
+            accounts.vault.ok_or(WagerError::InvalidTokenAccount)?.to_account_info()
+        };
+        return debit_balance(balance, accounts.balance_vault, &destination, accounts.token_program, amount);
+    }
+
+    if game.is_native_sol() {
+        return Ok(());
+    }
+
+    let vault = accounts.vault.ok_or(WagerError::InvalidTokenAccount)?;
+    require!(vault.mint == game.mint, WagerError::TokenMintMismatch);
+
+    match accounts.depositor_token_account {
+        Some(depositor_token_account) => {
+            require!(depositor_token_account.mint == game.mint, WagerError::TokenMintMismatch);
+
+            let transfer_ctx = CpiContext::new(
+                accounts.token_program.to_account_info(),
+                Transfer {
+                    from: depositor_token_account.to_account_info(),
+                    to: vault.to_account_info(),
+                    authority: accounts.depositor.to_account_info(),
+                }
+            );
+            token::transfer(transfer_ctx, amount)?;
+        },
+        None => {
+            // Only wrapped SOL games can be funded without a token account
+            require!(game.is_wrapped_sol(), WagerError::InvalidTokenAccount);
+
+            system_program::transfer(
+                CpiContext::new(
+                    accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: accounts.depositor.to_account_info(),
+                        to: vault.to_account_info(),
+                    }
+                ),
+                amount
+            )?;
+            token::sync_native(CpiContext::new(
+                accounts.token_program.to_account_info(),
+                SyncNative {
+                    account: vault.to_account_info(),
+                }
+            ))?;
+        },
+    }
+
+    Ok(())
+}
+
+/// Moves `amount` out of a player balance. Native SOL balances hold lamports
+/// on the balance account itself; SPL balances hold tokens in a vault owned
+/// by it.
+pub fn debit_balance<'info>(
+    balance: &mut Account<'info, PlayerBalance>,
+    balance_vault: Option<&Account<'info, TokenAccount>>,
+    destination: &AccountInfo<'info>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    require!(balance.amount >= amount, WagerError::InsufficientBalance);
+    balance.amount -= amount;
+
+    if balance.is_native_sol() {
+        balance.sub_lamports(amount)?;
+        destination.add_lamports(amount)?;
+        return Ok(());
+    }
+
+    let balance_vault = balance_vault.ok_or(WagerError::InvalidTokenAccount)?;
+    require_keys_eq!(balance_vault.owner, balance.key(), WagerError::InvalidTokenAccount);
+
+    // Create signer seeds for the balance PDA
+    let owner_key = balance.owner;
+    let mint_key = balance.mint;
+    let bump_bytes = [balance.bump];
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"balance",
+        owner_key.as_ref(),
+        mint_key.as_ref(),
+        &bump_bytes,
+    ]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        Transfer {
+            from: balance_vault.to_account_info(),
+            to: destination.clone(),
+            authority: balance.to_account_info(),
+        },
+        signer_seeds
+    );
+    token::transfer(transfer_ctx, amount)
+}
+
+/// Accounts used to move a player's NFT into its own vault on the game.
+pub struct NftDeposit<'a, 'info> {
+    pub depositor: &'a Signer<'info>,
+    pub nft_mint: Option<&'a Account<'info, Mint>>,
+    pub depositor_nft_account: Option<&'a Account<'info, TokenAccount>>,
+    pub nft_vault: Option<&'a Account<'info, TokenAccount>>,
+    pub nft_metadata: Option<&'a Account<'info, MetadataAccount>>,
+    pub token_program: &'a Program<'info, Token>,
+}
+
+/// Escrows the NFT staked against `asset` and returns its mint. The NFT must
+/// have supply 1, nobody able to mint more or freeze it in escrow, and, if the
+/// terms name a collection, carry verified Metaplex metadata for it.
+pub fn deposit_nft(accounts: NftDeposit<'_, '_>, asset: &StakeAsset) -> Result<Pubkey> {
+    let nft_mint = accounts.nft_mint.ok_or(WagerError::InvalidNft)?;
+    let depositor_nft_account = accounts.depositor_nft_account.ok_or(WagerError::InvalidNft)?;
+    let nft_vault = accounts.nft_vault.ok_or(WagerError::InvalidNft)?;
+
+    require!(nft_mint.supply == 1 && nft_mint.decimals == 0, WagerError::InvalidNft);
+
+    // Metaplex hands both authorities to the master edition, which can't use them
+    let master_edition = MasterEdition::find_pda(&nft_mint.key()).0;
+    let locked = |authority: COption<Pubkey>| authority.is_none() || authority == COption::Some(master_edition);
+    require!(
+        locked(nft_mint.mint_authority) && locked(nft_mint.freeze_authority),
+        WagerError::InvalidNft
+    );
+    require!(
+        asset.mint == Pubkey::default() || asset.mint == nft_mint.key(),
+        WagerError::InvalidNft
+    );
+
+    if asset.collection != Pubkey::default() {
+        let metadata = accounts.nft_metadata.ok_or(WagerError::CollectionMismatch)?;
+        require!(metadata.mint == nft_mint.key(), WagerError::CollectionMismatch);
+
+        let verified = metadata
+            .collection
+            .as_ref()
+            .is_some_and(|collection| collection.verified && collection.key == asset.collection);
+        require!(verified, WagerError::CollectionMismatch);
+    }
+
+    let transfer_ctx = CpiContext::new(
+        accounts.token_program.to_account_info(),
+        Transfer {
+            from: depositor_nft_account.to_account_info(),
+            to: nft_vault.to_account_info(),
+            authority: accounts.depositor.to_account_info(),
+        }
+    );
+    token::transfer(transfer_ctx, 1)?;
+
+    Ok(nft_mint.key())
+}
+
+/// Escrows every leg of `basket` into the game's per-mint basket vault,
+/// creating the vault on first use. `remaining_accounts` holds, for each used
+/// leg in order: the leg's mint, the depositor's token account and the vault.
+/// New vaults are funded by `payer`.
+pub fn deposit_basket<'info>(
+    depositor: &Signer<'info>,
+    payer: &Signer<'info>,
+    game_account: &AccountLoader<'info, Game>,
+    basket: &Basket,
+    remaining_accounts: &[AccountInfo<'info>],
+    token_program: &Program<'info, Token>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    let legs: Vec<&BasketLeg> = basket.used_legs().map(|(_, leg)| leg).collect();
+    require!(remaining_accounts.len() == legs.len() * 3, WagerError::BasketAccountMismatch);
+
+    for (leg, leg_accounts) in legs.into_iter().zip(remaining_accounts.chunks_exact(3)) {
+        let [mint, source, vault] = leg_accounts else {
+            return Err(WagerError::BasketAccountMismatch.into());
+        };
+        require_keys_eq!(mint.key(), leg.mint, WagerError::BasketAccountMismatch);
+
+        let game_key = game_account.key();
+        let (vault_key, vault_bump) = Pubkey::find_program_address(
+            &[b"basket_vault", game_key.as_ref(), leg.mint.as_ref()],
+            &crate::ID,
+        );
+        require_keys_eq!(vault.key(), vault_key, WagerError::BasketAccountMismatch);
+
+        // Legs of either side in the same mint share one vault
+        if vault.owner != &token_program.key() {
+            let bump_bytes = [vault_bump];
+            let vault_seeds: &[&[&[u8]]] = &[&[
+                b"basket_vault",
+                game_key.as_ref(),
+                leg.mint.as_ref(),
+                &bump_bytes,
+            ]];
+
+            system_program::create_account(
+                CpiContext::new_with_signer(
+                    system_program.to_account_info(),
+                    system_program::CreateAccount {
+                        from: payer.to_account_info(),
+                        to: vault.clone(),
+                    },
+                    vault_seeds
+                ),
+                Rent::get()?.minimum_balance(TokenAccount::LEN),
+                TokenAccount::LEN as u64,
+                &token_program.key()
+            )?;
+            token::initialize_account3(CpiContext::new(
+                token_program.to_account_info(),
+                InitializeAccount3 {
+                    account: vault.clone(),
+                    mint: mint.clone(),
+                    authority: game_account.to_account_info(),
+                }
+            ))?;
+        }
+
+        let transfer_ctx = CpiContext::new(
+            token_program.to_account_info(),
+            Transfer {
+                from: source.clone(),
+                to: vault.clone(),
+                authority: depositor.to_account_info(),
+            }
+        );
+        token::transfer(transfer_ctx, leg.amount)?;
+    }
+
+    Ok(())
+}
+
+/// Pays `amount` of a basket leg from its vault to `recipient_token_account`.
+pub fn withdraw_basket_leg<'info>(
+    game_account: &AccountLoader<'info, Game>,
+    leg: &BasketLeg,
+    vault: &AccountInfo<'info>,
+    recipient_token_account: &AccountInfo<'info>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    let game = game_account.load()?;
+    let (vault_key, _) = Pubkey::find_program_address(
+        &[b"basket_vault", game_account.key().as_ref(), leg.mint.as_ref()],
+        &crate::ID,
+    );
+    require_keys_eq!(vault.key(), vault_key, WagerError::BasketAccountMismatch);
+
+    let vault_balance = TokenAccount::try_deserialize(&mut &vault.try_borrow_data()?[..])?.amount;
+    require!(vault_balance >= amount, WagerError::InsufficientEscrow);
+
+    // Create signer seeds for the game PDA
+    let creator_key = game.creator;
+    let nonce_bytes = game.nonce.to_le_bytes();
+    let bump_bytes = [game.bump];
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"game",
+        creator_key.as_ref(),
+        nonce_bytes.as_ref(),
+        &bump_bytes,
+    ]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        Transfer {
+            from: vault.clone(),
+            to: recipient_token_account.clone(),
+            authority: game_account.to_account_info(),
+        },
+        signer_seeds
+    );
+    token::transfer(transfer_ctx, amount)
+}
+
+/// Reimburses a relayer `fee` out of the stake just escrowed: lamports from
+/// the SOL vault, or tokens from the vault to the relayer's token account.
+#[allow(clippy::too_many_arguments)]
+pub fn pay_relayer<'info>(
+    game_account: &AccountLoader<'info, Game>,
+    vault: Option<&Account<'info, TokenAccount>>,
+    sol_vault: &SystemAccount<'info>,
+    relayer: &AccountInfo<'info>,
+    relayer_token_account: Option<&Account<'info, TokenAccount>>,
+    token_program: &Program<'info, Token>,
+    system_program: &Program<'info, System>,
+    fee: u64,
+) -> Result<()> {
+    let game = game_account.load()?;
+    if fee == 0 {
+        return Ok(());
+    }
+
+    if game.is_native_sol() {
+        return transfer_from_sol_vault(game_account, sol_vault, system_program, relayer, fee);
+    }
+
+    let vault = vault.ok_or(WagerError::InvalidTokenAccount)?;
+    let relayer_token_account = relayer_token_account.ok_or(WagerError::InvalidTokenAccount)?;
+    require!(relayer_token_account.mint == game.mint, WagerError::TokenMintMismatch);
+
+    // Create signer seeds for the game PDA
+    let creator_key = game.creator;
+    let nonce_bytes = game.nonce.to_le_bytes();
+    let bump_bytes = [game.bump];
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"game",
+        creator_key.as_ref(),
+        nonce_bytes.as_ref(),
+        &bump_bytes,
+    ]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        Transfer {
+            from: vault.to_account_info(),
+            to: relayer_token_account.to_account_info(),
+            authority: game_account.to_account_info(),
+        },
+        signer_seeds
+    );
+    token::transfer(transfer_ctx, fee)
+}
+
+/// Accounts used to pay a recipient out of a game's escrow.
+pub struct Withdrawal<'a, 'info> {
+    pub game: &'a AccountLoader<'info, Game>,
+    pub recipient: &'a UncheckedAccount<'info>,
+    pub recipient_token_account: Option<&'a Account<'info, TokenAccount>>,
+    // Recipient's player balance to credit instead of their wallet, and its
+    // token vault for SPL games
+    pub recipient_balance: Option<&'a mut Account<'info, PlayerBalance>>,
+    pub recipient_balance_vault: Option<&'a Account<'info, TokenAccount>>,
+    pub vault: Option<&'a Account<'info, TokenAccount>>,
+    pub sol_vault: &'a SystemAccount<'info>,
+    // Temporary wSOL account used to unwrap payouts of wrapped SOL games, and
+    // the account that funded its rent
+    pub unwrap_account: Option<&'a Account<'info, TokenAccount>>,
+    pub rent_payer: &'a AccountInfo<'info>,
+    pub token_program: &'a Program<'info, Token>,
+    pub system_program: &'a Program<'info, System>,
+}
+
+/// Pays `amount` out of escrow after checking the escrow actually holds it.
+/// Wrapped SOL payouts without a recipient token account are unwrapped: tokens
+/// move to a temporary account that is closed into the SOL vault, which then
+/// forwards the SOL to the recipient and the temporary account's rent back to
+/// whoever funded it. Payouts into a player balance are credited to it.
+pub fn withdraw(mut accounts: Withdrawal<'_, '_>, amount: u64) -> Result<()> {
+    let game = accounts.game.load()?;
+
+    let balance_destination = match accounts.recipient_balance.as_deref_mut() {
+        Some(balance) => {
+            balance.amount = balance.amount.checked_add(amount).ok_or(WagerError::MathOverflow)?;
+            if game.is_native_sol() {
+                Some(balance.to_account_info())
+            } else {
+                let balance_vault = accounts.recipient_balance_vault.ok_or(WagerError::InvalidTokenAccount)?;
+                require_keys_eq!(balance_vault.owner, balance.key(), WagerError::InvalidTokenAccount);
+                Some(balance_vault.to_account_info())
+            }
+        },
+        None => None,
+    };
+
+    if game.is_native_sol() {
+        let reserve = Rent::get()?.minimum_balance(0);
+        let escrowed = accounts.sol_vault.lamports().saturating_sub(reserve);
+        require!(escrowed >= amount, WagerError::InsufficientEscrow);
+
+        let to = balance_destination.unwrap_or_else(|| accounts.recipient.to_account_info());
+        transfer_from_sol_vault(accounts.game, accounts.sol_vault, accounts.system_program, &to, amount)?;
+        return Ok(());
+    }
+
+    let vault = accounts.vault.ok_or(WagerError::InvalidTokenAccount)?;
+    require!(vault.amount >= amount, WagerError::InsufficientEscrow);
+
+    // Create signer seeds for the game PDA
+    let creator_key = game.creator;
+    let nonce_bytes = game.nonce.to_le_bytes();
+    let bump_bytes = [game.bump];
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"game",
+        creator_key.as_ref(),
+        nonce_bytes.as_ref(),
+        &bump_bytes,
+    ]];
+
+    let unwrap = balance_destination.is_none() && accounts.recipient_token_account.is_none();
+    let destination = match (balance_destination, accounts.recipient_token_account, accounts.unwrap_account) {
+        (Some(balance_destination), _, _) => balance_destination,
+        (None, Some(recipient_token_account), _) => recipient_token_account.to_account_info(),
+        (None, None, Some(unwrap_account)) if game.is_wrapped_sol() => unwrap_account.to_account_info(),
+        _ => return Err(WagerError::InvalidTokenAccount.into()),
+    };
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        accounts.token_program.to_account_info(),
+        Transfer {
+            from: vault.to_account_info(),
+            to: destination.clone(),
+            authority: accounts.game.to_account_info(),
+        },
+        signer_seeds
+    );
+    token::transfer(transfer_ctx, amount)?;
+
+    if unwrap {
+        // Close-to-unwrap into the SOL vault, then forward the SOL
+        let unwrap_lamports = destination.lamports();
+        let close_ctx = CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: destination,
+                destination: accounts.sol_vault.to_account_info(),
+                authority: accounts.game.to_account_info(),
+            },
+            signer_seeds
+        );
+        token::close_account(close_ctx)?;
+
+        let rent = unwrap_lamports.checked_sub(amount).ok_or(WagerError::MathOverflow)?;
+        transfer_from_sol_vault(accounts.game, accounts.sol_vault, accounts.system_program, accounts.recipient, amount)?;
+        transfer_from_sol_vault(accounts.game, accounts.sol_vault, accounts.system_program, accounts.rent_payer, rent)?;
+    }
+
+    Ok(())
+}
+
+/// Delivers an escrowed NFT to `recipient_token_account` and closes its vault,
+/// returning the vault's rent to `staker`, who funded it.
+pub fn withdraw_nft<'info>(
+    game_account: &AccountLoader<'info, Game>,
+    nft_vault: &Account<'info, TokenAccount>,
+    recipient_token_account: &Account<'info, TokenAccount>,
+    staker: &AccountInfo<'info>,
+    token_program: &Program<'info, Token>,
+) -> Result<()> {
+    let game = game_account.load()?;
+    require!(nft_vault.amount == 1, WagerError::InsufficientEscrow);
+
+    // Create signer seeds for the game PDA
+    let creator_key = game.creator;
+    let nonce_bytes = game.nonce.to_le_bytes();
+    let bump_bytes = [game.bump];
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"game",
+        creator_key.as_ref(),
+        nonce_bytes.as_ref(),
+        &bump_bytes,
+    ]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        Transfer {
+            from: nft_vault.to_account_info(),
+            to: recipient_token_account.to_account_info(),
+            authority: game_account.to_account_info(),
+        },
+        signer_seeds
+    );
+    token::transfer(transfer_ctx, 1)?;
+
+    let close_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        CloseAccount {
+            account: nft_vault.to_account_info(),
+            destination: staker.clone(),
+            authority: game_account.to_account_info(),
+        },
+        signer_seeds
+    );
+    token::close_account(close_ctx)
+}
+
+/// Accounts emptied when a fully paid-out game's escrow is closed.
+pub struct EscrowClosure<'a, 'info> {
+    pub game: &'a AccountLoader<'info, Game>,
+    pub vault: Option<&'a Account<'info, TokenAccount>>,
+    pub sol_vault: &'a SystemAccount<'info>,
+    // Gets whatever is left that nobody was owed: lamports above the SOL
+    // vault's reserve, and tokens into its token account
+    pub creator: &'a AccountInfo<'info>,
+    pub creator_token_account: Option<&'a Account<'info, TokenAccount>>,
+    // Funded the vaults' rent and the SOL vault's reserve, and gets them back
+    pub rent_receiver: &'a AccountInfo<'info>,
+    pub token_program: &'a Program<'info, Token>,
+    pub system_program: &'a Program<'info, System>,
+}
+
+/// Leftovers `close_escrow` sent to the creator.
+pub struct Sweep {
+    pub lamports: u64,
+    pub tokens: u64,
+}
+
+/// Empties a fully paid-out game's escrow: sweeps leftover tokens and
+/// lamports to the creator, then closes the token vault, if any, and returns
+/// its rent and the SOL vault's reserve to whoever funded them.
+pub fn close_escrow(accounts: EscrowClosure<'_, '_>) -> Result<Sweep> {
+    let mut sweep = Sweep { lamports: 0, tokens: 0 };
+    if let Some(vault) = accounts.vault {
+        let game = accounts.game.load()?;
+
+        // Create signer seeds for the game PDA
+        let creator_key = game.creator;
+        let nonce_bytes = game.nonce.to_le_bytes();
+        let bump_bytes = [game.bump];
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"game",
+            creator_key.as_ref(),
+            nonce_bytes.as_ref(),
+            &bump_bytes,
+        ]];
+
+        // Refunds have just left the vault; the token account can only be
+        // closed once nothing is left in it
+        let leftover = token::accessor::amount(&vault.to_account_info())?;
+        if leftover > 0 {
+            let creator_token_account = accounts.creator_token_account.ok_or(WagerError::InvalidTokenAccount)?;
+            let transfer_ctx = CpiContext::new_with_signer(
+                accounts.token_program.to_account_info(),
+                Transfer {
+                    from: vault.to_account_info(),
+                    to: creator_token_account.to_account_info(),
+                    authority: accounts.game.to_account_info(),
+                },
+                signer_seeds
+            );
+            token::transfer(transfer_ctx, leftover)?;
+            sweep.tokens = leftover;
+        }
+
+        let close_ctx = CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: vault.to_account_info(),
+                destination: accounts.rent_receiver.clone(),
+                authority: accounts.game.to_account_info(),
+            },
+            signer_seeds
+        );
+        token::close_account(close_ctx)?;
+    }
+
+    let remaining = accounts.sol_vault.lamports();
+    let reserve = remaining.min(Rent::get()?.minimum_balance(0));
+    let leftover = remaining - reserve;
+    if leftover > 0 {
+        transfer_from_sol_vault(accounts.game, accounts.sol_vault, accounts.system_program, accounts.creator, leftover)?;
+        sweep.lamports = leftover;
+    }
+    if reserve > 0 {
+        transfer_from_sol_vault(accounts.game, accounts.sol_vault, accounts.system_program, accounts.rent_receiver, reserve)?;
+    }
+    Ok(sweep)
+}
+
+/// Transfers lamports out of the SOL vault, signed by its seeds.
+fn transfer_from_sol_vault<'info>(
+    game_account: &AccountLoader<'info, Game>,
+    sol_vault: &SystemAccount<'info>,
+    system_program: &Program<'info, System>,
+    to: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let game = game_account.load()?;
+    let game_key = game_account.key();
+    let bump_bytes = [game.sol_vault_bump];
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"sol_vault",
+        game_key.as_ref(),
+        &bump_bytes,
+    ]];
+
+    system_program::transfer(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            system_program::Transfer {
+                from: sol_vault.to_account_info(),
+                to: to.clone(),
+            },
+            signer_seeds
+        ),
+        amount
+    )
+}
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+use anchor_lang::event::EVENT_IX_TAG_LE;
+use anchor_lang::Event;
+use anchor_lang::solana_program::{instruction::Instruction, program::invoke_signed};
+
+/// The `event_authority` account `#[event_cpi]` adds to an instruction, for
+/// helpers shared by several instructions; `emit_cpi!` needs `ctx` in scope.
+#[derive(Clone, Copy)]
+pub struct EventAuthority<'a, 'info> {
+    pub info: &'a AccountInfo<'info>,
+    pub bump: u8,
+}
+
+impl EventAuthority<'_, '_> {
+    /// Emits `event` the way `emit_cpi!` does: as the data of a self-invocation
+    /// signed by the event authority, which indexers read from the inner
+    /// instructions instead of the (truncatable) logs.
+    pub fn emit<E: Event>(&self, event: E) -> Result<()> {
+        let data = [EVENT_IX_TAG_LE, &event.data()].concat();
+        let ix = Instruction::new_with_bytes(
+            crate::ID,
+            &data,
+            vec![AccountMeta::new_readonly(self.info.key(), true)],
+        );
+        invoke_signed(&ix, std::slice::from_ref(self.info), &[&[b"__event_authority", &[self.bump]]])?;
+        Ok(())
+    }
+}
@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+use crate::{state::*, error::WagerError};
+
+#[derive(Accounts)]
+#[instruction(session_key: Pubkey)]
+pub struct ApproveSession<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = SessionAuthority::LEN,
+        seeds = [b"session", owner.key().as_ref(), session_key.as_ref()],
+        bump
+    )]
+    pub session: Box<Account<'info, SessionAuthority>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn handler(
+    ctx: Context<ApproveSession>,
+    session_key: Pubkey,
+    spend_cap: u64,
+    allowed_mints: Vec<Pubkey>,
+    expires_at: i64,
+    resolver: Pubkey,
+    dev_wallet: Pubkey,
+    min_payout_bps: u16,
+) -> Result<()> {
+    let clock = Clock::get()?;
+
+    // Validation
+    require!(session_key != ctx.accounts.owner.key(), WagerError::InvalidSession);
+    require!(expires_at > clock.unix_timestamp, WagerError::InvalidExpiryTime);
+    require!(
+        !allowed_mints.is_empty() && allowed_mints.len() <= SessionAuthority::MAX_MINTS,
+        WagerError::InvalidSession
+    );
+    require!(min_payout_bps < 10000, WagerError::InvalidPayoutBps);
+
+    let session = &mut ctx.accounts.session;
+    session.owner = ctx.accounts.owner.key();
+    session.session_key = session_key;
+    session.spend_cap = spend_cap;
+    session.spent = 0;
+    session.mint_count = allowed_mints.len() as u8;
+    session.allowed_mints = [Pubkey::default(); SessionAuthority::MAX_MINTS];
+    session.allowed_mints[..allowed_mints.len()].copy_from_slice(&allowed_mints);
+    session.expires_at = expires_at;
+    session.resolver = resolver;
+    session.dev_wallet = dev_wallet;
+    session.min_payout_bps = min_payout_bps;
+    session.bump = ctx.bumps.session;
+
+    emit!(SessionApproved {
+        owner: session.owner,
+        session_key,
+        spend_cap,
+        expires_at,
+        resolver,
+        dev_wallet,
+        min_payout_bps,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
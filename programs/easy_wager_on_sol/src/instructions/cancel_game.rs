@@ -1,176 +1,96 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 use crate::{state::*, error::WagerError};
+use super::check_pda;
+use super::resolve_game::settle_side_pool;
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct CancelGame<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
 
-    #[account(
-        mut,
-        seeds = [b"game", game.creator.as_ref(), game.nonce.to_le_bytes().as_ref()],
-        bump = game.bump
-    )]
-    pub game: Account<'info, Game>,
-
-    // Creator's token account (for SPL tokens)
-    #[account(
-        mut,
-        token::mint = game.mint,
-        token::authority = game.creator
-    )]
-    pub creator_token_account: Option<Account<'info, TokenAccount>>,
-
-    // Player2's token account (for SPL tokens) - if they joined
-    #[account(
-        mut,
-        token::mint = game.mint,
-        token::authority = game.player2
-    )]
-    pub player2_token_account: Option<Account<'info, TokenAccount>>,
+    #[account(mut)]
+    pub game: AccountLoader<'info, Game>,
 
-    // Game's vault (for SPL tokens)
+    // Side pool attached to this game, if any
     #[account(
         mut,
-        seeds = [b"vault", game.key().as_ref(), game.mint.as_ref()],
-        bump = game.vault_bump,
-        token::mint = game.mint,
-        token::authority = game
+        seeds = [b"side_pool", game.key().as_ref()],
+        bump = side_pool.bump
     )]
-    pub vault: Option<Account<'info, TokenAccount>>,
+    pub side_pool: Option<Account<'info, SidePool>>,
 
-    /// CHECK: Creator account - validated against game.creator
+    // Players' stats, required once both players have staked
     #[account(mut)]
-    pub creator_account: UncheckedAccount<'info>,
+    pub player1_stats: Option<Box<Account<'info, PlayerStats>>>,
 
-    /// CHECK: Player2 account - validated against game.player2
     #[account(mut)]
-    pub player2_account: Option<UncheckedAccount<'info>>,
-
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
+    pub player2_stats: Option<Box<Account<'info, PlayerStats>>>,
 }
 
 pub fn handler(ctx: Context<CancelGame>) -> Result<()> {
-    let game = &mut ctx.accounts.game;
+    let mut game = ctx.accounts.game.load_mut()?;
     let clock = Clock::get()?;
+    game.check_address(&ctx.accounts.game.key())?;
 
     // Validation - only creator or resolver can cancel
     require!(
-        ctx.accounts.authority.key() == game.creator || 
+        ctx.accounts.authority.key() == game.creator ||
         ctx.accounts.authority.key() == game.resolver,
         WagerError::UnauthorizedCreator
     );
 
-    require!(ctx.accounts.creator_account.key() == game.creator, WagerError::UnauthorizedCreator);
-
-    let mut reason = String::new();
-
-    match game.state {
+    let reason = match game.state()? {
         GameState::Open => {
             // Game not full, only creator can cancel if expired
             require!(game.is_expired(&clock), WagerError::GameNotExpired);
             require!(game.player2 == Pubkey::default(), WagerError::GameFull);
-            reason = "Expired without second player".to_string();
 
-            // No refunds needed since only creator exists and hasn't deposited yet
+            // Only the creator has deposited, they can claim their stake back
+            game.player1_claimable = game.deposit_for(Side::Player1)?;
+
+            CancelReason::ExpiredOpen
         },
         GameState::Ready => {
-            // Game is ready but expired, refund both players
+            // Game is ready but expired, both players can claim their stake back
+            // (staked NFTs are returned through `claim_nft`)
             require!(game.is_expired(&clock), WagerError::GameNotExpired);
-            reason = "Expired without resolution".to_string();
-
-            if game.is_native_sol() {
-                // Refund native SOL to both players
-                let game_lamports = game.to_account_info().lamports();
-                let refund_amount = game.wager;
-
-                // Refund creator (player1)
-                **game.to_account_info().try_borrow_mut_lamports()? = game_lamports
-                    .checked_sub(refund_amount)
-                    .ok_or(WagerError::MathOverflow)?;
-                **ctx.accounts.creator_account.try_borrow_mut_lamports()? = ctx.accounts.creator_account
-                    .to_account_info()
-                    .lamports()
-                    .checked_add(refund_amount)
-                    .ok_or(WagerError::MathOverflow)?;
-
-                // Refund player2
-                if let Some(player2_account) = &ctx.accounts.player2_account {
-                    require!(player2_account.key() == game.player2, WagerError::InvalidWinner);
-                    let remaining_lamports = game.to_account_info().lamports();
-                    **game.to_account_info().try_borrow_mut_lamports()? = remaining_lamports
-                        .checked_sub(refund_amount)
-                        .ok_or(WagerError::MathOverflow)?;
-                    **player2_account.try_borrow_mut_lamports()? = player2_account
-                        .to_account_info()
-                        .lamports()
-                        .checked_add(refund_amount)
-                        .ok_or(WagerError::MathOverflow)?;
-                }
-            } else {
-                // Refund SPL tokens to both players
-                require!(ctx.accounts.creator_token_account.is_some(), WagerError::InvalidTokenAccount);
-                require!(ctx.accounts.vault.is_some(), WagerError::InvalidTokenAccount);
-
-                let creator_token_account = ctx.accounts.creator_token_account.as_ref().unwrap();
-                let vault = ctx.accounts.vault.as_ref().unwrap();
-
-                // Create signer seeds for the game PDA
-                let creator_key = game.creator;
-                let nonce_bytes = game.nonce.to_le_bytes();
-                let bump_bytes = [game.bump];
-                let signer_seeds: &[&[&[u8]]] = &[&[
-                    b"game",
-                    creator_key.as_ref(),
-                    nonce_bytes.as_ref(),
-                    &bump_bytes,
-                ]];
-
-                // Refund creator
-                let creator_transfer_ctx = CpiContext::new_with_signer(
-                    ctx.accounts.token_program.to_account_info(),
-                    Transfer {
-                        from: vault.to_account_info(),
-                        to: creator_token_account.to_account_info(),
-                        authority: game.to_account_info(),
-                    },
-                    signer_seeds
-                );
-                token::transfer(creator_transfer_ctx, game.wager)?;
-
-                // Refund player2 if they joined
-                if game.player2 != Pubkey::default() {
-                    require!(ctx.accounts.player2_token_account.is_some(), WagerError::InvalidTokenAccount);
-                    let player2_token_account = ctx.accounts.player2_token_account.as_ref().unwrap();
-                    require!(player2_token_account.owner == game.player2, WagerError::InvalidTokenAccount);
-
-                    let player2_transfer_ctx = CpiContext::new_with_signer(
-                        ctx.accounts.token_program.to_account_info(),
-                        Transfer {
-                            from: vault.to_account_info(),
-                            to: player2_token_account.to_account_info(),
-                            authority: game.to_account_info(),
-                        },
-                        signer_seeds
-                    );
-                    token::transfer(player2_transfer_ctx, game.wager)?;
-                }
+
+            game.player1_claimable = game.deposit_for(Side::Player1)?;
+            game.player2_claimable = game.deposit_for(Side::Player2)?;
+
+            // Tallied apart from played games for both players
+            for (side, stats) in [
+                (Side::Player1, ctx.accounts.player1_stats.as_mut()),
+                (Side::Player2, ctx.accounts.player2_stats.as_mut()),
+            ] {
+                let stats = stats.ok_or(WagerError::MissingPlayerStats)?;
+                check_pda(&**stats, &[b"player_stats", game.player(side).as_ref()], stats.bump, WagerError::GameAccountMismatch)?;
+                stats.record_canceled()?;
             }
+
+            CancelReason::ExpiredUnresolved
         },
         _ => {
             return Err(WagerError::InvalidStateTransition.into());
         }
-    }
+    };
 
     // Update game state
-    game.state = GameState::Canceled;
+    game.set_state(GameState::Canceled);
+
+    // Spectator stakes are returned as well
+    settle_side_pool(&game, ctx.accounts.side_pool.as_mut(), SideOutcome::Refunded)?;
 
     // Emit event
-    emit!(GameCanceled {
-        game: game.key(),
+    emit_cpi!(GameCanceled {
+        game: ctx.accounts.game.key(),
+        canceled_by: ctx.accounts.authority.key(),
         reason,
+        mint: game.mint,
+        player1_refund: game.player1_claimable,
+        player2_refund: game.player2_claimable,
+        timestamp: clock.unix_timestamp,
     });
 
     Ok(())
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use crate::{state::*, error::WagerError};
+
+#[derive(Accounts)]
+pub struct CancelTournament<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"tournament", tournament.organizer.as_ref(), tournament.nonce.to_le_bytes().as_ref()],
+        bump = tournament.bump
+    )]
+    pub tournament: Account<'info, Tournament>,
+}
+
+/// Cancels a tournament that has not started. Entry fees become refundable
+/// through `claim_tournament_prize`.
+pub fn handler(ctx: Context<CancelTournament>) -> Result<()> {
+    let clock = Clock::get()?;
+    let tournament = &mut ctx.accounts.tournament;
+    let authority = ctx.accounts.authority.key();
+
+    require!(
+        matches!(tournament.state, TournamentState::Registration),
+        WagerError::InvalidStateTransition
+    );
+
+    // The organizer can cancel any time before seeding. Anyone can cancel once
+    // registration has closed without enough players to run a bracket.
+    let undersubscribed = clock.unix_timestamp >= tournament.registration_end_ts
+        && tournament.players.len() < 2;
+    require!(
+        authority == tournament.organizer || undersubscribed,
+        WagerError::UnauthorizedOrganizer
+    );
+
+    tournament.state = TournamentState::Canceled;
+
+    emit!(TournamentCanceled {
+        tournament: tournament.key(),
+        mint: tournament.mint,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,126 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use crate::{state::*, error::WagerError, escrow::{self, Withdrawal}};
+use super::{check_balance, check_escrow};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct Claim<'info> {
+    // Anyone may crank a claim; funds only ever go to the recipient. The claimer
+    // funds the recipient's associated token account if it doesn't exist yet.
+    #[account(mut)]
+    pub claimer: Signer<'info>,
+
+    #[account(mut)]
+    pub game: AccountLoader<'info, Game>,
+
+    // Recipient's associated token account (for SPL tokens)
+    #[account(
+        init_if_needed,
+        payer = claimer,
+        associated_token::mint = token_mint,
+        associated_token::authority = recipient_account
+    )]
+    pub recipient_token_account: Option<Account<'info, TokenAccount>>,
+
+    // Token mint (for SPL tokens)
+    pub token_mint: Option<Account<'info, Mint>>,
+
+    // Game's vault (for SPL tokens)
+    #[account(mut, token::authority = game)]
+    pub vault: Option<Account<'info, TokenAccount>>,
+
+    // System-owned vault holding native SOL stakes (and unwrapped wSOL payouts)
+    #[account(mut)]
+    pub sol_vault: SystemAccount<'info>,
+
+    // Temporary account to unwrap wrapped SOL payouts into (omit the recipient
+    // token account to be paid in SOL)
+    #[account(
+        init,
+        payer = claimer,
+        token::mint = token_mint,
+        token::authority = game,
+        seeds = [b"unwrap", game.key().as_ref()],
+        bump
+    )]
+    pub unwrap_account: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: Recipient account - validated against recipient
+    #[account(mut)]
+    pub recipient_account: UncheckedAccount<'info>,
+
+    // Recipient's player balance, credited instead of their wallet if passed;
+    // only the recipient may claim into it
+    #[account(mut)]
+    pub recipient_balance: Option<Box<Account<'info, PlayerBalance>>>,
+
+    #[account(mut, token::authority = recipient_balance)]
+    pub recipient_balance_vault: Option<Box<Account<'info, TokenAccount>>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<Claim>, recipient: Pubkey) -> Result<()> {
+    let mut game = ctx.accounts.game.load_mut()?;
+    check_escrow(&game, &ctx.accounts.game.key(), ctx.accounts.vault.as_ref(), &ctx.accounts.sol_vault)?;
+    if let Some(token_mint) = &ctx.accounts.token_mint {
+        require_keys_eq!(token_mint.key(), game.mint, WagerError::TokenMintMismatch);
+    }
+    check_balance(
+        ctx.accounts.recipient_balance.as_deref(),
+        ctx.accounts.recipient_balance_vault.as_deref(),
+        &recipient,
+        &game.mint,
+    )?;
+
+    // Validation
+    require!(
+        matches!(game.state()?, GameState::Paid | GameState::Canceled),
+        WagerError::GameNotReady
+    );
+    require!(ctx.accounts.recipient_account.key() == recipient, WagerError::InvalidWinner);
+    // Anyone may push a payout to the recipient's wallet, but only they may
+    // choose to park it in their balance
+    if ctx.accounts.recipient_balance.is_some() {
+        require_keys_eq!(ctx.accounts.claimer.key(), recipient, WagerError::UnauthorizedBalanceCredit);
+    }
+
+    let amount = game.claimable_by(&recipient)?;
+    require!(amount > 0, WagerError::NothingToClaim);
+
+    // Clear before moving funds
+    game.clear_claimable(&recipient);
+    let mint = game.mint;
+    drop(game);
+
+    escrow::withdraw(
+        Withdrawal {
+            game: &ctx.accounts.game,
+            recipient: &ctx.accounts.recipient_account,
+            recipient_token_account: ctx.accounts.recipient_token_account.as_ref(),
+            recipient_balance: ctx.accounts.recipient_balance.as_deref_mut(),
+            recipient_balance_vault: ctx.accounts.recipient_balance_vault.as_deref(),
+            vault: ctx.accounts.vault.as_ref(),
+            sol_vault: &ctx.accounts.sol_vault,
+            unwrap_account: ctx.accounts.unwrap_account.as_ref(),
+            rent_payer: &ctx.accounts.claimer,
+            token_program: &ctx.accounts.token_program,
+            system_program: &ctx.accounts.system_program,
+        },
+        amount,
+    )?;
+
+    emit_cpi!(GameClaimed {
+        game: ctx.accounts.game.key(),
+        recipient,
+        amount,
+        mint,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,101 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+use crate::{state::*, error::WagerError, escrow};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimBasket<'info> {
+    // Anyone may crank a claim; tokens only ever go to the recipient
+    pub claimer: Signer<'info>,
+
+    #[account(mut)]
+    pub game: AccountLoader<'info, Game>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Pays out every basket leg still owed to `recipient`. `remaining_accounts`
+/// holds, for each such leg in order (player1's legs, then player2's): the
+/// leg's basket vault and the recipient's token account for its mint.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ClaimBasket<'info>>,
+    recipient: Pubkey,
+) -> Result<()> {
+    // Validation
+    {
+        let game = ctx.accounts.game.load()?;
+        game.check_address(&ctx.accounts.game.key())?;
+        require!(
+            matches!(game.state()?, GameState::Paid | GameState::Canceled),
+            WagerError::GameNotReady
+        );
+    }
+
+    let mut remaining = ctx.remaining_accounts.chunks_exact(2);
+    let mut paid_any = false;
+
+    for side in [Side::Player1, Side::Player2] {
+        let basket = *ctx.accounts.game.load()?.basket(side);
+
+        for (index, leg) in basket.used_legs() {
+            let bit = Game::basket_leg_bit(side, index);
+            let mut amount: u64 = 0;
+
+            let mut game = ctx.accounts.game.load_mut()?;
+            if game.state()? == GameState::Paid {
+                // Winner takes each leg less the fee, the dev wallet the fee
+                let (winner_amount, fee_amount) = game.split_basket_leg(leg.amount)?;
+                if recipient == game.winner && game.basket_paid & bit == 0 {
+                    amount += winner_amount;
+                    game.basket_paid |= bit;
+                }
+                if recipient == game.dev_wallet && game.basket_fee_paid & bit == 0 {
+                    amount += fee_amount;
+                    game.basket_fee_paid |= bit;
+                }
+            } else if recipient == game.player(side) && game.basket_paid & bit == 0 {
+                // Canceled games refund each leg to its staker
+                amount = leg.amount;
+                game.basket_paid |= bit;
+            }
+            drop(game);
+
+            if amount == 0 {
+                continue;
+            }
+
+            let [vault, recipient_token_account] = remaining
+                .next()
+                .ok_or(WagerError::BasketAccountMismatch)?
+            else {
+                return Err(WagerError::BasketAccountMismatch.into());
+            };
+
+            let destination = Account::<TokenAccount>::try_from(recipient_token_account)?;
+            require_keys_eq!(destination.owner, recipient, WagerError::BasketAccountMismatch);
+            require_keys_eq!(destination.mint, leg.mint, WagerError::TokenMintMismatch);
+
+            escrow::withdraw_basket_leg(
+                &ctx.accounts.game,
+                leg,
+                vault,
+                recipient_token_account,
+                &ctx.accounts.token_program,
+                amount,
+            )?;
+            paid_any = true;
+
+            emit_cpi!(BasketLegClaimed {
+                game: ctx.accounts.game.key(),
+                recipient,
+                mint: leg.mint,
+                amount,
+                timestamp: Clock::get()?.unix_timestamp,
+            });
+        }
+    }
+
+    require!(paid_any, WagerError::NothingToClaim);
+
+    Ok(())
+}
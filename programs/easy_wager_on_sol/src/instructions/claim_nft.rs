@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use crate::{state::*, error::WagerError, escrow};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(side: Side)]
+pub struct ClaimNft<'info> {
+    // Anyone may crank an NFT claim; the NFT only ever goes to its recipient
+    #[account(mut)]
+    pub claimer: Signer<'info>,
+
+    #[account(mut)]
+    pub game: AccountLoader<'info, Game>,
+
+    pub nft_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [b"nft_vault", game.key().as_ref(), staker.key().as_ref()],
+        bump,
+        token::mint = nft_mint,
+        token::authority = game
+    )]
+    pub nft_vault: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Player who staked the NFT - validated in handler, receives the vault's rent
+    #[account(mut)]
+    pub staker: UncheckedAccount<'info>,
+
+    // Recipient's associated token account for the NFT
+    #[account(
+        init_if_needed,
+        payer = claimer,
+        associated_token::mint = nft_mint,
+        associated_token::authority = recipient_account
+    )]
+    pub recipient_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Winner, or the staker if the game was canceled - validated in handler
+    pub recipient_account: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ClaimNft>, side: Side) -> Result<()> {
+    let mut game = ctx.accounts.game.load_mut()?;
+    game.check_address(&ctx.accounts.game.key())?;
+
+    // Validation
+    require!(game.asset(side).is_nft(), WagerError::InvalidNft);
+    require_keys_eq!(ctx.accounts.nft_mint.key(), game.asset(side).mint, WagerError::InvalidNft);
+    require_keys_eq!(ctx.accounts.staker.key(), game.player(side), WagerError::InvalidNft);
+    require!(!game.nft_claimed(side), WagerError::NftAlreadyClaimed);
+
+    // Winner takes both NFTs; canceled games return each NFT to its staker
+    let recipient = match game.state()? {
+        GameState::Paid => game.winner,
+        GameState::Canceled => game.player(side),
+        _ => return Err(WagerError::GameNotReady.into()),
+    };
+    require!(ctx.accounts.recipient_account.key() == recipient, WagerError::InvalidWinner);
+
+    // Mark before moving the NFT
+    game.nfts_claimed |= 1 << side as u8;
+    drop(game);
+
+    escrow::withdraw_nft(
+        &ctx.accounts.game,
+        &ctx.accounts.nft_vault,
+        &ctx.accounts.recipient_token_account,
+        &ctx.accounts.staker,
+        &ctx.accounts.token_program,
+    )?;
+
+    emit_cpi!(NftClaimed {
+        game: ctx.accounts.game.key(),
+        nft_mint: ctx.accounts.nft_mint.key(),
+        recipient,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,170 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use crate::{state::*, error::WagerError};
+
+#[derive(Accounts)]
+pub struct ClaimSideBet<'info> {
+    // Anyone may crank a claim; funds only ever go to the bettor
+    // Funds the recipient's associated token account if it doesn't exist yet
+    #[account(mut)]
+    pub claimer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"side_pool", side_pool.game.as_ref()],
+        bump = side_pool.bump
+    )]
+    pub side_pool: Account<'info, SidePool>,
+
+    // Closed on claim, returning its rent to the bettor
+    #[account(
+        mut,
+        seeds = [b"side_bet", side_pool.key().as_ref(), side_bet.bettor.as_ref()],
+        bump = side_bet.bump,
+        close = bettor_account
+    )]
+    pub side_bet: Account<'info, SideBet>,
+
+    // Bettor's associated token account (for SPL tokens)
+    #[account(
+        init_if_needed,
+        payer = claimer,
+        associated_token::mint = token_mint,
+        associated_token::authority = bettor_account
+    )]
+    pub bettor_token_account: Option<Account<'info, TokenAccount>>,
+
+    // Token mint (for SPL tokens)
+    #[account(address = side_pool.mint @ WagerError::TokenMintMismatch)]
+    pub token_mint: Option<Account<'info, Mint>>,
+
+    // Side pool vault (for SPL tokens)
+    #[account(
+        mut,
+        seeds = [b"vault", side_pool.key().as_ref(), side_pool.mint.as_ref()],
+        bump = side_pool.vault_bump,
+        token::mint = side_pool.mint,
+        token::authority = side_pool
+    )]
+    pub vault: Option<Account<'info, TokenAccount>>,
+
+    // System-owned vault holding native SOL bets
+    #[account(
+        mut,
+        seeds = [b"sol_vault", side_pool.key().as_ref()],
+        bump = side_pool.sol_vault_bump
+    )]
+    pub sol_vault: SystemAccount<'info>,
+
+    /// CHECK: Bettor account - validated against side_bet.bettor
+    #[account(mut, address = side_bet.bettor @ WagerError::InvalidWinner)]
+    pub bettor_account: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ClaimSideBet>) -> Result<()> {
+    let side_pool = &ctx.accounts.side_pool;
+    require!(
+        !matches!(side_pool.outcome, SideOutcome::Pending),
+        WagerError::SidePoolNotSettled
+    );
+
+    let amount = side_pool.payout_for(&ctx.accounts.side_bet)?;
+
+    if amount > 0 {
+        transfer_from_side_pool(
+            SidePoolPayout {
+                side_pool,
+                recipient: &ctx.accounts.bettor_account,
+                recipient_token_account: ctx.accounts.bettor_token_account.as_ref(),
+                vault: ctx.accounts.vault.as_ref(),
+                sol_vault: &ctx.accounts.sol_vault,
+                token_program: &ctx.accounts.token_program,
+                system_program: &ctx.accounts.system_program,
+            },
+            amount,
+        )?;
+    }
+
+    emit!(SideBetClaimed {
+        side_pool: side_pool.key(),
+        bettor: ctx.accounts.side_bet.bettor,
+        amount,
+        mint: side_pool.mint,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    // The bet account is closed on exit
+    let side_pool = &mut ctx.accounts.side_pool;
+    side_pool.open_bets = side_pool.open_bets.checked_sub(1).ok_or(WagerError::MathOverflow)?;
+
+    Ok(())
+}
+
+/// Accounts used to pay out of a side pool's escrow.
+pub(crate) struct SidePoolPayout<'a, 'info> {
+    pub side_pool: &'a Account<'info, SidePool>,
+    pub recipient: &'a AccountInfo<'info>,
+    pub recipient_token_account: Option<&'a Account<'info, TokenAccount>>,
+    pub vault: Option<&'a Account<'info, TokenAccount>>,
+    pub sol_vault: &'a SystemAccount<'info>,
+    pub token_program: &'a Program<'info, Token>,
+    pub system_program: &'a Program<'info, System>,
+}
+
+/// Pays `amount` out of a side pool's escrow to the recipient: lamports from
+/// its SOL vault, or tokens from its token vault.
+pub(crate) fn transfer_from_side_pool(accounts: SidePoolPayout<'_, '_>, amount: u64) -> Result<()> {
+    let side_pool = accounts.side_pool;
+    if side_pool.is_native_sol() {
+        let side_pool_key = side_pool.key();
+        let bump_bytes = [side_pool.sol_vault_bump];
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"sol_vault",
+            side_pool_key.as_ref(),
+            &bump_bytes,
+        ]];
+
+        system_program::transfer(
+            CpiContext::new_with_signer(
+                accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: accounts.sol_vault.to_account_info(),
+                    to: accounts.recipient.clone(),
+                },
+                signer_seeds
+            ),
+            amount
+        )?;
+    } else {
+        let recipient_token_account = accounts.recipient_token_account.ok_or(WagerError::InvalidTokenAccount)?;
+        let vault = accounts.vault.ok_or(WagerError::InvalidTokenAccount)?;
+
+        // Create signer seeds for the side pool PDA
+        let game_key = side_pool.game;
+        let bump_bytes = [side_pool.bump];
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"side_pool",
+            game_key.as_ref(),
+            &bump_bytes,
+        ]];
+
+        let transfer_ctx = CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            Transfer {
+                from: vault.to_account_info(),
+                to: recipient_token_account.to_account_info(),
+                authority: side_pool.to_account_info(),
+            },
+            signer_seeds
+        );
+        token::transfer(transfer_ctx, amount)?;
+    }
+
+    Ok(())
+}
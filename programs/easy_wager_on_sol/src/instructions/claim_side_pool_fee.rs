@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use crate::{state::*, error::WagerError};
+use super::claim_side_bet::{transfer_from_side_pool, SidePoolPayout};
+
+#[derive(Accounts)]
+pub struct ClaimSidePoolFee<'info> {
+    // Funds the recipient's associated token account if it doesn't exist yet
+    #[account(mut)]
+    pub claimer: Signer<'info>,
+
+    pub game: AccountLoader<'info, Game>,
+
+    #[account(
+        mut,
+        seeds = [b"side_pool", game.key().as_ref()],
+        bump = side_pool.bump
+    )]
+    pub side_pool: Account<'info, SidePool>,
+
+    // Dev wallet's associated token account (for SPL tokens)
+    #[account(
+        init_if_needed,
+        payer = claimer,
+        associated_token::mint = token_mint,
+        associated_token::authority = dev_wallet_account
+    )]
+    pub dev_token_account: Option<Account<'info, TokenAccount>>,
+
+    // Token mint (for SPL tokens)
+    #[account(address = side_pool.mint @ WagerError::TokenMintMismatch)]
+    pub token_mint: Option<Account<'info, Mint>>,
+
+    // Side pool vault (for SPL tokens)
+    #[account(
+        mut,
+        seeds = [b"vault", side_pool.key().as_ref(), side_pool.mint.as_ref()],
+        bump = side_pool.vault_bump,
+        token::mint = side_pool.mint,
+        token::authority = side_pool
+    )]
+    pub vault: Option<Account<'info, TokenAccount>>,
+
+    // System-owned vault holding native SOL bets
+    #[account(
+        mut,
+        seeds = [b"sol_vault", side_pool.key().as_ref()],
+        bump = side_pool.sol_vault_bump
+    )]
+    pub sol_vault: SystemAccount<'info>,
+
+    /// CHECK: Dev wallet account - validated against game.dev_wallet in handler
+    #[account(mut)]
+    pub dev_wallet_account: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ClaimSidePoolFee>) -> Result<()> {
+    {
+        let game = ctx.accounts.game.load()?;
+        game.check_address(&ctx.accounts.game.key())?;
+        require_keys_eq!(ctx.accounts.dev_wallet_account.key(), game.dev_wallet, WagerError::InvalidDevWallet);
+    }
+
+    let side_pool = &ctx.accounts.side_pool;
+    require!(
+        !matches!(side_pool.outcome, SideOutcome::Pending),
+        WagerError::SidePoolNotSettled
+    );
+    require!(!side_pool.fee_claimed, WagerError::PrizeAlreadyClaimed);
+
+    let amount = side_pool.fee_amount()?;
+    require!(amount > 0, WagerError::NothingToClaim);
+
+    transfer_from_side_pool(
+        SidePoolPayout {
+            side_pool,
+            recipient: &ctx.accounts.dev_wallet_account,
+            recipient_token_account: ctx.accounts.dev_token_account.as_ref(),
+            vault: ctx.accounts.vault.as_ref(),
+            sol_vault: &ctx.accounts.sol_vault,
+            token_program: &ctx.accounts.token_program,
+            system_program: &ctx.accounts.system_program,
+        },
+        amount,
+    )?;
+
+    ctx.accounts.side_pool.fee_claimed = true;
+
+    Ok(())
+}
@@ -0,0 +1,157 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use crate::{state::*, error::WagerError};
+
+#[derive(Accounts)]
+pub struct ClaimTournamentPrize<'info> {
+    // Anyone may crank a claim; funds only ever go to the recipient
+    // Funds the recipient's associated token account if it doesn't exist yet
+    #[account(mut)]
+    pub claimer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"tournament", tournament.organizer.as_ref(), tournament.nonce.to_le_bytes().as_ref()],
+        bump = tournament.bump
+    )]
+    pub tournament: Account<'info, Tournament>,
+
+    // Recipient's associated token account (for SPL tokens)
+    #[account(
+        init_if_needed,
+        payer = claimer,
+        associated_token::mint = token_mint,
+        associated_token::authority = recipient_account
+    )]
+    pub recipient_token_account: Option<Account<'info, TokenAccount>>,
+
+    // Token mint (for SPL tokens)
+    #[account(address = tournament.mint @ WagerError::TokenMintMismatch)]
+    pub token_mint: Option<Account<'info, Mint>>,
+
+    // Tournament's prize vault (for SPL tokens)
+    #[account(
+        mut,
+        seeds = [b"vault", tournament.key().as_ref(), tournament.mint.as_ref()],
+        bump = tournament.vault_bump,
+        token::mint = tournament.mint,
+        token::authority = tournament
+    )]
+    pub vault: Option<Account<'info, TokenAccount>>,
+
+    // System-owned vault holding native SOL entry fees
+    #[account(
+        mut,
+        seeds = [b"sol_vault", tournament.key().as_ref()],
+        bump = tournament.sol_vault_bump
+    )]
+    pub sol_vault: SystemAccount<'info>,
+
+    /// CHECK: Recipient account - validated against recipient
+    #[account(mut)]
+    pub recipient_account: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ClaimTournamentPrize>, recipient: Pubkey) -> Result<()> {
+    let tournament = &mut ctx.accounts.tournament;
+    require!(ctx.accounts.recipient_account.key() == recipient, WagerError::InvalidWinner);
+
+    let player_index = tournament.player_index(&recipient);
+    let unclaimed_player = player_index.filter(|index| !tournament.is_claimed(*index));
+
+    let amount = match tournament.state {
+        TournamentState::Canceled => {
+            // Canceled before start - refund the entry fee
+            let index = unclaimed_player.ok_or(WagerError::NothingToClaim)?;
+            tournament.claimed |= 1 << index;
+            tournament.entry_fee
+        },
+        TournamentState::Completed => {
+            let prize = match unclaimed_player {
+                Some(index) => {
+                    let tier = tournament.finishing_tier(&recipient);
+                    let prize = match tier {
+                        Some(tier) => tournament.tier_prize(tier)?,
+                        None => 0,
+                    };
+                    tournament.claimed |= 1 << index;
+                    prize
+                },
+                None => 0,
+            };
+
+            if prize > 0 {
+                prize
+            } else if recipient == tournament.dev_wallet && !tournament.fee_claimed {
+                tournament.fee_claimed = true;
+                tournament.fee_amount()?
+            } else {
+                return Err(WagerError::NothingToClaim.into());
+            }
+        },
+        _ => return Err(WagerError::TournamentNotSettled.into()),
+    };
+
+    if tournament.is_native_sol() {
+        let tournament_key = tournament.key();
+        let bump_bytes = [tournament.sol_vault_bump];
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"sol_vault",
+            tournament_key.as_ref(),
+            &bump_bytes,
+        ]];
+
+        system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.sol_vault.to_account_info(),
+                    to: ctx.accounts.recipient_account.to_account_info(),
+                },
+                signer_seeds
+            ),
+            amount
+        )?;
+    } else {
+        let recipient_token_account = ctx.accounts.recipient_token_account.as_ref().ok_or(WagerError::InvalidTokenAccount)?;
+        let vault = ctx.accounts.vault.as_ref().ok_or(WagerError::InvalidTokenAccount)?;
+
+        // Create signer seeds for the tournament PDA
+        let organizer_key = tournament.organizer;
+        let nonce_bytes = tournament.nonce.to_le_bytes();
+        let bump_bytes = [tournament.bump];
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"tournament",
+            organizer_key.as_ref(),
+            nonce_bytes.as_ref(),
+            &bump_bytes,
+        ]];
+
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: vault.to_account_info(),
+                to: recipient_token_account.to_account_info(),
+                authority: tournament.to_account_info(),
+            },
+            signer_seeds
+        );
+        token::transfer(transfer_ctx, amount)?;
+    }
+
+    emit!(TournamentPrizeClaimed {
+        tournament: tournament.key(),
+        recipient,
+        amount,
+        mint: tournament.mint,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,145 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, CloseAccount, Mint, Token, TokenAccount};
+use crate::{state::*, error::WagerError};
+use super::claim_side_bet::{transfer_from_side_pool, SidePoolPayout};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseSidePool<'info> {
+    // Anyone may close a fully claimed pool; everything goes to the creator.
+    // Funds the creator's associated token account if it doesn't exist yet
+    #[account(mut)]
+    pub closer: Signer<'info>,
+
+    pub game: AccountLoader<'info, Game>,
+
+    // Its rent goes back to the creator, who paid it
+    #[account(
+        mut,
+        seeds = [b"side_pool", game.key().as_ref()],
+        bump = side_pool.bump,
+        close = creator_account
+    )]
+    pub side_pool: Account<'info, SidePool>,
+
+    // Creator's associated token account for the dust (for SPL tokens)
+    #[account(
+        init_if_needed,
+        payer = closer,
+        associated_token::mint = token_mint,
+        associated_token::authority = creator_account
+    )]
+    pub creator_token_account: Option<Account<'info, TokenAccount>>,
+
+    // Token mint (for SPL tokens)
+    #[account(address = side_pool.mint @ WagerError::TokenMintMismatch)]
+    pub token_mint: Option<Account<'info, Mint>>,
+
+    // Side pool vault (for SPL tokens)
+    #[account(
+        mut,
+        seeds = [b"vault", side_pool.key().as_ref(), side_pool.mint.as_ref()],
+        bump = side_pool.vault_bump,
+        token::mint = side_pool.mint,
+        token::authority = side_pool
+    )]
+    pub vault: Option<Account<'info, TokenAccount>>,
+
+    // System-owned vault holding native SOL bets
+    #[account(
+        mut,
+        seeds = [b"sol_vault", side_pool.key().as_ref()],
+        bump = side_pool.sol_vault_bump
+    )]
+    pub sol_vault: SystemAccount<'info>,
+
+    /// CHECK: Game creator - validated against game.creator in handler
+    #[account(mut)]
+    pub creator_account: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Closes a settled side pool once every bet and the fee have been claimed.
+/// The winners' pro rata split rounds down, so the escrow can still hold a
+/// few units nobody is owed; those go to the creator with the vaults' rent.
+pub fn handler(ctx: Context<CloseSidePool>) -> Result<()> {
+    let creator = {
+        let game = ctx.accounts.game.load()?;
+        game.check_address(&ctx.accounts.game.key())?;
+        require_keys_eq!(ctx.accounts.side_pool.key(), game.side_pool, WagerError::SidePoolMismatch);
+        require_keys_eq!(ctx.accounts.creator_account.key(), game.creator, WagerError::UnauthorizedCreator);
+        game.creator
+    };
+
+    let side_pool = &ctx.accounts.side_pool;
+    require!(
+        !matches!(side_pool.outcome, SideOutcome::Pending),
+        WagerError::SidePoolNotSettled
+    );
+    require!(
+        side_pool.open_bets == 0 && (side_pool.fee_claimed || side_pool.fee_amount()? == 0),
+        WagerError::SidePoolNotEmpty
+    );
+
+    let payout = || SidePoolPayout {
+        side_pool,
+        recipient: &ctx.accounts.creator_account,
+        recipient_token_account: ctx.accounts.creator_token_account.as_ref(),
+        vault: ctx.accounts.vault.as_ref(),
+        sol_vault: &ctx.accounts.sol_vault,
+        token_program: &ctx.accounts.token_program,
+        system_program: &ctx.accounts.system_program,
+    };
+
+    let dust = if side_pool.is_native_sol() {
+        // The reserve goes too, leaving the SOL vault empty
+        let dust = ctx.accounts.sol_vault.lamports().saturating_sub(Rent::get()?.minimum_balance(0));
+        let remaining = ctx.accounts.sol_vault.lamports();
+        if remaining > 0 {
+            transfer_from_side_pool(payout(), remaining)?;
+        }
+        dust
+    } else {
+        let vault = ctx.accounts.vault.as_ref().ok_or(WagerError::InvalidTokenAccount)?;
+        let dust = vault.amount;
+        if dust > 0 {
+            transfer_from_side_pool(payout(), dust)?;
+        }
+
+        // Create signer seeds for the side pool PDA
+        let game_key = side_pool.game;
+        let bump_bytes = [side_pool.bump];
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"side_pool",
+            game_key.as_ref(),
+            &bump_bytes,
+        ]];
+
+        let close_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: vault.to_account_info(),
+                destination: ctx.accounts.creator_account.to_account_info(),
+                authority: side_pool.to_account_info(),
+            },
+            signer_seeds
+        );
+        token::close_account(close_ctx)?;
+        dust
+    };
+
+    emit_cpi!(SidePoolClosed {
+        game: ctx.accounts.game.key(),
+        side_pool: side_pool.key(),
+        creator,
+        dust,
+        mint: side_pool.mint,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,286 @@
+use anchor_lang::prelude::*;
+use anchor_lang::AccountsClose;
+use anchor_spl::token::{Token, TokenAccount};
+use crate::{state::*, error::WagerError, escrow::{self, EscrowClosure, Withdrawal}};
+use super::check_pda;
+use super::resolve_many::optional;
+
+// Accounts per game in `remaining_accounts`: the game, its creator (receives
+// leftover escrow), whoever paid its rent (gets the rent back), the SOL vault,
+// the token vault, the creator's token account for leftover tokens, where
+// each player's refund goes (wallet for native SOL, token account otherwise)
+// and both players' stats. Pass the program id for the token vault of native
+// SOL games and for player2's accounts when nobody joined. The creator's token
+// account is only read when the vault holds more than the refunds.
+pub const CRANK_GROUP_LEN: usize = 10;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CrankExpired<'info> {
+    // Anyone may crank; earns each closed game's share of its rent
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Refunds and closes expired games in one go, so they don't pile up waiting
+/// for their creator or resolver. Games the crank can't close (not expired,
+/// NFT or basket stakes, side pools), whose accounts don't check out or that
+/// were already passed earlier in the batch are skipped; settle those with `cancel_if_expired` and the claim instructions.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, CrankExpired<'info>>) -> Result<()> {
+    let clock = Clock::get()?;
+    let chunks = ctx.remaining_accounts.chunks_exact(CRANK_GROUP_LEN);
+    require!(chunks.remainder().is_empty(), WagerError::BatchAccountMismatch);
+
+    let mut skipped: u16 = 0;
+    // Games whose escrow is empty, closed once every transfer is done
+    let mut closing = Vec::new();
+    let mut seen: Vec<Pubkey> = Vec::new();
+
+    for accounts in chunks {
+        // A game passed twice would otherwise be closed, and pay its bounty,
+        // twice
+        if seen.contains(accounts[0].key) {
+            msg!("Skipping game {}: passed more than once", accounts[0].key);
+            skipped += 1;
+            continue;
+        }
+        seen.push(*accounts[0].key);
+
+        // Nothing is written until a game passes every check, so a skipped
+        // game is left exactly as it was
+        let mut group = match CrankGroup::load(accounts, &clock) {
+            Ok(group) => group,
+            Err(err) => {
+                msg!("Skipping game {}: {}", accounts[0].key, err);
+                skipped += 1;
+                continue;
+            }
+        };
+        let game_key = group.game.key();
+
+        // Cancel the game as `cancel_if_expired` would, then pay the refunds
+        // out straight away
+        let mut game = group.game.load_mut()?;
+        for stats in group.stats.iter_mut().flatten() {
+            stats.record_canceled()?;
+            stats.exit(&crate::ID)?;
+        }
+        game.set_state(GameState::Canceled);
+        game.player1_claimable = 0;
+        game.player2_claimable = 0;
+        let mint = game.mint;
+        let crank_bounty_bps = game.crank_bounty_bps;
+        drop(game);
+
+        if let Some(reason) = group.reason {
+            emit_cpi!(GameCanceled {
+                game: game_key,
+                canceled_by: ctx.accounts.cranker.key(),
+                reason,
+                mint,
+                player1_refund: group.refunds[0].amount,
+                player2_refund: group.refunds[1].amount,
+                timestamp: clock.unix_timestamp,
+            });
+        }
+
+        for refund in &group.refunds {
+            if refund.amount == 0 {
+                continue;
+            }
+
+            escrow::withdraw(
+                Withdrawal {
+                    game: &group.game,
+                    recipient: &UncheckedAccount::try_from(refund.destination),
+                    recipient_token_account: refund.token_account.as_ref(),
+                    recipient_balance: None,
+                    recipient_balance_vault: None,
+                    vault: group.vault.as_ref(),
+                    sol_vault: &group.sol_vault,
+                    unwrap_account: None,
+                    rent_payer: ctx.accounts.cranker.as_ref(),
+                    token_program: &ctx.accounts.token_program,
+                    system_program: &ctx.accounts.system_program,
+                },
+                refund.amount,
+            )?;
+
+            emit_cpi!(GameClaimed {
+                game: game_key,
+                recipient: refund.player,
+                amount: refund.amount,
+                mint,
+                timestamp: clock.unix_timestamp,
+            });
+        }
+
+        let sweep = escrow::close_escrow(EscrowClosure {
+            game: &group.game,
+            vault: group.vault.as_ref(),
+            sol_vault: &group.sol_vault,
+            creator: group.creator,
+            creator_token_account: group.creator_token_account.as_ref(),
+            rent_receiver: group.rent_receiver,
+            token_program: &ctx.accounts.token_program,
+            system_program: &ctx.accounts.system_program,
+        })?;
+        if sweep.lamports > 0 || sweep.tokens > 0 {
+            emit_cpi!(EscrowSwept {
+                game: game_key,
+                creator: group.creator.key(),
+                mint,
+                lamports: sweep.lamports,
+                tokens: sweep.tokens,
+                timestamp: clock.unix_timestamp,
+            });
+        }
+
+        closing.push((group.game, group.rent_receiver, crank_bounty_bps));
+    }
+
+    require!(!closing.is_empty(), WagerError::GameNotCrankable);
+
+    // The cranker's bounty comes out of each game's rent, the rest goes back
+    // to whoever paid it
+    let bounties = closing
+        .iter()
+        .map(|(game, _, crank_bounty_bps)| game.to_account_info().lamports() * *crank_bounty_bps as u64 / 10_000)
+        .collect::<Vec<_>>();
+
+    emit_cpi!(ExpiredGamesCranked {
+        cranker: ctx.accounts.cranker.key(),
+        closed: closing.len() as u16,
+        skipped,
+        bounty: bounties.iter().sum(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    // Lamports are moved by hand only after the last CPI, which would
+    // otherwise see the balances out of step
+    for ((game, rent_receiver, _), game_bounty) in closing.iter().zip(bounties) {
+        game.to_account_info().sub_lamports(game_bounty)?;
+        ctx.accounts.cranker.add_lamports(game_bounty)?;
+        game.close(rent_receiver.to_account_info())?;
+    }
+
+    Ok(())
+}
+
+/// A player's refund and where it goes.
+struct Refund<'info> {
+    player: Pubkey,
+    amount: u64,
+    destination: &'info AccountInfo<'info>,
+    // Set for SPL games
+    token_account: Option<Account<'info, TokenAccount>>,
+}
+
+/// One game's accounts, deserialized and checked against the game.
+struct CrankGroup<'info> {
+    game: AccountLoader<'info, Game>,
+    creator: &'info AccountInfo<'info>,
+    rent_receiver: &'info AccountInfo<'info>,
+    sol_vault: SystemAccount<'info>,
+    vault: Option<Account<'info, TokenAccount>>,
+    creator_token_account: Option<Account<'info, TokenAccount>>,
+    refunds: [Refund<'info>; 2],
+    // Both players' stats, for games canceled here once both had staked
+    stats: Option<[Account<'info, PlayerStats>; 2]>,
+    // Unset for games that were already canceled
+    reason: Option<CancelReason>,
+}
+
+impl<'info> CrankGroup<'info> {
+    /// Everything the crank would reject for this game, checked up front.
+    fn load(accounts: &'info [AccountInfo<'info>], clock: &Clock) -> Result<Self> {
+        let [game_info, creator, rent_receiver, sol_vault, vault, creator_token_account, player1_destination, player2_destination, player1_stats, player2_stats] = accounts else {
+            return Err(WagerError::BatchAccountMismatch.into());
+        };
+        require!(
+            accounts.iter().all(|info| info.key == &crate::ID || info.is_writable),
+            WagerError::BatchAccountMismatch
+        );
+
+        let game_account = AccountLoader::<Game>::try_from(game_info)?;
+        let game = game_account.load()?;
+        game.check_address(game_info.key)?;
+        require!(game.can_crank(clock), WagerError::GameNotCrankable);
+        require_keys_eq!(creator.key(), game.creator, WagerError::BatchAccountMismatch);
+        require_keys_eq!(rent_receiver.key(), game.rent_receiver(), WagerError::BatchAccountMismatch);
+
+        let sol_vault = SystemAccount::try_from(sol_vault)?;
+        check_pda(&sol_vault, &[b"sol_vault", game_info.key.as_ref()], game.sol_vault_bump, WagerError::BatchAccountMismatch)?;
+        let vault = optional::<TokenAccount>(vault)?;
+        match &vault {
+            Some(vault) => check_pda(vault, &[b"vault", game_info.key.as_ref(), game.mint.as_ref()], game.vault_bump, WagerError::BatchAccountMismatch)?,
+            None => require!(game.is_native_sol(), WagerError::InvalidTokenAccount),
+        }
+
+        let (amounts, stats, reason) = match game.state()? {
+            GameState::Open => ([game.deposit_for(Side::Player1)?, 0], None, Some(CancelReason::ExpiredOpen)),
+            GameState::Ready => {
+                // Tallied apart from played games for both players
+                let stats = [Account::<PlayerStats>::try_from(player1_stats)?, Account::<PlayerStats>::try_from(player2_stats)?];
+                for (side, stats) in [Side::Player1, Side::Player2].into_iter().zip(&stats) {
+                    check_pda(stats, &[b"player_stats", game.player(side).as_ref()], stats.bump, WagerError::BatchAccountMismatch)?;
+                }
+                let amounts = [game.deposit_for(Side::Player1)?, game.deposit_for(Side::Player2)?];
+                (amounts, Some(stats), Some(CancelReason::ExpiredUnresolved))
+            },
+            // Already canceled; pay out whatever is still unclaimed
+            _ => ([game.player1_claimable, game.player2_claimable], None, None),
+        };
+
+        let refund = |side: Side, amount: u64, destination: &'info AccountInfo<'info>| -> Result<Refund<'info>> {
+            let player = game.player(side);
+            let token_account = if amount == 0 || game.is_native_sol() {
+                require!(amount == 0 || destination.key() == player, WagerError::BatchAccountMismatch);
+                None
+            } else {
+                let token_account = Account::<TokenAccount>::try_from(destination)?;
+                require_keys_eq!(token_account.owner, player, WagerError::BatchAccountMismatch);
+                require_keys_eq!(token_account.mint, game.mint, WagerError::TokenMintMismatch);
+                Some(token_account)
+            };
+            Ok(Refund { player, amount, destination, token_account })
+        };
+        let refunds = [
+            refund(Side::Player1, amounts[0], player1_destination)?,
+            refund(Side::Player2, amounts[1], player2_destination)?,
+        ];
+
+        // The escrow must cover the refunds; anything beyond them goes to the
+        // creator, into their token account for SPL games
+        let refunded = amounts[0].checked_add(amounts[1]).ok_or(WagerError::MathOverflow)?;
+        let escrowed = match &vault {
+            Some(vault) => vault.amount,
+            None => sol_vault.lamports().saturating_sub(Rent::get()?.minimum_balance(0)),
+        };
+        require!(escrowed >= refunded, WagerError::InsufficientEscrow);
+        let creator_token_account = if vault.is_some() && escrowed > refunded {
+            let creator_token_account = Account::<TokenAccount>::try_from(creator_token_account)?;
+            require_keys_eq!(creator_token_account.owner, game.creator, WagerError::BatchAccountMismatch);
+            require_keys_eq!(creator_token_account.mint, game.mint, WagerError::TokenMintMismatch);
+            Some(creator_token_account)
+        } else {
+            None
+        };
+        drop(game);
+
+        Ok(Self {
+            game: game_account,
+            creator,
+            rent_receiver,
+            sol_vault,
+            vault,
+            creator_token_account,
+            refunds,
+            stats,
+            reason,
+        })
+    }
+}
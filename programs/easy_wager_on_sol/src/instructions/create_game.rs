@@ -1,60 +1,190 @@
 use anchor_lang::prelude::*;
+use anchor_spl::metadata::MetadataAccount;
 use anchor_spl::token::{Token, TokenAccount, Mint};
-use crate::{state::*, error::WagerError};
+use crate::{state::*, error::WagerError, escrow::{self, Deposit, NftDeposit}};
 
+#[event_cpi]
 #[derive(Accounts)]
-#[instruction(mint: Pubkey, wager: u64, payout_bps: u16, expiry_ts: i64, resolver_pubkey: Option<Pubkey>, nonce: u64)]
+#[instruction(args: CreateGameArgs)]
 pub struct CreateGame<'info> {
+    // Creator's wallet, or a session key approved by it
     #[account(mut)]
     pub creator: Signer<'info>,
 
+    // Pays rent and fees; a relayer sponsoring the transaction, or `creator` itself
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    // Relayer's token account for its fee (SPL games with a relayer fee)
+    #[account(
+        mut,
+        token::mint = args.mint,
+        token::authority = payer
+    )]
+    pub relayer_token_account: Option<Account<'info, TokenAccount>>,
+
     #[account(
         init,
-        payer = creator,
+        payer = payer,
         space = Game::LEN,
-        seeds = [b"game", creator.key().as_ref(), nonce.to_le_bytes().as_ref()],
+        seeds = [b"game", SessionAuthority::wallet_for(creator.key(), &session).as_ref(), args.nonce.to_le_bytes().as_ref()],
         bump
     )]
-    pub game: Account<'info, Game>,
+    pub game: AccountLoader<'info, Game>,
 
     // For SPL tokens, we need a vault token account
     #[account(
         init,
-        payer = creator,
+        payer = payer,
         token::mint = token_mint,
         token::authority = game,
-        seeds = [b"vault", game.key().as_ref(), token_mint.key().as_ref()],
+        seeds = [b"vault", game.key().as_ref(), args.mint.as_ref()],
         bump
     )]
     pub vault: Option<Account<'info, TokenAccount>>,
 
+    // Lifetime record of the creator, created on their first game
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = PlayerStats::LEN,
+        seeds = [b"player_stats", SessionAuthority::wallet_for(creator.key(), &session).as_ref()],
+        bump
+    )]
+    pub creator_stats: Box<Account<'info, PlayerStats>>,
+
+    // Creator's rating in the game type (ranked games only)
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = Rating::LEN,
+        seeds = [b"rating", SessionAuthority::wallet_for(creator.key(), &session).as_ref(), args.game_type.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub creator_rating: Option<Box<Account<'info, Rating>>>,
+
+    // System-owned vault holding native SOL stakes (and unwrapped wSOL payouts)
+    #[account(
+        mut,
+        seeds = [b"sol_vault", game.key().as_ref()],
+        bump
+    )]
+    pub sol_vault: SystemAccount<'info>,
+
     // Token mint (ignored if using native SOL)
     pub token_mint: Option<Account<'info, Mint>>,
 
+    // Creator's token account (for SPL tokens; optional for wrapped SOL)
+    #[account(
+        mut,
+        token::mint = args.mint,
+        token::authority = creator
+    )]
+    pub creator_token_account: Option<Account<'info, TokenAccount>>,
+
+    // Creator's pre-funded balance, debited instead of their wallet if passed
+    #[account(
+        mut,
+        seeds = [b"balance", SessionAuthority::wallet_for(creator.key(), &session).as_ref(), args.mint.as_ref()],
+        bump = creator_balance.bump
+    )]
+    pub creator_balance: Option<Box<Account<'info, PlayerBalance>>>,
+
+    #[account(
+        mut,
+        seeds = [b"balance_vault", SessionAuthority::wallet_for(creator.key(), &session).as_ref(), args.mint.as_ref()],
+        bump,
+        token::mint = args.mint,
+        token::authority = creator_balance
+    )]
+    pub creator_balance_vault: Option<Box<Account<'info, TokenAccount>>>,
+
+    // NFT staked by the creator, if player1's asset is an NFT
+    pub nft_mint: Option<Box<Account<'info, Mint>>>,
+
+    #[account(
+        mut,
+        token::mint = nft_mint,
+        token::authority = creator
+    )]
+    pub creator_nft_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(
+        init,
+        payer = payer,
+        token::mint = nft_mint,
+        token::authority = game,
+        seeds = [b"nft_vault", game.key().as_ref(), creator.key().as_ref()],
+        bump
+    )]
+    pub nft_vault: Option<Box<Account<'info, TokenAccount>>>,
+
+    // Metaplex metadata of the NFT (required when a collection is enforced)
+    pub nft_metadata: Option<Box<Account<'info, MetadataAccount>>>,
+
     /// CHECK: Dev wallet can be any valid pubkey
     pub dev_wallet: UncheckedAccount<'info>,
 
+    // Approval for `creator` to sign as a session key of its owner's wallet
+    #[account(
+        mut,
+        seeds = [b"session", session.owner.as_ref(), creator.key().as_ref()],
+        bump = session.bump
+    )]
+    pub session: Option<Box<Account<'info, SessionAuthority>>>,
+
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
 
-pub fn handler(
-    ctx: Context<CreateGame>,
-    mint: Pubkey,
-    wager: u64,
-    payout_bps: u16,
-    expiry_ts: i64,
-    resolver_pubkey: Option<Pubkey>,
-    nonce: u64,
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CreateGame<'info>>,
+    args: CreateGameArgs,
 ) -> Result<()> {
+    let CreateGameArgs {
+        mint,
+        wager,
+        payout_bps,
+        expiry_ts,
+        resolver,
+        nonce,
+        best_of,
+        player1_asset,
+        player2_asset,
+        asset_fee,
+        player1_basket,
+        player2_basket,
+        game_type,
+        min_rating,
+        max_rating,
+        relayer_fee,
+        crank_bounty_bps,
+    } = args;
     let clock = Clock::get()?;
     
     // Validation
-    require!(wager > 0, WagerError::InvalidWagerAmount);
     require!(payout_bps > 0 && payout_bps < 10000, WagerError::InvalidPayoutBps);
     require!(expiry_ts > clock.unix_timestamp, WagerError::InvalidExpiryTime);
     require!(ctx.accounts.dev_wallet.key() != Pubkey::default(), WagerError::InvalidDevWallet);
+    require!(
+        best_of % 2 == 1 && best_of <= Game::MAX_BEST_OF,
+        WagerError::InvalidSeriesLength
+    );
+    player1_basket.validate()?;
+    player2_basket.validate()?;
+    require!(min_rating <= max_rating, WagerError::InvalidRatingBand);
+    require!(
+        asset_fee == 0 || ((player1_asset.is_nft() || player2_asset.is_nft()) && asset_fee <= Game::MAX_ASSET_FEE),
+        WagerError::InvalidAssetFee
+    );
+    require!(crank_bounty_bps <= 10000, WagerError::InvalidPayoutBps);
+    // Only a relayer sponsoring the transaction is reimbursed, and a session
+    // key can't sign one away for its owner
+    require!(
+        relayer_fee == 0 || (ctx.accounts.session.is_none() && ctx.accounts.payer.key() != ctx.accounts.creator.key()),
+        WagerError::RelayerFeeNotAllowed
+    );
 
     // Check if using native SOL or SPL token
     let is_native_sol = mint == Pubkey::default();
@@ -68,36 +198,153 @@ pub fn handler(
         require!(token_mint.key() == mint, WagerError::TokenMintMismatch);
     }
 
-    let vault_bump = if let Some(vault) = &ctx.accounts.vault {
-        ctx.bumps.get("vault").copied().unwrap_or(0)
-    } else {
-        0
-    };
+    let vault_bump = ctx.bumps.vault.unwrap_or(0);
 
-    let game = &mut ctx.accounts.game;
-    game.creator = ctx.accounts.creator.key();
-    game.player1 = ctx.accounts.creator.key();
+    // A session key creates the game for the wallet it was approved by
+    let creator_key = SessionAuthority::wallet_for(ctx.accounts.creator.key(), &ctx.accounts.session);
+
+    let mut game = ctx.accounts.game.load_init()?;
+    game.creator = creator_key;
+    game.player1 = creator_key;
     game.player2 = Pubkey::default(); // Will be set when someone joins
-    game.resolver = resolver_pubkey.unwrap_or(ctx.accounts.creator.key());
+    game.resolver = resolver.unwrap_or(creator_key);
     game.dev_wallet = ctx.accounts.dev_wallet.key();
     game.mint = mint;
     game.wager = wager;
     game.payout_bps = payout_bps;
-    game.state = GameState::Open;
+    game.set_state(GameState::Open);
     game.expiry_ts = expiry_ts;
     game.nonce = nonce;
     game.bump = ctx.bumps.game;
     game.vault_bump = vault_bump;
+    game.best_of = best_of;
+    game.player1_wins = 0;
+    game.player2_wins = 0;
+    game.side_pool = Pubkey::default();
+    game.winner = Pubkey::default();
+    game.player1_claimable = 0;
+    game.player2_claimable = 0;
+    game.fee_claimable = 0;
+    game.sol_vault_bump = ctx.bumps.sol_vault;
+    game.player1_asset = player1_asset;
+    game.player2_asset = player2_asset;
+    game.asset_fee = asset_fee;
+    game.nfts_claimed = 0;
+    game.player1_basket = player1_basket;
+    game.player2_basket = player2_basket;
+    game.basket_paid = 0;
+    game.basket_fee_paid = 0;
+    game.game_type = game_type;
+    game.min_rating = min_rating;
+    game.max_rating = max_rating;
+    game.rent_payer = ctx.accounts.payer.key();
+    game.crank_bounty_bps = crank_bounty_bps;
+    game.version = Game::VERSION;
+
+    require!(
+        game.stakes_something(Side::Player1) && game.stakes_something(Side::Player2),
+        WagerError::EmptyStake
+    );
+    // A pot too large to split could never be resolved
+    game.calculate_payouts()?;
+
+    // Creator stakes their side of the wager up front, plus any relayer fee
+    let amount = game.deposit_for(Side::Player1)?;
+    let total = amount.checked_add(relayer_fee).ok_or(WagerError::MathOverflow)?;
+
+    // Write the discriminator now so the escrow helpers can load the game
+    drop(game);
+    ctx.accounts.game.exit(&crate::ID)?;
+
+    ctx.accounts.creator_stats.init_if_new(creator_key, ctx.bumps.creator_stats);
+
+    if game_type != 0 {
+        let creator_rating = ctx.accounts.creator_rating.as_mut().ok_or(WagerError::MissingRating)?;
+        creator_rating.init_if_new(creator_key, game_type, ctx.bumps.creator_rating.unwrap_or_default());
+    }
+
+    if player1_asset.is_nft() {
+        let nft_mint = escrow::deposit_nft(
+            NftDeposit {
+                depositor: &ctx.accounts.creator,
+                nft_mint: ctx.accounts.nft_mint.as_deref(),
+                depositor_nft_account: ctx.accounts.creator_nft_account.as_deref(),
+                nft_vault: ctx.accounts.nft_vault.as_deref(),
+                nft_metadata: ctx.accounts.nft_metadata.as_deref(),
+                token_program: &ctx.accounts.token_program,
+            },
+            &player1_asset,
+        )?;
+        ctx.accounts.game.load_mut()?.player1_asset.mint = nft_mint;
+    }
+
+    if let Some(session) = ctx.accounts.session.as_mut() {
+        // Session keys can't move the owner's NFTs or tokens directly
+        require!(
+            ctx.accounts.creator_balance.is_some() && !player1_asset.is_nft() && player1_basket.is_empty(),
+            WagerError::SessionStakeNotAllowed
+        );
+        session.authorize_terms(&*ctx.accounts.game.load()?)?;
+        session.authorize_spend(mint, total, &clock)?;
+    }
+    escrow::deposit(
+        Deposit {
+            depositor: &ctx.accounts.creator,
+            payer: &ctx.accounts.payer,
+            depositor_token_account: ctx.accounts.creator_token_account.as_ref(),
+            balance: ctx.accounts.creator_balance.as_deref_mut(),
+            balance_vault: ctx.accounts.creator_balance_vault.as_deref(),
+            game: &ctx.accounts.game,
+            vault: ctx.accounts.vault.as_ref(),
+            sol_vault: &ctx.accounts.sol_vault,
+            token_program: &ctx.accounts.token_program,
+            system_program: &ctx.accounts.system_program,
+        },
+        total,
+    )?;
+    escrow::pay_relayer(
+        &ctx.accounts.game,
+        ctx.accounts.vault.as_ref(),
+        &ctx.accounts.sol_vault,
+        &ctx.accounts.payer,
+        ctx.accounts.relayer_token_account.as_ref(),
+        &ctx.accounts.token_program,
+        &ctx.accounts.system_program,
+        relayer_fee,
+    )?;
+
+    // Basket legs are passed as remaining accounts
+    escrow::deposit_basket(
+        &ctx.accounts.creator,
+        &ctx.accounts.payer,
+        &ctx.accounts.game,
+        &player1_basket,
+        ctx.remaining_accounts,
+        &ctx.accounts.token_program,
+        &ctx.accounts.system_program,
+    )?;
 
     // Emit event
-    emit!(GameCreated {
-        game: game.key(),
-        creator: game.creator,
-        mint: game.mint,
-        wager: game.wager,
-        payout_bps: game.payout_bps,
-        expiry_ts: game.expiry_ts,
+    let game_key = ctx.accounts.game.key();
+    emit_cpi!(GameCreated {
+        game: game_key,
+        creator: creator_key,
+        mint,
+        wager,
+        amount,
+        payout_bps,
+        expiry_ts,
+        timestamp: clock.unix_timestamp,
     });
+    if relayer_fee > 0 {
+        emit_cpi!(RelayerReimbursed {
+            game: game_key,
+            relayer: ctx.accounts.payer.key(),
+            fee: relayer_fee,
+            mint,
+            timestamp: clock.unix_timestamp,
+        });
+    }
 
     Ok(())
 }
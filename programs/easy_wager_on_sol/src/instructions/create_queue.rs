@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, Mint};
+use crate::{state::*, error::WagerError};
+
+#[derive(Accounts)]
+#[instruction(mint: Pubkey, wager: u64, game_type: u32)]
+pub struct CreateQueue<'info> {
+    // Game server operating the queue; resolves every game it matches
+    #[account(mut)]
+    pub resolver: Signer<'info>,
+
+    #[account(
+        init,
+        payer = resolver,
+        space = MatchQueue::LEN,
+        seeds = [
+            b"queue",
+            resolver.key().as_ref(),
+            mint.as_ref(),
+            wager.to_le_bytes().as_ref(),
+            game_type.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub queue: Box<Account<'info, MatchQueue>>,
+
+    // Queue's vault holding waiting players' stakes (for SPL tokens)
+    #[account(
+        init,
+        payer = resolver,
+        token::mint = token_mint,
+        token::authority = queue,
+        seeds = [b"vault", queue.key().as_ref(), mint.as_ref()],
+        bump
+    )]
+    pub vault: Option<Account<'info, TokenAccount>>,
+
+    // System-owned vault holding waiting players' native SOL stakes
+    #[account(
+        seeds = [b"sol_vault", queue.key().as_ref()],
+        bump
+    )]
+    pub sol_vault: SystemAccount<'info>,
+
+    // Token mint (ignored if using native SOL)
+    pub token_mint: Option<Account<'info, Mint>>,
+
+    /// CHECK: Dev wallet can be any valid pubkey
+    pub dev_wallet: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn handler(
+    ctx: Context<CreateQueue>,
+    mint: Pubkey,
+    wager: u64,
+    game_type: u32,
+    payout_bps: u16,
+    game_duration: i64,
+) -> Result<()> {
+    // Validation
+    require!(wager > 0, WagerError::InvalidWagerAmount);
+    require!(payout_bps > 0 && payout_bps < 10000, WagerError::InvalidPayoutBps);
+    require!(game_duration > 0, WagerError::InvalidExpiryTime);
+    require!(ctx.accounts.dev_wallet.key() != Pubkey::default(), WagerError::InvalidDevWallet);
+
+    if mint != Pubkey::default() {
+        // Ensure token mint and vault are provided for SPL tokens
+        let token_mint = ctx.accounts.token_mint.as_ref().ok_or(WagerError::TokenMintMismatch)?;
+        require!(token_mint.key() == mint, WagerError::TokenMintMismatch);
+        require!(ctx.accounts.vault.is_some(), WagerError::InvalidTokenAccount);
+    }
+
+    let queue = &mut ctx.accounts.queue;
+    queue.resolver = ctx.accounts.resolver.key();
+    queue.dev_wallet = ctx.accounts.dev_wallet.key();
+    queue.mint = mint;
+    queue.wager = wager;
+    queue.game_type = game_type;
+    queue.payout_bps = payout_bps;
+    queue.game_duration = game_duration;
+    queue.games_matched = 0;
+    queue.bump = ctx.bumps.queue;
+    queue.vault_bump = ctx.bumps.vault.unwrap_or(0);
+    queue.sol_vault_bump = ctx.bumps.sol_vault;
+    queue.len = 0;
+    queue.entries = [Pubkey::default(); MatchQueue::MAX_ENTRIES];
+
+    emit!(QueueCreated {
+        queue: queue.key(),
+        resolver: queue.resolver,
+        mint,
+        wager,
+        game_type,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::{Token, TokenAccount, Mint};
+use crate::{state::*, error::WagerError};
+
+#[derive(Accounts)]
+pub struct CreateSidePool<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(mut)]
+    pub game: AccountLoader<'info, Game>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = SidePool::LEN,
+        seeds = [b"side_pool", game.key().as_ref()],
+        bump
+    )]
+    pub side_pool: Account<'info, SidePool>,
+
+    // Side pool vault (for SPL tokens). Created here, so it is derived from the
+    // game's mint in the constraints
+    #[account(
+        init,
+        payer = creator,
+        token::mint = token_mint,
+        token::authority = side_pool,
+        seeds = [b"vault", side_pool.key().as_ref(), game.load()?.mint.as_ref()],
+        bump
+    )]
+    pub vault: Option<Account<'info, TokenAccount>>,
+
+    // System-owned vault holding native SOL bets
+    #[account(
+        mut,
+        seeds = [b"sol_vault", side_pool.key().as_ref()],
+        bump
+    )]
+    pub sol_vault: SystemAccount<'info>,
+
+    // Token mint (ignored if using native SOL)
+    pub token_mint: Option<Account<'info, Mint>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn handler(ctx: Context<CreateSidePool>, fee_bps: u16, cutoff_ts: i64) -> Result<()> {
+    let clock = Clock::get()?;
+    let mut game = ctx.accounts.game.load_mut()?;
+    game.check_address(&ctx.accounts.game.key())?;
+    require_keys_eq!(ctx.accounts.creator.key(), game.creator, WagerError::UnauthorizedCreator);
+    if let Some(token_mint) = &ctx.accounts.token_mint {
+        require_keys_eq!(token_mint.key(), game.mint, WagerError::TokenMintMismatch);
+    }
+
+    // Validation
+    require!(matches!(game.state()?, GameState::Open), WagerError::GameNotOpen);
+    require!(fee_bps < 10000, WagerError::InvalidPayoutBps);
+    require!(
+        cutoff_ts == 0 || cutoff_ts > clock.unix_timestamp,
+        WagerError::InvalidExpiryTime
+    );
+    if game.is_native_sol() {
+        // The creator funds the SOL vault's rent reserve, returned on close
+        let reserve = Rent::get()?.minimum_balance(0);
+        let top_up = reserve.saturating_sub(ctx.accounts.sol_vault.lamports());
+        if top_up > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.creator.to_account_info(),
+                        to: ctx.accounts.sol_vault.to_account_info(),
+                    }
+                ),
+                top_up
+            )?;
+        }
+    } else {
+        require!(ctx.accounts.vault.is_some(), WagerError::InvalidTokenAccount);
+    }
+
+    let side_pool = &mut ctx.accounts.side_pool;
+    side_pool.game = ctx.accounts.game.key();
+    side_pool.mint = game.mint;
+    side_pool.fee_bps = fee_bps;
+    side_pool.cutoff_ts = cutoff_ts;
+    side_pool.total_player1 = 0;
+    side_pool.total_player2 = 0;
+    side_pool.outcome = SideOutcome::Pending;
+    side_pool.fee_claimed = false;
+    side_pool.bump = ctx.bumps.side_pool;
+    side_pool.vault_bump = ctx.bumps.vault.unwrap_or(0);
+    side_pool.sol_vault_bump = ctx.bumps.sol_vault;
+    side_pool.open_bets = 0;
+
+    game.side_pool = side_pool.key();
+
+    emit!(SidePoolCreated {
+        game: ctx.accounts.game.key(),
+        side_pool: side_pool.key(),
+        fee_bps,
+        cutoff_ts,
+        mint: side_pool.mint,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
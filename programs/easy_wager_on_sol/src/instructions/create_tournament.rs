@@ -0,0 +1,153 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::{Token, TokenAccount, Mint};
+use crate::{state::*, error::WagerError};
+
+#[derive(Accounts)]
+#[instruction(mint: Pubkey, entry_fee: u64, fee_bps: u16, max_players: u8, registration_end_ts: i64, payout_table: Vec<u16>, resolver_pubkey: Option<Pubkey>, nonce: u64)]
+pub struct CreateTournament<'info> {
+    #[account(mut)]
+    pub organizer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = organizer,
+        space = Tournament::LEN,
+        seeds = [b"tournament", organizer.key().as_ref(), nonce.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub tournament: Account<'info, Tournament>,
+
+    // Shared prize vault (for SPL tokens)
+    #[account(
+        init,
+        payer = organizer,
+        token::mint = token_mint,
+        token::authority = tournament,
+        seeds = [b"vault", tournament.key().as_ref(), mint.as_ref()],
+        bump
+    )]
+    pub vault: Option<Account<'info, TokenAccount>>,
+
+    // System-owned vault holding native SOL entry fees
+    #[account(
+        mut,
+        seeds = [b"sol_vault", tournament.key().as_ref()],
+        bump
+    )]
+    pub sol_vault: SystemAccount<'info>,
+
+    // Token mint (ignored if using native SOL)
+    pub token_mint: Option<Account<'info, Mint>>,
+
+    /// CHECK: Dev wallet can be any valid pubkey
+    pub dev_wallet: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn handler(
+    ctx: Context<CreateTournament>,
+    mint: Pubkey,
+    entry_fee: u64,
+    fee_bps: u16,
+    max_players: u8,
+    registration_end_ts: i64,
+    payout_table: Vec<u16>,
+    resolver_pubkey: Option<Pubkey>,
+    nonce: u64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+
+    // Validation
+    require!(entry_fee > 0, WagerError::InvalidWagerAmount);
+    require!(fee_bps < 10000, WagerError::InvalidPayoutBps);
+    require!(registration_end_ts > clock.unix_timestamp, WagerError::InvalidExpiryTime);
+    require!(ctx.accounts.dev_wallet.key() != Pubkey::default(), WagerError::InvalidDevWallet);
+    require!(
+        max_players >= 2
+            && max_players.is_power_of_two()
+            && max_players as usize <= Tournament::MAX_PLAYERS,
+        WagerError::InvalidBracketSize
+    );
+
+    // One entry per finishing tier; tier t is shared by max(1, 2^(t-1)) players
+    let tiers = max_players.trailing_zeros() as usize + 1;
+    require!(
+        !payout_table.is_empty() && payout_table.len() <= tiers,
+        WagerError::InvalidPayoutTable
+    );
+    let total_bps = payout_table
+        .iter()
+        .enumerate()
+        .try_fold(0u64, |acc, (tier, bps)| {
+            let players_in_tier = if tier == 0 { 1 } else { 1u64 << (tier - 1) };
+            acc.checked_add(players_in_tier.checked_mul(*bps as u64)?)
+        })
+        .ok_or(WagerError::MathOverflow)?;
+    require!(total_bps == 10_000, WagerError::InvalidPayoutTable);
+
+    let is_native_sol = mint == Pubkey::default();
+
+    if is_native_sol {
+        // The organizer funds the SOL vault's rent reserve, so paying out
+        // every entry fee never leaves it below rent exemption
+        let reserve = Rent::get()?.minimum_balance(0);
+        let top_up = reserve.saturating_sub(ctx.accounts.sol_vault.lamports());
+        if top_up > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.organizer.to_account_info(),
+                        to: ctx.accounts.sol_vault.to_account_info(),
+                    }
+                ),
+                top_up
+            )?;
+        }
+    } else {
+        // Ensure token mint and vault are provided for SPL tokens
+        require!(ctx.accounts.token_mint.is_some(), WagerError::TokenMintMismatch);
+        require!(ctx.accounts.vault.is_some(), WagerError::InvalidTokenAccount);
+
+        let token_mint = ctx.accounts.token_mint.as_ref().unwrap();
+        require!(token_mint.key() == mint, WagerError::TokenMintMismatch);
+    }
+
+    let tournament = &mut ctx.accounts.tournament;
+    tournament.organizer = ctx.accounts.organizer.key();
+    tournament.resolver = resolver_pubkey.unwrap_or(ctx.accounts.organizer.key());
+    tournament.dev_wallet = ctx.accounts.dev_wallet.key();
+    tournament.mint = mint;
+    tournament.entry_fee = entry_fee;
+    tournament.fee_bps = fee_bps;
+    tournament.max_players = max_players;
+    tournament.bracket_size = 0;
+    tournament.state = TournamentState::Registration;
+    tournament.registration_end_ts = registration_end_ts;
+    tournament.nonce = nonce;
+    tournament.bump = ctx.bumps.tournament;
+    tournament.vault_bump = ctx.bumps.vault.unwrap_or(0);
+    tournament.sol_vault_bump = ctx.bumps.sol_vault;
+    tournament.claimed = 0;
+    tournament.fee_claimed = false;
+    tournament.payout_table = payout_table;
+    tournament.players = Vec::new();
+    tournament.matches = Vec::new();
+
+    emit!(TournamentCreated {
+        tournament: tournament.key(),
+        organizer: tournament.organizer,
+        mint: tournament.mint,
+        entry_fee: tournament.entry_fee,
+        max_players: tournament.max_players,
+        registration_end_ts: tournament.registration_end_ts,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::{self, Token, TokenAccount, Mint, Transfer};
+use crate::{state::*, error::WagerError};
+
+#[derive(Accounts)]
+#[instruction(mint: Pubkey)]
+pub struct DepositBalance<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = PlayerBalance::LEN,
+        seeds = [b"balance", owner.key().as_ref(), mint.as_ref()],
+        bump
+    )]
+    pub balance: Box<Account<'info, PlayerBalance>>,
+
+    // Tokens held for the balance (for SPL tokens)
+    #[account(
+        init_if_needed,
+        payer = owner,
+        token::mint = token_mint,
+        token::authority = balance,
+        seeds = [b"balance_vault", owner.key().as_ref(), mint.as_ref()],
+        bump
+    )]
+    pub balance_vault: Option<Box<Account<'info, TokenAccount>>>,
+
+    // Token mint (ignored if using native SOL)
+    #[account(address = mint @ WagerError::TokenMintMismatch)]
+    pub token_mint: Option<Box<Account<'info, Mint>>>,
+
+    // Owner's token account (for SPL tokens)
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = owner
+    )]
+    pub owner_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn handler(ctx: Context<DepositBalance>, mint: Pubkey, amount: u64) -> Result<()> {
+    require!(amount > 0, WagerError::InvalidWagerAmount);
+
+    let balance = &mut ctx.accounts.balance;
+    if balance.owner == Pubkey::default() {
+        balance.owner = ctx.accounts.owner.key();
+        balance.mint = mint;
+        balance.bump = ctx.bumps.balance;
+        balance.vault_bump = ctx.bumps.balance_vault.unwrap_or(0);
+    }
+
+    if balance.is_native_sol() {
+        // Lamports sit on the balance account, on top of its rent
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.owner.to_account_info(),
+                    to: balance.to_account_info(),
+                }
+            ),
+            amount
+        )?;
+    } else {
+        let owner_token_account = ctx.accounts.owner_token_account.as_ref().ok_or(WagerError::InvalidTokenAccount)?;
+        let balance_vault = ctx.accounts.balance_vault.as_ref().ok_or(WagerError::InvalidTokenAccount)?;
+
+        let transfer_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: owner_token_account.to_account_info(),
+                to: balance_vault.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            }
+        );
+        token::transfer(transfer_ctx, amount)?;
+    }
+
+    balance.amount = balance.amount.checked_add(amount).ok_or(WagerError::MathOverflow)?;
+
+    emit!(BalanceDeposited {
+        owner: balance.owner,
+        mint,
+        amount,
+        balance: balance.amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
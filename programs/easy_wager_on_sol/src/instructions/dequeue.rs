@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::{state::*, error::WagerError};
+
+#[derive(Accounts)]
+pub struct Dequeue<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"queue",
+            queue.resolver.as_ref(),
+            queue.mint.as_ref(),
+            queue.wager.to_le_bytes().as_ref(),
+            queue.game_type.to_le_bytes().as_ref()
+        ],
+        bump = queue.bump
+    )]
+    pub queue: Box<Account<'info, MatchQueue>>,
+
+    // Player's token account (for SPL tokens)
+    #[account(
+        mut,
+        token::mint = queue.mint,
+        token::authority = player
+    )]
+    pub player_token_account: Option<Account<'info, TokenAccount>>,
+
+    // Queue's vault (for SPL tokens)
+    #[account(
+        mut,
+        seeds = [b"vault", queue.key().as_ref(), queue.mint.as_ref()],
+        bump = queue.vault_bump,
+        token::mint = queue.mint,
+        token::authority = queue
+    )]
+    pub vault: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_vault", queue.key().as_ref()],
+        bump = queue.sol_vault_bump
+    )]
+    pub sol_vault: SystemAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Leaves the queue and refunds the player's stake straight away.
+pub fn handler(ctx: Context<Dequeue>) -> Result<()> {
+    let queue = &ctx.accounts.queue;
+    let player = ctx.accounts.player.key();
+
+    let index = queue.position(&player).ok_or(WagerError::NotQueued)?;
+    let refund = queue.wager;
+
+    if queue.is_native_sol() {
+        let queue_key = queue.key();
+        let bump_bytes = [queue.sol_vault_bump];
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"sol_vault",
+            queue_key.as_ref(),
+            &bump_bytes,
+        ]];
+
+        system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.sol_vault.to_account_info(),
+                    to: ctx.accounts.player.to_account_info(),
+                },
+                signer_seeds
+            ),
+            refund
+        )?;
+    } else {
+        let player_token_account = ctx.accounts.player_token_account.as_ref().ok_or(WagerError::InvalidTokenAccount)?;
+        let vault = ctx.accounts.vault.as_ref().ok_or(WagerError::InvalidTokenAccount)?;
+
+        // Create signer seeds for the queue PDA
+        let wager_bytes = queue.wager.to_le_bytes();
+        let game_type_bytes = queue.game_type.to_le_bytes();
+        let bump_bytes = [queue.bump];
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"queue",
+            queue.resolver.as_ref(),
+            queue.mint.as_ref(),
+            wager_bytes.as_ref(),
+            game_type_bytes.as_ref(),
+            &bump_bytes,
+        ]];
+
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: vault.to_account_info(),
+                to: player_token_account.to_account_info(),
+                authority: queue.to_account_info(),
+            },
+            signer_seeds
+        );
+        token::transfer(transfer_ctx, refund)?;
+    }
+
+    let queue = &mut ctx.accounts.queue;
+    queue.remove(index);
+
+    emit!(PlayerDequeued {
+        queue: queue.key(),
+        player,
+        refund,
+        mint: queue.mint,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,132 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::{state::*, error::WagerError};
+
+#[derive(Accounts)]
+pub struct Enqueue<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"queue",
+            queue.resolver.as_ref(),
+            queue.mint.as_ref(),
+            queue.wager.to_le_bytes().as_ref(),
+            queue.game_type.to_le_bytes().as_ref()
+        ],
+        bump = queue.bump
+    )]
+    pub queue: Box<Account<'info, MatchQueue>>,
+
+    // Player's token account (for SPL tokens)
+    #[account(
+        mut,
+        token::mint = queue.mint,
+        token::authority = player
+    )]
+    pub player_token_account: Option<Account<'info, TokenAccount>>,
+
+    // Queue's vault (for SPL tokens)
+    #[account(
+        mut,
+        seeds = [b"vault", queue.key().as_ref(), queue.mint.as_ref()],
+        bump = queue.vault_bump,
+        token::mint = queue.mint,
+        token::authority = queue
+    )]
+    pub vault: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_vault", queue.key().as_ref()],
+        bump = queue.sol_vault_bump
+    )]
+    pub sol_vault: SystemAccount<'info>,
+
+    // Lifetime record of the player, needed to settle the matched game
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = PlayerStats::LEN,
+        seeds = [b"player_stats", player.key().as_ref()],
+        bump
+    )]
+    pub player_stats: Box<Account<'info, PlayerStats>>,
+
+    // Player's rating in the queue's game type (ranked queues only)
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = Rating::LEN,
+        seeds = [b"rating", player.key().as_ref(), queue.game_type.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub player_rating: Option<Box<Account<'info, Rating>>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<Enqueue>) -> Result<()> {
+    let queue = &ctx.accounts.queue;
+    let player = ctx.accounts.player.key();
+
+    // Validation
+    require!(queue.position(&player).is_none(), WagerError::AlreadyQueued);
+    require!((queue.len as usize) < MatchQueue::MAX_ENTRIES, WagerError::QueueFull);
+
+    if queue.is_native_sol() {
+        // The first player also funds the vault's rent reserve
+        let reserve = Rent::get()?.minimum_balance(0);
+        let top_up = reserve.saturating_sub(ctx.accounts.sol_vault.lamports());
+
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.player.to_account_info(),
+                    to: ctx.accounts.sol_vault.to_account_info(),
+                }
+            ),
+            queue.wager.checked_add(top_up).ok_or(WagerError::MathOverflow)?
+        )?;
+    } else {
+        let player_token_account = ctx.accounts.player_token_account.as_ref().ok_or(WagerError::InvalidTokenAccount)?;
+        let vault = ctx.accounts.vault.as_ref().ok_or(WagerError::InvalidTokenAccount)?;
+
+        let transfer_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: player_token_account.to_account_info(),
+                to: vault.to_account_info(),
+                authority: ctx.accounts.player.to_account_info(),
+            }
+        );
+        token::transfer(transfer_ctx, queue.wager)?;
+    }
+
+    ctx.accounts.player_stats.init_if_new(player, ctx.bumps.player_stats);
+
+    let game_type = queue.game_type;
+    if game_type != 0 {
+        let player_rating = ctx.accounts.player_rating.as_mut().ok_or(WagerError::MissingRating)?;
+        player_rating.init_if_new(player, game_type, ctx.bumps.player_rating.unwrap_or_default());
+    }
+
+    let queue = &mut ctx.accounts.queue;
+    queue.push(player)?;
+
+    emit!(PlayerQueued {
+        queue: queue.key(),
+        player,
+        waiting: queue.len,
+        mint: queue.mint,
+        amount: queue.wager,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
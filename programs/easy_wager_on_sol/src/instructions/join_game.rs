@@ -1,100 +1,239 @@
 use anchor_lang::prelude::*;
-use anchor_lang::system_program;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
-use crate::{state::*, error::WagerError};
+use anchor_spl::metadata::MetadataAccount;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use crate::{state::*, error::WagerError, escrow::{self, Deposit, NftDeposit}, rating};
+use super::{check_balance, check_escrow};
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct JoinGame<'info> {
+    // Player2's wallet, or a session key approved by it
     #[account(mut)]
     pub player2: Signer<'info>,
 
+    // Pays rent and fees; a relayer sponsoring the transaction, or `player2` itself
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    // Relayer's token account for its fee (SPL games with a relayer fee)
+    #[account(mut, token::authority = payer)]
+    pub relayer_token_account: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub game: AccountLoader<'info, Game>,
+
+    // Player2's token account (for SPL tokens; optional for wrapped SOL)
+    #[account(mut, token::authority = player2)]
+    pub player2_token_account: Option<Account<'info, TokenAccount>>,
+
+    // Player2's pre-funded balance, debited instead of their wallet if passed
+    #[account(mut)]
+    pub player2_balance: Option<Box<Account<'info, PlayerBalance>>>,
+
+    #[account(mut, token::authority = player2_balance)]
+    pub player2_balance_vault: Option<Box<Account<'info, TokenAccount>>>,
+
+    // Game's vault (for SPL tokens)
+    #[account(mut, token::authority = game)]
+    pub vault: Option<Account<'info, TokenAccount>>,
+
+    // NFT staked by player2, if player2's asset is an NFT
+    pub nft_mint: Option<Box<Account<'info, Mint>>>,
+
     #[account(
         mut,
-        seeds = [b"game", game.creator.as_ref(), game.nonce.to_le_bytes().as_ref()],
-        bump = game.bump
+        token::mint = nft_mint,
+        token::authority = player2
     )]
-    pub game: Account<'info, Game>,
+    pub player2_nft_account: Option<Box<Account<'info, TokenAccount>>>,
 
-    // Player2's token account (for SPL tokens)
     #[account(
-        mut,
-        token::mint = game.mint,
-        token::authority = player2
+        init,
+        payer = payer,
+        token::mint = nft_mint,
+        token::authority = game,
+        seeds = [b"nft_vault", game.key().as_ref(), player2.key().as_ref()],
+        bump
     )]
-    pub player2_token_account: Option<Account<'info, TokenAccount>>,
+    pub nft_vault: Option<Box<Account<'info, TokenAccount>>>,
 
-    // Game's vault (for SPL tokens)
+    // Metaplex metadata of the NFT (required when a collection is enforced)
+    pub nft_metadata: Option<Box<Account<'info, MetadataAccount>>>,
+
+    // Lifetime record of the player, created on their first game
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = PlayerStats::LEN,
+        seeds = [b"player_stats", SessionAuthority::wallet_for(player2.key(), &session).as_ref()],
+        bump
+    )]
+    pub player2_stats: Box<Account<'info, PlayerStats>>,
+
+    // Player2's rating in the game type (ranked games only). Created here, so
+    // unlike the game's other accounts it is derived in the constraints
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = Rating::LEN,
+        seeds = [b"rating", SessionAuthority::wallet_for(player2.key(), &session).as_ref(), game.load()?.game_type.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub player2_rating: Option<Box<Account<'info, Rating>>>,
+
+    // System-owned vault holding native SOL stakes (and unwrapped wSOL payouts)
+    #[account(mut)]
+    pub sol_vault: SystemAccount<'info>,
+
+    // Approval for `player2` to sign as a session key of its owner's wallet
     #[account(
         mut,
-        seeds = [b"vault", game.key().as_ref(), game.mint.as_ref()],
-        bump = game.vault_bump,
-        token::mint = game.mint,
-        token::authority = game
+        seeds = [b"session", session.owner.as_ref(), player2.key().as_ref()],
+        bump = session.bump
     )]
-    pub vault: Option<Account<'info, TokenAccount>>,
+    pub session: Option<Box<Account<'info, SessionAuthority>>>,
 
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<JoinGame>) -> Result<()> {
-    let game = &mut ctx.accounts.game;
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, JoinGame<'info>>, relayer_fee: u64) -> Result<()> {
     let clock = Clock::get()?;
 
+    // A session key joins for the wallet it was approved by
+    let player2_key = SessionAuthority::wallet_for(ctx.accounts.player2.key(), &ctx.accounts.session);
+
     // Validation
+    let game = ctx.accounts.game.load()?;
+    check_escrow(&game, &ctx.accounts.game.key(), ctx.accounts.vault.as_ref(), &ctx.accounts.sol_vault)?;
+    for token_account in [&ctx.accounts.relayer_token_account, &ctx.accounts.player2_token_account].into_iter().flatten() {
+        require_keys_eq!(token_account.mint, game.mint, WagerError::TokenMintMismatch);
+    }
+    check_balance(
+        ctx.accounts.player2_balance.as_deref(),
+        ctx.accounts.player2_balance_vault.as_deref(),
+        &player2_key,
+        &game.mint,
+    )?;
     require!(game.can_join(), WagerError::GameNotOpen);
     require!(!game.is_expired(&clock), WagerError::GameNotExpired);
-    require!(ctx.accounts.player2.key() != game.player1, WagerError::CannotJoinOwnGame);
-
-    if game.is_native_sol() {
-        // Handle native SOL transfer
-        let lamports = game.wager;
-        
-        system_program::transfer(
-            CpiContext::new(
-                ctx.accounts.system_program.to_account_info(),
-                system_program::Transfer {
-                    from: ctx.accounts.player2.to_account_info(),
-                    to: ctx.accounts.game.to_account_info(),
-                }
-            ),
-            lamports
-        )?;
-    } else {
-        // Handle SPL token transfer
-        require!(ctx.accounts.player2_token_account.is_some(), WagerError::InvalidTokenAccount);
-        require!(ctx.accounts.vault.is_some(), WagerError::InvalidTokenAccount);
-
-        let player2_token_account = ctx.accounts.player2_token_account.as_ref().unwrap();
-        let vault = ctx.accounts.vault.as_ref().unwrap();
-
-        // Verify token account mint matches game mint
-        require!(player2_token_account.mint == game.mint, WagerError::TokenMintMismatch);
-        require!(vault.mint == game.mint, WagerError::TokenMintMismatch);
-
-        // Transfer tokens from player2 to vault
-        let transfer_ctx = CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: player2_token_account.to_account_info(),
-                to: vault.to_account_info(),
-                authority: ctx.accounts.player2.to_account_info(),
-            }
+    require!(player2_key != game.player1, WagerError::CannotJoinOwnGame);
+    // Only a relayer sponsoring the transaction is reimbursed, and a session
+    // key can't sign one away for its owner
+    require!(
+        relayer_fee == 0 || (ctx.accounts.session.is_none() && ctx.accounts.payer.key() != ctx.accounts.player2.key()),
+        WagerError::RelayerFeeNotAllowed
+    );
+
+    let player2_asset = game.player2_asset;
+    let player2_basket = game.player2_basket;
+    let amount = game.deposit_for(Side::Player2)?;
+    let total = amount.checked_add(relayer_fee).ok_or(WagerError::MathOverflow)?;
+    let rating_band = game.is_ranked().then_some((game.game_type, game.min_rating, game.max_rating));
+
+    if let Some(session) = ctx.accounts.session.as_mut() {
+        // Session keys can't move the owner's NFTs or tokens directly
+        require!(
+            ctx.accounts.player2_balance.is_some() && !player2_asset.is_nft() && player2_basket.is_empty(),
+            WagerError::SessionStakeNotAllowed
         );
+        session.authorize_terms(&game)?;
+        session.authorize_spend(game.mint, total, &clock)?;
+    }
+    drop(game);
+
+    let nft_mint = if player2_asset.is_nft() {
+        escrow::deposit_nft(
+            NftDeposit {
+                depositor: &ctx.accounts.player2,
+                nft_mint: ctx.accounts.nft_mint.as_deref(),
+                depositor_nft_account: ctx.accounts.player2_nft_account.as_deref(),
+                nft_vault: ctx.accounts.nft_vault.as_deref(),
+                nft_metadata: ctx.accounts.nft_metadata.as_deref(),
+                token_program: &ctx.accounts.token_program,
+            },
+            &player2_asset,
+        )?
+    } else {
+        Pubkey::default()
+    };
+
+    escrow::deposit(
+        Deposit {
+            depositor: &ctx.accounts.player2,
+            payer: &ctx.accounts.payer,
+            depositor_token_account: ctx.accounts.player2_token_account.as_ref(),
+            balance: ctx.accounts.player2_balance.as_deref_mut(),
+            balance_vault: ctx.accounts.player2_balance_vault.as_deref(),
+            game: &ctx.accounts.game,
+            vault: ctx.accounts.vault.as_ref(),
+            sol_vault: &ctx.accounts.sol_vault,
+            token_program: &ctx.accounts.token_program,
+            system_program: &ctx.accounts.system_program,
+        },
+        total,
+    )?;
+    escrow::pay_relayer(
+        &ctx.accounts.game,
+        ctx.accounts.vault.as_ref(),
+        &ctx.accounts.sol_vault,
+        &ctx.accounts.payer,
+        ctx.accounts.relayer_token_account.as_ref(),
+        &ctx.accounts.token_program,
+        &ctx.accounts.system_program,
+        relayer_fee,
+    )?;
 
-        token::transfer(transfer_ctx, game.wager)?;
+    // Basket legs are passed as remaining accounts
+    escrow::deposit_basket(
+        &ctx.accounts.player2,
+        &ctx.accounts.payer,
+        &ctx.accounts.game,
+        &player2_basket,
+        ctx.remaining_accounts,
+        &ctx.accounts.token_program,
+        &ctx.accounts.system_program,
+    )?;
+
+    ctx.accounts.player2_stats.init_if_new(player2_key, ctx.bumps.player2_stats);
+
+    // Ranked games only accept opponents inside the creator's rating band
+    if let Some((game_type, min_rating, max_rating)) = rating_band {
+        let player2_rating = ctx.accounts.player2_rating.as_mut().ok_or(WagerError::MissingRating)?;
+        player2_rating.init_if_new(player2_key, game_type, ctx.bumps.player2_rating.unwrap_or_default());
+        require!(
+            rating::in_band(player2_rating.rating, min_rating, max_rating),
+            WagerError::RatingOutOfBand
+        );
     }
 
     // Update game state
-    game.player2 = ctx.accounts.player2.key();
-    game.state = GameState::Ready;
+    let mut game = ctx.accounts.game.load_mut()?;
+    game.player2 = player2_key;
+    if player2_asset.is_nft() {
+        game.player2_asset.mint = nft_mint;
+    }
+    game.set_state(GameState::Ready);
 
     // Emit event
-    emit!(GameJoined {
-        game: game.key(),
+    let game_key = ctx.accounts.game.key();
+    emit_cpi!(GameJoined {
+        game: game_key,
         player1: game.player1,
         player2: game.player2,
+        mint: game.mint,
+        amount: game.deposit_for(Side::Player2)?,
+        timestamp: clock.unix_timestamp,
     });
+    if relayer_fee > 0 {
+        emit_cpi!(RelayerReimbursed {
+            game: game_key,
+            relayer: ctx.accounts.payer.key(),
+            fee: relayer_fee,
+            mint: game.mint,
+            timestamp: clock.unix_timestamp,
+        });
+    }
 
     Ok(())
 }
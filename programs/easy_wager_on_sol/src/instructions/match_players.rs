@@ -0,0 +1,221 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::{self, Token, TokenAccount, Mint, Transfer};
+use crate::{state::*, error::WagerError};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MatchPlayers<'info> {
+    // Anyone may crank the queue; they fund the new game's rent
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"queue",
+            queue.resolver.as_ref(),
+            queue.mint.as_ref(),
+            queue.wager.to_le_bytes().as_ref(),
+            queue.game_type.to_le_bytes().as_ref()
+        ],
+        bump = queue.bump
+    )]
+    pub queue: Box<Account<'info, MatchQueue>>,
+
+    // Matched games are created by the queue, numbered by games_matched
+    #[account(
+        init,
+        payer = cranker,
+        space = Game::LEN,
+        seeds = [b"game", queue.key().as_ref(), queue.games_matched.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub game: AccountLoader<'info, Game>,
+
+    // Queue's vault (for SPL tokens)
+    #[account(
+        mut,
+        seeds = [b"vault", queue.key().as_ref(), queue.mint.as_ref()],
+        bump = queue.vault_bump,
+        token::mint = queue.mint,
+        token::authority = queue
+    )]
+    pub queue_vault: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_vault", queue.key().as_ref()],
+        bump = queue.sol_vault_bump
+    )]
+    pub queue_sol_vault: SystemAccount<'info>,
+
+    // Game's vault (for SPL tokens)
+    #[account(
+        init,
+        payer = cranker,
+        token::mint = token_mint,
+        token::authority = game,
+        seeds = [b"vault", game.key().as_ref(), queue.mint.as_ref()],
+        bump
+    )]
+    pub vault: Option<Box<Account<'info, TokenAccount>>>,
+
+    // Game's system-owned vault for native SOL stakes
+    #[account(
+        mut,
+        seeds = [b"sol_vault", game.key().as_ref()],
+        bump
+    )]
+    pub sol_vault: SystemAccount<'info>,
+
+    // Token mint (ignored if using native SOL)
+    #[account(address = queue.mint @ WagerError::TokenMintMismatch)]
+    pub token_mint: Option<Box<Account<'info, Mint>>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Pairs the two longest-waiting players into a `Ready` game, moving both
+/// stakes from the queue's escrow into the game's.
+pub fn handler(ctx: Context<MatchPlayers>) -> Result<()> {
+    let clock = Clock::get()?;
+    let queue = &ctx.accounts.queue;
+
+    require!(queue.len >= 2, WagerError::NotEnoughQueued);
+    let pot = queue.wager.checked_mul(2).ok_or(WagerError::MathOverflow)?;
+
+    if queue.is_native_sol() {
+        // The cranker funds the game vault's rent reserve
+        let reserve = Rent::get()?.minimum_balance(0);
+        let top_up = reserve.saturating_sub(ctx.accounts.sol_vault.lamports());
+        if top_up > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.cranker.to_account_info(),
+                        to: ctx.accounts.sol_vault.to_account_info(),
+                    }
+                ),
+                top_up
+            )?;
+        }
+
+        let queue_key = queue.key();
+        let bump_bytes = [queue.sol_vault_bump];
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"sol_vault",
+            queue_key.as_ref(),
+            &bump_bytes,
+        ]];
+
+        system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.queue_sol_vault.to_account_info(),
+                    to: ctx.accounts.sol_vault.to_account_info(),
+                },
+                signer_seeds
+            ),
+            pot
+        )?;
+    } else {
+        let queue_vault = ctx.accounts.queue_vault.as_ref().ok_or(WagerError::InvalidTokenAccount)?;
+        let vault = ctx.accounts.vault.as_ref().ok_or(WagerError::InvalidTokenAccount)?;
+
+        // Create signer seeds for the queue PDA
+        let wager_bytes = queue.wager.to_le_bytes();
+        let game_type_bytes = queue.game_type.to_le_bytes();
+        let bump_bytes = [queue.bump];
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"queue",
+            queue.resolver.as_ref(),
+            queue.mint.as_ref(),
+            wager_bytes.as_ref(),
+            game_type_bytes.as_ref(),
+            &bump_bytes,
+        ]];
+
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: queue_vault.to_account_info(),
+                to: vault.to_account_info(),
+                authority: queue.to_account_info(),
+            },
+            signer_seeds
+        );
+        token::transfer(transfer_ctx, pot)?;
+    }
+
+    let queue = &mut ctx.accounts.queue;
+    let (player1, player2) = queue.pop_pair()?;
+    let nonce = queue.games_matched;
+    queue.games_matched = nonce.checked_add(1).ok_or(WagerError::MathOverflow)?;
+
+    let game_key = ctx.accounts.game.key();
+    let mut game = ctx.accounts.game.load_init()?;
+    game.creator = queue.key();
+    game.player1 = player1;
+    game.player2 = player2;
+    game.resolver = queue.resolver;
+    game.dev_wallet = queue.dev_wallet;
+    game.mint = queue.mint;
+    game.wager = queue.wager;
+    game.payout_bps = queue.payout_bps;
+    game.set_state(GameState::Ready);
+    game.expiry_ts = clock.unix_timestamp.checked_add(queue.game_duration).ok_or(WagerError::MathOverflow)?;
+    game.nonce = nonce;
+    game.bump = ctx.bumps.game;
+    game.vault_bump = ctx.bumps.vault.unwrap_or(0);
+    game.best_of = 1;
+    game.player1_wins = 0;
+    game.player2_wins = 0;
+    game.side_pool = Pubkey::default();
+    game.winner = Pubkey::default();
+    game.player1_claimable = 0;
+    game.player2_claimable = 0;
+    game.fee_claimable = 0;
+    game.sol_vault_bump = ctx.bumps.sol_vault;
+    game.player1_asset = StakeAsset::default();
+    game.player2_asset = StakeAsset::default();
+    game.asset_fee = 0;
+    game.nfts_claimed = 0;
+    game.player1_basket = Basket::default();
+    game.player2_basket = Basket::default();
+    game.basket_paid = 0;
+    game.basket_fee_paid = 0;
+    game.game_type = queue.game_type;
+    game.min_rating = 0;
+    game.max_rating = u32::MAX;
+    game.rent_payer = ctx.accounts.cranker.key();
+    game.crank_bounty_bps = Game::DEFAULT_CRANK_BOUNTY_BPS;
+    game.version = Game::VERSION;
+
+    // Emit events
+    emit_cpi!(GameCreated {
+        game: game_key,
+        creator: game.creator,
+        mint: game.mint,
+        wager: game.wager,
+        amount: game.deposit_for(Side::Player1)?,
+        payout_bps: game.payout_bps,
+        expiry_ts: game.expiry_ts,
+        timestamp: clock.unix_timestamp,
+    });
+
+    emit_cpi!(GameJoined {
+        game: game_key,
+        player1,
+        player2,
+        mint: game.mint,
+        amount: game.deposit_for(Side::Player2)?,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,247 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::TokenAccount;
+use crate::{state::*, error::WagerError};
+
+#[derive(Accounts)]
+pub struct MigrateGame<'info> {
+    // Funds the rent of the added space; anyone may migrate a game
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: May still be in a legacy layout Anchor can't deserialize; the
+    /// discriminator is checked in the handler
+    #[account(mut, owner = crate::ID @ WagerError::InvalidGameAccount)]
+    pub game: UncheckedAccount<'info>,
+
+    // Receives native SOL stakes that early releases kept on the game account
+    #[account(
+        mut,
+        seeds = [b"sol_vault", game.key().as_ref()],
+        bump
+    )]
+    pub sol_vault: SystemAccount<'info>,
+
+    // Token vault of SPL games, checked against the game's mint in the handler
+    pub vault: Option<Box<Account<'info, TokenAccount>>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Last Borsh layout of `Game` (version 1). Version 0 accounts are a prefix of
+/// it, missing the rating band, version and reserved bytes; the first release
+/// stopped at `vault_bump`.
+#[derive(AnchorDeserialize)]
+struct LegacyGame {
+    creator: Pubkey,
+    player1: Pubkey,
+    player2: Pubkey,
+    resolver: Pubkey,
+    dev_wallet: Pubkey,
+    mint: Pubkey,
+    wager: u64,
+    payout_bps: u16,
+    state: u8,
+    expiry_ts: i64,
+    nonce: u64,
+    bump: u8,
+    vault_bump: u8,
+    best_of: u8,
+    player1_wins: u8,
+    player2_wins: u8,
+    side_pool: Pubkey,
+    winner: Pubkey,
+    player1_claimable: u64,
+    player2_claimable: u64,
+    fee_claimable: u64,
+    _sol_vault_bump: u8, // rederived: the first release had no SOL vault
+    player1_asset: StakeAsset,
+    player2_asset: StakeAsset,
+    asset_fee: u64,
+    nfts_claimed: u8,
+    player1_basket: Basket,
+    player2_basket: Basket,
+    basket_paid: u8,
+    basket_fee_paid: u8,
+    game_type: u32,
+    min_rating: u32,
+    max_rating: u32,
+    version: u8,
+    _reserved: [u8; 64],
+}
+
+impl LegacyGame {
+    const LEN: usize = 859;
+}
+
+pub fn handler(ctx: Context<MigrateGame>) -> Result<()> {
+    let info = ctx.accounts.game.to_account_info();
+    require!(
+        info.try_borrow_data()?.starts_with(Game::DISCRIMINATOR),
+        WagerError::InvalidGameAccount
+    );
+
+    // Zero-copy accounts already have the current size; later versions only
+    // claim reserved bytes, so bumping the version is all that's left
+    if info.data_len() == Game::LEN {
+        let mut data = info.try_borrow_mut_data()?;
+        let game: &mut Game = bytemuck::from_bytes_mut(&mut data[8..]);
+        let from_version = game.version;
+        require!(from_version < Game::VERSION, WagerError::GameAlreadyMigrated);
+        // Version 2 games didn't record who paid their rent, which then goes
+        // back to the creator, and were all cranked for the default bounty
+        if from_version < 3 {
+            game.crank_bounty_bps = Game::DEFAULT_CRANK_BOUNTY_BPS;
+        }
+        game.version = Game::VERSION;
+
+        emit!(GameMigrated {
+            game: info.key(),
+            from_version,
+            to_version: Game::VERSION,
+            mint: game.mint,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        return Ok(());
+    }
+
+    // Borsh accounts: zero-extend to the last Borsh layout and decode it
+    require!(info.data_len() <= LegacyGame::LEN, WagerError::InvalidGameAccount);
+    let mut legacy_data = info.try_borrow_data()?[8..].to_vec();
+    legacy_data.resize(LegacyGame::LEN - 8, 0);
+    let legacy = LegacyGame::deserialize(&mut &legacy_data[..])?;
+    let is_native_sol = legacy.mint == Pubkey::default();
+
+    // The first release kept native SOL stakes on the game account itself;
+    // anything above its rent moves into the SOL vault
+    let rent = Rent::get()?;
+    let stranded = if is_native_sol {
+        info.lamports().saturating_sub(rent.minimum_balance(info.data_len()))
+    } else {
+        0
+    };
+    if is_native_sol {
+        let reserve = rent.minimum_balance(0);
+        let top_up = reserve.saturating_sub(ctx.accounts.sol_vault.lamports());
+        if top_up > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.payer.to_account_info(),
+                        to: ctx.accounts.sol_vault.to_account_info(),
+                    }
+                ),
+                top_up
+            )?;
+        }
+    }
+
+    let escrowed = if is_native_sol {
+        ctx.accounts.sol_vault.lamports().saturating_sub(rent.minimum_balance(0)) + stranded
+    } else {
+        let vault = ctx.accounts.vault.as_ref().ok_or(WagerError::InvalidTokenAccount)?;
+        let expected = Pubkey::create_program_address(
+            &[b"vault", info.key.as_ref(), legacy.mint.as_ref(), &[legacy.vault_bump]],
+            &crate::ID,
+        ).map_err(|_| WagerError::InvalidTokenAccount)?;
+        require_keys_eq!(vault.key(), expected, WagerError::InvalidTokenAccount);
+        vault.amount
+    };
+
+    let required = rent.minimum_balance(Game::LEN);
+    let top_up = required.saturating_sub(info.lamports() - stranded);
+    if top_up > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: info.clone(),
+                }
+            ),
+            top_up
+        )?;
+    }
+    info.resize(Game::LEN)?;
+
+    // Moved after the CPIs above, which only see the accounts they are passed
+    **info.try_borrow_mut_lamports()? -= stranded;
+    **ctx.accounts.sol_vault.try_borrow_mut_lamports()? += stranded;
+
+    let mut data = info.try_borrow_mut_data()?;
+    let game: &mut Game = bytemuck::from_bytes_mut(&mut data[8..]);
+    *game = Game {
+        wager: legacy.wager,
+        expiry_ts: legacy.expiry_ts,
+        nonce: legacy.nonce,
+        player1_claimable: legacy.player1_claimable,
+        player2_claimable: legacy.player2_claimable,
+        fee_claimable: legacy.fee_claimable,
+        asset_fee: legacy.asset_fee,
+        player1_basket: legacy.player1_basket,
+        player2_basket: legacy.player2_basket,
+        game_type: legacy.game_type,
+        min_rating: legacy.min_rating,
+        max_rating: legacy.max_rating,
+        payout_bps: legacy.payout_bps,
+        creator: legacy.creator,
+        player1: legacy.player1,
+        player2: legacy.player2,
+        resolver: legacy.resolver,
+        dev_wallet: legacy.dev_wallet,
+        mint: legacy.mint,
+        side_pool: legacy.side_pool,
+        winner: legacy.winner,
+        player1_asset: legacy.player1_asset,
+        player2_asset: legacy.player2_asset,
+        state: legacy.state,
+        bump: legacy.bump,
+        vault_bump: legacy.vault_bump,
+        sol_vault_bump: ctx.bumps.sol_vault,
+        // Games from before series were single games
+        best_of: legacy.best_of.max(1),
+        player1_wins: legacy.player1_wins,
+        player2_wins: legacy.player2_wins,
+        nfts_claimed: legacy.nfts_claimed,
+        basket_paid: legacy.basket_paid,
+        basket_fee_paid: legacy.basket_fee_paid,
+        version: Game::VERSION,
+        padding: [0; 5],
+        rent_payer: legacy.creator,
+        crank_bounty_bps: Game::DEFAULT_CRANK_BOUNTY_BPS,
+        reserved: [0; 30],
+    };
+
+    // The first release didn't escrow the creator's stake, so its open and
+    // ready games hold less than they owe. Cancel those and refund what is
+    // actually escrowed, player2's deposit first.
+    let owed = match game.state()? {
+        GameState::Open => game.deposit_for(Side::Player1)?,
+        GameState::Ready => game
+            .deposit_for(Side::Player1)?
+            .checked_add(game.deposit_for(Side::Player2)?)
+            .ok_or(WagerError::MathOverflow)?,
+        _ => 0,
+    };
+    if escrowed < owed {
+        let player2_refund = if game.state()? == GameState::Ready {
+            escrowed.min(game.deposit_for(Side::Player2)?)
+        } else {
+            0
+        };
+        game.player1_claimable = escrowed - player2_refund;
+        game.player2_claimable = player2_refund;
+        game.set_state(GameState::Canceled);
+    }
+
+    emit!(GameMigrated {
+        game: info.key(),
+        from_version: legacy.version,
+        to_version: Game::VERSION,
+        mint: legacy.mint,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -1,11 +1,115 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::{state::{Game, PlayerBalance}, error::WagerError};
+
 pub mod create_game;
 pub mod join_game;
 pub mod resolve_game;
 pub mod cancel_game;
+pub mod claim;
 pub mod update_resolver;
+pub mod resolve_with_attestation;
+pub mod report_round;
+pub mod create_tournament;
+pub mod register_tournament;
+pub mod start_tournament;
+pub mod report_match;
+pub mod cancel_tournament;
+pub mod claim_tournament_prize;
+pub mod create_side_pool;
+pub mod place_side_bet;
+pub mod claim_side_bet;
+pub mod claim_side_pool_fee;
+pub mod close_side_pool;
+pub mod claim_nft;
+pub mod claim_basket;
+pub mod create_queue;
+pub mod enqueue;
+pub mod dequeue;
+pub mod match_players;
+pub mod deposit_balance;
+pub mod withdraw;
+pub mod approve_session;
+pub mod revoke_session;
+pub mod migrate_game;
+pub mod resolve_many;
+pub mod crank_expired;
 
 pub use create_game::*;
 pub use join_game::*;
 pub use resolve_game::*;
 pub use cancel_game::*;
+pub use claim::*;
 pub use update_resolver::*;
+pub use resolve_with_attestation::*;
+pub use report_round::*;
+pub use create_tournament::*;
+pub use register_tournament::*;
+pub use start_tournament::*;
+pub use report_match::*;
+pub use cancel_tournament::*;
+pub use claim_tournament_prize::*;
+pub use create_side_pool::*;
+pub use place_side_bet::*;
+pub use claim_side_bet::*;
+pub use claim_side_pool_fee::*;
+pub use close_side_pool::*;
+pub use claim_nft::*;
+pub use claim_basket::*;
+pub use create_queue::*;
+pub use enqueue::*;
+pub use dequeue::*;
+pub use match_players::*;
+pub use deposit_balance::*;
+pub use withdraw::*;
+pub use approve_session::*;
+pub use revoke_session::*;
+pub use migrate_game::*;
+pub use resolve_many::*;
+pub use crank_expired::*;
+
+/// Checks `info` is the program address of `seeds` and `bump`, failing with
+/// `error` otherwise. Accounts derived from a zero-copy game's fields are
+/// checked this way once the handler has loaded the game.
+pub(crate) fn check_pda(info: &impl Key, seeds: &[&[u8]], bump: u8, error: WagerError) -> Result<()> {
+    let bump = [bump];
+    let mut seeds = seeds.to_vec();
+    seeds.push(&bump);
+    let expected = Pubkey::create_program_address(&seeds, &crate::ID).map_err(|_| error)?;
+    require_keys_eq!(info.key(), expected, error);
+    Ok(())
+}
+
+/// Checks the escrow accounts passed for a game are its own: its address, its
+/// SOL vault and, if passed, its token vault.
+pub(crate) fn check_escrow(
+    game: &Game,
+    game_key: &Pubkey,
+    vault: Option<&Account<TokenAccount>>,
+    sol_vault: &impl Key,
+) -> Result<()> {
+    game.check_address(game_key)?;
+    check_pda(sol_vault, &[b"sol_vault", game_key.as_ref()], game.sol_vault_bump, WagerError::GameAccountMismatch)?;
+    if let Some(vault) = vault {
+        check_pda(vault, &[b"vault", game_key.as_ref(), game.mint.as_ref()], game.vault_bump, WagerError::GameAccountMismatch)?;
+    }
+    Ok(())
+}
+
+/// Checks a player balance, and its token vault, are `wallet`'s in `mint`.
+pub(crate) fn check_balance(
+    balance: Option<&Account<PlayerBalance>>,
+    balance_vault: Option<&Account<TokenAccount>>,
+    wallet: &Pubkey,
+    mint: &Pubkey,
+) -> Result<()> {
+    let Some(balance) = balance else {
+        require!(balance_vault.is_none(), WagerError::GameAccountMismatch);
+        return Ok(());
+    };
+    check_pda(balance, &[b"balance", wallet.as_ref(), mint.as_ref()], balance.bump, WagerError::GameAccountMismatch)?;
+    if let Some(vault) = balance_vault {
+        check_pda(vault, &[b"balance_vault", wallet.as_ref(), mint.as_ref()], balance.vault_bump, WagerError::GameAccountMismatch)?;
+    }
+    Ok(())
+}
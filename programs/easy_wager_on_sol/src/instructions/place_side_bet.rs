@@ -0,0 +1,129 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::{state::*, error::WagerError};
+
+#[derive(Accounts)]
+pub struct PlaceSideBet<'info> {
+    #[account(mut)]
+    pub bettor: Signer<'info>,
+
+    pub game: AccountLoader<'info, Game>,
+
+    #[account(
+        mut,
+        seeds = [b"side_pool", game.key().as_ref()],
+        bump = side_pool.bump
+    )]
+    pub side_pool: Account<'info, SidePool>,
+
+    #[account(
+        init_if_needed,
+        payer = bettor,
+        space = SideBet::LEN,
+        seeds = [b"side_bet", side_pool.key().as_ref(), bettor.key().as_ref()],
+        bump
+    )]
+    pub side_bet: Account<'info, SideBet>,
+
+    // Bettor's token account (for SPL tokens)
+    #[account(
+        mut,
+        token::mint = side_pool.mint,
+        token::authority = bettor
+    )]
+    pub bettor_token_account: Option<Account<'info, TokenAccount>>,
+
+    // Side pool vault (for SPL tokens)
+    #[account(
+        mut,
+        seeds = [b"vault", side_pool.key().as_ref(), side_pool.mint.as_ref()],
+        bump = side_pool.vault_bump,
+        token::mint = side_pool.mint,
+        token::authority = side_pool
+    )]
+    pub vault: Option<Account<'info, TokenAccount>>,
+
+    // System-owned vault holding native SOL bets
+    #[account(
+        mut,
+        seeds = [b"sol_vault", side_pool.key().as_ref()],
+        bump = side_pool.sol_vault_bump
+    )]
+    pub sol_vault: SystemAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<PlaceSideBet>, side: Side, amount: u64) -> Result<()> {
+    let clock = Clock::get()?;
+
+    // Validation
+    require!(amount > 0, WagerError::InvalidWagerAmount);
+    {
+        let game = ctx.accounts.game.load()?;
+        game.check_address(&ctx.accounts.game.key())?;
+        require!(ctx.accounts.side_pool.is_open(&game, &clock), WagerError::SideBettingClosed);
+    }
+
+    if ctx.accounts.side_pool.is_native_sol() {
+        // Stakes held in the side pool's SOL vault
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.bettor.to_account_info(),
+                    to: ctx.accounts.sol_vault.to_account_info(),
+                }
+            ),
+            amount
+        )?;
+    } else {
+        let bettor_token_account = ctx.accounts.bettor_token_account.as_ref().ok_or(WagerError::InvalidTokenAccount)?;
+        let vault = ctx.accounts.vault.as_ref().ok_or(WagerError::InvalidTokenAccount)?;
+
+        let transfer_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: bettor_token_account.to_account_info(),
+                to: vault.to_account_info(),
+                authority: ctx.accounts.bettor.to_account_info(),
+            }
+        );
+        token::transfer(transfer_ctx, amount)?;
+    }
+
+    let side_pool = &mut ctx.accounts.side_pool;
+    let side_bet = &mut ctx.accounts.side_bet;
+
+    // First bet from this wallet
+    if side_bet.side_pool == Pubkey::default() {
+        side_bet.side_pool = side_pool.key();
+        side_bet.bettor = ctx.accounts.bettor.key();
+        side_bet.bump = ctx.bumps.side_bet;
+        side_pool.open_bets = side_pool.open_bets.checked_add(1).ok_or(WagerError::MathOverflow)?;
+    }
+
+    match side {
+        Side::Player1 => {
+            side_bet.on_player1 = side_bet.on_player1.checked_add(amount).ok_or(WagerError::MathOverflow)?;
+            side_pool.total_player1 = side_pool.total_player1.checked_add(amount).ok_or(WagerError::MathOverflow)?;
+        },
+        Side::Player2 => {
+            side_bet.on_player2 = side_bet.on_player2.checked_add(amount).ok_or(WagerError::MathOverflow)?;
+            side_pool.total_player2 = side_pool.total_player2.checked_add(amount).ok_or(WagerError::MathOverflow)?;
+        },
+    }
+
+    emit!(SideBetPlaced {
+        side_pool: side_pool.key(),
+        bettor: side_bet.bettor,
+        side,
+        amount,
+        mint: side_pool.mint,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
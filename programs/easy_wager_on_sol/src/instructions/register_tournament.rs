@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::{state::*, error::WagerError};
+
+#[derive(Accounts)]
+pub struct RegisterTournament<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"tournament", tournament.organizer.as_ref(), tournament.nonce.to_le_bytes().as_ref()],
+        bump = tournament.bump
+    )]
+    pub tournament: Account<'info, Tournament>,
+
+    // Player's token account (for SPL tokens)
+    #[account(
+        mut,
+        token::mint = tournament.mint,
+        token::authority = player
+    )]
+    pub player_token_account: Option<Account<'info, TokenAccount>>,
+
+    // Tournament's prize vault (for SPL tokens)
+    #[account(
+        mut,
+        seeds = [b"vault", tournament.key().as_ref(), tournament.mint.as_ref()],
+        bump = tournament.vault_bump,
+        token::mint = tournament.mint,
+        token::authority = tournament
+    )]
+    pub vault: Option<Account<'info, TokenAccount>>,
+
+    // System-owned vault holding native SOL entry fees
+    #[account(
+        mut,
+        seeds = [b"sol_vault", tournament.key().as_ref()],
+        bump = tournament.sol_vault_bump
+    )]
+    pub sol_vault: SystemAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<RegisterTournament>) -> Result<()> {
+    let clock = Clock::get()?;
+    let tournament = &ctx.accounts.tournament;
+    let player = ctx.accounts.player.key();
+
+    // Validation
+    require!(
+        matches!(tournament.state, TournamentState::Registration)
+            && clock.unix_timestamp < tournament.registration_end_ts,
+        WagerError::RegistrationClosed
+    );
+    require!(
+        tournament.players.len() < tournament.max_players as usize,
+        WagerError::TournamentFull
+    );
+    require!(tournament.player_index(&player).is_none(), WagerError::AlreadyRegistered);
+
+    if tournament.is_native_sol() {
+        // Entry fee held in the tournament's SOL vault
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.player.to_account_info(),
+                    to: ctx.accounts.sol_vault.to_account_info(),
+                }
+            ),
+            tournament.entry_fee
+        )?;
+    } else {
+        let player_token_account = ctx.accounts.player_token_account.as_ref().ok_or(WagerError::InvalidTokenAccount)?;
+        let vault = ctx.accounts.vault.as_ref().ok_or(WagerError::InvalidTokenAccount)?;
+
+        let transfer_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: player_token_account.to_account_info(),
+                to: vault.to_account_info(),
+                authority: ctx.accounts.player.to_account_info(),
+            }
+        );
+        token::transfer(transfer_ctx, tournament.entry_fee)?;
+    }
+
+    let tournament = &mut ctx.accounts.tournament;
+    tournament.players.push(player);
+
+    emit!(TournamentRegistered {
+        tournament: tournament.key(),
+        player,
+        players: tournament.players.len() as u8,
+        mint: tournament.mint,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use crate::{state::*, error::WagerError};
+use super::start_tournament::advance;
+
+#[derive(Accounts)]
+pub struct ReportMatch<'info> {
+    pub resolver: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"tournament", tournament.organizer.as_ref(), tournament.nonce.to_le_bytes().as_ref()],
+        bump = tournament.bump
+    )]
+    pub tournament: Account<'info, Tournament>,
+}
+
+pub fn handler(ctx: Context<ReportMatch>, match_index: u8, winner: Pubkey) -> Result<()> {
+    let tournament = &mut ctx.accounts.tournament;
+    let index = match_index as usize;
+
+    // Validation
+    require!(ctx.accounts.resolver.key() == tournament.resolver, WagerError::UnauthorizedResolver);
+    require!(
+        matches!(tournament.state, TournamentState::InProgress),
+        WagerError::TournamentNotInProgress
+    );
+    let bracket_match = tournament.matches.get(index).ok_or(WagerError::InvalidMatch)?;
+    require!(bracket_match.is_ready(), WagerError::InvalidMatch);
+
+    let loser = if winner == bracket_match.player_a {
+        bracket_match.player_b
+    } else if winner == bracket_match.player_b {
+        bracket_match.player_a
+    } else {
+        return Err(WagerError::InvalidWinner.into());
+    };
+
+    tournament.matches[index].winner = winner;
+    advance(&mut tournament.matches, index, winner);
+
+    emit!(MatchReported {
+        tournament: tournament.key(),
+        match_index,
+        winner,
+        loser,
+        mint: tournament.mint,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    // The final is always match 0
+    if index == 0 {
+        tournament.state = TournamentState::Completed;
+
+        emit!(TournamentCompleted {
+            tournament: tournament.key(),
+            champion: winner,
+            mint: tournament.mint,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+use crate::{state::*, error::WagerError, events::EventAuthority};
+use super::resolve_game::{check_player_records, settle, PlayerRecords};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ReportRound<'info> {
+    #[account(mut)]
+    pub resolver: Signer<'info>,
+
+    #[account(mut)]
+    pub game: AccountLoader<'info, Game>,
+
+    // Side pool attached to this game, if any
+    #[account(
+        mut,
+        seeds = [b"side_pool", game.key().as_ref()],
+        bump = side_pool.bump
+    )]
+    pub side_pool: Option<Account<'info, SidePool>>,
+
+    #[account(mut)]
+    pub player1_stats: Box<Account<'info, PlayerStats>>,
+
+    #[account(mut)]
+    pub player2_stats: Box<Account<'info, PlayerStats>>,
+
+    // Players' ratings, required for ranked games
+    #[account(mut)]
+    pub player1_rating: Option<Box<Account<'info, Rating>>>,
+
+    #[account(mut)]
+    pub player2_rating: Option<Box<Account<'info, Rating>>>,
+}
+
+pub fn handler(ctx: Context<ReportRound>, winner: Pubkey) -> Result<()> {
+    let accounts = ctx.accounts;
+    let mut game = accounts.game.load_mut()?;
+    game.check_address(&accounts.game.key())?;
+    check_player_records(
+        &game,
+        [&accounts.player1_stats, &accounts.player2_stats],
+        [accounts.player1_rating.as_deref(), accounts.player2_rating.as_deref()],
+        WagerError::GameAccountMismatch,
+    )?;
+
+    // Validation
+    require!(game.is_series(), WagerError::NotASeries);
+    require!(game.can_resolve(), WagerError::GameNotReady);
+    require!(accounts.resolver.key() == game.resolver, WagerError::UnauthorizedResolver);
+
+    if winner == game.player1 {
+        game.player1_wins = game.player1_wins.checked_add(1).ok_or(WagerError::MathOverflow)?;
+    } else if winner == game.player2 {
+        game.player2_wins = game.player2_wins.checked_add(1).ok_or(WagerError::MathOverflow)?;
+    } else {
+        return Err(WagerError::InvalidWinner.into());
+    }
+
+    emit!(RoundReported {
+        game: accounts.game.key(),
+        round: game.rounds_played(),
+        winner,
+        player1_wins: game.player1_wins,
+        player2_wins: game.player2_wins,
+        mint: game.mint,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    // Settle as soon as one player has clinched the series
+    let series_winner = game.series_winner();
+    drop(game);
+    match series_winner {
+        Some(series_winner) => settle(
+            &accounts.game,
+            series_winner,
+            accounts.side_pool.as_mut(),
+            PlayerRecords {
+                player1_stats: &mut accounts.player1_stats,
+                player2_stats: &mut accounts.player2_stats,
+                player1_rating: accounts.player1_rating.as_deref_mut().map(|rating| &mut **rating),
+                player2_rating: accounts.player2_rating.as_deref_mut().map(|rating| &mut **rating),
+            },
+            EventAuthority { info: &accounts.event_authority, bump: ctx.bumps.event_authority },
+        ),
+        None => Ok(()),
+    }
+}
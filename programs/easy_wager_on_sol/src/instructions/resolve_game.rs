@@ -1,156 +1,238 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
-use crate::{state::*, error::WagerError};
+use crate::{state::*, error::WagerError, events::EventAuthority};
+use super::check_pda;
 
+#[event_cpi]
 #[derive(Accounts)]
-#[instruction(winner: Pubkey)]
 pub struct ResolveGame<'info> {
     #[account(mut)]
     pub resolver: Signer<'info>,
 
-    #[account(
-        mut,
-        seeds = [b"game", game.creator.as_ref(), game.nonce.to_le_bytes().as_ref()],
-        bump = game.bump
-    )]
-    pub game: Account<'info, Game>,
-
-    // Winner's token account (for SPL tokens)
-    #[account(
-        mut,
-        token::mint = game.mint,
-        token::authority = winner
-    )]
-    pub winner_token_account: Option<Account<'info, TokenAccount>>,
+    #[account(mut)]
+    pub game: AccountLoader<'info, Game>,
 
-    // Dev wallet's token account (for SPL tokens)
+    // Side pool attached to this game, if any
     #[account(
         mut,
-        token::mint = game.mint,
-        token::authority = game.dev_wallet
+        seeds = [b"side_pool", game.key().as_ref()],
+        bump = side_pool.bump
     )]
-    pub dev_token_account: Option<Account<'info, TokenAccount>>,
+    pub side_pool: Option<Account<'info, SidePool>>,
 
-    // Game's vault (for SPL tokens)
-    #[account(
-        mut,
-        seeds = [b"vault", game.key().as_ref(), game.mint.as_ref()],
-        bump = game.vault_bump,
-        token::mint = game.mint,
-        token::authority = game
-    )]
-    pub vault: Option<Account<'info, TokenAccount>>,
+    #[account(mut)]
+    pub player1_stats: Box<Account<'info, PlayerStats>>,
 
-    /// CHECK: Winner account - validated in instruction
     #[account(mut)]
-    pub winner_account: UncheckedAccount<'info>,
+    pub player2_stats: Box<Account<'info, PlayerStats>>,
 
-    /// CHECK: Dev wallet account - validated against game.dev_wallet
+    // Players' ratings, required for ranked games
     #[account(mut)]
-    pub dev_wallet_account: UncheckedAccount<'info>,
+    pub player1_rating: Option<Box<Account<'info, Rating>>>,
 
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
+    #[account(mut)]
+    pub player2_rating: Option<Box<Account<'info, Rating>>>,
 }
 
 pub fn handler(ctx: Context<ResolveGame>, winner: Pubkey) -> Result<()> {
-    let game = &mut ctx.accounts.game;
+    let accounts = ctx.accounts;
+    {
+        let game = accounts.game.load()?;
+        game.check_address(&accounts.game.key())?;
+        check_player_records(
+            &game,
+            [&accounts.player1_stats, &accounts.player2_stats],
+            [accounts.player1_rating.as_deref(), accounts.player2_rating.as_deref()],
+            WagerError::GameAccountMismatch,
+        )?;
+        require!(accounts.resolver.key() == game.resolver, WagerError::UnauthorizedResolver);
+        require!(!game.is_series(), WagerError::SeriesGame);
+    }
+
+    settle(
+        &accounts.game,
+        winner,
+        accounts.side_pool.as_mut(),
+        PlayerRecords {
+            player1_stats: &mut accounts.player1_stats,
+            player2_stats: &mut accounts.player2_stats,
+            player1_rating: accounts.player1_rating.as_deref_mut().map(|rating| &mut **rating),
+            player2_rating: accounts.player2_rating.as_deref_mut().map(|rating| &mut **rating),
+        },
+        EventAuthority { info: &accounts.event_authority, bump: ctx.bumps.event_authority },
+    )
+}
+
+/// Per-player accounts updated when a game settles.
+pub(crate) struct PlayerRecords<'a> {
+    pub player1_stats: &'a mut PlayerStats,
+    pub player2_stats: &'a mut PlayerStats,
+    // Only needed for ranked games
+    pub player1_rating: Option<&'a mut Rating>,
+    pub player2_rating: Option<&'a mut Rating>,
+}
+
+/// Checks the stats and ratings passed for a game are its players', failing
+/// with `error` otherwise.
+pub(crate) fn check_player_records(
+    game: &Game,
+    stats: [&Account<'_, PlayerStats>; 2],
+    ratings: [Option<&Account<'_, Rating>>; 2],
+    error: WagerError,
+) -> Result<()> {
+    let game_type = game.game_type.to_le_bytes();
+    for (side, (stats, rating)) in [Side::Player1, Side::Player2].into_iter().zip(stats.into_iter().zip(ratings)) {
+        let player = game.player(side);
+        check_pda(stats, &[b"player_stats", player.as_ref()], stats.bump, error)?;
+        if let Some(rating) = rating {
+            check_pda(rating, &[b"rating", player.as_ref(), game_type.as_ref()], rating.bump, error)?;
+        }
+    }
+    Ok(())
+}
+
+/// Records the outcome of a game once the caller has authorized it. Funds stay
+/// in escrow; the winner and dev wallet withdraw their shares with `claim`.
+/// A `Game::DRAW` result refunds both players instead, like a cancellation.
+pub(crate) fn settle(
+    game_account: &AccountLoader<'_, Game>,
+    winner: Pubkey,
+    side_pool: Option<&mut Account<'_, SidePool>>,
+    records: PlayerRecords<'_>,
+    event_authority: EventAuthority<'_, '_>,
+) -> Result<()> {
+    let mut game = game_account.load_mut()?;
+    let clock = Clock::get()?;
 
     // Validation
     require!(game.can_resolve(), WagerError::GameNotReady);
-    require!(ctx.accounts.resolver.key() == game.resolver, WagerError::UnauthorizedResolver);
-    require!(
-        winner == game.player1 || winner == game.player2,
-        WagerError::InvalidWinner
-    );
-    require!(ctx.accounts.winner_account.key() == winner, WagerError::InvalidWinner);
-    require!(ctx.accounts.dev_wallet_account.key() == game.dev_wallet, WagerError::InvalidDevWallet);
-
-    let (winner_amount, fee_amount) = game.calculate_payouts();
-
-    if game.is_native_sol() {
-        // Handle native SOL payouts
-        let game_lamports = game.to_account_info().lamports();
-        
-        // Transfer to winner
-        **game.to_account_info().try_borrow_mut_lamports()? = game_lamports
-            .checked_sub(winner_amount)
-            .ok_or(WagerError::MathOverflow)?;
-        **ctx.accounts.winner_account.try_borrow_mut_lamports()? = ctx.accounts.winner_account
-            .to_account_info()
-            .lamports()
-            .checked_add(winner_amount)
-            .ok_or(WagerError::MathOverflow)?;
-
-        // Transfer to dev wallet
-        let remaining_lamports = game.to_account_info().lamports();
-        **game.to_account_info().try_borrow_mut_lamports()? = remaining_lamports
-            .checked_sub(fee_amount)
-            .ok_or(WagerError::MathOverflow)?;
-        **ctx.accounts.dev_wallet_account.try_borrow_mut_lamports()? = ctx.accounts.dev_wallet_account
-            .to_account_info()
-            .lamports()
-            .checked_add(fee_amount)
-            .ok_or(WagerError::MathOverflow)?;
+    require!(game.is_outcome(&winner), WagerError::InvalidWinner);
+    if winner == Game::DRAW {
+        return settle_draw(game_account, game, side_pool, records, event_authority);
+    }
+
+    let (winner_amount, fee_amount) = game.calculate_payouts()?;
+
+    // Record entitlements
+    if winner == game.player1 {
+        game.player1_claimable = winner_amount;
     } else {
-        // Handle SPL token payouts
-        require!(ctx.accounts.winner_token_account.is_some(), WagerError::InvalidTokenAccount);
-        require!(ctx.accounts.dev_token_account.is_some(), WagerError::InvalidTokenAccount);
-        require!(ctx.accounts.vault.is_some(), WagerError::InvalidTokenAccount);
-
-        let winner_token_account = ctx.accounts.winner_token_account.as_ref().unwrap();
-        let dev_token_account = ctx.accounts.dev_token_account.as_ref().unwrap();
-        let vault = ctx.accounts.vault.as_ref().unwrap();
-
-        // Verify token accounts
-        require!(winner_token_account.mint == game.mint, WagerError::TokenMintMismatch);
-        require!(dev_token_account.mint == game.mint, WagerError::TokenMintMismatch);
-
-        // Create signer seeds for the game PDA
-        let creator_key = game.creator;
-        let nonce_bytes = game.nonce.to_le_bytes();
-        let bump_bytes = [game.bump];
-        let signer_seeds: &[&[&[u8]]] = &[&[
-            b"game",
-            creator_key.as_ref(),
-            nonce_bytes.as_ref(),
-            &bump_bytes,
-        ]];
-
-        // Transfer to winner
-        let winner_transfer_ctx = CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: vault.to_account_info(),
-                to: winner_token_account.to_account_info(),
-                authority: game.to_account_info(),
-            },
-            signer_seeds
-        );
-        token::transfer(winner_transfer_ctx, winner_amount)?;
-
-        // Transfer to dev wallet
-        let dev_transfer_ctx = CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: vault.to_account_info(),
-                to: dev_token_account.to_account_info(),
-                authority: game.to_account_info(),
-            },
-            signer_seeds
-        );
-        token::transfer(dev_transfer_ctx, fee_amount)?;
+        game.player2_claimable = winner_amount;
     }
+    game.fee_claimable = fee_amount;
+    game.winner = winner;
 
     // Update game state
-    game.state = GameState::Paid;
+    game.set_state(GameState::Paid);
+
+    // Update both players' records
+    let player1_won = winner == game.player1;
+    records.player1_stats.record_game(&game, Side::Player1, player1_won)?;
+    records.player2_stats.record_game(&game, Side::Player2, !player1_won)?;
+
+    if game.is_ranked() {
+        let (Some(player1_rating), Some(player2_rating)) = (records.player1_rating, records.player2_rating) else {
+            return Err(WagerError::MissingRating.into());
+        };
+        if player1_won {
+            Rating::record_result(player1_rating, player2_rating)?;
+        } else {
+            Rating::record_result(player2_rating, player1_rating)?;
+        }
+
+        emit!(RatingsUpdated {
+            game: game_account.key(),
+            game_type: game.game_type,
+            player1_rating: player1_rating.rating,
+            player2_rating: player2_rating.rating,
+            mint: game.mint,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    let outcome = if winner == game.player1 { SideOutcome::Player1 } else { SideOutcome::Player2 };
+    settle_side_pool(&game, side_pool, outcome)?;
 
     // Emit event
-    emit!(GameResolved {
-        game: game.key(),
+    event_authority.emit(GameResolved {
+        game: game_account.key(),
         winner,
         winner_amount,
         fee_amount,
+        mint: game.mint,
+        timestamp: clock.unix_timestamp,
+    })
+}
+
+/// Settles a drawn game: each player can claim back what they deposited,
+/// NFTs and basket legs included, and no fee is charged.
+fn settle_draw(
+    game_account: &AccountLoader<'_, Game>,
+    mut game: std::cell::RefMut<'_, Game>,
+    side_pool: Option<&mut Account<'_, SidePool>>,
+    records: PlayerRecords<'_>,
+    event_authority: EventAuthority<'_, '_>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+
+    game.player1_claimable = game.deposit_for(Side::Player1)?;
+    game.player2_claimable = game.deposit_for(Side::Player2)?;
+    game.set_state(GameState::Canceled);
+
+    records.player1_stats.record_draw(&game, Side::Player1)?;
+    records.player2_stats.record_draw(&game, Side::Player2)?;
+
+    if game.is_ranked() {
+        let (Some(player1_rating), Some(player2_rating)) = (records.player1_rating, records.player2_rating) else {
+            return Err(WagerError::MissingRating.into());
+        };
+        Rating::record_draw(player1_rating, player2_rating)?;
+
+        emit!(RatingsUpdated {
+            game: game_account.key(),
+            game_type: game.game_type,
+            player1_rating: player1_rating.rating,
+            player2_rating: player2_rating.rating,
+            mint: game.mint,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    settle_side_pool(&game, side_pool, SideOutcome::Refunded)?;
+
+    event_authority.emit(GameCanceled {
+        game: game_account.key(),
+        canceled_by: game.resolver,
+        reason: CancelReason::Draw,
+        mint: game.mint,
+        player1_refund: game.player1_claimable,
+        player2_refund: game.player2_claimable,
+        timestamp: clock.unix_timestamp,
+    })
+}
+
+/// Locks in the outcome of the game's side pool, if it has one. Games with a
+/// side pool cannot finish without it so spectator stakes are never stranded.
+pub(crate) fn settle_side_pool(
+    game: &Game,
+    side_pool: Option<&mut Account<'_, SidePool>>,
+    outcome: SideOutcome,
+) -> Result<()> {
+    if game.side_pool == Pubkey::default() {
+        return Ok(());
+    }
+
+    let side_pool = side_pool.ok_or(WagerError::SidePoolMismatch)?;
+    require!(side_pool.key() == game.side_pool, WagerError::SidePoolMismatch);
+
+    side_pool.outcome = outcome;
+
+    emit!(SidePoolSettled {
+        side_pool: side_pool.key(),
+        outcome: side_pool.outcome.clone(),
+        total_player1: side_pool.total_player1,
+        total_player2: side_pool.total_player2,
+        mint: game.mint,
+        timestamp: Clock::get()?.unix_timestamp,
     });
 
     Ok(())
@@ -0,0 +1,178 @@
+use anchor_lang::prelude::*;
+use crate::{state::*, error::WagerError, events::EventAuthority};
+use super::check_pda;
+use super::resolve_game::{check_player_records, settle, PlayerRecords};
+
+// Accounts per game in `remaining_accounts`: the game, both players' stats,
+// both players' ratings and the side pool. Pass the program id in place of the
+// ratings of unranked games and the side pool of games without one.
+pub const RESOLVE_GROUP_LEN: usize = 6;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ResolveMany<'info> {
+    // Must be the resolver of every game in the batch
+    pub resolver: Signer<'info>,
+}
+
+/// Settles several single-game wagers in one instruction. Each resolution
+/// points at a group of `RESOLVE_GROUP_LEN` remaining accounts; groups are
+/// settled one after another so players shared between games see every update.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ResolveMany<'info>>,
+    resolutions: Vec<BatchResolution>,
+    policy: BatchPolicy,
+) -> Result<()> {
+    let resolver = ctx.accounts.resolver.key();
+    let event_authority = EventAuthority {
+        info: &ctx.accounts.event_authority,
+        bump: ctx.bumps.event_authority,
+    };
+    let chunks = ctx.remaining_accounts.chunks_exact(RESOLVE_GROUP_LEN);
+    require!(chunks.remainder().is_empty(), WagerError::BatchAccountMismatch);
+    let groups: Vec<&'info [AccountInfo<'info>]> = chunks.collect();
+
+    let mut resolved: u16 = 0;
+    let mut skipped: u16 = 0;
+
+    for resolution in resolutions {
+        let accounts = groups
+            .get(resolution.game_index as usize)
+            .ok_or(WagerError::BatchAccountMismatch)?;
+
+        // Nothing is written until a game passes every check, so a skipped
+        // game is left exactly as it was
+        let group = GameGroup::load(accounts)
+            .and_then(|group| group.check(resolver, resolution.winner).map(|_| group));
+        let mut group = match group {
+            Ok(group) => group,
+            Err(err) if policy == BatchPolicy::SkipInvalid => {
+                msg!("Skipping game {}: {}", accounts[0].key, err);
+                emit!(GameResolutionSkipped {
+                    game: accounts[0].key(),
+                    winner: resolution.winner,
+                    timestamp: Clock::get()?.unix_timestamp,
+                });
+                skipped += 1;
+                continue;
+            }
+            Err(err) => return Err(err),
+        };
+
+        group.settle(resolution.winner, event_authority)?;
+        resolved += 1;
+    }
+
+    emit!(BatchResolved {
+        resolver,
+        resolved,
+        skipped,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// One game's accounts, deserialized and checked against the game's PDAs.
+struct GameGroup<'info> {
+    game: AccountLoader<'info, Game>,
+    player1_stats: Account<'info, PlayerStats>,
+    player2_stats: Account<'info, PlayerStats>,
+    player1_rating: Option<Account<'info, Rating>>,
+    player2_rating: Option<Account<'info, Rating>>,
+    side_pool: Option<Account<'info, SidePool>>,
+}
+
+impl<'info> GameGroup<'info> {
+    fn load(accounts: &'info [AccountInfo<'info>]) -> Result<Self> {
+        let [game_info, player1_stats, player2_stats, player1_rating, player2_rating, side_pool] = accounts else {
+            return Err(WagerError::BatchAccountMismatch.into());
+        };
+        require!(
+            accounts.iter().all(|info| info.key == &crate::ID || info.is_writable),
+            WagerError::BatchAccountMismatch
+        );
+
+        let game = AccountLoader::<Game>::try_from(game_info)?;
+        let player1_stats = Account::<PlayerStats>::try_from(player1_stats)?;
+        let player2_stats = Account::<PlayerStats>::try_from(player2_stats)?;
+        let player1_rating = optional::<Rating>(player1_rating)?;
+        let player2_rating = optional::<Rating>(player2_rating)?;
+        {
+            let game = game.load()?;
+            game.check_address(game_info.key)?;
+            check_player_records(
+                &game,
+                [&player1_stats, &player2_stats],
+                [player1_rating.as_ref(), player2_rating.as_ref()],
+                WagerError::BatchAccountMismatch,
+            )?;
+        }
+
+        let side_pool = optional::<SidePool>(side_pool)?;
+        if let Some(side_pool) = &side_pool {
+            check_pda(side_pool, &[b"side_pool", game_info.key.as_ref()], side_pool.bump, WagerError::BatchAccountMismatch)?;
+        }
+
+        Ok(Self { game, player1_stats, player2_stats, player1_rating, player2_rating, side_pool })
+    }
+
+    /// Everything `settle` would reject, checked up front.
+    fn check(&self, resolver: Pubkey, winner: Pubkey) -> Result<()> {
+        let game = self.game.load()?;
+        require!(resolver == game.resolver, WagerError::UnauthorizedResolver);
+        require!(!game.is_series(), WagerError::SeriesGame);
+        require!(game.can_resolve(), WagerError::GameNotReady);
+        require!(game.is_outcome(&winner), WagerError::InvalidWinner);
+        if game.is_ranked() {
+            require!(
+                self.player1_rating.is_some() && self.player2_rating.is_some(),
+                WagerError::MissingRating
+            );
+        }
+        if game.side_pool != Pubkey::default() {
+            require!(
+                self.side_pool.as_ref().map(|side_pool| side_pool.key()) == Some(game.side_pool),
+                WagerError::SidePoolMismatch
+            );
+        }
+        Ok(())
+    }
+
+    fn settle(&mut self, winner: Pubkey, event_authority: EventAuthority<'_, '_>) -> Result<()> {
+        settle(
+            &self.game,
+            winner,
+            self.side_pool.as_mut(),
+            PlayerRecords {
+                player1_stats: &mut self.player1_stats,
+                player2_stats: &mut self.player2_stats,
+                player1_rating: self.player1_rating.as_deref_mut(),
+                player2_rating: self.player2_rating.as_deref_mut(),
+            },
+            event_authority,
+        )?;
+
+        // Write back now so the next group reads these accounts fresh
+        self.player1_stats.exit(&crate::ID)?;
+        self.player2_stats.exit(&crate::ID)?;
+        for rating in [&self.player1_rating, &self.player2_rating].into_iter().flatten() {
+            rating.exit(&crate::ID)?;
+        }
+        if let Some(side_pool) = &self.side_pool {
+            side_pool.exit(&crate::ID)?;
+        }
+        Ok(())
+    }
+}
+
+/// Treats the program id as "not passed", like Anchor's optional accounts.
+pub(crate) fn optional<'info, T>(info: &'info AccountInfo<'info>) -> Result<Option<Account<'info, T>>>
+where
+    T: AccountSerialize + AccountDeserialize + Owner + Clone,
+{
+    if info.key == &crate::ID {
+        return Ok(None);
+    }
+    Account::try_from(info).map(Some)
+}
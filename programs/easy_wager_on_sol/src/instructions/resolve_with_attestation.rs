@@ -0,0 +1,153 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
+use crate::{state::*, error::WagerError, events::EventAuthority};
+use super::resolve_game::{check_player_records, settle, PlayerRecords};
+
+// Layout of the Ed25519 precompile instruction data
+const ED25519_HEADER_LEN: usize = 2;        // num_signatures (u8) + padding (u8)
+const ED25519_OFFSETS_LEN: usize = 14;      // seven u16 offsets per signature
+const ED25519_PUBKEY_LEN: usize = 32;
+const ED25519_SIGNATURE_LEN: usize = 64;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ResolveWithAttestation<'info> {
+    // Anyone may submit an attestation; the resolver's signature is checked instead
+    #[account(mut)]
+    pub submitter: Signer<'info>,
+
+    #[account(mut)]
+    pub game: AccountLoader<'info, Game>,
+
+    // Side pool attached to this game, if any
+    #[account(
+        mut,
+        seeds = [b"side_pool", game.key().as_ref()],
+        bump = side_pool.bump
+    )]
+    pub side_pool: Option<Account<'info, SidePool>>,
+
+    #[account(mut)]
+    pub player1_stats: Box<Account<'info, PlayerStats>>,
+
+    #[account(mut)]
+    pub player2_stats: Box<Account<'info, PlayerStats>>,
+
+    // Players' ratings, required for ranked games
+    #[account(mut)]
+    pub player1_rating: Option<Box<Account<'info, Rating>>>,
+
+    #[account(mut)]
+    pub player2_rating: Option<Box<Account<'info, Rating>>>,
+
+    /// CHECK: Instructions sysvar - address checked
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<ResolveWithAttestation>, winner: Pubkey) -> Result<()> {
+    let accounts = ctx.accounts;
+    {
+        let game = accounts.game.load()?;
+        game.check_address(&accounts.game.key())?;
+        check_player_records(
+            &game,
+            [&accounts.player1_stats, &accounts.player2_stats],
+            [accounts.player1_rating.as_deref(), accounts.player2_rating.as_deref()],
+            WagerError::GameAccountMismatch,
+        )?;
+        require!(!game.is_series(), WagerError::SeriesGame);
+
+        let message = attestation_message(&accounts.game.key(), &winner, game.nonce);
+        verify_attestation(&accounts.instructions, &game.resolver, &message)?;
+    }
+
+    settle(
+        &accounts.game,
+        winner,
+        accounts.side_pool.as_mut(),
+        PlayerRecords {
+            player1_stats: &mut accounts.player1_stats,
+            player2_stats: &mut accounts.player2_stats,
+            player1_rating: accounts.player1_rating.as_deref_mut().map(|rating| &mut **rating),
+            player2_rating: accounts.player2_rating.as_deref_mut().map(|rating| &mut **rating),
+        },
+        EventAuthority { info: &accounts.event_authority, bump: ctx.bumps.event_authority },
+    )
+}
+
+/// Message the resolver signs off-chain: game pubkey || winner || nonce (LE).
+pub fn attestation_message(game: &Pubkey, winner: &Pubkey, nonce: u64) -> [u8; 72] {
+    let mut message = [0u8; 72];
+    message[..32].copy_from_slice(game.as_ref());
+    message[32..64].copy_from_slice(winner.as_ref());
+    message[64..].copy_from_slice(&nonce.to_le_bytes());
+    message
+}
+
+/// Looks for an Ed25519 precompile instruction earlier in the transaction that
+/// verified `resolver`'s signature over `message`. The precompile has already
+/// checked the signature by the time we run; we only confirm what it checked.
+fn verify_attestation(
+    instructions: &AccountInfo,
+    resolver: &Pubkey,
+    message: &[u8],
+) -> Result<()> {
+    let current_index = load_current_index_checked(instructions)?;
+
+    for index in 0..current_index {
+        let ix = load_instruction_at_checked(index as usize, instructions)?;
+        if ix.program_id != ed25519_program::ID {
+            continue;
+        }
+        if ed25519_instruction_matches(&ix.data, resolver, message) {
+            return Ok(());
+        }
+    }
+
+    Err(WagerError::InvalidAttestation.into())
+}
+
+fn ed25519_instruction_matches(data: &[u8], resolver: &Pubkey, message: &[u8]) -> bool {
+    if data.len() < ED25519_HEADER_LEN {
+        return false;
+    }
+    let num_signatures = data[0] as usize;
+
+    for i in 0..num_signatures {
+        let start = ED25519_HEADER_LEN + i * ED25519_OFFSETS_LEN;
+        let Some(offsets) = data.get(start..start + ED25519_OFFSETS_LEN) else {
+            return false;
+        };
+        let read_u16 = |at: usize| u16::from_le_bytes([offsets[at], offsets[at + 1]]);
+
+        let signature_offset = read_u16(0) as usize;
+        let signature_ix_index = read_u16(2);
+        let pubkey_offset = read_u16(4) as usize;
+        let pubkey_ix_index = read_u16(6);
+        let message_offset = read_u16(8) as usize;
+        let message_size = read_u16(10) as usize;
+        let message_ix_index = read_u16(12);
+
+        // All data must live in the precompile instruction itself, otherwise the
+        // bytes we compare against are not the ones that were verified
+        if signature_ix_index != u16::MAX || pubkey_ix_index != u16::MAX || message_ix_index != u16::MAX {
+            continue;
+        }
+        if data.get(signature_offset..signature_offset + ED25519_SIGNATURE_LEN).is_none() {
+            continue;
+        }
+
+        let pubkey = data.get(pubkey_offset..pubkey_offset + ED25519_PUBKEY_LEN);
+        let signed = data.get(message_offset..message_offset + message_size);
+
+        if pubkey == Some(resolver.as_ref()) && signed == Some(message) {
+            return true;
+        }
+    }
+
+    false
+}
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct RevokeSession<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    // Closing the approval returns its rent to the owner
+    #[account(
+        mut,
+        seeds = [b"session", owner.key().as_ref(), session.session_key.as_ref()],
+        bump = session.bump,
+        has_one = owner,
+        close = owner
+    )]
+    pub session: Box<Account<'info, SessionAuthority>>,
+}
+
+pub fn handler(ctx: Context<RevokeSession>) -> Result<()> {
+    emit!(SessionRevoked {
+        owner: ctx.accounts.owner.key(),
+        session_key: ctx.accounts.session.session_key,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
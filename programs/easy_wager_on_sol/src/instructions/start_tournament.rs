@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+use crate::{state::*, error::WagerError};
+
+#[derive(Accounts)]
+pub struct StartTournament<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"tournament", tournament.organizer.as_ref(), tournament.nonce.to_le_bytes().as_ref()],
+        bump = tournament.bump
+    )]
+    pub tournament: Account<'info, Tournament>,
+}
+
+/// Seeds the bracket. `seeding[i]` is the registration index of seed `i + 1`;
+/// seeds are placed so that the top seeds can only meet in the late rounds.
+pub fn handler(ctx: Context<StartTournament>, seeding: Vec<u8>) -> Result<()> {
+    let clock = Clock::get()?;
+    let tournament = &mut ctx.accounts.tournament;
+    let authority = ctx.accounts.authority.key();
+
+    // Validation - organizer or resolver can seed
+    require!(
+        authority == tournament.organizer || authority == tournament.resolver,
+        WagerError::UnauthorizedOrganizer
+    );
+    require!(
+        matches!(tournament.state, TournamentState::Registration),
+        WagerError::RegistrationClosed
+    );
+
+    let player_count = tournament.players.len();
+    require!(
+        player_count == tournament.max_players as usize
+            || clock.unix_timestamp >= tournament.registration_end_ts,
+        WagerError::RegistrationStillOpen
+    );
+    require!(player_count >= 2, WagerError::NotEnoughPlayers);
+
+    // Seeding must name every registered player exactly once
+    require!(seeding.len() == player_count, WagerError::InvalidSeeding);
+    let mut seen: u32 = 0;
+    for index in seeding.iter() {
+        let bit = 1u32.checked_shl(*index as u32).unwrap_or(0);
+        require!((*index as usize) < player_count && seen & bit == 0, WagerError::InvalidSeeding);
+        seen |= bit;
+    }
+
+    let bracket_size = player_count.next_power_of_two();
+    let first_leaf = bracket_size / 2 - 1;
+    let mut matches = vec![BracketMatch::default(); bracket_size - 1];
+
+    // Slot k of the first round holds seed bracket_order[k]; missing seeds are byes
+    let order = bracket_order(bracket_size);
+    for (leaf, pair) in order.chunks(2).enumerate() {
+        let seed_player = |seed: usize| {
+            seeding
+                .get(seed - 1)
+                .map(|index| tournament.players[*index as usize])
+                .unwrap_or_default()
+        };
+        let m = &mut matches[first_leaf + leaf];
+        m.player_a = seed_player(pair[0]);
+        m.player_b = seed_player(pair[1]);
+    }
+
+    // Players drawn against a bye advance immediately. Since the bracket is the
+    // smallest power of two that fits, a first-round match never has two byes.
+    for index in first_leaf..matches.len() {
+        if matches[index].player_b == Pubkey::default() {
+            let winner = matches[index].player_a;
+            matches[index].winner = winner;
+            advance(&mut matches, index, winner);
+        }
+    }
+
+    tournament.bracket_size = bracket_size as u8;
+    tournament.matches = matches;
+    tournament.fit_payout_table();
+    tournament.state = TournamentState::InProgress;
+
+    emit!(TournamentStarted {
+        tournament: tournament.key(),
+        bracket_size: tournament.bracket_size,
+        players: player_count as u8,
+        mint: tournament.mint,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Moves the winner of match `index` into its slot in the next round.
+pub(crate) fn advance(matches: &mut [BracketMatch], index: usize, winner: Pubkey) {
+    if let Some(parent) = BracketMatch::parent(index) {
+        if index % 2 == 1 {
+            matches[parent].player_a = winner;
+        } else {
+            matches[parent].player_b = winner;
+        }
+    }
+}
+
+/// Standard bracket seed order (1-based), e.g. [1, 8, 4, 5, 2, 7, 3, 6] for 8 slots.
+fn bracket_order(size: usize) -> Vec<usize> {
+    let mut order = vec![1];
+    while order.len() < size {
+        let round_size = order.len() * 2;
+        order = order
+            .iter()
+            .flat_map(|seed| [*seed, round_size + 1 - seed])
+            .collect();
+    }
+    order
+}
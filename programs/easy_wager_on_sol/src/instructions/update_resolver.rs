@@ -1,33 +1,40 @@
 use anchor_lang::prelude::*;
 use crate::{state::*, error::WagerError};
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(new_resolver: Pubkey)]
 pub struct UpdateResolver<'info> {
     #[account(mut)]
     pub creator: Signer<'info>,
 
-    #[account(
-        mut,
-        seeds = [b"game", game.creator.as_ref(), game.nonce.to_le_bytes().as_ref()],
-        bump = game.bump,
-        has_one = creator @ WagerError::UnauthorizedCreator
-    )]
-    pub game: Account<'info, Game>,
+    #[account(mut)]
+    pub game: AccountLoader<'info, Game>,
 }
 
 pub fn handler(ctx: Context<UpdateResolver>, new_resolver: Pubkey) -> Result<()> {
-    let game = &mut ctx.accounts.game;
+    let mut game = ctx.accounts.game.load_mut()?;
+    game.check_address(&ctx.accounts.game.key())?;
+    require_keys_eq!(ctx.accounts.creator.key(), game.creator, WagerError::UnauthorizedCreator);
 
     // Only allow updating resolver while game is still open (no deposits yet)
     require!(
-        matches!(game.state, GameState::Open),
+        matches!(game.state()?, GameState::Open),
         WagerError::CannotUpdateResolverAfterDeposits
     );
 
     require!(new_resolver != Pubkey::default(), WagerError::UnauthorizedResolver);
 
+    let old_resolver = game.resolver;
     game.resolver = new_resolver;
 
+    emit_cpi!(ResolverUpdated {
+        game: ctx.accounts.game.key(),
+        old_resolver,
+        new_resolver,
+        mint: game.mint,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
     Ok(())
 }
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+use crate::{state::*, error::WagerError, escrow};
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"balance", owner.key().as_ref(), balance.mint.as_ref()],
+        bump = balance.bump,
+        has_one = owner
+    )]
+    pub balance: Box<Account<'info, PlayerBalance>>,
+
+    // Tokens held for the balance (for SPL tokens)
+    #[account(
+        mut,
+        seeds = [b"balance_vault", owner.key().as_ref(), balance.mint.as_ref()],
+        bump = balance.vault_bump
+    )]
+    pub balance_vault: Option<Box<Account<'info, TokenAccount>>>,
+
+    // Owner's token account (for SPL tokens)
+    #[account(
+        mut,
+        token::mint = balance.mint
+    )]
+    pub owner_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Moves `amount` out of the owner's balance back to their wallet.
+pub fn handler(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+    require!(amount > 0, WagerError::NothingToClaim);
+
+    let destination = if ctx.accounts.balance.is_native_sol() {
+        ctx.accounts.owner.to_account_info()
+    } else {
+        ctx.accounts
+            .owner_token_account
+            .as_ref()
+            .ok_or(WagerError::InvalidTokenAccount)?
+            .to_account_info()
+    };
+
+    escrow::debit_balance(
+        &mut ctx.accounts.balance,
+        ctx.accounts.balance_vault.as_deref(),
+        &destination,
+        &ctx.accounts.token_program,
+        amount,
+    )?;
+
+    let balance = &ctx.accounts.balance;
+    emit!(BalanceWithdrawn {
+        owner: balance.owner,
+        mint: balance.mint,
+        amount,
+        balance: balance.amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
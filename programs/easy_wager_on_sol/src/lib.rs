@@ -1,45 +1,213 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 
-mod state;
+pub mod state;
 mod instructions;
-mod error;
+pub mod error;
+mod escrow;
+mod events;
+pub mod rating;
 
 use state::*;
 use instructions::*;
-use error::*;
 
 declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
 
-#[program]
-pub mod easy_wager_on_sol {
+pub use anchor_program::*;
+
+// Anchor's generated IDL handlers still call the deprecated
+// `AccountInfo::realloc`, so the program is wrapped to keep the allow off the
+// rest of the crate
+#[allow(deprecated)]
+mod anchor_program {
     use super::*;
 
-    pub fn create_game(
-        ctx: Context<CreateGame>,
-        mint: Pubkey,
-        wager: u64,
-        payout_bps: u16,
-        expiry_ts: i64,
-        resolver_pubkey: Option<Pubkey>,
-        nonce: u64,
-    ) -> Result<()> {
-        instructions::create_game::handler(ctx, mint, wager, payout_bps, expiry_ts, resolver_pubkey, nonce)
-    }
+    #[program]
+    pub mod easy_wager_on_sol {
+        use super::*;
 
-    pub fn join_game(ctx: Context<JoinGame>) -> Result<()> {
-        instructions::join_game::handler(ctx)
-    }
+        pub fn create_game<'info>(
+            ctx: Context<'_, '_, 'info, 'info, CreateGame<'info>>,
+            args: CreateGameArgs,
+        ) -> Result<()> {
+            instructions::create_game::handler(ctx, args)
+        }
 
-    pub fn resolve_game(ctx: Context<ResolveGame>, winner: Pubkey) -> Result<()> {
-        instructions::resolve_game::handler(ctx, winner)
-    }
+        pub fn join_game<'info>(ctx: Context<'_, '_, 'info, 'info, JoinGame<'info>>, relayer_fee: u64) -> Result<()> {
+            instructions::join_game::handler(ctx, relayer_fee)
+        }
 
-    pub fn cancel_if_expired(ctx: Context<CancelGame>) -> Result<()> {
-        instructions::cancel_game::handler(ctx)
-    }
+        pub fn resolve_game(ctx: Context<ResolveGame>, winner: Pubkey) -> Result<()> {
+            instructions::resolve_game::handler(ctx, winner)
+        }
+
+        pub fn cancel_if_expired(ctx: Context<CancelGame>) -> Result<()> {
+            instructions::cancel_game::handler(ctx)
+        }
+
+        pub fn claim(ctx: Context<Claim>, recipient: Pubkey) -> Result<()> {
+            instructions::claim::handler(ctx, recipient)
+        }
+
+        pub fn update_resolver(ctx: Context<UpdateResolver>, new_resolver: Pubkey) -> Result<()> {
+            instructions::update_resolver::handler(ctx, new_resolver)
+        }
+
+        pub fn resolve_with_attestation(ctx: Context<ResolveWithAttestation>, winner: Pubkey) -> Result<()> {
+            instructions::resolve_with_attestation::handler(ctx, winner)
+        }
+
+        pub fn report_round(ctx: Context<ReportRound>, winner: Pubkey) -> Result<()> {
+            instructions::report_round::handler(ctx, winner)
+        }
+
+        #[allow(clippy::too_many_arguments)]
+        pub fn create_tournament(
+            ctx: Context<CreateTournament>,
+            mint: Pubkey,
+            entry_fee: u64,
+            fee_bps: u16,
+            max_players: u8,
+            registration_end_ts: i64,
+            payout_table: Vec<u16>,
+            resolver_pubkey: Option<Pubkey>,
+            nonce: u64,
+        ) -> Result<()> {
+            instructions::create_tournament::handler(
+                ctx,
+                mint,
+                entry_fee,
+                fee_bps,
+                max_players,
+                registration_end_ts,
+                payout_table,
+                resolver_pubkey,
+                nonce,
+            )
+        }
+
+        pub fn register_tournament(ctx: Context<RegisterTournament>) -> Result<()> {
+            instructions::register_tournament::handler(ctx)
+        }
+
+        pub fn start_tournament(ctx: Context<StartTournament>, seeding: Vec<u8>) -> Result<()> {
+            instructions::start_tournament::handler(ctx, seeding)
+        }
+
+        pub fn report_match(ctx: Context<ReportMatch>, match_index: u8, winner: Pubkey) -> Result<()> {
+            instructions::report_match::handler(ctx, match_index, winner)
+        }
+
+        pub fn cancel_tournament(ctx: Context<CancelTournament>) -> Result<()> {
+            instructions::cancel_tournament::handler(ctx)
+        }
+
+        pub fn claim_tournament_prize(ctx: Context<ClaimTournamentPrize>, recipient: Pubkey) -> Result<()> {
+            instructions::claim_tournament_prize::handler(ctx, recipient)
+        }
+
+        pub fn create_side_pool(ctx: Context<CreateSidePool>, fee_bps: u16, cutoff_ts: i64) -> Result<()> {
+            instructions::create_side_pool::handler(ctx, fee_bps, cutoff_ts)
+        }
+
+        pub fn place_side_bet(ctx: Context<PlaceSideBet>, side: Side, amount: u64) -> Result<()> {
+            instructions::place_side_bet::handler(ctx, side, amount)
+        }
+
+        pub fn claim_side_bet(ctx: Context<ClaimSideBet>) -> Result<()> {
+            instructions::claim_side_bet::handler(ctx)
+        }
+
+        pub fn claim_side_pool_fee(ctx: Context<ClaimSidePoolFee>) -> Result<()> {
+            instructions::claim_side_pool_fee::handler(ctx)
+        }
+
+        pub fn close_side_pool(ctx: Context<CloseSidePool>) -> Result<()> {
+            instructions::close_side_pool::handler(ctx)
+        }
+
+        pub fn claim_nft(ctx: Context<ClaimNft>, side: Side) -> Result<()> {
+            instructions::claim_nft::handler(ctx, side)
+        }
+
+        pub fn claim_basket<'info>(
+            ctx: Context<'_, '_, 'info, 'info, ClaimBasket<'info>>,
+            recipient: Pubkey,
+        ) -> Result<()> {
+            instructions::claim_basket::handler(ctx, recipient)
+        }
+
+        pub fn create_queue(
+            ctx: Context<CreateQueue>,
+            mint: Pubkey,
+            wager: u64,
+            game_type: u32,
+            payout_bps: u16,
+            game_duration: i64,
+        ) -> Result<()> {
+            instructions::create_queue::handler(ctx, mint, wager, game_type, payout_bps, game_duration)
+        }
+
+        pub fn enqueue(ctx: Context<Enqueue>) -> Result<()> {
+            instructions::enqueue::handler(ctx)
+        }
+
+        pub fn dequeue(ctx: Context<Dequeue>) -> Result<()> {
+            instructions::dequeue::handler(ctx)
+        }
+
+        pub fn match_players(ctx: Context<MatchPlayers>) -> Result<()> {
+            instructions::match_players::handler(ctx)
+        }
+
+        pub fn deposit_balance(ctx: Context<DepositBalance>, mint: Pubkey, amount: u64) -> Result<()> {
+            instructions::deposit_balance::handler(ctx, mint, amount)
+        }
+
+        pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+            instructions::withdraw::handler(ctx, amount)
+        }
+
+        #[allow(clippy::too_many_arguments)]
+        pub fn approve_session(
+            ctx: Context<ApproveSession>,
+            session_key: Pubkey,
+            spend_cap: u64,
+            allowed_mints: Vec<Pubkey>,
+            expires_at: i64,
+            resolver: Pubkey,
+            dev_wallet: Pubkey,
+            min_payout_bps: u16,
+        ) -> Result<()> {
+            instructions::approve_session::handler(
+                ctx,
+                session_key,
+                spend_cap,
+                allowed_mints,
+                expires_at,
+                resolver,
+                dev_wallet,
+                min_payout_bps,
+            )
+        }
+
+        pub fn revoke_session(ctx: Context<RevokeSession>) -> Result<()> {
+            instructions::revoke_session::handler(ctx)
+        }
+
+        pub fn migrate_game(ctx: Context<MigrateGame>) -> Result<()> {
+            instructions::migrate_game::handler(ctx)
+        }
+
+        pub fn resolve_many<'info>(
+            ctx: Context<'_, '_, 'info, 'info, ResolveMany<'info>>,
+            resolutions: Vec<BatchResolution>,
+            policy: BatchPolicy,
+        ) -> Result<()> {
+            instructions::resolve_many::handler(ctx, resolutions, policy)
+        }
 
-    pub fn update_resolver(ctx: Context<UpdateResolver>, new_resolver: Pubkey) -> Result<()> {
-        instructions::update_resolver::handler(ctx, new_resolver)
+        pub fn crank_expired<'info>(ctx: Context<'_, '_, 'info, 'info, CrankExpired<'info>>) -> Result<()> {
+            instructions::crank_expired::handler(ctx)
+        }
     }
 }
@@ -0,0 +1,54 @@
+// Fixed-point Elo for ranked games. Expected scores come from a lookup table
+// of 1 / (1 + 10^(-d/400)) in basis points, linearly interpolated, so no
+// floating point is needed on-chain.
+
+/// Rating every wallet starts from in a game type.
+pub const INITIAL_RATING: u32 = 1200;
+
+/// Most points a single game can move.
+pub const K_FACTOR: u64 = 32;
+
+// Rating difference between table entries; differences past the last entry
+// are clamped to it
+const STEP: u32 = 25;
+const EXPECTED_BPS: [u16; 33] = [
+    5000, 5359, 5715, 6063, 6401, 6725, 7034, 7325, 7597, 7850, 8083,
+    8296, 8490, 8666, 8823, 8965, 9091, 9203, 9302, 9390, 9468, 9536,
+    9595, 9648, 9693, 9733, 9768, 9799, 9825, 9848, 9868, 9886, 9901,
+];
+
+/// Expected score, in bps, of a player rated `rating` against `opponent`.
+pub fn expected_score_bps(rating: u32, opponent: u32) -> u64 {
+    let max_diff = STEP * (EXPECTED_BPS.len() as u32 - 1);
+    let diff = rating.abs_diff(opponent).min(max_diff);
+
+    let index = (diff / STEP) as usize;
+    let low = EXPECTED_BPS[index] as u32;
+    let high = EXPECTED_BPS[(index + 1).min(EXPECTED_BPS.len() - 1)] as u32;
+    let favorite = low + (high - low) * (diff % STEP) / STEP;
+
+    if rating >= opponent {
+        favorite as u64
+    } else {
+        10_000 - favorite as u64
+    }
+}
+
+/// Whether a player rated `rating` may join a game open to
+/// `min_rating..=max_rating`.
+pub fn in_band(rating: u32, min_rating: u32, max_rating: u32) -> bool {
+    (min_rating..=max_rating).contains(&rating)
+}
+
+/// Points the winner gains and the loser gives up, rounded to the nearest point.
+pub fn rating_delta(winner: u32, loser: u32) -> u32 {
+    let expected = expected_score_bps(winner, loser);
+    ((K_FACTOR * (10_000 - expected) + 5_000) / 10_000) as u32
+}
+
+/// Points the favorite gives up to the underdog in a draw: scoring half when
+/// expected to score more. Rounded to the nearest point.
+pub fn draw_delta(favorite: u32, underdog: u32) -> u32 {
+    let expected = expected_score_bps(favorite, underdog);
+    ((K_FACTOR * (expected - 5_000) + 5_000) / 10_000) as u32
+}
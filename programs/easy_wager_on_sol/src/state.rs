@@ -1,79 +1,1114 @@
 use anchor_lang::prelude::*;
+use crate::error::WagerError;
+use crate::rating;
 
-#[account]
+/// Zero-copy, so instructions read and write fields in place instead of
+/// Borsh-decoding the whole account. Fields are ordered by alignment so the
+/// `repr(C)` layout has no implicit padding.
+#[account(zero_copy)]
+#[repr(C)]
 pub struct Game {
+    pub wager: u64,               // 8 bytes - in smallest units
+    pub expiry_ts: i64,           // 8 bytes
+    pub nonce: u64,               // 8 bytes
+    pub player1_claimable: u64,   // 8 bytes - owed to player1 (winnings or refund)
+    pub player2_claimable: u64,   // 8 bytes - owed to player2 (winnings or refund)
+    pub fee_claimable: u64,       // 8 bytes - owed to dev_wallet
+    pub asset_fee: u64,           // 8 bytes - fungible fee per side when NFTs are staked
+    pub player1_basket: Basket,   // 160 bytes - extra token amounts player1 escrows
+    pub player2_basket: Basket,   // 160 bytes - extra token amounts player2 escrows
+    pub game_type: u32,           // 4 bytes - ranked ladder id, 0 for unranked
+    pub min_rating: u32,          // 4 bytes - lowest rating allowed to join
+    pub max_rating: u32,          // 4 bytes - highest rating allowed to join
+    pub payout_bps: u16,          // 2 bytes - Y * 100 (e.g., 8500 = 85% to winner)
     pub creator: Pubkey,          // 32 bytes
     pub player1: Pubkey,          // 32 bytes
     pub player2: Pubkey,          // 32 bytes - zero until joined
     pub resolver: Pubkey,         // 32 bytes - optional if commit-reveal/VRF
     pub dev_wallet: Pubkey,       // 32 bytes - receives fees
     pub mint: Pubkey,             // 32 bytes - spl mint or native SOL marker
-    pub wager: u64,               // 8 bytes - in smallest units
-    pub payout_bps: u16,          // 2 bytes - Y * 100 (e.g., 8500 = 85% to winner)
-    pub state: GameState,         // 1 byte
-    pub expiry_ts: i64,           // 8 bytes
-    pub nonce: u64,               // 8 bytes
+    pub side_pool: Pubkey,        // 32 bytes - zero unless spectators can bet
+    pub winner: Pubkey,           // 32 bytes - zero until resolved
+    pub player1_asset: StakeAsset, // 65 bytes - what player1 stakes
+    pub player2_asset: StakeAsset, // 65 bytes - what player2 stakes
+    pub state: u8,                // 1 byte - a `GameState`, see `state()`
     pub bump: u8,                 // 1 byte
     pub vault_bump: u8,           // 1 byte
+    pub sol_vault_bump: u8,       // 1 byte - system-owned vault holding native SOL
+    pub best_of: u8,              // 1 byte - rounds in the series (1 = single game)
+    pub player1_wins: u8,         // 1 byte - rounds won by player1
+    pub player2_wins: u8,         // 1 byte - rounds won by player2
+    pub nfts_claimed: u8,         // 1 byte - bit 0: player1's NFT paid out, bit 1: player2's
+    pub basket_paid: u8,          // 1 byte - bit per leg: winnings or refund paid out
+    pub basket_fee_paid: u8,      // 1 byte - bit per leg: fee paid out
+    pub version: u8,              // 1 byte - layout version
+    pub padding: [u8; 5],         // 5 bytes - keeps the size a multiple of 8
+    pub rent_payer: Pubkey,       // 32 bytes - funded the rent of the game and its vaults
+    pub crank_bounty_bps: u16,    // 2 bytes - share of the rent paid to `crank_expired`
+    pub reserved: [u8; 30],       // 30 bytes - zeroed space for future fields
 }
 
 impl Game {
     pub const LEN: usize = 8 + // discriminator
+        8 +  // wager
+        8 +  // expiry_ts
+        8 +  // nonce
+        8 +  // player1_claimable
+        8 +  // player2_claimable
+        8 +  // fee_claimable
+        8 +  // asset_fee
+        Basket::LEN + // player1_basket
+        Basket::LEN + // player2_basket
+        4 +  // game_type
+        4 +  // min_rating
+        4 +  // max_rating
+        2 +  // payout_bps
         32 + // creator
         32 + // player1
         32 + // player2
         32 + // resolver
         32 + // dev_wallet
         32 + // mint
-        8 +  // wager
-        2 +  // payout_bps
+        32 + // side_pool
+        32 + // winner
+        StakeAsset::LEN + // player1_asset
+        StakeAsset::LEN + // player2_asset
         1 +  // state
-        8 +  // expiry_ts
-        8 +  // nonce
         1 +  // bump
-        1;   // vault_bump
+        1 +  // vault_bump
+        1 +  // sol_vault_bump
+        1 +  // best_of
+        1 +  // player1_wins
+        1 +  // player2_wins
+        1 +  // nfts_claimed
+        1 +  // basket_paid
+        1 +  // basket_fee_paid
+        1 +  // version
+        5 +  // padding
+        32 + // rent_payer
+        2 +  // crank_bounty_bps
+        30;  // reserved
+
+    /// Layout version written by this program. New fields are carved out of
+    /// `reserved` and bump it, so the account size stays the same.
+    /// `migrate_game` upgrades older accounts in place: version 2 moved from
+    /// Borsh to this zero-copy layout, version 3 added `rent_payer` and
+    /// `crank_bounty_bps`.
+    pub const VERSION: u8 = 3;
+
+    pub const MAX_BEST_OF: u8 = 9;
+
+    /// Winner a resolver passes to settle the game as a draw: both stakes go
+    /// back to their players and no fee is charged.
+    pub const DRAW: Pubkey = Pubkey::new_from_array([0; 32]);
+
+    /// Largest NFT fee: both sides' fees, and a wager beside either, stay
+    /// well within a u64.
+    pub const MAX_ASSET_FEE: u64 = u64::MAX / 4;
+
+    /// `crank_bounty_bps` of games whose creator doesn't pick one: 10% of the
+    /// game account's rent goes to whoever closes it with `crank_expired`.
+    pub const DEFAULT_CRANK_BOUNTY_BPS: u16 = 1_000;
+
+    pub fn state(&self) -> Result<GameState> {
+        GameState::try_from(self.state)
+    }
+
+    pub fn set_state(&mut self, state: GameState) {
+        self.state = state as u8;
+    }
+
+    /// Checks `key` is the address this game was created at. Zero-copy games
+    /// are loaded once in handlers, which check their addresses there.
+    pub fn check_address(&self, key: &Pubkey) -> Result<()> {
+        let expected = Pubkey::create_program_address(
+            &[b"game", self.creator.as_ref(), self.nonce.to_le_bytes().as_ref(), &[self.bump]],
+            &crate::ID,
+        ).map_err(|_| WagerError::InvalidGameAccount)?;
+        require_keys_eq!(*key, expected, WagerError::InvalidGameAccount);
+        Ok(())
+    }
+
+    /// Who gets the rent of the game and its vaults back when it's closed:
+    /// whoever paid it, or the creator for games from before it was recorded.
+    pub fn rent_receiver(&self) -> Pubkey {
+        if self.rent_payer == Pubkey::default() {
+            self.creator
+        } else {
+            self.rent_payer
+        }
+    }
 
     pub fn is_native_sol(&self) -> bool {
         self.mint == Pubkey::default()
     }
 
-    pub fn calculate_payouts(&self) -> (u64, u64) {
-        let pot = self.wager.checked_mul(2).unwrap();
-        let winner_amount = pot
-            .checked_mul(self.payout_bps as u64)
-            .unwrap()
-            .checked_div(10_000)
-            .unwrap();
-        let fee_amount = pot.checked_sub(winner_amount).unwrap();
-        (winner_amount, fee_amount)
+    /// Native SOL escrowed as wSOL in the vault, like any other SPL game.
+    pub fn is_wrapped_sol(&self) -> bool {
+        self.mint == anchor_spl::token::spl_token::native_mint::ID
+    }
+
+    pub fn calculate_payouts(&self) -> Result<(u64, u64)> {
+        let pot = self
+            .wager_for(Side::Player1)
+            .checked_add(self.wager_for(Side::Player2))
+            .ok_or(WagerError::MathOverflow)?;
+
+        // NFTs can't be split, so their fee is paid separately and the winner
+        // takes the whole fungible pot
+        if self.has_nft_stake() {
+            let fee_amount = self.asset_fee.checked_mul(2).ok_or(WagerError::MathOverflow)?;
+            return Ok((pot, fee_amount));
+        }
+        self.split_basket_leg(pot)
     }
 
     pub fn is_expired(&self, clock: &Clock) -> bool {
         clock.unix_timestamp >= self.expiry_ts
     }
 
+    pub fn asset(&self, side: Side) -> &StakeAsset {
+        match side {
+            Side::Player1 => &self.player1_asset,
+            Side::Player2 => &self.player2_asset,
+        }
+    }
+
+    pub fn player(&self, side: Side) -> Pubkey {
+        match side {
+            Side::Player1 => self.player1,
+            Side::Player2 => self.player2,
+        }
+    }
+
+    pub fn has_nft_stake(&self) -> bool {
+        self.player1_asset.is_nft() || self.player2_asset.is_nft()
+    }
+
+    /// Fungible wager staked by `side`; sides staking an NFT stake none.
+    pub fn wager_for(&self, side: Side) -> u64 {
+        if self.asset(side).is_nft() { 0 } else { self.wager }
+    }
+
+    /// Fungible amount `side` deposits: its wager plus the NFT fee, if any.
+    pub fn deposit_for(&self, side: Side) -> Result<u64> {
+        let fee = if self.has_nft_stake() { self.asset_fee } else { 0 };
+        Ok(self.wager_for(side).checked_add(fee).ok_or(WagerError::MathOverflow)?)
+    }
+
+    pub fn nft_claimed(&self, side: Side) -> bool {
+        self.nfts_claimed & (1 << side as u8) != 0
+    }
+
+    pub fn basket(&self, side: Side) -> &Basket {
+        match side {
+            Side::Player1 => &self.player1_basket,
+            Side::Player2 => &self.player2_basket,
+        }
+    }
+
+    /// Bit tracking leg `index` of `side`'s basket in the paid bitmasks.
+    pub fn basket_leg_bit(side: Side, index: usize) -> u8 {
+        1 << (side as usize * Basket::MAX_LEGS + index)
+    }
+
+    /// Every side must put something at risk: the wager, an NFT or a basket.
+    pub fn stakes_something(&self, side: Side) -> bool {
+        self.wager_for(side) > 0 || self.asset(side).is_nft() || !self.basket(side).is_empty()
+    }
+
+    /// Splits a basket leg, or the pot, by `payout_bps`: the winner's share
+    /// and the fee.
+    pub fn split_basket_leg(&self, amount: u64) -> Result<(u64, u64)> {
+        let winner_amount = (amount as u128)
+            .checked_mul(self.payout_bps as u128)
+            .ok_or(WagerError::MathOverflow)?
+            / 10_000;
+        let winner_amount = winner_amount as u64;
+        Ok((winner_amount, amount - winner_amount))
+    }
+
     pub fn can_cancel(&self, clock: &Clock) -> bool {
-        matches!(self.state, GameState::Open) && self.is_expired(clock)
+        matches!(self.state(), Ok(GameState::Open)) && self.is_expired(clock)
     }
 
     pub fn can_join(&self) -> bool {
-        matches!(self.state, GameState::Open) && self.player2 == Pubkey::default()
+        matches!(self.state(), Ok(GameState::Open)) && self.player2 == Pubkey::default()
+    }
+
+    /// Whether `winner` is an outcome the resolver may record: either player,
+    /// or `DRAW`.
+    pub fn is_outcome(&self, winner: &Pubkey) -> bool {
+        *winner == self.player1 || *winner == self.player2 || *winner == Self::DRAW
     }
 
     pub fn can_resolve(&self) -> bool {
-        matches!(self.state, GameState::Ready)
+        matches!(self.state(), Ok(GameState::Ready))
+    }
+
+    /// Total still owed to `recipient`, who may hold more than one role.
+    pub fn claimable_by(&self, recipient: &Pubkey) -> Result<u64> {
+        let mut amount: u64 = 0;
+        if *recipient == self.player1 {
+            amount = amount.checked_add(self.player1_claimable).ok_or(WagerError::MathOverflow)?;
+        }
+        if *recipient == self.player2 {
+            amount = amount.checked_add(self.player2_claimable).ok_or(WagerError::MathOverflow)?;
+        }
+        if *recipient == self.dev_wallet {
+            amount = amount.checked_add(self.fee_claimable).ok_or(WagerError::MathOverflow)?;
+        }
+        Ok(amount)
+    }
+
+    pub fn clear_claimable(&mut self, recipient: &Pubkey) {
+        if *recipient == self.player1 {
+            self.player1_claimable = 0;
+        }
+        if *recipient == self.player2 {
+            self.player2_claimable = 0;
+        }
+        if *recipient == self.dev_wallet {
+            self.fee_claimable = 0;
+        }
+    }
+
+    pub fn is_ranked(&self) -> bool {
+        self.game_type != 0
+    }
+
+    /// Expired or canceled games holding nothing but fungible stakes, which
+    /// `crank_expired` can refund and close in one go.
+    pub fn can_crank(&self, clock: &Clock) -> bool {
+        let expired = matches!(self.state(), Ok(GameState::Open | GameState::Ready)) && self.is_expired(clock);
+        (expired || matches!(self.state(), Ok(GameState::Canceled)))
+            && !self.has_nft_stake()
+            && self.player1_basket.is_empty()
+            && self.player2_basket.is_empty()
+            && self.side_pool == Pubkey::default()
+    }
+
+    pub fn is_series(&self) -> bool {
+        self.best_of > 1
+    }
+
+    pub fn wins_required(&self) -> u8 {
+        self.best_of / 2 + 1
+    }
+
+    pub fn rounds_played(&self) -> u8 {
+        self.player1_wins + self.player2_wins
+    }
+
+    pub fn series_winner(&self) -> Option<Pubkey> {
+        if self.player1_wins >= self.wins_required() {
+            Some(self.player1)
+        } else if self.player2_wins >= self.wins_required() {
+            Some(self.player2)
+        } else {
+            None
+        }
     }
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+// The hand-computed size must match the `repr(C)` layout
+const _: () = assert!(Game::LEN == 8 + std::mem::size_of::<Game>());
+
+/// What one side of a game stakes: the game's fungible wager or an NFT.
+#[zero_copy]
+#[derive(AnchorSerialize, AnchorDeserialize, PartialEq, Eq, Default)]
+pub struct StakeAsset {
+    pub kind: u8,                 // 1 byte - an `AssetKind`
+    pub mint: Pubkey,             // 32 bytes - NFT mint; zero accepts any NFT until staked
+    pub collection: Pubkey,       // 32 bytes - required verified collection, zero for none
+}
+
+impl StakeAsset {
+    pub const LEN: usize = 1 + 32 + 32;
+
+    pub fn is_nft(&self) -> bool {
+        self.kind == AssetKind::Nft as u8
+    }
+}
+
+/// Extra token amounts a side escrows on top of its stake, each held in a
+/// per-mint vault derived from the game. Unused legs have a zero mint.
+#[zero_copy]
+#[derive(AnchorSerialize, AnchorDeserialize, PartialEq, Eq, Default)]
+pub struct Basket {
+    pub legs: [BasketLeg; Basket::MAX_LEGS],
+}
+
+impl Basket {
+    pub const MAX_LEGS: usize = 4;
+    pub const LEN: usize = BasketLeg::LEN * Self::MAX_LEGS;
+
+    pub fn is_empty(&self) -> bool {
+        self.legs.iter().all(|leg| !leg.is_used())
+    }
+
+    /// Used legs with their index in `legs`.
+    pub fn used_legs(&self) -> impl Iterator<Item = (usize, &BasketLeg)> {
+        self.legs.iter().enumerate().filter(|(_, leg)| leg.is_used())
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        for leg in &self.legs {
+            require!(leg.is_used() == (leg.amount > 0), WagerError::InvalidBasket);
+        }
+        Ok(())
+    }
+}
+
+#[zero_copy]
+#[derive(AnchorSerialize, AnchorDeserialize, PartialEq, Eq, Default)]
+pub struct BasketLeg {
+    pub mint: Pubkey,             // 32 bytes - zero for an unused leg
+    pub amount: u64,              // 8 bytes - in smallest units
+}
+
+impl BasketLeg {
+    pub const LEN: usize = 32 + 8;
+
+    pub fn is_used(&self) -> bool {
+        self.mint != Pubkey::default()
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(u8)]
+pub enum AssetKind {
+    #[default]
+    Fungible,  // The game's wager in its mint (or SOL)
+    Nft,       // A single SPL token with supply 1
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
 pub enum GameState {
     Open,      // Waiting for second player
     Ready,     // Both players deposited, awaiting resolution
-    Paid,      // Winner paid out
-    Canceled,  // Canceled due to expiry or other reason
+    Paid,      // Resolved, winnings claimable
+    Canceled,  // Canceled due to expiry, or drawn; stakes refunded
     Expired,   // Expired without resolution
 }
 
+impl TryFrom<u8> for GameState {
+    type Error = Error;
+
+    fn try_from(state: u8) -> Result<Self> {
+        match state {
+            0 => Ok(GameState::Open),
+            1 => Ok(GameState::Ready),
+            2 => Ok(GameState::Paid),
+            3 => Ok(GameState::Canceled),
+            4 => Ok(GameState::Expired),
+            _ => Err(WagerError::InvalidGameState.into()),
+        }
+    }
+}
+
+#[account]
+pub struct Tournament {
+    pub organizer: Pubkey,            // 32 bytes
+    pub resolver: Pubkey,             // 32 bytes - reports match outcomes
+    pub dev_wallet: Pubkey,           // 32 bytes - receives fees
+    pub mint: Pubkey,                 // 32 bytes - spl mint or native SOL marker
+    pub entry_fee: u64,               // 8 bytes - in smallest units
+    pub fee_bps: u16,                 // 2 bytes - dev cut of the pot
+    pub max_players: u8,              // 1 byte - power of two
+    pub bracket_size: u8,             // 1 byte - set when seeded
+    pub state: TournamentState,       // 1 byte
+    pub registration_end_ts: i64,     // 8 bytes
+    pub nonce: u64,                   // 8 bytes
+    pub bump: u8,                     // 1 byte
+    pub vault_bump: u8,               // 1 byte
+    pub sol_vault_bump: u8,           // 1 byte - system-owned vault holding native SOL entry fees
+    pub claimed: u32,                 // 4 bytes - bitmask of paid player indexes
+    pub fee_claimed: bool,            // 1 byte
+    pub payout_table: Vec<u16>,       // bps of the prize pool per finishing tier
+    pub players: Vec<Pubkey>,         // registration order
+    pub matches: Vec<BracketMatch>,   // heap layout, matches[0] is the final
+}
+
+impl Tournament {
+    pub const MAX_PLAYERS: usize = 32;
+    pub const MAX_TIERS: usize = 6; // champion, runner-up, semis, quarters, ...
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // organizer
+        32 + // resolver
+        32 + // dev_wallet
+        32 + // mint
+        8 +  // entry_fee
+        2 +  // fee_bps
+        1 +  // max_players
+        1 +  // bracket_size
+        1 +  // state
+        8 +  // registration_end_ts
+        8 +  // nonce
+        1 +  // bump
+        1 +  // vault_bump
+        1 +  // sol_vault_bump
+        4 +  // claimed
+        1 +  // fee_claimed
+        4 + 2 * Self::MAX_TIERS + // payout_table
+        4 + 32 * Self::MAX_PLAYERS + // players
+        4 + BracketMatch::LEN * (Self::MAX_PLAYERS - 1); // matches
+
+    pub fn is_native_sol(&self) -> bool {
+        self.mint == Pubkey::default()
+    }
+
+    pub fn pot(&self) -> Result<u64> {
+        self.entry_fee
+            .checked_mul(self.players.len() as u64)
+            .ok_or(error!(WagerError::MathOverflow))
+    }
+
+    /// Prize paid to each player finishing in `tier` (0 = champion, 1 = runner-up,
+    /// 2 = semifinal losers, ...).
+    pub fn tier_prize(&self, tier: usize) -> Result<u64> {
+        let pot = self.pot()?;
+        let fee = pot
+            .checked_mul(self.fee_bps as u64)
+            .and_then(|v| v.checked_div(10_000))
+            .ok_or(WagerError::MathOverflow)?;
+        let prize_pool = pot.checked_sub(fee).ok_or(WagerError::MathOverflow)?;
+        let bps = self.payout_table.get(tier).copied().unwrap_or(0);
+        prize_pool
+            .checked_mul(bps as u64)
+            .and_then(|v| v.checked_div(10_000))
+            .ok_or(error!(WagerError::MathOverflow))
+    }
+
+    /// Players finishing in `tier` of the seeded bracket. First-round byes
+    /// leave part of the last tier empty.
+    pub fn tier_size(&self, tier: usize) -> u64 {
+        let rounds = self.bracket_size.trailing_zeros() as usize;
+        match tier {
+            0 => 1,
+            t if t < rounds => 1 << (t - 1),
+            t if t == rounds => (self.players.len() as u64).saturating_sub(self.bracket_size as u64 / 2),
+            _ => 0,
+        }
+    }
+
+    /// Rescales the payout table (validated against `max_players`) to the
+    /// seeded bracket, so the share of tiers nobody finishes in goes to the
+    /// players rather than the dev wallet. Rounding dust goes to the champion.
+    pub fn fit_payout_table(&mut self) {
+        let tiers = self.bracket_size.trailing_zeros() as usize + 1;
+        let sizes: Vec<u64> = (0..tiers).map(|tier| self.tier_size(tier)).collect();
+        let weighted = |table: &[u16]| -> u64 {
+            table.iter().zip(&sizes).map(|(bps, size)| *bps as u64 * size).sum()
+        };
+
+        self.payout_table.truncate(tiers);
+        let total = weighted(&self.payout_table);
+        if total == 0 {
+            self.payout_table = vec![10_000];
+            return;
+        }
+        let mut table: Vec<u16> = self
+            .payout_table
+            .iter()
+            .map(|bps| (*bps as u64 * 10_000 / total) as u16)
+            .collect();
+        table[0] += (10_000 - weighted(&table)) as u16;
+        self.payout_table = table;
+    }
+
+    /// Finishing tier of a player, or None if they are still in the bracket.
+    pub fn finishing_tier(&self, player: &Pubkey) -> Option<usize> {
+        let champion = self.matches.first().map(|m| m.winner)?;
+        if champion == *player {
+            return Some(0);
+        }
+        self.matches.iter().enumerate().find_map(|(index, m)| {
+            let lost = m.winner != Pubkey::default()
+                && m.winner != *player
+                && (m.player_a == *player || m.player_b == *player);
+            lost.then(|| BracketMatch::depth(index) + 1)
+        })
+    }
+
+    /// Everything left after player prizes, including rounding dust, goes to the dev wallet.
+    pub fn fee_amount(&self) -> Result<u64> {
+        let mut paid_out: u64 = 0;
+        for player in self.players.iter() {
+            if let Some(tier) = self.finishing_tier(player) {
+                paid_out = paid_out
+                    .checked_add(self.tier_prize(tier)?)
+                    .ok_or(WagerError::MathOverflow)?;
+            }
+        }
+        self.pot()?
+            .checked_sub(paid_out)
+            .ok_or(error!(WagerError::MathOverflow))
+    }
+
+    pub fn player_index(&self, player: &Pubkey) -> Option<usize> {
+        self.players.iter().position(|p| p == player)
+    }
+
+    pub fn is_claimed(&self, index: usize) -> bool {
+        self.claimed & (1 << index) != 0
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default, PartialEq, Eq)]
+pub struct BracketMatch {
+    pub player_a: Pubkey,
+    pub player_b: Pubkey,
+    pub winner: Pubkey, // zero until reported
+}
+
+impl BracketMatch {
+    pub const LEN: usize = 32 + 32 + 32;
+
+    /// Rounds away from the final (0 = final, 1 = semifinals, ...).
+    pub fn depth(index: usize) -> usize {
+        (usize::BITS - (index + 1).leading_zeros() - 1) as usize
+    }
+
+    /// Index of the match the winner of `index` advances to.
+    pub fn parent(index: usize) -> Option<usize> {
+        (index > 0).then(|| (index - 1) / 2)
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.player_a != Pubkey::default()
+            && self.player_b != Pubkey::default()
+            && self.winner == Pubkey::default()
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum TournamentState {
+    Registration, // Accepting entries
+    InProgress,   // Bracket seeded, matches being reported
+    Completed,    // Final reported, prizes claimable
+    Canceled,     // Canceled before start, entry fees refundable
+}
+
+#[account]
+pub struct SidePool {
+    pub game: Pubkey,             // 32 bytes
+    pub mint: Pubkey,             // 32 bytes - always the game's mint
+    pub fee_bps: u16,             // 2 bytes - dev cut of the losing side
+    pub cutoff_ts: i64,           // 8 bytes - zero = open until the game is Ready
+    pub total_player1: u64,       // 8 bytes - staked on player1
+    pub total_player2: u64,       // 8 bytes - staked on player2
+    pub outcome: SideOutcome,     // 1 byte
+    pub fee_claimed: bool,        // 1 byte
+    pub bump: u8,                 // 1 byte
+    pub vault_bump: u8,           // 1 byte
+    pub sol_vault_bump: u8,       // 1 byte - system-owned vault holding native SOL bets
+    pub open_bets: u32,           // 4 bytes - bettors yet to claim
+}
+
+impl SidePool {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // game
+        32 + // mint
+        2 +  // fee_bps
+        8 +  // cutoff_ts
+        8 +  // total_player1
+        8 +  // total_player2
+        1 +  // outcome
+        1 +  // fee_claimed
+        1 +  // bump
+        1 +  // vault_bump
+        1 +  // sol_vault_bump
+        4;   // open_bets
+
+    pub fn is_native_sol(&self) -> bool {
+        self.mint == Pubkey::default()
+    }
+
+    pub fn is_open(&self, game: &Game, clock: &Clock) -> bool {
+        matches!(self.outcome, SideOutcome::Pending)
+            && matches!(game.state(), Ok(GameState::Open))
+            && (self.cutoff_ts == 0 || clock.unix_timestamp < self.cutoff_ts)
+    }
+
+    /// Returns (winning side total, losing side total), or None if bets are refunded.
+    fn totals(&self) -> Option<(u64, u64)> {
+        let totals = match self.outcome {
+            SideOutcome::Player1 => (self.total_player1, self.total_player2),
+            SideOutcome::Player2 => (self.total_player2, self.total_player1),
+            _ => return None,
+        };
+        // Nobody backed the winner - everyone gets their stake back
+        (totals.0 > 0).then_some(totals)
+    }
+
+    pub fn fee_amount(&self) -> Result<u64> {
+        match self.totals() {
+            Some((_, losing)) => (losing as u128)
+                .checked_mul(self.fee_bps as u128)
+                .and_then(|v| v.checked_div(10_000))
+                .map(|fee| fee as u64)
+                .ok_or(error!(WagerError::MathOverflow)),
+            None => Ok(0),
+        }
+    }
+
+    /// Amount owed to a bet once the pool is settled. Winners split the losing
+    /// side (after fee) pro rata to their stake on top of getting it back.
+    pub fn payout_for(&self, bet: &SideBet) -> Result<u64> {
+        let Some((winning, losing)) = self.totals() else {
+            return bet.on_player1
+                .checked_add(bet.on_player2)
+                .ok_or(error!(WagerError::MathOverflow));
+        };
+        let stake = match self.outcome {
+            SideOutcome::Player1 => bet.on_player1,
+            _ => bet.on_player2,
+        };
+        let distributable = losing
+            .checked_sub(self.fee_amount()?)
+            .ok_or(WagerError::MathOverflow)?;
+        let winnings = (stake as u128)
+            .checked_mul(distributable as u128)
+            .and_then(|v| v.checked_div(winning as u128))
+            .ok_or(WagerError::MathOverflow)?;
+        stake
+            .checked_add(winnings as u64)
+            .ok_or(error!(WagerError::MathOverflow))
+    }
+}
+
+#[account]
+pub struct SideBet {
+    pub side_pool: Pubkey,        // 32 bytes
+    pub bettor: Pubkey,           // 32 bytes
+    pub on_player1: u64,          // 8 bytes
+    pub on_player2: u64,          // 8 bytes
+    pub bump: u8,                 // 1 byte
+}
+
+impl SideBet {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // side_pool
+        32 + // bettor
+        8 +  // on_player1
+        8 +  // on_player2
+        1;   // bump
+}
+
+#[account]
+pub struct PlayerStats {
+    pub wallet: Pubkey,           // 32 bytes
+    pub games_played: u32,        // 4 bytes
+    pub wins: u32,                // 4 bytes
+    pub losses: u32,              // 4 bytes
+    pub canceled: u32,            // 4 bytes - refunded after both staked; not in games_played
+    pub draws: u32,               // 4 bytes - resolved as a draw; in games_played
+    pub mint_count: u8,           // 1 byte - used entries of mint_totals
+    // Per-mint volume for the first MAX_MINTS mints the wallet plays with;
+    // later mints count towards the game totals above but get no entry here
+    pub mint_totals: [MintTotals; PlayerStats::MAX_MINTS],
+    pub bump: u8,                 // 1 byte
+}
+
+impl PlayerStats {
+    pub const MAX_MINTS: usize = 8;
+
+    /// Sets up a freshly created stats account; no-op for existing ones.
+    pub fn init_if_new(&mut self, wallet: Pubkey, bump: u8) {
+        if self.wallet == Pubkey::default() {
+            self.wallet = wallet;
+            self.bump = bump;
+        }
+    }
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // wallet
+        4 +  // games_played
+        4 +  // wins
+        4 +  // losses
+        4 +  // canceled
+        4 +  // draws
+        1 +  // mint_count
+        MintTotals::LEN * Self::MAX_MINTS + // mint_totals
+        1;   // bump
+
+    /// Records a resolved game for the player on `side`.
+    pub fn record_game(&mut self, game: &Game, side: Side, won: bool) -> Result<()> {
+        if won {
+            self.wins = self.wins.checked_add(1).ok_or(WagerError::MathOverflow)?;
+        } else {
+            self.losses = self.losses.checked_add(1).ok_or(WagerError::MathOverflow)?;
+        }
+        self.record_played(game, side)?;
+
+        if won {
+            let (winner_amount, _) = game.calculate_payouts()?;
+            self.add_won(game.mint, winner_amount)?;
+            for basket_side in [Side::Player1, Side::Player2] {
+                for (_, leg) in game.basket(basket_side).used_legs() {
+                    let (leg_winnings, _) = game.split_basket_leg(leg.amount)?;
+                    self.add_won(leg.mint, leg_winnings)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Records a game drawn by the player on `side`. It was played out, so it
+    /// counts towards the volume, but nothing was won.
+    pub fn record_draw(&mut self, game: &Game, side: Side) -> Result<()> {
+        self.draws = self.draws.checked_add(1).ok_or(WagerError::MathOverflow)?;
+        self.record_played(game, side)
+    }
+
+    fn record_played(&mut self, game: &Game, side: Side) -> Result<()> {
+        self.games_played = self.games_played.checked_add(1).ok_or(WagerError::MathOverflow)?;
+        self.add_wagered(game.mint, game.wager_for(side))?;
+        for (_, leg) in game.basket(side).used_legs() {
+            self.add_wagered(leg.mint, leg.amount)?;
+        }
+        Ok(())
+    }
+
+    fn add_wagered(&mut self, mint: Pubkey, amount: u64) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+        if let Some(totals) = self.totals_for(mint) {
+            totals.wagered = totals.wagered.checked_add(amount).ok_or(WagerError::MathOverflow)?;
+        }
+        Ok(())
+    }
+
+    fn add_won(&mut self, mint: Pubkey, amount: u64) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+        if let Some(totals) = self.totals_for(mint) {
+            totals.won = totals.won.checked_add(amount).ok_or(WagerError::MathOverflow)?;
+        }
+        Ok(())
+    }
+
+    /// Records a game refunded after both players staked. Nobody played it out,
+    /// so it counts neither as played nor towards the volume.
+    pub fn record_canceled(&mut self) -> Result<()> {
+        self.canceled = self.canceled.checked_add(1).ok_or(WagerError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Totals entry for `mint`, claiming a free one if needed. Mints beyond
+    /// the first `MAX_MINTS` a wallet plays with are not tallied.
+    fn totals_for(&mut self, mint: Pubkey) -> Option<&mut MintTotals> {
+        let used = self.mint_count as usize;
+        match self.mint_totals[..used].iter().position(|totals| totals.mint == mint) {
+            Some(index) => Some(&mut self.mint_totals[index]),
+            None if used < Self::MAX_MINTS => {
+                self.mint_count += 1;
+                self.mint_totals[used].mint = mint;
+                Some(&mut self.mint_totals[used])
+            },
+            None => None,
+        }
+    }
+}
+
+#[account]
+pub struct Rating {
+    pub wallet: Pubkey,           // 32 bytes
+    pub game_type: u32,           // 4 bytes - ladder this rating belongs to
+    pub rating: u32,              // 4 bytes - Elo rating
+    pub games: u32,               // 4 bytes - ranked games played
+    pub bump: u8,                 // 1 byte
+}
+
+impl Rating {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // wallet
+        4 +  // game_type
+        4 +  // rating
+        4 +  // games
+        1;   // bump
+
+    /// Sets up a freshly created rating at the starting value; no-op for
+    /// existing ones.
+    pub fn init_if_new(&mut self, wallet: Pubkey, game_type: u32, bump: u8) {
+        if self.wallet == Pubkey::default() {
+            self.wallet = wallet;
+            self.game_type = game_type;
+            self.rating = rating::INITIAL_RATING;
+            self.bump = bump;
+        }
+    }
+
+    /// Applies the result of a ranked game between `winner` and `loser`.
+    /// Returns the points exchanged.
+    pub fn record_result(winner: &mut Rating, loser: &mut Rating) -> Result<u32> {
+        let delta = rating::rating_delta(winner.rating, loser.rating);
+        winner.rating = winner.rating.checked_add(delta).ok_or(WagerError::MathOverflow)?;
+        loser.rating = loser.rating.saturating_sub(delta);
+        winner.games = winner.games.checked_add(1).ok_or(WagerError::MathOverflow)?;
+        loser.games = loser.games.checked_add(1).ok_or(WagerError::MathOverflow)?;
+        Ok(delta)
+    }
+
+    /// Applies a ranked draw: the higher rated player gives up points to the
+    /// lower rated one. Returns the points exchanged.
+    pub fn record_draw(player1: &mut Rating, player2: &mut Rating) -> Result<u32> {
+        let (favorite, underdog) = if player1.rating >= player2.rating {
+            (player1, player2)
+        } else {
+            (player2, player1)
+        };
+        let delta = rating::draw_delta(favorite.rating, underdog.rating);
+        favorite.rating = favorite.rating.saturating_sub(delta);
+        underdog.rating = underdog.rating.checked_add(delta).ok_or(WagerError::MathOverflow)?;
+        favorite.games = favorite.games.checked_add(1).ok_or(WagerError::MathOverflow)?;
+        underdog.games = underdog.games.checked_add(1).ok_or(WagerError::MathOverflow)?;
+        Ok(delta)
+    }
+}
+
+/// Funds a player keeps on deposit for one mint, so games can be staked from
+/// it and winnings credited to it without a wallet transfer each time. Native
+/// SOL is held on this account; SPL tokens in a vault it owns.
+#[account]
+pub struct PlayerBalance {
+    pub owner: Pubkey,            // 32 bytes
+    pub mint: Pubkey,             // 32 bytes - spl mint or native SOL marker
+    pub amount: u64,              // 8 bytes - available to stake or withdraw
+    pub bump: u8,                 // 1 byte
+    pub vault_bump: u8,           // 1 byte
+}
+
+impl PlayerBalance {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // owner
+        32 + // mint
+        8 +  // amount
+        1 +  // bump
+        1;   // vault_bump
+
+    pub fn is_native_sol(&self) -> bool {
+        self.mint == Pubkey::default()
+    }
+}
+
+/// A wallet's approval for an ephemeral session key to create and join games
+/// on its behalf. Stakes are debited from the owner's player balance and
+/// winnings are owed to the owner; the session key only signs.
+#[account]
+pub struct SessionAuthority {
+    pub owner: Pubkey,            // 32 bytes - wallet the session acts for
+    pub session_key: Pubkey,      // 32 bytes - ephemeral signer
+    pub spend_cap: u64,           // 8 bytes - most the session may stake in total
+    pub spent: u64,               // 8 bytes - staked so far
+    pub mint_count: u8,           // 1 byte - used entries of allowed_mints
+    pub allowed_mints: [Pubkey; 4], // 128 bytes - mints the session may stake
+    pub expires_at: i64,          // 8 bytes
+    pub resolver: Pubkey,         // 32 bytes - must resolve every game the session stakes on
+    pub dev_wallet: Pubkey,       // 32 bytes - must receive the fees of those games
+    pub min_payout_bps: u16,      // 2 bytes - lowest payout those games may have
+    pub bump: u8,                 // 1 byte
+}
+
+impl SessionAuthority {
+    pub const MAX_MINTS: usize = 4;
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // owner
+        32 + // session_key
+        8 +  // spend_cap
+        8 +  // spent
+        1 +  // mint_count
+        32 * Self::MAX_MINTS + // allowed_mints
+        8 +  // expires_at
+        32 + // resolver
+        32 + // dev_wallet
+        2 +  // min_payout_bps
+        1;   // bump
+
+    /// Wallet `signer` acts for: the session's owner when signing with an
+    /// approved session key, otherwise the signer itself.
+    pub fn wallet_for(signer: Pubkey, session: &Option<Box<Account<SessionAuthority>>>) -> Pubkey {
+        session.as_ref().map_or(signer, |session| session.owner)
+    }
+
+    /// Charges a stake of `amount` in `mint` against the session.
+    pub fn authorize_spend(&mut self, mint: Pubkey, amount: u64, clock: &Clock) -> Result<()> {
+        require!(clock.unix_timestamp < self.expires_at, WagerError::SessionExpired);
+        require!(
+            self.allowed_mints[..self.mint_count as usize].contains(&mint),
+            WagerError::SessionMintNotAllowed
+        );
+
+        let spent = self.spent.checked_add(amount).ok_or(WagerError::MathOverflow)?;
+        require!(spent <= self.spend_cap, WagerError::SessionCapExceeded);
+        self.spent = spent;
+        Ok(())
+    }
+
+    /// Checks `game` was set up on the terms the owner approved, so a leaked
+    /// session key can't stake on a game it resolves or collects fees from.
+    pub fn authorize_terms(&self, game: &Game) -> Result<()> {
+        require!(
+            game.resolver == self.resolver
+                && game.dev_wallet == self.dev_wallet
+                && game.payout_bps >= self.min_payout_bps,
+            WagerError::SessionTermsNotAllowed
+        );
+        Ok(())
+    }
+}
+
+/// Open matchmaking queue for one (mint, wager, game type), run by a resolver.
+/// Players escrow their wager when they enqueue and are paired in arrival order.
+#[account]
+pub struct MatchQueue {
+    pub resolver: Pubkey,         // 32 bytes - opened the queue, resolves its games
+    pub dev_wallet: Pubkey,       // 32 bytes - receives fees of matched games
+    pub mint: Pubkey,             // 32 bytes - spl mint or native SOL marker
+    pub wager: u64,               // 8 bytes - stake per player
+    pub game_type: u32,           // 4 bytes - ranked ladder id, 0 for unranked
+    pub payout_bps: u16,          // 2 bytes - payout of matched games
+    pub game_duration: i64,       // 8 bytes - seconds a matched game has before expiring
+    pub games_matched: u64,       // 8 bytes - nonce of the next matched game
+    pub bump: u8,                 // 1 byte
+    pub vault_bump: u8,           // 1 byte
+    pub sol_vault_bump: u8,       // 1 byte
+    pub len: u8,                  // 1 byte - players waiting
+    pub entries: [Pubkey; 16],    // 512 bytes - waiting players, oldest first
+}
+
+impl MatchQueue {
+    pub const MAX_ENTRIES: usize = 16;
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // resolver
+        32 + // dev_wallet
+        32 + // mint
+        8 +  // wager
+        4 +  // game_type
+        2 +  // payout_bps
+        8 +  // game_duration
+        8 +  // games_matched
+        1 +  // bump
+        1 +  // vault_bump
+        1 +  // sol_vault_bump
+        1 +  // len
+        32 * Self::MAX_ENTRIES; // entries
+
+    pub fn is_native_sol(&self) -> bool {
+        self.mint == Pubkey::default()
+    }
+
+    pub fn waiting(&self) -> &[Pubkey] {
+        &self.entries[..self.len as usize]
+    }
+
+    pub fn position(&self, player: &Pubkey) -> Option<usize> {
+        self.waiting().iter().position(|entry| entry == player)
+    }
+
+    pub fn push(&mut self, player: Pubkey) -> Result<()> {
+        require!((self.len as usize) < Self::MAX_ENTRIES, WagerError::QueueFull);
+        self.entries[self.len as usize] = player;
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Removes the entry at `index`, keeping the others in arrival order.
+    pub fn remove(&mut self, index: usize) -> Pubkey {
+        let player = self.entries[index];
+        let len = self.len as usize;
+        self.entries.copy_within(index + 1..len, index);
+        self.entries[len - 1] = Pubkey::default();
+        self.len -= 1;
+        player
+    }
+
+    /// Takes the two players who have waited longest.
+    pub fn pop_pair(&mut self) -> Result<(Pubkey, Pubkey)> {
+        require!(self.len >= 2, WagerError::NotEnoughQueued);
+        Ok((self.remove(0), self.remove(0)))
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MintTotals {
+    pub mint: Pubkey,             // 32 bytes - spl mint or native SOL marker
+    pub wagered: u64,             // 8 bytes - staked across finished games
+    pub won: u64,                 // 8 bytes - winnings received
+}
+
+impl MintTotals {
+    pub const LEN: usize = 32 + 8 + 8;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Player1,
+    Player2,
+}
+
+/// Terms of a new game, passed to `create_game` as one argument so builders
+/// can't mix up the order of its fields.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct CreateGameArgs {
+    pub mint: Pubkey,              // Pubkey::default() for native SOL
+    pub wager: u64,
+    pub payout_bps: u16,
+    pub expiry_ts: i64,
+    pub resolver: Option<Pubkey>,  // The creator when None
+    pub nonce: u64,
+    pub best_of: u8,
+    pub player1_asset: StakeAsset,
+    pub player2_asset: StakeAsset,
+    pub asset_fee: u64,
+    pub player1_basket: Basket,
+    pub player2_basket: Basket,
+    pub game_type: u32,
+    pub min_rating: u32,
+    pub max_rating: u32,
+    pub relayer_fee: u64,
+    pub crank_bounty_bps: u16,
+}
+
+/// One entry of a `resolve_many` batch: the index of the game's account group
+/// in `remaining_accounts` and the winner to record.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct BatchResolution {
+    pub game_index: u16,
+    pub winner: Pubkey,
+}
+
+/// What `resolve_many` does with an entry that fails validation.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum BatchPolicy {
+    Atomic,       // Fail the whole batch
+    SkipInvalid,  // Leave the game untouched and carry on
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum SideOutcome {
+    Pending,   // Betting open or game unresolved
+    Player1,   // Player1 won the game
+    Player2,   // Player2 won the game
+    Refunded,  // Game canceled, stakes returned
+}
+
+/// Why a game was canceled, carried by `GameCanceled`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum CancelReason {
+    ExpiredOpen,        // Expired before a second player joined
+    ExpiredUnresolved,  // Expired with both players in but no result
+    Draw,               // Resolved as a draw
+}
+
 // Events for indexing
 #[event]
 pub struct GameCreated {
@@ -81,8 +1116,10 @@ pub struct GameCreated {
     pub creator: Pubkey,
     pub mint: Pubkey,
     pub wager: u64,
+    pub amount: u64, // Deposited by the creator
     pub payout_bps: u16,
     pub expiry_ts: i64,
+    pub timestamp: i64,
 }
 
 #[event]
@@ -90,6 +1127,20 @@ pub struct GameJoined {
     pub game: Pubkey,
     pub player1: Pubkey,
     pub player2: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RoundReported {
+    pub game: Pubkey,
+    pub round: u8,
+    pub winner: Pubkey,
+    pub player1_wins: u8,
+    pub player2_wins: u8,
+    pub mint: Pubkey,
+    pub timestamp: i64,
 }
 
 #[event]
@@ -98,10 +1149,296 @@ pub struct GameResolved {
     pub winner: Pubkey,
     pub winner_amount: u64,
     pub fee_amount: u64,
+    pub mint: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GameResolutionSkipped {
+    pub game: Pubkey,
+    pub winner: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BatchResolved {
+    pub resolver: Pubkey,
+    pub resolved: u16,
+    pub skipped: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ExpiredGamesCranked {
+    pub cranker: Pubkey,
+    pub closed: u16,
+    pub skipped: u16,
+    pub bounty: u64,
+    pub timestamp: i64,
+}
+
+/// What `crank_expired` found left in a game's escrow once everyone owed had
+/// been paid, and sent to the creator.
+#[event]
+pub struct EscrowSwept {
+    pub game: Pubkey,
+    pub creator: Pubkey,
+    pub mint: Pubkey,
+    pub lamports: u64, // Above the SOL vault's reserve
+    pub tokens: u64,   // Left in the token vault, in `mint`
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GameClaimed {
+    pub game: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub mint: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RatingsUpdated {
+    pub game: Pubkey,
+    pub game_type: u32,
+    pub player1_rating: u32,
+    pub player2_rating: u32,
+    pub mint: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BasketLegClaimed {
+    pub game: Pubkey,
+    pub recipient: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct NftClaimed {
+    pub game: Pubkey,
+    pub nft_mint: Pubkey,
+    pub recipient: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BalanceDeposited {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub balance: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BalanceWithdrawn {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub balance: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SessionApproved {
+    pub owner: Pubkey,
+    pub session_key: Pubkey,
+    pub spend_cap: u64,
+    pub expires_at: i64,
+    pub resolver: Pubkey,
+    pub dev_wallet: Pubkey,
+    pub min_payout_bps: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SessionRevoked {
+    pub owner: Pubkey,
+    pub session_key: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct QueueCreated {
+    pub queue: Pubkey,
+    pub resolver: Pubkey,
+    pub mint: Pubkey,
+    pub wager: u64,
+    pub game_type: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PlayerQueued {
+    pub queue: Pubkey,
+    pub player: Pubkey,
+    pub waiting: u8,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PlayerDequeued {
+    pub queue: Pubkey,
+    pub player: Pubkey,
+    pub refund: u64,
+    pub mint: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RelayerReimbursed {
+    pub game: Pubkey,
+    pub relayer: Pubkey,
+    pub fee: u64,
+    pub mint: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GameMigrated {
+    pub game: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
+    pub mint: Pubkey,
+    pub timestamp: i64,
 }
 
 #[event]
 pub struct GameCanceled {
     pub game: Pubkey,
-    pub reason: String,
+    pub canceled_by: Pubkey,
+    pub reason: CancelReason,
+    pub mint: Pubkey,
+    pub player1_refund: u64,
+    pub player2_refund: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ResolverUpdated {
+    pub game: Pubkey,
+    pub old_resolver: Pubkey,
+    pub new_resolver: Pubkey,
+    pub mint: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TournamentCreated {
+    pub tournament: Pubkey,
+    pub organizer: Pubkey,
+    pub mint: Pubkey,
+    pub entry_fee: u64,
+    pub max_players: u8,
+    pub registration_end_ts: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TournamentRegistered {
+    pub tournament: Pubkey,
+    pub player: Pubkey,
+    pub players: u8,
+    pub mint: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TournamentStarted {
+    pub tournament: Pubkey,
+    pub bracket_size: u8,
+    pub players: u8,
+    pub mint: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MatchReported {
+    pub tournament: Pubkey,
+    pub match_index: u8,
+    pub winner: Pubkey,
+    pub loser: Pubkey,
+    pub mint: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TournamentCompleted {
+    pub tournament: Pubkey,
+    pub champion: Pubkey,
+    pub mint: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TournamentCanceled {
+    pub tournament: Pubkey,
+    pub mint: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TournamentPrizeClaimed {
+    pub tournament: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub mint: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SidePoolCreated {
+    pub game: Pubkey,
+    pub side_pool: Pubkey,
+    pub fee_bps: u16,
+    pub cutoff_ts: i64,
+    pub mint: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SideBetPlaced {
+    pub side_pool: Pubkey,
+    pub bettor: Pubkey,
+    pub side: Side,
+    pub amount: u64,
+    pub mint: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SidePoolSettled {
+    pub side_pool: Pubkey,
+    pub outcome: SideOutcome,
+    pub total_player1: u64,
+    pub total_player2: u64,
+    pub mint: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SideBetClaimed {
+    pub side_pool: Pubkey,
+    pub bettor: Pubkey,
+    pub amount: u64,
+    pub mint: Pubkey,
+    pub timestamp: i64,
+}
+
+/// A fully claimed side pool closed, with the rounding remainder of the
+/// winners' split sent to the game's creator along with the rent.
+#[event]
+pub struct SidePoolClosed {
+    pub game: Pubkey,
+    pub side_pool: Pubkey,
+    pub creator: Pubkey,
+    pub dust: u64, // Left in escrow after every payout, in `mint`
+    pub mint: Pubkey,
+    pub timestamp: i64,
 }
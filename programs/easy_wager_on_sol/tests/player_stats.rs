@@ -0,0 +1,79 @@
+//! Lifetime records kept in `PlayerStats`.
+
+use anchor_lang::prelude::Pubkey;
+use bytemuck::Zeroable;
+use easy_wager_on_sol::state::{Game, GameState, MintTotals, PlayerStats, Side};
+
+fn stats() -> PlayerStats {
+    PlayerStats {
+        wallet: Pubkey::new_unique(),
+        games_played: 0,
+        wins: 0,
+        losses: 0,
+        canceled: 0,
+        draws: 0,
+        mint_count: 0,
+        mint_totals: [MintTotals::default(); PlayerStats::MAX_MINTS],
+        bump: 0,
+    }
+}
+
+fn game(mint: Pubkey, wager: u64) -> Game {
+    let mut game = Game::zeroed();
+    game.mint = mint;
+    game.wager = wager;
+    game.payout_bps = 9_000;
+    game.best_of = 1;
+    game.set_state(GameState::Paid);
+    game
+}
+
+#[test]
+fn resolved_games_tally_results_and_volume() {
+    let mut stats = stats();
+    let game = game(Pubkey::default(), 1_000);
+    stats.record_game(&game, Side::Player1, true).unwrap();
+    stats.record_game(&game, Side::Player2, false).unwrap();
+
+    assert_eq!((stats.games_played, stats.wins, stats.losses, stats.canceled), (2, 1, 1, 0));
+    assert_eq!(stats.mint_count, 1);
+    assert_eq!(stats.mint_totals[0].mint, Pubkey::default());
+    assert_eq!(stats.mint_totals[0].wagered, 2_000);
+    assert_eq!(stats.mint_totals[0].won, 1_800);
+}
+
+#[test]
+fn canceled_games_are_not_played_games() {
+    let mut stats = stats();
+    stats.record_canceled().unwrap();
+
+    assert_eq!((stats.games_played, stats.wins, stats.losses, stats.canceled), (0, 0, 0, 1));
+    assert_eq!(stats.mint_count, 0);
+}
+
+#[test]
+fn draws_are_played_games_without_winnings() {
+    let mut stats = stats();
+    stats.record_draw(&game(Pubkey::default(), 1_000), Side::Player2).unwrap();
+
+    assert_eq!((stats.games_played, stats.wins, stats.losses, stats.draws), (1, 0, 0, 1));
+    assert_eq!(stats.mint_totals[0].wagered, 1_000);
+    assert_eq!(stats.mint_totals[0].won, 0);
+}
+
+#[test]
+fn mints_beyond_the_cap_count_as_games_without_volume() {
+    let mut stats = stats();
+    let mints: Vec<Pubkey> = (0..=PlayerStats::MAX_MINTS).map(|_| Pubkey::new_unique()).collect();
+    for mint in &mints {
+        stats.record_game(&game(*mint, 100), Side::Player1, true).unwrap();
+    }
+
+    assert_eq!(stats.games_played, PlayerStats::MAX_MINTS as u32 + 1);
+    assert_eq!(stats.mint_count as usize, PlayerStats::MAX_MINTS);
+    assert!(stats.mint_totals.iter().all(|totals| totals.mint != mints[PlayerStats::MAX_MINTS]));
+
+    // Mints already tallied keep accumulating
+    stats.record_game(&game(mints[0], 100), Side::Player1, false).unwrap();
+    assert_eq!(stats.mint_totals[0].wagered, 200);
+}
@@ -0,0 +1,90 @@
+//! Fixed-point Elo used by ranked games.
+
+use anchor_lang::prelude::Pubkey;
+use easy_wager_on_sol::rating::{draw_delta, expected_score_bps, in_band, rating_delta, INITIAL_RATING, K_FACTOR};
+use easy_wager_on_sol::state::Rating;
+
+fn rating(value: u32) -> Rating {
+    Rating { wallet: Pubkey::new_unique(), game_type: 1, rating: value, games: 0, bump: 0 }
+}
+
+#[test]
+fn expected_scores_are_complementary() {
+    assert_eq!(expected_score_bps(1200, 1200), 5_000);
+    for (a, b) in [(1200, 1300), (1500, 1100), (1000, 1999), (0, 5000)] {
+        assert_eq!(expected_score_bps(a, b) + expected_score_bps(b, a), 10_000);
+    }
+}
+
+#[test]
+fn expected_score_follows_the_elo_curve() {
+    // Table points: 100 points ahead is 64%, 400 ahead is 91%
+    assert_eq!(expected_score_bps(1300, 1200), 6_401);
+    assert_eq!(expected_score_bps(1600, 1200), 9_091);
+    // Interpolated between the 0 and 25 point entries
+    assert_eq!(expected_score_bps(1212, 1200), 5_000 + 359 * 12 / 25);
+    // Clamped past the end of the table
+    assert_eq!(expected_score_bps(3000, 1200), 9_901);
+    assert_eq!(expected_score_bps(1200, 3000), 99);
+
+    let mut previous = 0;
+    for diff in 0..1000 {
+        let expected = expected_score_bps(1200 + diff, 1200);
+        assert!(expected >= previous);
+        previous = expected;
+    }
+}
+
+#[test]
+fn upsets_move_more_points() {
+    assert_eq!(rating_delta(1200, 1200), K_FACTOR as u32 / 2);
+    assert!(rating_delta(1100, 1400) > rating_delta(1200, 1200));
+    assert!(rating_delta(1400, 1100) < rating_delta(1200, 1200));
+    assert_eq!(rating_delta(1100, 1400) + rating_delta(1400, 1100), K_FACTOR as u32);
+    assert!(rating_delta(u32::MAX, 0) <= K_FACTOR as u32);
+}
+
+#[test]
+fn result_moves_points_from_loser_to_winner() {
+    let mut winner = rating(INITIAL_RATING);
+    let mut loser = rating(INITIAL_RATING);
+    let delta = Rating::record_result(&mut winner, &mut loser).unwrap();
+
+    assert_eq!(delta, 16);
+    assert_eq!(winner.rating, INITIAL_RATING + 16);
+    assert_eq!(loser.rating, INITIAL_RATING - 16);
+    assert_eq!((winner.games, loser.games), (1, 1));
+}
+
+#[test]
+fn draw_moves_points_from_favorite_to_underdog() {
+    assert_eq!(draw_delta(1200, 1200), 0);
+    assert!(draw_delta(1400, 1100) > draw_delta(1300, 1200));
+
+    let mut player1 = rating(1200);
+    let mut player2 = rating(1400);
+    let delta = Rating::record_draw(&mut player1, &mut player2).unwrap();
+
+    assert_eq!(delta, draw_delta(1400, 1200));
+    assert_eq!(player1.rating, 1200 + delta);
+    assert_eq!(player2.rating, 1400 - delta);
+    assert_eq!((player1.games, player2.games), (1, 1));
+}
+
+#[test]
+fn rating_does_not_go_below_zero() {
+    let mut winner = rating(10);
+    let mut loser = rating(5);
+    Rating::record_result(&mut winner, &mut loser).unwrap();
+    assert_eq!(loser.rating, 0);
+}
+
+#[test]
+fn band_is_inclusive() {
+    assert!(in_band(1200, 1100, 1300));
+    assert!(in_band(1100, 1100, 1300));
+    assert!(in_band(1300, 1100, 1300));
+    assert!(!in_band(1099, 1100, 1300));
+    assert!(!in_band(1301, 1100, 1300));
+    assert!(in_band(INITIAL_RATING, 0, u32::MAX));
+}
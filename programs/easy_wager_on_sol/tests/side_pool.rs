@@ -0,0 +1,78 @@
+//! Settlement maths of spectator side pools.
+
+use anchor_lang::prelude::Pubkey;
+use easy_wager_on_sol::state::{SideBet, SideOutcome, SidePool};
+
+fn pool(outcome: SideOutcome, total_player1: u64, total_player2: u64, fee_bps: u16) -> SidePool {
+    SidePool {
+        game: Pubkey::new_unique(),
+        mint: Pubkey::default(),
+        fee_bps,
+        cutoff_ts: 0,
+        total_player1,
+        total_player2,
+        outcome,
+        fee_claimed: false,
+        bump: 0,
+        vault_bump: 0,
+        sol_vault_bump: 0,
+        open_bets: 0,
+    }
+}
+
+fn bet(on_player1: u64, on_player2: u64) -> SideBet {
+    SideBet {
+        side_pool: Pubkey::new_unique(),
+        bettor: Pubkey::new_unique(),
+        on_player1,
+        on_player2,
+        bump: 0,
+    }
+}
+
+#[test]
+fn winners_split_the_losing_side_after_fee() {
+    // 300 on player1, 1_000 on player2; 10% of the losing side is the fee
+    let pool = pool(SideOutcome::Player1, 300, 1_000, 1_000);
+    assert_eq!(pool.fee_amount().unwrap(), 100);
+
+    // 900 distributable, split 1:2 between the player1 backers
+    assert_eq!(pool.payout_for(&bet(100, 0)).unwrap(), 100 + 300);
+    assert_eq!(pool.payout_for(&bet(200, 0)).unwrap(), 200 + 600);
+    assert_eq!(pool.payout_for(&bet(0, 1_000)).unwrap(), 0);
+}
+
+#[test]
+fn hedged_bet_is_paid_for_the_winning_side_only() {
+    let pool = pool(SideOutcome::Player2, 500, 500, 0);
+    assert_eq!(pool.payout_for(&bet(200, 100)).unwrap(), 100 + 100);
+}
+
+#[test]
+fn rounding_dust_stays_in_the_pool() {
+    let pool = pool(SideOutcome::Player1, 3, 10, 0);
+    let paid: u64 = [1, 1, 1].iter().map(|stake| pool.payout_for(&bet(*stake, 0)).unwrap()).sum();
+    assert_eq!(paid, 3 * (1 + 3));
+    assert!(paid + pool.fee_amount().unwrap() <= 3 + 10);
+}
+
+#[test]
+fn stakes_are_refunded_when_nobody_backed_the_winner() {
+    let pool = pool(SideOutcome::Player1, 0, 1_000, 1_000);
+    assert_eq!(pool.fee_amount().unwrap(), 0);
+    assert_eq!(pool.payout_for(&bet(0, 400)).unwrap(), 400);
+}
+
+#[test]
+fn canceled_game_refunds_both_sides_without_fee() {
+    let pool = pool(SideOutcome::Refunded, 300, 1_000, 1_000);
+    assert_eq!(pool.fee_amount().unwrap(), 0);
+    assert_eq!(pool.payout_for(&bet(100, 50)).unwrap(), 150);
+}
+
+#[test]
+fn large_pools_do_not_overflow() {
+    let pool = pool(SideOutcome::Player2, u64::MAX / 2, u64::MAX / 2, 100);
+    let payout = pool.payout_for(&bet(0, u64::MAX / 2)).unwrap();
+    assert_eq!(payout, u64::MAX / 2 + (u64::MAX / 2 - pool.fee_amount().unwrap()));
+}
@@ -0,0 +1,148 @@
+//! Prize maths of `Tournament`: the payout table fitted to the seeded
+//! bracket, finishing tiers and the prize paid for each.
+
+use anchor_lang::prelude::Pubkey;
+use easy_wager_on_sol::state::{BracketMatch, Tournament, TournamentState};
+
+fn tournament(max_players: u8, registered: usize, payout_table: Vec<u16>) -> Tournament {
+    Tournament {
+        organizer: Pubkey::new_unique(),
+        resolver: Pubkey::new_unique(),
+        dev_wallet: Pubkey::new_unique(),
+        mint: Pubkey::default(),
+        entry_fee: 1_000_000,
+        fee_bps: 500,
+        max_players,
+        bracket_size: registered.next_power_of_two() as u8,
+        state: TournamentState::InProgress,
+        registration_end_ts: 0,
+        nonce: 0,
+        bump: 0,
+        vault_bump: 0,
+        sol_vault_bump: 0,
+        claimed: 0,
+        fee_claimed: false,
+        payout_table,
+        players: (0..registered).map(|_| Pubkey::new_unique()).collect(),
+        matches: Vec::new(),
+    }
+}
+
+/// Payout table bps weighted by the players finishing in each tier.
+fn weighted_total(tournament: &Tournament) -> u64 {
+    (0..tournament.payout_table.len())
+        .map(|tier| tournament.payout_table[tier] as u64 * tournament.tier_size(tier))
+        .sum()
+}
+
+#[test]
+fn full_bracket_keeps_the_payout_table() {
+    let mut tournament = tournament(8, 8, vec![4_000, 2_000, 1_000, 500]);
+    tournament.fit_payout_table();
+    assert_eq!(tournament.payout_table, vec![4_000, 2_000, 1_000, 500]);
+    assert_eq!(weighted_total(&tournament), 10_000);
+}
+
+#[test]
+fn smaller_bracket_drops_unreachable_tiers() {
+    // Four players seed a 4-slot bracket, so there are no quarterfinal losers
+    let mut tournament = tournament(8, 4, vec![4_000, 2_000, 1_000, 500]);
+    tournament.fit_payout_table();
+    assert_eq!(tournament.payout_table, vec![5_000, 2_500, 1_250]);
+    assert_eq!(weighted_total(&tournament), 10_000);
+}
+
+#[test]
+fn byes_shrink_the_last_tier() {
+    // Five players in an 8-slot bracket: three byes, one quarterfinal loser
+    let mut tournament = tournament(8, 5, vec![4_000, 2_000, 1_000, 500]);
+    assert_eq!(tournament.tier_size(3), 1);
+    tournament.fit_payout_table();
+    assert_eq!(tournament.payout_table, vec![4_708, 2_352, 1_176, 588]);
+    assert_eq!(weighted_total(&tournament), 10_000);
+}
+
+#[test]
+fn table_paying_only_unreachable_tiers_goes_to_the_champion() {
+    let mut tournament = tournament(4, 2, vec![0, 0, 5_000]);
+    tournament.fit_payout_table();
+    assert_eq!(tournament.payout_table, vec![10_000]);
+}
+
+/// Seeds a bracket from `pairs` (first-round matches in order, `None` for a
+/// bye) and fits the payout table to it.
+fn seed(tournament: &mut Tournament, pairs: &[(usize, Option<usize>)]) {
+    let size = tournament.bracket_size as usize;
+    let first_leaf = size / 2 - 1;
+    tournament.matches = vec![BracketMatch::default(); size - 1];
+    for (leaf, (a, b)) in pairs.iter().enumerate() {
+        tournament.matches[first_leaf + leaf].player_a = tournament.players[*a];
+        match b {
+            Some(b) => tournament.matches[first_leaf + leaf].player_b = tournament.players[*b],
+            None => play(tournament, first_leaf + leaf, *a),
+        }
+    }
+    tournament.fit_payout_table();
+}
+
+/// Reports `player` as the winner of match `index`.
+fn play(tournament: &mut Tournament, index: usize, player: usize) {
+    let winner = tournament.players[player];
+    tournament.matches[index].winner = winner;
+    if let Some(parent) = BracketMatch::parent(index) {
+        if index % 2 == 1 {
+            tournament.matches[parent].player_a = winner;
+        } else {
+            tournament.matches[parent].player_b = winner;
+        }
+    }
+}
+
+fn tiers(tournament: &Tournament) -> Vec<Option<usize>> {
+    tournament.players.iter().map(|player| tournament.finishing_tier(player)).collect()
+}
+
+#[test]
+fn finishing_tiers_follow_the_bracket() {
+    let mut tournament = tournament(4, 4, vec![5_000, 2_500, 1_250]);
+    seed(&mut tournament, &[(0, Some(3)), (1, Some(2))]);
+    assert_eq!(tiers(&tournament), vec![None; 4]);
+
+    play(&mut tournament, 1, 0);
+    assert_eq!(tiers(&tournament), vec![None, None, None, Some(2)]);
+    play(&mut tournament, 2, 2);
+    play(&mut tournament, 0, 0);
+    assert_eq!(tiers(&tournament), vec![Some(0), Some(2), Some(1), Some(2)]);
+}
+
+#[test]
+fn prizes_follow_the_payout_table() {
+    let mut tournament = tournament(4, 4, vec![5_000, 2_500, 1_250]);
+    seed(&mut tournament, &[(0, Some(3)), (1, Some(2))]);
+    play(&mut tournament, 1, 0);
+    play(&mut tournament, 2, 1);
+    play(&mut tournament, 0, 0);
+
+    // 4_000_000 pot, 5% fee, 3_800_000 prize pool
+    assert_eq!(tournament.tier_prize(0).unwrap(), 1_900_000);
+    assert_eq!(tournament.tier_prize(1).unwrap(), 950_000);
+    assert_eq!(tournament.tier_prize(2).unwrap(), 475_000);
+    assert_eq!(tournament.tier_prize(3).unwrap(), 0);
+    assert_eq!(tournament.fee_amount().unwrap(), 200_000);
+}
+
+#[test]
+fn byes_do_not_leave_prizes_to_the_dev_wallet() {
+    // Three players in a 4-slot bracket of an 8-player tournament
+    let mut tournament = tournament(8, 3, vec![4_000, 2_000, 1_000, 500]);
+    seed(&mut tournament, &[(0, None), (1, Some(2))]);
+    assert_eq!(tournament.payout_table, vec![5_715, 2_857, 1_428]);
+    play(&mut tournament, 2, 1);
+    play(&mut tournament, 0, 1);
+    assert_eq!(tiers(&tournament), vec![Some(1), Some(0), Some(2)]);
+
+    // 3_000_000 pot, 150_000 fee; only rounding dust is added to it
+    let prizes: u64 = (0..3).map(|tier| tournament.tier_prize(tier).unwrap()).sum();
+    assert_eq!(prizes, 2_850_000);
+    assert_eq!(tournament.fee_amount().unwrap(), 150_000);
+}
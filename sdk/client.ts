@@ -12,6 +12,8 @@ import BN from 'bn.js';
 import {
   GameAccount,
   GameState,
+  BatchPolicy,
+  BatchResolution,
   CreateGameArgs,
   GamePDAs,
   PayoutAmounts,
@@ -20,10 +22,26 @@ import {
   GameNotFoundError,
   InvalidGameStateError,
   TokenInfo,
+  Side,
+  StakeAsset,
+  AssetKind,
+  BasketLeg,
+  MAX_BASKET_LEGS,
+  PlayerStatsAccount,
+  MintTotals,
+  RatingAccount,
+  INITIAL_RATING,
+  CreateQueueArgs,
+  MatchQueueAccount,
+  PlayerBalanceAccount,
+  ApproveSessionArgs,
 } from './types';
 import {
   WAGER_PROGRAM_ID,
   deriveGamePDAs,
+  derivePlayerStatsPDA,
+  deriveRatingPDA,
+  derivePlayerBalancePDAs,
   calculatePayouts,
   getTokenInfo,
   isExpired,
@@ -36,9 +54,23 @@ import {
 import {
   createCreateGameInstruction,
   createJoinGameInstruction,
+  createMigrateGameInstruction,
   createResolveGameInstruction,
+  createResolveManyInstruction,
   createCancelGameInstruction,
+  createCrankExpiredInstruction,
+  createClaimInstruction,
+  createClaimNftInstruction,
+  createClaimBasketInstruction,
   createUpdateResolverInstruction,
+  createCreateQueueInstruction,
+  createEnqueueInstruction,
+  createDequeueInstruction,
+  createMatchPlayersInstruction,
+  createDepositBalanceInstruction,
+  createWithdrawInstruction,
+  createApproveSessionInstruction,
+  createRevokeSessionInstruction,
 } from './instructions';
 
 export class WagerClient {
@@ -62,12 +94,13 @@ export class WagerClient {
    */
   async createGame(
     creator: Signer,
-    args: CreateGameArgs
+    args: CreateGameArgs,
+    payer?: Signer // Relayer sponsoring the transaction
   ): Promise<{ signature: string; gamePda: PublicKey }> {
     const { instruction, gamePda, preInstructions } = await createCreateGameInstruction(
       this.connection,
       creator.publicKey,
-      args,
+      { ...args, payer: payer?.publicKey },
       this.programId
     );
 
@@ -77,7 +110,7 @@ export class WagerClient {
     const signature = await sendAndConfirmTransaction(
       this.connection,
       transaction,
-      [creator],
+      payer ? [payer, creator] : [creator],
       this.confirmOptions
     );
 
@@ -89,7 +122,12 @@ export class WagerClient {
    */
   async joinGame(
     player2: Signer,
-    gamePda: PublicKey
+    gamePda: PublicKey,
+    nftMint?: PublicKey, // NFT to stake when the game asks player2 for one
+    useBalance?: boolean, // Stake from player2's PlayerBalance instead of their wallet
+    sessionOwner?: PublicKey, // Wallet player2 is a session key for
+    payer?: Signer, // Relayer sponsoring the transaction
+    relayerFee?: BN // Reimbursed to the relayer from player2's stake
   ): Promise<{ signature: string }> {
     const gameAccount = await this.getGame(gamePda);
     
@@ -102,7 +140,12 @@ export class WagerClient {
       player2.publicKey,
       gamePda,
       gameAccount,
-      this.programId
+      this.programId,
+      nftMint,
+      useBalance,
+      sessionOwner,
+      payer?.publicKey,
+      relayerFee
     );
 
     const transaction = new Transaction();
@@ -111,7 +154,7 @@ export class WagerClient {
     const signature = await sendAndConfirmTransaction(
       this.connection,
       transaction,
-      [player2],
+      payer ? [payer, player2] : [player2],
       this.confirmOptions
     );
 
@@ -160,6 +203,60 @@ export class WagerClient {
     return { signature, payouts };
   }
 
+  /**
+   * Resolve several single-game wagers in one transaction. With
+   * `BatchPolicy.SkipInvalid`, games that can't be resolved are left untouched.
+   */
+  async resolveMany(
+    resolver: Signer,
+    resolutions: BatchResolution[],
+    policy: BatchPolicy = BatchPolicy.Atomic
+  ): Promise<{ signature: string }> {
+    const games = await Promise.all(resolutions.map(({ gamePda }) => this.getGame(gamePda)));
+
+    const instruction = createResolveManyInstruction(
+      resolver.publicKey,
+      resolutions,
+      games,
+      policy,
+      this.programId
+    );
+
+    const transaction = new Transaction();
+    transaction.add(instruction);
+
+    const signature = await sendAndConfirmTransaction(
+      this.connection,
+      transaction,
+      [resolver],
+      this.confirmOptions
+    );
+
+    return { signature };
+  }
+
+  /**
+   * Refund and close expired games that hold only fungible stakes; the
+   * cranker keeps a share of each closed game's rent
+   */
+  async crankExpired(cranker: Signer, gamePdas: PublicKey[]): Promise<{ signature: string }> {
+    const games = await Promise.all(gamePdas.map((gamePda) => this.getGame(gamePda)));
+
+    const instruction = createCrankExpiredInstruction(cranker.publicKey, gamePdas, games, this.programId);
+
+    const transaction = new Transaction();
+    transaction.add(instruction);
+
+    const signature = await sendAndConfirmTransaction(
+      this.connection,
+      transaction,
+      [cranker],
+      this.confirmOptions
+    );
+
+    return { signature };
+  }
+
   /**
    * Cancel an expired game
    */
@@ -194,6 +291,105 @@ export class WagerClient {
     return { signature };
   }
 
+  /**
+   * Withdraw a recipient's winnings, fee or refund after resolution/cancel
+   */
+  async claim(
+    claimer: Signer,
+    gamePda: PublicKey,
+    recipient: PublicKey = claimer.publicKey,
+    toBalance?: boolean // Credit the recipient's PlayerBalance instead of their wallet
+  ): Promise<{ signature: string }> {
+    const gameAccount = await this.getGame(gamePda);
+
+    const { instruction, preInstructions } = await createClaimInstruction(
+      this.connection,
+      claimer.publicKey,
+      gamePda,
+      recipient,
+      gameAccount,
+      this.programId,
+      toBalance
+    );
+
+    const transaction = new Transaction();
+    transaction.add(...preInstructions, instruction);
+
+    const signature = await sendAndConfirmTransaction(
+      this.connection,
+      transaction,
+      [claimer],
+      this.confirmOptions
+    );
+
+    return { signature };
+  }
+
+  /**
+   * Deliver the NFT staked by `side` to the winner, or back to its staker
+   * if the game was canceled
+   */
+  async claimNft(
+    claimer: Signer,
+    gamePda: PublicKey,
+    side: Side
+  ): Promise<{ signature: string }> {
+    const gameAccount = await this.getGame(gamePda);
+
+    const { instruction, preInstructions } = await createClaimNftInstruction(
+      this.connection,
+      claimer.publicKey,
+      gamePda,
+      side,
+      gameAccount,
+      this.programId
+    );
+
+    const transaction = new Transaction();
+    transaction.add(...preInstructions, instruction);
+
+    const signature = await sendAndConfirmTransaction(
+      this.connection,
+      transaction,
+      [claimer],
+      this.confirmOptions
+    );
+
+    return { signature };
+  }
+
+  /**
+   * Pay out every basket leg still owed to `recipient`
+   */
+  async claimBasket(
+    claimer: Signer,
+    gamePda: PublicKey,
+    recipient: PublicKey = claimer.publicKey
+  ): Promise<{ signature: string }> {
+    const gameAccount = await this.getGame(gamePda);
+
+    const { instruction, preInstructions } = await createClaimBasketInstruction(
+      this.connection,
+      claimer.publicKey,
+      gamePda,
+      recipient,
+      gameAccount,
+      this.programId
+    );
+
+    const transaction = new Transaction();
+    transaction.add(...preInstructions, instruction);
+
+    const signature = await sendAndConfirmTransaction(
+      this.connection,
+      transaction,
+      [claimer],
+      this.confirmOptions
+    );
+
+    return { signature };
+  }
+
   /**
    * Update game resolver (only before deposits)
    */
@@ -242,6 +438,366 @@ export class WagerClient {
     return this.deserializeGameAccount(accountInfo.data);
   }
 
+  /**
+   * Pre-fund the owner's balance for a mint so games can be staked from it
+   */
+  async depositBalance(
+    owner: Signer,
+    mint: PublicKey,
+    amount: BN
+  ): Promise<{ signature: string }> {
+    const { instruction, preInstructions } = await createDepositBalanceInstruction(
+      this.connection,
+      owner.publicKey,
+      mint,
+      amount,
+      this.programId
+    );
+
+    const transaction = new Transaction();
+    transaction.add(...preInstructions, instruction);
+
+    const signature = await sendAndConfirmTransaction(
+      this.connection,
+      transaction,
+      [owner],
+      this.confirmOptions
+    );
+
+    return { signature };
+  }
+
+  /**
+   * Withdraw funds from the owner's balance back to their wallet
+   */
+  async withdraw(
+    owner: Signer,
+    mint: PublicKey,
+    amount: BN
+  ): Promise<{ signature: string }> {
+    const { instruction, preInstructions } = await createWithdrawInstruction(
+      this.connection,
+      owner.publicKey,
+      mint,
+      amount,
+      this.programId
+    );
+
+    const transaction = new Transaction();
+    transaction.add(...preInstructions, instruction);
+
+    const signature = await sendAndConfirmTransaction(
+      this.connection,
+      transaction,
+      [owner],
+      this.confirmOptions
+    );
+
+    return { signature };
+  }
+
+  /**
+   * Get a wallet's balance for a mint, or null before its first deposit
+   */
+  async getPlayerBalance(owner: PublicKey, mint: PublicKey): Promise<PlayerBalanceAccount | null> {
+    const { balancePda } = derivePlayerBalancePDAs(owner, mint, this.programId);
+    const accountInfo = await this.connection.getAccountInfo(balancePda);
+    if (!accountInfo || !accountInfo.data) {
+      return null;
+    }
+
+    const data = accountInfo.data;
+    const offset = 8 + 32 + 32; // Skip discriminator, owner and mint
+    return { owner, mint, amount: new BN(data.slice(offset, offset + 8), 'le') };
+  }
+
+  /**
+   * Approve an ephemeral session key to create and join games for the owner
+   */
+  async approveSession(
+    owner: Signer,
+    args: ApproveSessionArgs
+  ): Promise<{ signature: string }> {
+    const { instruction, preInstructions } = await createApproveSessionInstruction(
+      this.connection,
+      owner.publicKey,
+      args,
+      this.programId
+    );
+
+    const transaction = new Transaction();
+    transaction.add(...preInstructions, instruction);
+
+    const signature = await sendAndConfirmTransaction(
+      this.connection,
+      transaction,
+      [owner],
+      this.confirmOptions
+    );
+
+    return { signature };
+  }
+
+  /**
+   * Revoke a session key's approval
+   */
+  async revokeSession(owner: Signer, sessionKey: PublicKey): Promise<{ signature: string }> {
+    const { instruction, preInstructions } = await createRevokeSessionInstruction(
+      this.connection,
+      owner.publicKey,
+      sessionKey,
+      this.programId
+    );
+
+    const transaction = new Transaction();
+    transaction.add(...preInstructions, instruction);
+
+    const signature = await sendAndConfirmTransaction(
+      this.connection,
+      transaction,
+      [owner],
+      this.confirmOptions
+    );
+
+    return { signature };
+  }
+
+  /**
+   * Upgrade a game created under an older layout; `payer` funds the added rent
+   */
+  async migrateGame(payer: Signer, gamePda: PublicKey, mint: PublicKey): Promise<{ signature: string }> {
+    const instruction = await createMigrateGameInstruction(payer.publicKey, gamePda, mint, this.programId);
+
+    const transaction = new Transaction();
+    transaction.add(instruction);
+
+    const signature = await sendAndConfirmTransaction(
+      this.connection,
+      transaction,
+      [payer],
+      this.confirmOptions
+    );
+
+    return { signature };
+  }
+
+  /**
+   * Open a matchmaking queue; `resolver` resolves every game it matches
+   */
+  async createQueue(
+    resolver: Signer,
+    args: CreateQueueArgs
+  ): Promise<{ signature: string; queuePda: PublicKey }> {
+    const { instruction, queuePda, preInstructions } = await createCreateQueueInstruction(
+      this.connection,
+      resolver.publicKey,
+      args,
+      this.programId
+    );
+
+    const transaction = new Transaction();
+    transaction.add(...preInstructions, instruction);
+
+    const signature = await sendAndConfirmTransaction(
+      this.connection,
+      transaction,
+      [resolver],
+      this.confirmOptions
+    );
+
+    return { signature, queuePda };
+  }
+
+  /**
+   * Escrow the queue's wager and wait to be matched
+   */
+  async enqueue(player: Signer, queuePda: PublicKey): Promise<{ signature: string }> {
+    const queueAccount = await this.getQueue(queuePda);
+
+    const { instruction, preInstructions } = await createEnqueueInstruction(
+      this.connection,
+      player.publicKey,
+      queuePda,
+      queueAccount,
+      this.programId
+    );
+
+    const transaction = new Transaction();
+    transaction.add(...preInstructions, instruction);
+
+    const signature = await sendAndConfirmTransaction(
+      this.connection,
+      transaction,
+      [player],
+      this.confirmOptions
+    );
+
+    return { signature };
+  }
+
+  /**
+   * Leave a queue before being matched and get the wager back
+   */
+  async dequeue(player: Signer, queuePda: PublicKey): Promise<{ signature: string }> {
+    const queueAccount = await this.getQueue(queuePda);
+
+    const { instruction, preInstructions } = await createDequeueInstruction(
+      this.connection,
+      player.publicKey,
+      queuePda,
+      queueAccount,
+      this.programId
+    );
+
+    const transaction = new Transaction();
+    transaction.add(...preInstructions, instruction);
+
+    const signature = await sendAndConfirmTransaction(
+      this.connection,
+      transaction,
+      [player],
+      this.confirmOptions
+    );
+
+    return { signature };
+  }
+
+  /**
+   * Pair the two longest-waiting players of a queue into a new Ready game
+   */
+  async matchPlayers(
+    cranker: Signer,
+    queuePda: PublicKey
+  ): Promise<{ signature: string; gamePda: PublicKey }> {
+    const queueAccount = await this.getQueue(queuePda);
+
+    if (queueAccount.waiting.length < 2) {
+      throw new WagerError('At least two players must be waiting to make a match');
+    }
+
+    const { instruction, gamePda, preInstructions } = await createMatchPlayersInstruction(
+      this.connection,
+      cranker.publicKey,
+      queuePda,
+      queueAccount,
+      this.programId
+    );
+
+    const transaction = new Transaction();
+    transaction.add(...preInstructions, instruction);
+
+    const signature = await sendAndConfirmTransaction(
+      this.connection,
+      transaction,
+      [cranker],
+      this.confirmOptions
+    );
+
+    return { signature, gamePda };
+  }
+
+  /**
+   * Get matchmaking queue data
+   */
+  async getQueue(queuePda: PublicKey): Promise<MatchQueueAccount> {
+    const accountInfo = await this.connection.getAccountInfo(queuePda);
+    if (!accountInfo || !accountInfo.data) {
+      throw new WagerError(`Queue not found: ${queuePda.toString()}`);
+    }
+
+    const data = accountInfo.data;
+    let offset = 8; // Skip discriminator
+    const resolver = new PublicKey(data.slice(offset, offset + 32));
+    offset += 32;
+    const devWallet = new PublicKey(data.slice(offset, offset + 32));
+    offset += 32;
+    const mint = new PublicKey(data.slice(offset, offset + 32));
+    offset += 32;
+    const wager = new BN(data.slice(offset, offset + 8), 'le');
+    offset += 8;
+    const gameType = data.readUInt32LE(offset);
+    offset += 4;
+    const payoutBps = data.readUInt16LE(offset);
+    offset += 2;
+    const gameDuration = new BN(data.slice(offset, offset + 8), 'le');
+    offset += 8;
+    const gamesMatched = new BN(data.slice(offset, offset + 8), 'le');
+    offset += 8;
+    offset += 3; // bump, vault_bump, sol_vault_bump
+    const len = data.readUInt8(offset);
+    offset += 1;
+
+    const waiting: PublicKey[] = [];
+    for (let i = 0; i < len; i++) {
+      waiting.push(new PublicKey(data.slice(offset + i * 32, offset + (i + 1) * 32)));
+    }
+
+    return { resolver, devWallet, mint, wager, gameType, payoutBps, gameDuration, gamesMatched, waiting };
+  }
+
+  /**
+   * Get a wallet's lifetime stats, or null before its first game
+   */
+  async getPlayerStats(wallet: PublicKey): Promise<PlayerStatsAccount | null> {
+    const accountInfo = await this.connection.getAccountInfo(
+      derivePlayerStatsPDA(wallet, this.programId)
+    );
+    if (!accountInfo || !accountInfo.data) {
+      return null;
+    }
+
+    const data = accountInfo.data;
+    let offset = 8; // Skip discriminator
+    const statsWallet = new PublicKey(data.slice(offset, offset + 32));
+    offset += 32;
+    const gamesPlayed = data.readUInt32LE(offset);
+    offset += 4;
+    const wins = data.readUInt32LE(offset);
+    offset += 4;
+    const losses = data.readUInt32LE(offset);
+    offset += 4;
+    const canceled = data.readUInt32LE(offset);
+    offset += 4;
+    const draws = data.readUInt32LE(offset);
+    offset += 4;
+    const mintCount = data.readUInt8(offset);
+    offset += 1;
+
+    const mintTotals: MintTotals[] = [];
+    for (let i = 0; i < mintCount; i++) {
+      const start = offset + i * 48;
+      mintTotals.push({
+        mint: new PublicKey(data.slice(start, start + 32)),
+        wagered: new BN(data.slice(start + 32, start + 40), 'le'),
+        won: new BN(data.slice(start + 40, start + 48), 'le'),
+      });
+    }
+
+    return { wallet: statsWallet, gamesPlayed, wins, losses, canceled, draws, mintTotals };
+  }
+
+  /**
+   * Get a wallet's rating in a game type; wallets that have not played it yet
+   * start at INITIAL_RATING
+   */
+  async getRating(wallet: PublicKey, gameType: number): Promise<RatingAccount> {
+    const accountInfo = await this.connection.getAccountInfo(
+      deriveRatingPDA(wallet, gameType, this.programId)
+    );
+    if (!accountInfo || !accountInfo.data) {
+      return { wallet, gameType, rating: INITIAL_RATING, games: 0 };
+    }
+
+    const data = accountInfo.data;
+    const offset = 8 + 32 + 4; // Skip discriminator, wallet and game type
+    return {
+      wallet,
+      gameType,
+      rating: data.readUInt32LE(offset),
+      games: data.readUInt32LE(offset + 4),
+    };
+  }
+
   /**
    * Get multiple games by PDAs
    */
@@ -268,7 +824,7 @@ export class WagerClient {
     const filters: GetProgramAccountsFilter[] = [
       {
         memcmp: {
-          offset: 8, // Skip discriminator
+          offset: GAME_CREATOR_OFFSET,
           bytes: creator.toBase58(),
         },
       },
@@ -294,7 +850,7 @@ export class WagerClient {
    * Get all games by state
    */
   async getGamesByState(state: GameState): Promise<GameAccount[]> {
-    const stateOffset = 8 + 32 * 5 + 8 + 2; // After all pubkeys, wager, and payoutBps
+    const stateOffset = GAME_STATE_OFFSET;
     const filters: GetProgramAccountsFilter[] = [
       {
         memcmp: {
@@ -428,10 +984,36 @@ export class WagerClient {
    * Deserialize game account data
    */
   private deserializeGameAccount(data: Buffer): GameAccount {
-    // This is a simplified deserializer - in a real implementation,
-    // you'd use the Anchor-generated deserializer or Borsh
+    // Zero-copy `repr(C)` layout: fields are ordered by alignment, not by
+    // declaration order in the older Borsh layout
     let offset = 8; // Skip discriminator
 
+    const wager = new BN(data.slice(offset, offset + 8), 'le');
+    offset += 8;
+    const expiryTs = new BN(data.slice(offset, offset + 8), 'le');
+    offset += 8;
+    const nonce = new BN(data.slice(offset, offset + 8), 'le');
+    offset += 8;
+    const player1Claimable = new BN(data.slice(offset, offset + 8), 'le');
+    offset += 8;
+    const player2Claimable = new BN(data.slice(offset, offset + 8), 'le');
+    offset += 8;
+    const feeClaimable = new BN(data.slice(offset, offset + 8), 'le');
+    offset += 8;
+    const assetFee = new BN(data.slice(offset, offset + 8), 'le');
+    offset += 8;
+    const player1Basket = decodeBasket(data, offset);
+    offset += BASKET_LEG_LEN * MAX_BASKET_LEGS;
+    const player2Basket = decodeBasket(data, offset);
+    offset += BASKET_LEG_LEN * MAX_BASKET_LEGS;
+    const gameType = data.readUInt32LE(offset);
+    offset += 4;
+    const minRating = data.readUInt32LE(offset);
+    offset += 4;
+    const maxRating = data.readUInt32LE(offset);
+    offset += 4;
+    const payoutBps = data.readUInt16LE(offset);
+    offset += 2;
     const creator = new PublicKey(data.slice(offset, offset + 32));
     offset += 32;
     const player1 = new PublicKey(data.slice(offset, offset + 32));
@@ -444,19 +1026,39 @@ export class WagerClient {
     offset += 32;
     const mint = new PublicKey(data.slice(offset, offset + 32));
     offset += 32;
-    const wager = new BN(data.slice(offset, offset + 8), 'le');
-    offset += 8;
-    const payoutBps = data.readUInt16LE(offset);
-    offset += 2;
+    const sidePool = new PublicKey(data.slice(offset, offset + 32));
+    offset += 32;
+    const winner = new PublicKey(data.slice(offset, offset + 32));
+    offset += 32;
+    const player1Asset = decodeStakeAsset(data, offset);
+    offset += STAKE_ASSET_LEN;
+    const player2Asset = decodeStakeAsset(data, offset);
+    offset += STAKE_ASSET_LEN;
     const state = data.readUInt8(offset) as GameState;
     offset += 1;
-    const expiryTs = new BN(data.slice(offset, offset + 8), 'le');
-    offset += 8;
-    const nonce = new BN(data.slice(offset, offset + 8), 'le');
-    offset += 8;
     const bump = data.readUInt8(offset);
     offset += 1;
     const vaultBump = data.readUInt8(offset);
+    offset += 1;
+    const solVaultBump = data.readUInt8(offset);
+    offset += 1;
+    const bestOf = data.readUInt8(offset);
+    offset += 1;
+    const player1Wins = data.readUInt8(offset);
+    offset += 1;
+    const player2Wins = data.readUInt8(offset);
+    offset += 1;
+    const nftsClaimed = data[offset];
+    offset += 1;
+    const basketPaid = data[offset];
+    offset += 1;
+    const basketFeePaid = data[offset];
+    offset += 1;
+    const version = data[offset];
+    offset += 6; // version and padding
+    const rentPayer = new PublicKey(data.slice(offset, offset + 32));
+    offset += 32;
+    const crankBountyBps = data.readUInt16LE(offset);
 
     return new GameAccountImpl({
       creator,
@@ -472,10 +1074,61 @@ export class WagerClient {
       nonce,
       bump,
       vaultBump,
+      bestOf,
+      player1Wins,
+      player2Wins,
+      sidePool,
+      winner,
+      player1Claimable,
+      player2Claimable,
+      feeClaimable,
+      solVaultBump,
+      player1Asset,
+      player2Asset,
+      assetFee,
+      nftsClaimed,
+      player1Basket,
+      player2Basket,
+      basketPaid,
+      basketFeePaid,
+      gameType,
+      minRating,
+      maxRating,
+      version,
+      rentPayer,
+      crankBountyBps,
     });
   }
 }
 
+const STAKE_ASSET_LEN = 65;
+
+// Offsets in the zero-copy Game layout. `creator` follows the discriminator,
+// seven u64s, two baskets, three u32s and payout_bps; `state` follows eight
+// pubkeys and two stake assets after that
+const GAME_CREATOR_OFFSET = 8 + 7 * 8 + 2 * 160 + 3 * 4 + 2;
+const GAME_STATE_OFFSET = 8 + 7 * 8 + 2 * 160 + 3 * 4 + 2 + 8 * 32 + 2 * 65;
+
+function decodeStakeAsset(data: Buffer, offset: number): StakeAsset {
+  return {
+    kind: data.readUInt8(offset) as AssetKind,
+    mint: new PublicKey(data.slice(offset + 1, offset + 33)),
+    collection: new PublicKey(data.slice(offset + 33, offset + 65)),
+  };
+}
+
+const BASKET_LEG_LEN = 40;
+
+function decodeBasket(data: Buffer, offset: number): BasketLeg[] {
+  return Array.from({ length: MAX_BASKET_LEGS }, (_, i) => {
+    const start = offset + i * BASKET_LEG_LEN;
+    return {
+      mint: new PublicKey(data.slice(start, start + 32)),
+      amount: new BN(data.slice(start + 32, start + 40), 'le'),
+    };
+  });
+}
+
 /**
  * Implementation of GameAccount with helper methods
  */
@@ -493,6 +1146,29 @@ class GameAccountImpl implements GameAccount {
   public readonly nonce!: BN;
   public readonly bump!: number;
   public readonly vaultBump!: number;
+  public readonly bestOf!: number;
+  public readonly player1Wins!: number;
+  public readonly player2Wins!: number;
+  public readonly sidePool!: PublicKey;
+  public readonly winner!: PublicKey;
+  public readonly player1Claimable!: BN;
+  public readonly player2Claimable!: BN;
+  public readonly feeClaimable!: BN;
+  public readonly solVaultBump!: number;
+  public readonly player1Asset!: StakeAsset;
+  public readonly player2Asset!: StakeAsset;
+  public readonly assetFee!: BN;
+  public readonly nftsClaimed!: number;
+  public readonly player1Basket!: BasketLeg[];
+  public readonly player2Basket!: BasketLeg[];
+  public readonly basketPaid!: number;
+  public readonly basketFeePaid!: number;
+  public readonly gameType!: number;
+  public readonly minRating!: number;
+  public readonly maxRating!: number;
+  public readonly version!: number;
+  public readonly rentPayer!: PublicKey;
+  public readonly crankBountyBps!: number;
 
   constructor(data: {
     creator: PublicKey;
@@ -508,6 +1184,29 @@ class GameAccountImpl implements GameAccount {
     nonce: BN;
     bump: number;
     vaultBump: number;
+    bestOf: number;
+    player1Wins: number;
+    player2Wins: number;
+    sidePool: PublicKey;
+    winner: PublicKey;
+    player1Claimable: BN;
+    player2Claimable: BN;
+    feeClaimable: BN;
+    solVaultBump: number;
+    player1Asset: StakeAsset;
+    player2Asset: StakeAsset;
+    assetFee: BN;
+    nftsClaimed: number;
+    player1Basket: BasketLeg[];
+    player2Basket: BasketLeg[];
+    basketPaid: number;
+    basketFeePaid: number;
+    gameType: number;
+    minRating: number;
+    maxRating: number;
+    version: number;
+    rentPayer: PublicKey;
+    crankBountyBps: number;
   }) {
     Object.assign(this, data);
   }
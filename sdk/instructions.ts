@@ -8,14 +8,27 @@ import {
 } from '@solana/web3.js';
 import {
   TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  NATIVE_MINT,
   getAssociatedTokenAddressSync,
   createAssociatedTokenAccountInstruction,
 } from '@solana/spl-token';
 import * as anchor from '@coral-xyz/anchor';
 import BN from 'bn.js';
 import {
+  AssetKind,
+  BatchPolicy,
+  BasketLeg,
+  MAX_BASKET_LEGS,
   CreateGameArgs,
+  CreateQueueArgs,
+  ApproveSessionArgs,
+  DEFAULT_CRANK_BOUNTY_BPS,
+  MAX_SESSION_MINTS,
   GamePDAs,
+  GameState,
+  Side,
+  StakeAsset,
   WagerError,
 } from './types';
 import {
@@ -25,8 +38,47 @@ import {
   getOrCreateAssociatedTokenAccount,
   validatePayoutBps,
   generateNonce,
+  deriveNftVaultPDA,
+  deriveMetadataPDA,
+  deriveBasketVaultPDA,
+  derivePlayerStatsPDA,
+  deriveRatingPDA,
+  deriveQueuePDA,
+  derivePlayerBalancePDAs,
+  deriveSessionPDA,
+  deriveEventAuthorityPDA,
 } from './utils';
 
+/**
+ * Anchor instruction discriminators, the first 8 bytes of
+ * sha256("global:<instruction name>").
+ */
+const DISCRIMINATORS = {
+  create_game: [124, 69, 75, 66, 184, 220, 72, 206],
+  join_game: [107, 112, 18, 38, 56, 173, 60, 128],
+  resolve_game: [25, 119, 183, 229, 196, 69, 169, 79],
+  cancel_if_expired: [172, 1, 191, 137, 223, 156, 3, 166],
+  claim: [62, 198, 214, 193, 213, 159, 108, 210],
+  update_resolver: [108, 227, 28, 163, 123, 230, 190, 84],
+  claim_nft: [6, 193, 146, 120, 48, 218, 69, 33],
+  claim_basket: [177, 220, 187, 83, 89, 222, 176, 251],
+  create_queue: [92, 193, 143, 97, 164, 230, 128, 134],
+  enqueue: [151, 163, 241, 121, 38, 25, 78, 144],
+  dequeue: [216, 28, 122, 250, 248, 136, 52, 59],
+  match_players: [29, 175, 224, 34, 171, 46, 252, 234],
+  deposit_balance: [126, 124, 133, 139, 113, 62, 17, 176],
+  withdraw: [183, 18, 70, 156, 148, 109, 161, 34],
+  approve_session: [231, 149, 247, 237, 145, 218, 106, 16],
+  revoke_session: [86, 92, 198, 120, 144, 2, 7, 194],
+  migrate_game: [166, 248, 193, 244, 30, 49, 33, 64],
+  resolve_many: [209, 8, 183, 76, 213, 29, 160, 158],
+  crank_expired: [242, 214, 197, 141, 189, 175, 150, 24],
+} as const;
+
+function discriminator(name: keyof typeof DISCRIMINATORS): Buffer {
+  return Buffer.from([...DISCRIMINATORS[name]]);
+}
+
 /**
  * Create a new game instruction
  */
@@ -42,8 +94,12 @@ export async function createCreateGameInstruction(
 }> {
   validatePayoutBps(args.payoutBps);
 
+  // A session key creates the game for its owner, staking from the owner's balance
+  const owner = args.sessionOwner ?? creator;
+  const useBalance = args.useBalance || !!args.sessionOwner;
+
   const nonce = args.nonce || generateNonce();
-  const { gamePda, vaultPda } = deriveGamePDAs(creator, nonce, args.mint, programId);
+  const { gamePda, vaultPda, solVaultPda } = deriveGamePDAs(owner, nonce, args.mint, programId);
 
   const preInstructions: TransactionInstruction[] = [];
   const accounts: any = {
@@ -67,28 +123,65 @@ export async function createCreateGameInstruction(
     accounts.tokenMint = null;
   }
 
+  const crankBountyBps = args.crankBountyBps ?? DEFAULT_CRANK_BOUNTY_BPS;
+  if (crankBountyBps > 10000) {
+    throw new WagerError('Crank bounty cannot exceed 10000 basis points');
+  }
+
   const data = Buffer.concat([
-    Buffer.from([0]), // create_game discriminator (placeholder)
+    discriminator('create_game'),
     args.mint.toBuffer(),
     args.wager.toArrayLike(Buffer, 'le', 8),
     Buffer.from([args.payoutBps & 0xff, (args.payoutBps >> 8) & 0xff]),
     args.expiryTs.toArrayLike(Buffer, 'le', 8),
-    Buffer.from([args.resolverPubkey ? 1 : 0]),
-    args.resolverPubkey ? args.resolverPubkey.toBuffer() : Buffer.alloc(32),
+    // Option<Pubkey>: a 0 tag alone for None
+    args.resolverPubkey ? Buffer.concat([Buffer.from([1]), args.resolverPubkey.toBuffer()]) : Buffer.from([0]),
     nonce.toArrayLike(Buffer, 'le', 8),
+    Buffer.from([args.bestOf ?? 1]),
+    encodeStakeAsset(args.player1Asset),
+    encodeStakeAsset(args.player2Asset),
+    (args.assetFee ?? new BN(0)).toArrayLike(Buffer, 'le', 8),
+    encodeBasket(args.player1Basket),
+    encodeBasket(args.player2Basket),
+    u32Buffer(args.gameType ?? 0),
+    u32Buffer(args.minRating ?? 0),
+    u32Buffer(args.maxRating ?? 0xffffffff),
+    (args.relayerFee ?? new BN(0)).toArrayLike(Buffer, 'le', 8),
+    Buffer.from([crankBountyBps & 0xff, (crankBountyBps >> 8) & 0xff]),
   ]);
 
+  const player1NftMint = args.player1Asset?.kind === AssetKind.Nft ? args.player1Asset.mint : undefined;
+  if (args.player1Asset?.kind === AssetKind.Nft && !player1NftMint) {
+    throw new WagerError('The creator must name the NFT they stake');
+  }
+
   // Build instruction manually since we don't have the IDL
   const instruction = new TransactionInstruction({
     keys: [
       { pubkey: creator, isSigner: true, isWritable: true },
+      ...relayerMetas(args.payer ?? creator, args.mint, args.relayerFee, programId),
       { pubkey: gamePda, isSigner: false, isWritable: true },
-      ...(vaultPda ? [{ pubkey: vaultPda, isSigner: false, isWritable: true }] : []),
-      ...(isNativeSOL(args.mint) ? [] : [{ pubkey: args.mint, isSigner: false, isWritable: false }]),
+      vaultPda
+        ? { pubkey: vaultPda, isSigner: false, isWritable: true }
+        : { pubkey: programId, isSigner: false, isWritable: false },
+      { pubkey: derivePlayerStatsPDA(owner, programId), isSigner: false, isWritable: true },
+      ratingMeta(owner, args.gameType ?? 0, programId),
+      { pubkey: solVaultPda, isSigner: false, isWritable: true },
+      isNativeSOL(args.mint)
+        ? { pubkey: programId, isSigner: false, isWritable: false }
+        : { pubkey: args.mint, isSigner: false, isWritable: false },
+      useBalance
+        ? { pubkey: programId, isSigner: false, isWritable: false }
+        : depositorTokenAccountMeta(creator, args.mint, programId),
+      ...balanceMetas(owner, args.mint, useBalance, programId),
+      ...nftDepositMetas(creator, gamePda, player1NftMint, programId),
       { pubkey: args.devWallet, isSigner: false, isWritable: false },
+      sessionMeta(creator, args.sessionOwner, programId),
       { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
       { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
       { pubkey: SYSVAR_RENT_PUBKEY, isSigner: false, isWritable: false },
+      ...eventCpiMetas(programId),
+      ...basketDepositMetas(creator, gamePda, args.player1Basket, programId),
     ],
     programId,
     data,
@@ -102,55 +195,218 @@ export async function createCreateGameInstruction(
 }
 
 /**
- * Create join game instruction
+ * The event authority and the program itself, which `#[event_cpi]` appends to
+ * the accounts of instructions emitting events through self-invocation.
+ */
+function eventCpiMetas(programId: PublicKey) {
+  return [
+    { pubkey: deriveEventAuthorityPDA(programId), isSigner: false, isWritable: false },
+    { pubkey: programId, isSigner: false, isWritable: false },
+  ];
+}
+
+/**
+ * The transaction's payer and, for SPL games with a relayer fee, the token
+ * account it is reimbursed to.
+ */
+function relayerMetas(payer: PublicKey, mint: PublicKey, relayerFee: BN | undefined, programId: PublicKey) {
+  const reimbursed = relayerFee && !relayerFee.isZero() && !isNativeSOL(mint);
+  return [
+    { pubkey: payer, isSigner: true, isWritable: true },
+    reimbursed
+      ? { pubkey: getAssociatedTokenAddressSync(mint, payer, true), isSigner: false, isWritable: true }
+      : { pubkey: programId, isSigner: false, isWritable: false },
+  ];
+}
+
+/**
+ * Token account a player stakes from. Native SOL games and wrapped SOL games
+ * (funded with plain SOL and wrapped by the program) pass none.
+ */
+function depositorTokenAccountMeta(owner: PublicKey, mint: PublicKey, programId: PublicKey) {
+  if (isNativeSOL(mint) || mint.equals(NATIVE_MINT)) {
+    return { pubkey: programId, isSigner: false, isWritable: false };
+  }
+  return {
+    pubkey: getAssociatedTokenAddressSync(mint, owner, true),
+    isSigner: false,
+    isWritable: true,
+  };
+}
+
+/**
+ * A player's balance and its token vault, or placeholders when the player
+ * stakes from (or is paid to) their wallet.
+ */
+function balanceMetas(owner: PublicKey, mint: PublicKey, useBalance: boolean | undefined, programId: PublicKey) {
+  const placeholder = { pubkey: programId, isSigner: false, isWritable: false };
+  if (!useBalance) {
+    return [placeholder, placeholder];
+  }
+  const { balancePda, balanceVaultPda } = derivePlayerBalancePDAs(owner, mint, programId);
+  return [
+    { pubkey: balancePda, isSigner: false, isWritable: true },
+    isNativeSOL(mint) ? placeholder : { pubkey: balanceVaultPda, isSigner: false, isWritable: true },
+  ];
+}
+
+/**
+ * Session approval for `signer`, or a placeholder when it signs for itself.
+ */
+function sessionMeta(signer: PublicKey, sessionOwner: PublicKey | undefined, programId: PublicKey) {
+  if (!sessionOwner) {
+    return { pubkey: programId, isSigner: false, isWritable: false };
+  }
+  return { pubkey: deriveSessionPDA(sessionOwner, signer, programId), isSigner: false, isWritable: true };
+}
+
+/**
+ * Borsh encoding of a side's stake: kind, NFT mint, collection.
+ */
+function encodeStakeAsset(asset?: StakeAsset): Buffer {
+  return Buffer.concat([
+    Buffer.from([asset?.kind ?? AssetKind.Fungible]),
+    (asset?.mint ?? PublicKey.default).toBuffer(),
+    (asset?.collection ?? PublicKey.default).toBuffer(),
+  ]);
+}
+
+/**
+ * Borsh encoding of a basket: MAX_BASKET_LEGS legs of (mint, amount), unused
+ * legs zeroed.
+ */
+function encodeBasket(basket: BasketLeg[] = []): Buffer {
+  if (basket.length > MAX_BASKET_LEGS) {
+    throw new WagerError(`A basket holds at most ${MAX_BASKET_LEGS} legs`);
+  }
+  const legs = Array.from({ length: MAX_BASKET_LEGS }, (_, i) => basket[i]);
+  return Buffer.concat(legs.map((leg) => Buffer.concat([
+    (leg?.mint ?? PublicKey.default).toBuffer(),
+    (leg?.amount ?? new BN(0)).toArrayLike(Buffer, 'le', 8),
+  ])));
+}
+
+/**
+ * Remaining accounts escrowing a basket: per leg, its mint, the depositor's
+ * associated token account and the game's basket vault for the mint.
+ */
+function basketDepositMetas(
+  depositor: PublicKey,
+  gamePda: PublicKey,
+  basket: BasketLeg[] = [],
+  programId: PublicKey
+) {
+  return basket
+    .filter((leg) => !leg.mint.equals(PublicKey.default))
+    .flatMap((leg) => [
+      { pubkey: leg.mint, isSigner: false, isWritable: false },
+      { pubkey: getAssociatedTokenAddressSync(leg.mint, depositor, true), isSigner: false, isWritable: true },
+      { pubkey: deriveBasketVaultPDA(gamePda, leg.mint, programId), isSigner: false, isWritable: true },
+    ]);
+}
+
+/**
+ * NFT mint, staker's token account, NFT vault and metadata accounts, or
+ * placeholders when the side stakes the fungible wager.
+ */
+function nftDepositMetas(
+  staker: PublicKey,
+  gamePda: PublicKey,
+  nftMint: PublicKey | undefined,
+  programId: PublicKey
+) {
+  if (!nftMint) {
+    return Array.from({ length: 4 }, () => ({ pubkey: programId, isSigner: false, isWritable: false }));
+  }
+  return [
+    { pubkey: nftMint, isSigner: false, isWritable: false },
+    { pubkey: getAssociatedTokenAddressSync(nftMint, staker, true), isSigner: false, isWritable: true },
+    { pubkey: deriveNftVaultPDA(gamePda, staker, programId), isSigner: false, isWritable: true },
+    { pubkey: deriveMetadataPDA(nftMint), isSigner: false, isWritable: false },
+  ];
+}
+
+/**
+ * Create join game instruction. `nftMint` is the NFT player2 stakes when the
+ * game asks them for one.
  */
 export async function createJoinGameInstruction(
   connection: Connection,
   player2: PublicKey,
   gamePda: PublicKey,
   gameAccount: any, // Game account data
-  programId: PublicKey = WAGER_PROGRAM_ID
+  programId: PublicKey = WAGER_PROGRAM_ID,
+  nftMint?: PublicKey,
+  useBalance?: boolean, // Stake from player2's PlayerBalance instead of their wallet
+  sessionOwner?: PublicKey, // Wallet player2 is a session key for (stakes from its balance)
+  payer?: PublicKey, // Relayer paying rent and fees, defaults to player2
+  relayerFee?: BN // Reimbursed to the relayer from player2's stake, in the game's mint
 ): Promise<{
   instruction: TransactionInstruction;
   preInstructions: TransactionInstruction[];
 }> {
   const preInstructions: TransactionInstruction[] = [];
   const mint = gameAccount.mint;
+  const owner = sessionOwner ?? player2;
+  useBalance = useBalance || !!sessionOwner;
+
+  if (gameAccount.player2Asset?.kind === AssetKind.Nft && !nftMint) {
+    throw new WagerError('This game requires player2 to stake an NFT');
+  }
   
+  const { vaultPda, solVaultPda } =
+    deriveGamePDAs(gameAccount.creator, gameAccount.nonce, mint, programId);
+
   const accounts = [
     { pubkey: player2, isSigner: true, isWritable: true },
+    ...relayerMetas(payer ?? player2, mint, relayerFee, programId),
     { pubkey: gamePda, isSigner: false, isWritable: true },
   ];
 
   if (!isNativeSOL(mint)) {
     // For SPL tokens, get player2's token account and vault
-    const { address: player2TokenAccount, instruction: createTokenAccountIx } = 
-      await getOrCreateAssociatedTokenAccount(connection, player2, mint, player2);
-    
-    if (createTokenAccountIx) {
-      preInstructions.push(createTokenAccountIx);
+    // Wrapped SOL games are funded with plain SOL, so no token account is needed
+    if (!mint.equals(NATIVE_MINT) && !useBalance) {
+      const { instruction: createTokenAccountIx } =
+        await getOrCreateAssociatedTokenAccount(connection, player2, mint, player2);
+
+      if (createTokenAccountIx) {
+        preInstructions.push(createTokenAccountIx);
+      }
     }
 
-    const vaultPda = deriveGamePDAs(gameAccount.creator, gameAccount.nonce, mint, programId).vaultPda!;
-    
     accounts.push(
-      { pubkey: player2TokenAccount, isSigner: false, isWritable: true },
-      { pubkey: vaultPda, isSigner: false, isWritable: true }
+      useBalance
+        ? { pubkey: programId, isSigner: false, isWritable: false }
+        : depositorTokenAccountMeta(player2, mint, programId),
+      ...balanceMetas(owner, mint, useBalance, programId),
+      { pubkey: vaultPda!, isSigner: false, isWritable: true }
     );
   } else {
-    // For native SOL, add null accounts
+    // For native SOL, pass placeholders for the token accounts
     accounts.push(
-      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }, // placeholder
-      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }  // placeholder
+      { pubkey: programId, isSigner: false, isWritable: false }, // placeholder
+      ...balanceMetas(owner, mint, useBalance, programId),
+      { pubkey: programId, isSigner: false, isWritable: false }  // placeholder
     );
   }
 
   accounts.push(
+    ...nftDepositMetas(player2, gamePda, nftMint, programId),
+    { pubkey: derivePlayerStatsPDA(owner, programId), isSigner: false, isWritable: true },
+    ratingMeta(owner, gameAccount.gameType, programId),
+    { pubkey: solVaultPda, isSigner: false, isWritable: true },
+    sessionMeta(player2, sessionOwner, programId),
     { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
-    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ...eventCpiMetas(programId),
+    ...basketDepositMetas(player2, gamePda, gameAccount.player2Basket, programId)
   );
 
-  const data = Buffer.from([1]); // join_game discriminator
+  const data = Buffer.concat([
+    discriminator('join_game'),
+    (relayerFee ?? new BN(0)).toArrayLike(Buffer, 'le', 8),
+  ]);
 
   const instruction = new TransactionInstruction({
     keys: accounts,
@@ -165,7 +421,46 @@ export async function createJoinGameInstruction(
 }
 
 /**
- * Create resolve game instruction
+ * Both players' stats and rating accounts; player2's stats are a placeholder
+ * until they join, and ratings are placeholders for unranked games.
+ */
+function playerStatsMetas(gameAccount: any, programId: PublicKey) {
+  const players: PublicKey[] = [gameAccount.player1, gameAccount.player2];
+  const stats = players.map((player) => ({
+    pubkey: player.equals(PublicKey.default) ? programId : derivePlayerStatsPDA(player, programId),
+    isSigner: false,
+    isWritable: !player.equals(PublicKey.default),
+  }));
+  const ratings = players.map((player) => ratingMeta(player, gameAccount.gameType, programId));
+  return [...stats, ...ratings];
+}
+
+function ratingMeta(player: PublicKey, gameType: number, programId: PublicKey) {
+  if (!gameType || player.equals(PublicKey.default)) {
+    return { pubkey: programId, isSigner: false, isWritable: false };
+  }
+  return { pubkey: deriveRatingPDA(player, gameType, programId), isSigner: false, isWritable: true };
+}
+
+function u32Buffer(value: number): Buffer {
+  const buffer = Buffer.alloc(4);
+  buffer.writeUInt32LE(value);
+  return buffer;
+}
+
+/**
+ * Optional side pool account for resolve/cancel (program ID placeholder when absent)
+ */
+function sidePoolMeta(gameAccount: any, programId: PublicKey) {
+  if (gameAccount.sidePool && !gameAccount.sidePool.equals(PublicKey.default)) {
+    return { pubkey: gameAccount.sidePool, isSigner: false, isWritable: true };
+  }
+  return { pubkey: programId, isSigner: false, isWritable: false };
+}
+
+/**
+ * Create resolve game instruction. Resolution only records entitlements;
+ * the winner and dev wallet withdraw with `createClaimInstruction`.
  */
 export async function createResolveGameInstruction(
   connection: Connection,
@@ -178,55 +473,16 @@ export async function createResolveGameInstruction(
   instruction: TransactionInstruction;
   preInstructions: TransactionInstruction[];
 }> {
-  const preInstructions: TransactionInstruction[] = [];
-  const mint = gameAccount.mint;
-  
   const accounts = [
     { pubkey: resolver, isSigner: true, isWritable: true },
     { pubkey: gamePda, isSigner: false, isWritable: true },
+    sidePoolMeta(gameAccount, programId),
+    ...playerStatsMetas(gameAccount, programId),
+    ...eventCpiMetas(programId),
   ];
 
-  if (!isNativeSOL(mint)) {
-    // For SPL tokens, get winner and dev wallet token accounts
-    const { address: winnerTokenAccount, instruction: createWinnerTokenAccountIx } = 
-      await getOrCreateAssociatedTokenAccount(connection, resolver, mint, winner);
-    
-    if (createWinnerTokenAccountIx) {
-      preInstructions.push(createWinnerTokenAccountIx);
-    }
-
-    const { address: devTokenAccount, instruction: createDevTokenAccountIx } = 
-      await getOrCreateAssociatedTokenAccount(connection, resolver, mint, gameAccount.devWallet);
-    
-    if (createDevTokenAccountIx) {
-      preInstructions.push(createDevTokenAccountIx);
-    }
-
-    const vaultPda = deriveGamePDAs(gameAccount.creator, gameAccount.nonce, mint, programId).vaultPda!;
-    
-    accounts.push(
-      { pubkey: winnerTokenAccount, isSigner: false, isWritable: true },
-      { pubkey: devTokenAccount, isSigner: false, isWritable: true },
-      { pubkey: vaultPda, isSigner: false, isWritable: true }
-    );
-  } else {
-    // For native SOL, add winner and dev wallet accounts directly
-    accounts.push(
-      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }, // placeholder for token account
-      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }, // placeholder for token account
-      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }  // placeholder for vault
-    );
-  }
-
-  accounts.push(
-    { pubkey: winner, isSigner: false, isWritable: true },
-    { pubkey: gameAccount.devWallet, isSigner: false, isWritable: true },
-    { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
-    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }
-  );
-
   const data = Buffer.concat([
-    Buffer.from([2]), // resolve_game discriminator
+    discriminator('resolve_game'),
     winner.toBuffer(),
   ]);
 
@@ -238,12 +494,13 @@ export async function createResolveGameInstruction(
 
   return {
     instruction,
-    preInstructions,
+    preInstructions: [],
   };
 }
 
 /**
- * Create cancel game instruction
+ * Create cancel game instruction. Refunds are recorded as entitlements and
+ * withdrawn with `createClaimInstruction`.
  */
 export async function createCancelGameInstruction(
   connection: Connection,
@@ -255,60 +512,113 @@ export async function createCancelGameInstruction(
   instruction: TransactionInstruction;
   preInstructions: TransactionInstruction[];
 }> {
-  const preInstructions: TransactionInstruction[] = [];
-  const mint = gameAccount.mint;
-  
   const accounts = [
     { pubkey: authority, isSigner: true, isWritable: true },
     { pubkey: gamePda, isSigner: false, isWritable: true },
+    sidePoolMeta(gameAccount, programId),
+    // Stats only; cancellation doesn't touch ratings
+    ...playerStatsMetas(gameAccount, programId).slice(0, 2),
+    ...eventCpiMetas(programId),
+  ];
+
+  const data = discriminator('cancel_if_expired');
+
+  const instruction = new TransactionInstruction({
+    keys: accounts,
+    programId,
+    data,
+  });
+
+  return {
+    instruction,
+    preInstructions: [],
+  };
+}
+
+/**
+ * Create claim instruction to withdraw a recipient's winnings, fee or refund.
+ * For SPL games the recipient's ATA is created by the program if missing,
+ * with the claimer paying rent. Wrapped SOL games pay out in plain SOL.
+ */
+export async function createClaimInstruction(
+  connection: Connection,
+  claimer: PublicKey,
+  gamePda: PublicKey,
+  recipient: PublicKey,
+  gameAccount: any, // Game account data
+  programId: PublicKey = WAGER_PROGRAM_ID,
+  toBalance?: boolean // Credit the recipient's PlayerBalance instead of their wallet
+): Promise<{
+  instruction: TransactionInstruction;
+  preInstructions: TransactionInstruction[];
+}> {
+  const mint = gameAccount.mint;
+  const { vaultPda, solVaultPda } =
+    deriveGamePDAs(gameAccount.creator, gameAccount.nonce, mint, programId);
+  const solVaultMeta = { pubkey: solVaultPda, isSigner: false, isWritable: true };
+
+  const accounts = [
+    { pubkey: claimer, isSigner: true, isWritable: true },
+    { pubkey: gamePda, isSigner: false, isWritable: true },
   ];
 
   if (!isNativeSOL(mint)) {
-    // For SPL tokens, get creator and player2 token accounts
-    const { address: creatorTokenAccount, instruction: createCreatorTokenAccountIx } = 
-      await getOrCreateAssociatedTokenAccount(connection, authority, mint, gameAccount.creator);
-    
-    if (createCreatorTokenAccountIx) {
-      preInstructions.push(createCreatorTokenAccountIx);
-    }
 
-    let player2TokenAccount = SystemProgram.programId; // placeholder
-    if (gameAccount.player2 && !gameAccount.player2.equals(PublicKey.default)) {
-      const { address, instruction: createPlayer2TokenAccountIx } = 
-        await getOrCreateAssociatedTokenAccount(connection, authority, mint, gameAccount.player2);
-      
-      if (createPlayer2TokenAccountIx) {
-        preInstructions.push(createPlayer2TokenAccountIx);
-      }
-      player2TokenAccount = address;
+    if (toBalance) {
+      // Paid into the balance vault, so neither a token account nor unwrapping is needed
+      accounts.push(
+        { pubkey: programId, isSigner: false, isWritable: false }, // placeholder
+        { pubkey: mint, isSigner: false, isWritable: false },
+        { pubkey: vaultPda!, isSigner: false, isWritable: true },
+        solVaultMeta,
+        { pubkey: programId, isSigner: false, isWritable: false }  // placeholder
+      );
+    } else if (mint.equals(NATIVE_MINT)) {
+      // Wrapped SOL payouts are unwrapped through a temporary account
+      const [unwrapPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from('unwrap'), gamePda.toBuffer()],
+        programId
+      );
+      accounts.push(
+        { pubkey: programId, isSigner: false, isWritable: false }, // placeholder
+        { pubkey: mint, isSigner: false, isWritable: false },
+        { pubkey: vaultPda!, isSigner: false, isWritable: true },
+        solVaultMeta,
+        { pubkey: unwrapPda, isSigner: false, isWritable: true }
+      );
+    } else {
+      const recipientTokenAccount = getAssociatedTokenAddressSync(mint, recipient, true);
+      accounts.push(
+        { pubkey: recipientTokenAccount, isSigner: false, isWritable: true },
+        { pubkey: mint, isSigner: false, isWritable: false },
+        { pubkey: vaultPda!, isSigner: false, isWritable: true },
+        solVaultMeta,
+        { pubkey: programId, isSigner: false, isWritable: false }  // placeholder
+      );
     }
-
-    const vaultPda = deriveGamePDAs(gameAccount.creator, gameAccount.nonce, mint, programId).vaultPda!;
-    
-    accounts.push(
-      { pubkey: creatorTokenAccount, isSigner: false, isWritable: true },
-      { pubkey: player2TokenAccount, isSigner: false, isWritable: true },
-      { pubkey: vaultPda, isSigner: false, isWritable: true }
-    );
   } else {
-    // For native SOL, add placeholders
     accounts.push(
-      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }, // placeholder
-      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }, // placeholder
-      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }  // placeholder
+      { pubkey: programId, isSigner: false, isWritable: false }, // placeholder
+      { pubkey: programId, isSigner: false, isWritable: false }, // placeholder
+      { pubkey: programId, isSigner: false, isWritable: false }, // placeholder
+      solVaultMeta,
+      { pubkey: programId, isSigner: false, isWritable: false }  // placeholder
     );
   }
 
   accounts.push(
-    { pubkey: gameAccount.creator, isSigner: false, isWritable: true },
-    ...(gameAccount.player2 && !gameAccount.player2.equals(PublicKey.default) ? 
-      [{ pubkey: gameAccount.player2, isSigner: false, isWritable: true }] : 
-      [{ pubkey: SystemProgram.programId, isSigner: false, isWritable: false }]),
+    { pubkey: recipient, isSigner: false, isWritable: true },
+    ...balanceMetas(recipient, mint, toBalance, programId),
     { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
-    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }
+    { pubkey: ASSOCIATED_TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ...eventCpiMetas(programId)
   );
 
-  const data = Buffer.from([3]); // cancel_if_expired discriminator
+  const data = Buffer.concat([
+    discriminator('claim'),
+    recipient.toBuffer(),
+  ]);
 
   const instruction = new TransactionInstruction({
     keys: accounts,
@@ -318,7 +628,7 @@ export async function createCancelGameInstruction(
 
   return {
     instruction,
-    preInstructions,
+    preInstructions: [],
   };
 }
 
@@ -333,7 +643,7 @@ export async function createUpdateResolverInstruction(
   programId: PublicKey = WAGER_PROGRAM_ID
 ): Promise<TransactionInstruction> {
   const data = Buffer.concat([
-    Buffer.from([4]), // update_resolver discriminator
+    discriminator('update_resolver'),
     newResolver.toBuffer(),
   ]);
 
@@ -341,8 +651,653 @@ export async function createUpdateResolverInstruction(
     keys: [
       { pubkey: creator, isSigner: true, isWritable: true },
       { pubkey: gamePda, isSigner: false, isWritable: true },
+      ...eventCpiMetas(programId),
     ],
     programId,
     data,
   });
 }
+
+/**
+ * Create claim NFT instruction to deliver the NFT staked by `side` to the
+ * winner, or back to its staker if the game was canceled.
+ */
+export async function createClaimNftInstruction(
+  connection: Connection,
+  claimer: PublicKey,
+  gamePda: PublicKey,
+  side: Side,
+  gameAccount: any, // Game account data
+  programId: PublicKey = WAGER_PROGRAM_ID
+): Promise<{
+  instruction: TransactionInstruction;
+  preInstructions: TransactionInstruction[];
+}> {
+  const asset = side === Side.Player1 ? gameAccount.player1Asset : gameAccount.player2Asset;
+  const staker = side === Side.Player1 ? gameAccount.player1 : gameAccount.player2;
+  const recipient = gameAccount.state === GameState.Paid ? gameAccount.winner : staker;
+
+  const accounts = [
+    { pubkey: claimer, isSigner: true, isWritable: true },
+    { pubkey: gamePda, isSigner: false, isWritable: true },
+    { pubkey: asset.mint, isSigner: false, isWritable: false },
+    { pubkey: deriveNftVaultPDA(gamePda, staker, programId), isSigner: false, isWritable: true },
+    { pubkey: staker, isSigner: false, isWritable: true },
+    { pubkey: getAssociatedTokenAddressSync(asset.mint, recipient, true), isSigner: false, isWritable: true },
+    { pubkey: recipient, isSigner: false, isWritable: false },
+    { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+    { pubkey: ASSOCIATED_TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ...eventCpiMetas(programId),
+  ];
+
+  const data = Buffer.concat([
+    discriminator('claim_nft'),
+    Buffer.from([side]),
+  ]);
+
+  const instruction = new TransactionInstruction({
+    keys: accounts,
+    programId,
+    data,
+  });
+
+  return {
+    instruction,
+    preInstructions: [],
+  };
+}
+
+/**
+ * Create claim basket instruction to pay out every basket leg still owed to
+ * `recipient`. The recipient's associated token accounts must already exist.
+ */
+export async function createClaimBasketInstruction(
+  connection: Connection,
+  claimer: PublicKey,
+  gamePda: PublicKey,
+  recipient: PublicKey,
+  gameAccount: any, // Game account data
+  programId: PublicKey = WAGER_PROGRAM_ID
+): Promise<{
+  instruction: TransactionInstruction;
+  preInstructions: TransactionInstruction[];
+}> {
+  const accounts = [
+    { pubkey: claimer, isSigner: true, isWritable: false },
+    { pubkey: gamePda, isSigner: false, isWritable: true },
+    { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+    ...eventCpiMetas(programId),
+  ];
+
+  // Mirror the program's walk over legs owed to the recipient
+  const sides = [
+    { staker: gameAccount.player1, basket: gameAccount.player1Basket as BasketLeg[], offset: 0 },
+    { staker: gameAccount.player2, basket: gameAccount.player2Basket as BasketLeg[], offset: MAX_BASKET_LEGS },
+  ];
+  for (const { staker, basket, offset } of sides) {
+    basket.forEach((leg, index) => {
+      if (leg.mint.equals(PublicKey.default)) {
+        return;
+      }
+      const bit = 1 << (offset + index);
+      const winnerAmount = leg.amount.muln(gameAccount.payoutBps).divn(10000);
+      const feeAmount = leg.amount.sub(winnerAmount);
+
+      let owed = false;
+      if (gameAccount.state === GameState.Paid) {
+        owed ||= recipient.equals(gameAccount.winner) && (gameAccount.basketPaid & bit) === 0 && !winnerAmount.isZero();
+        owed ||= recipient.equals(gameAccount.devWallet) && (gameAccount.basketFeePaid & bit) === 0 && !feeAmount.isZero();
+      } else {
+        owed = recipient.equals(staker) && (gameAccount.basketPaid & bit) === 0;
+      }
+
+      if (owed) {
+        accounts.push(
+          { pubkey: deriveBasketVaultPDA(gamePda, leg.mint, programId), isSigner: false, isWritable: true },
+          { pubkey: getAssociatedTokenAddressSync(leg.mint, recipient, true), isSigner: false, isWritable: true }
+        );
+      }
+    });
+  }
+
+  const data = Buffer.concat([
+    discriminator('claim_basket'),
+    recipient.toBuffer(),
+  ]);
+
+  const instruction = new TransactionInstruction({
+    keys: accounts,
+    programId,
+    data,
+  });
+
+  return {
+    instruction,
+    preInstructions: [],
+  };
+}
+
+/**
+ * The queue's token vault and SOL vault; the token vault is a placeholder for
+ * native SOL queues.
+ */
+function queueEscrowMetas(queuePda: PublicKey, mint: PublicKey, programId: PublicKey) {
+  const [solVault] = PublicKey.findProgramAddressSync(
+    [Buffer.from('sol_vault'), queuePda.toBuffer()],
+    programId
+  );
+  const vault = isNativeSOL(mint)
+    ? { pubkey: programId, isSigner: false, isWritable: false }
+    : {
+        pubkey: PublicKey.findProgramAddressSync(
+          [Buffer.from('vault'), queuePda.toBuffer(), mint.toBuffer()],
+          programId
+        )[0],
+        isSigner: false,
+        isWritable: true,
+      };
+  return { vault, solVault: { pubkey: solVault, isSigner: false, isWritable: true } };
+}
+
+/**
+ * Create a matchmaking queue run by `resolver`
+ */
+export async function createCreateQueueInstruction(
+  connection: Connection,
+  resolver: PublicKey,
+  args: CreateQueueArgs,
+  programId: PublicKey = WAGER_PROGRAM_ID
+): Promise<{
+  instruction: TransactionInstruction;
+  preInstructions: TransactionInstruction[];
+  queuePda: PublicKey;
+}> {
+  validatePayoutBps(args.payoutBps);
+
+  const gameType = args.gameType ?? 0;
+  const queuePda = deriveQueuePDA(resolver, args.mint, args.wager, gameType, programId);
+  const { vault, solVault } = queueEscrowMetas(queuePda, args.mint, programId);
+
+  const accounts = [
+    { pubkey: resolver, isSigner: true, isWritable: true },
+    { pubkey: queuePda, isSigner: false, isWritable: true },
+    vault,
+    { ...solVault, isWritable: false },
+    isNativeSOL(args.mint)
+      ? { pubkey: programId, isSigner: false, isWritable: false }
+      : { pubkey: args.mint, isSigner: false, isWritable: false },
+    { pubkey: args.devWallet, isSigner: false, isWritable: false },
+    { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    { pubkey: SYSVAR_RENT_PUBKEY, isSigner: false, isWritable: false },
+  ];
+
+  const data = Buffer.concat([
+    discriminator('create_queue'),
+    args.mint.toBuffer(),
+    args.wager.toArrayLike(Buffer, 'le', 8),
+    u32Buffer(gameType),
+    Buffer.from([args.payoutBps & 0xff, (args.payoutBps >> 8) & 0xff]),
+    args.gameDuration.toArrayLike(Buffer, 'le', 8),
+  ]);
+
+  const instruction = new TransactionInstruction({
+    keys: accounts,
+    programId,
+    data,
+  });
+
+  return {
+    instruction,
+    preInstructions: [],
+    queuePda,
+  };
+}
+
+/**
+ * Create enqueue instruction escrowing the player's wager in the queue
+ */
+export async function createEnqueueInstruction(
+  connection: Connection,
+  player: PublicKey,
+  queuePda: PublicKey,
+  queueAccount: any, // Queue account data
+  programId: PublicKey = WAGER_PROGRAM_ID
+): Promise<{
+  instruction: TransactionInstruction;
+  preInstructions: TransactionInstruction[];
+}> {
+  const { vault, solVault } = queueEscrowMetas(queuePda, queueAccount.mint, programId);
+
+  const accounts = [
+    { pubkey: player, isSigner: true, isWritable: true },
+    { pubkey: queuePda, isSigner: false, isWritable: true },
+    depositorTokenAccountMeta(player, queueAccount.mint, programId),
+    vault,
+    solVault,
+    { pubkey: derivePlayerStatsPDA(player, programId), isSigner: false, isWritable: true },
+    ratingMeta(player, queueAccount.gameType, programId),
+    { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+  ];
+
+  const data = discriminator('enqueue');
+
+  const instruction = new TransactionInstruction({
+    keys: accounts,
+    programId,
+    data,
+  });
+
+  return {
+    instruction,
+    preInstructions: [],
+  };
+}
+
+/**
+ * Create dequeue instruction leaving the queue with a refund
+ */
+export async function createDequeueInstruction(
+  connection: Connection,
+  player: PublicKey,
+  queuePda: PublicKey,
+  queueAccount: any, // Queue account data
+  programId: PublicKey = WAGER_PROGRAM_ID
+): Promise<{
+  instruction: TransactionInstruction;
+  preInstructions: TransactionInstruction[];
+}> {
+  const { vault, solVault } = queueEscrowMetas(queuePda, queueAccount.mint, programId);
+
+  const accounts = [
+    { pubkey: player, isSigner: true, isWritable: true },
+    { pubkey: queuePda, isSigner: false, isWritable: true },
+    depositorTokenAccountMeta(player, queueAccount.mint, programId),
+    vault,
+    solVault,
+    { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+  ];
+
+  const data = discriminator('dequeue');
+
+  const instruction = new TransactionInstruction({
+    keys: accounts,
+    programId,
+    data,
+  });
+
+  return {
+    instruction,
+    preInstructions: [],
+  };
+}
+
+/**
+ * Create match instruction pairing the two longest-waiting players into a
+ * new game created by the queue
+ */
+export async function createMatchPlayersInstruction(
+  connection: Connection,
+  cranker: PublicKey,
+  queuePda: PublicKey,
+  queueAccount: any, // Queue account data
+  programId: PublicKey = WAGER_PROGRAM_ID
+): Promise<{
+  instruction: TransactionInstruction;
+  preInstructions: TransactionInstruction[];
+  gamePda: PublicKey;
+}> {
+  const mint = queueAccount.mint;
+  const { vault: queueVault, solVault: queueSolVault } = queueEscrowMetas(queuePda, mint, programId);
+  const { gamePda, vaultPda, solVaultPda } =
+    deriveGamePDAs(queuePda, queueAccount.gamesMatched, mint, programId);
+
+  const accounts = [
+    { pubkey: cranker, isSigner: true, isWritable: true },
+    { pubkey: queuePda, isSigner: false, isWritable: true },
+    { pubkey: gamePda, isSigner: false, isWritable: true },
+    queueVault,
+    queueSolVault,
+    vaultPda
+      ? { pubkey: vaultPda, isSigner: false, isWritable: true }
+      : { pubkey: programId, isSigner: false, isWritable: false },
+    { pubkey: solVaultPda, isSigner: false, isWritable: true },
+    isNativeSOL(mint)
+      ? { pubkey: programId, isSigner: false, isWritable: false }
+      : { pubkey: mint, isSigner: false, isWritable: false },
+    { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    { pubkey: SYSVAR_RENT_PUBKEY, isSigner: false, isWritable: false },
+    ...eventCpiMetas(programId),
+  ];
+
+  const data = discriminator('match_players');
+
+  const instruction = new TransactionInstruction({
+    keys: accounts,
+    programId,
+    data,
+  });
+
+  return {
+    instruction,
+    preInstructions: [],
+    gamePda,
+  };
+}
+
+/**
+ * Create deposit instruction pre-funding the owner's balance for a mint
+ */
+export async function createDepositBalanceInstruction(
+  connection: Connection,
+  owner: PublicKey,
+  mint: PublicKey,
+  amount: BN,
+  programId: PublicKey = WAGER_PROGRAM_ID
+): Promise<{
+  instruction: TransactionInstruction;
+  preInstructions: TransactionInstruction[];
+}> {
+  const placeholder = { pubkey: programId, isSigner: false, isWritable: false };
+  const { balancePda, balanceVaultPda } = derivePlayerBalancePDAs(owner, mint, programId);
+  const native = isNativeSOL(mint);
+
+  const accounts = [
+    { pubkey: owner, isSigner: true, isWritable: true },
+    { pubkey: balancePda, isSigner: false, isWritable: true },
+    native ? placeholder : { pubkey: balanceVaultPda, isSigner: false, isWritable: true },
+    native ? placeholder : { pubkey: mint, isSigner: false, isWritable: false },
+    native
+      ? placeholder
+      : { pubkey: getAssociatedTokenAddressSync(mint, owner, true), isSigner: false, isWritable: true },
+    { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    { pubkey: SYSVAR_RENT_PUBKEY, isSigner: false, isWritable: false },
+  ];
+
+  const data = Buffer.concat([
+    discriminator('deposit_balance'),
+    mint.toBuffer(),
+    amount.toArrayLike(Buffer, 'le', 8),
+  ]);
+
+  const instruction = new TransactionInstruction({
+    keys: accounts,
+    programId,
+    data,
+  });
+
+  return {
+    instruction,
+    preInstructions: [],
+  };
+}
+
+/**
+ * Create withdraw instruction moving funds from the owner's balance back to
+ * their wallet. The owner's associated token account must exist for SPL mints.
+ */
+export async function createWithdrawInstruction(
+  connection: Connection,
+  owner: PublicKey,
+  mint: PublicKey,
+  amount: BN,
+  programId: PublicKey = WAGER_PROGRAM_ID
+): Promise<{
+  instruction: TransactionInstruction;
+  preInstructions: TransactionInstruction[];
+}> {
+  const placeholder = { pubkey: programId, isSigner: false, isWritable: false };
+  const { balancePda, balanceVaultPda } = derivePlayerBalancePDAs(owner, mint, programId);
+  const native = isNativeSOL(mint);
+
+  const accounts = [
+    { pubkey: owner, isSigner: true, isWritable: true },
+    { pubkey: balancePda, isSigner: false, isWritable: true },
+    native ? placeholder : { pubkey: balanceVaultPda, isSigner: false, isWritable: true },
+    native
+      ? placeholder
+      : { pubkey: getAssociatedTokenAddressSync(mint, owner, true), isSigner: false, isWritable: true },
+    { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+  ];
+
+  const data = Buffer.concat([
+    discriminator('withdraw'),
+    amount.toArrayLike(Buffer, 'le', 8),
+  ]);
+
+  const instruction = new TransactionInstruction({
+    keys: accounts,
+    programId,
+    data,
+  });
+
+  return {
+    instruction,
+    preInstructions: [],
+  };
+}
+
+/**
+ * Create approve session instruction letting an ephemeral key create and join
+ * games for the owner, staking from the owner's balances
+ */
+export async function createApproveSessionInstruction(
+  connection: Connection,
+  owner: PublicKey,
+  args: ApproveSessionArgs,
+  programId: PublicKey = WAGER_PROGRAM_ID
+): Promise<{
+  instruction: TransactionInstruction;
+  preInstructions: TransactionInstruction[];
+}> {
+  if (args.allowedMints.length === 0 || args.allowedMints.length > MAX_SESSION_MINTS) {
+    throw new WagerError(`A session allows between 1 and ${MAX_SESSION_MINTS} mints`);
+  }
+
+  const accounts = [
+    { pubkey: owner, isSigner: true, isWritable: true },
+    { pubkey: deriveSessionPDA(owner, args.sessionKey, programId), isSigner: false, isWritable: true },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+  ];
+
+  const mintCount = Buffer.alloc(4);
+  mintCount.writeUInt32LE(args.allowedMints.length);
+  const minPayoutBps = Buffer.alloc(2);
+  minPayoutBps.writeUInt16LE(args.minPayoutBps);
+  const data = Buffer.concat([
+    discriminator('approve_session'),
+    args.sessionKey.toBuffer(),
+    args.spendCap.toArrayLike(Buffer, 'le', 8),
+    mintCount,
+    ...args.allowedMints.map((mint) => mint.toBuffer()),
+    args.expiresAt.toArrayLike(Buffer, 'le', 8),
+    args.resolver.toBuffer(),
+    args.devWallet.toBuffer(),
+    minPayoutBps,
+  ]);
+
+  const instruction = new TransactionInstruction({
+    keys: accounts,
+    programId,
+    data,
+  });
+
+  return {
+    instruction,
+    preInstructions: [],
+  };
+}
+
+/**
+ * Create revoke session instruction closing the approval
+ */
+export async function createRevokeSessionInstruction(
+  connection: Connection,
+  owner: PublicKey,
+  sessionKey: PublicKey,
+  programId: PublicKey = WAGER_PROGRAM_ID
+): Promise<{
+  instruction: TransactionInstruction;
+  preInstructions: TransactionInstruction[];
+}> {
+  const accounts = [
+    { pubkey: owner, isSigner: true, isWritable: true },
+    { pubkey: deriveSessionPDA(owner, sessionKey, programId), isSigner: false, isWritable: true },
+  ];
+
+  const data = discriminator('revoke_session');
+
+  const instruction = new TransactionInstruction({
+    keys: accounts,
+    programId,
+    data,
+  });
+
+  return {
+    instruction,
+    preInstructions: [],
+  };
+}
+
+/**
+ * Migrate a game account in `mint` to the current layout
+ */
+export async function createMigrateGameInstruction(
+  payer: PublicKey,
+  gamePda: PublicKey,
+  mint: PublicKey,
+  programId: PublicKey = WAGER_PROGRAM_ID
+): Promise<TransactionInstruction> {
+  const [solVaultPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from('sol_vault'), gamePda.toBuffer()],
+    programId
+  );
+  const [vaultPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from('vault'), gamePda.toBuffer(), mint.toBuffer()],
+    programId
+  );
+
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: payer, isSigner: true, isWritable: true },
+      { pubkey: gamePda, isSigner: false, isWritable: true },
+      { pubkey: solVaultPda, isSigner: false, isWritable: true },
+      { pubkey: isNativeSOL(mint) ? programId : vaultPda, isSigner: false, isWritable: false },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ],
+    programId,
+    data: discriminator('migrate_game'),
+  });
+}
+
+/**
+ * Resolve several games in one instruction. Each game contributes a group of
+ * six accounts (game, both players' stats and ratings, side pool); `games`
+ * holds the decoded account of each entry in `resolutions`, in order.
+ */
+export function createResolveManyInstruction(
+  resolver: PublicKey,
+  resolutions: { gamePda: PublicKey; winner: PublicKey }[],
+  games: any[], // Game account data
+  policy: BatchPolicy = BatchPolicy.Atomic,
+  programId: PublicKey = WAGER_PROGRAM_ID
+): TransactionInstruction {
+  const keys = [
+    { pubkey: resolver, isSigner: true, isWritable: false },
+    ...eventCpiMetas(programId),
+  ];
+  const entries: Buffer[] = [];
+
+  resolutions.forEach(({ gamePda, winner }, index) => {
+    keys.push(
+      { pubkey: gamePda, isSigner: false, isWritable: true },
+      ...playerStatsMetas(games[index], programId),
+      sidePoolMeta(games[index], programId)
+    );
+
+    const gameIndex = Buffer.alloc(2);
+    gameIndex.writeUInt16LE(index);
+    entries.push(gameIndex, winner.toBuffer());
+  });
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: Buffer.concat([
+      discriminator('resolve_many'),
+      u32Buffer(resolutions.length),
+      ...entries,
+      Buffer.from([policy]),
+    ]),
+  });
+}
+
+/**
+ * Refund and close expired games; anyone may send it and earns each closed
+ * game's `crankBountyBps` share of its rent. Each game contributes a group of
+ * ten accounts (game, creator, rent payer, SOL vault, token vault, the
+ * creator's token account for leftover tokens, both players' refund
+ * destinations and stats). Games that fail their checks are skipped. `games`
+ * holds the decoded account of each entry in `gamePdas`.
+ */
+export function createCrankExpiredInstruction(
+  cranker: PublicKey,
+  gamePdas: PublicKey[],
+  games: any[], // Game account data
+  programId: PublicKey = WAGER_PROGRAM_ID
+): TransactionInstruction {
+  const keys = [
+    { pubkey: cranker, isSigner: true, isWritable: true },
+    { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ...eventCpiMetas(programId),
+  ];
+  const placeholder = { pubkey: programId, isSigner: false, isWritable: false };
+
+  gamePdas.forEach((gamePda, index) => {
+    const game = games[index];
+    const { vaultPda, solVaultPda } = deriveGamePDAs(game.creator, game.nonce, game.mint, programId);
+    const native = isNativeSOL(game.mint);
+
+    // Refunds go to player wallets for native SOL, their token accounts otherwise
+    const playerMetas = (player: PublicKey) => {
+      if (player.equals(PublicKey.default)) {
+        return [placeholder, placeholder];
+      }
+      const destination = native ? player : getAssociatedTokenAddressSync(game.mint, player, true);
+      return [
+        { pubkey: destination, isSigner: false, isWritable: true },
+        { pubkey: derivePlayerStatsPDA(player, programId), isSigner: false, isWritable: true },
+      ];
+    };
+    const [player1Destination, player1Stats] = playerMetas(game.player1);
+    const [player2Destination, player2Stats] = playerMetas(game.player2);
+
+    keys.push(
+      { pubkey: gamePda, isSigner: false, isWritable: true },
+      { pubkey: game.creator, isSigner: false, isWritable: true },
+      // Games from before `rentPayer` was recorded return their rent to the creator
+      { pubkey: game.rentPayer.equals(PublicKey.default) ? game.creator : game.rentPayer, isSigner: false, isWritable: true },
+      { pubkey: solVaultPda, isSigner: false, isWritable: true },
+      vaultPda ? { pubkey: vaultPda, isSigner: false, isWritable: true } : placeholder,
+      native
+        ? placeholder
+        : { pubkey: getAssociatedTokenAddressSync(game.mint, game.creator, true), isSigner: false, isWritable: true },
+      player1Destination,
+      player2Destination,
+      player1Stats,
+      player2Stats
+    );
+  });
+
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: discriminator('crank_expired'),
+  });
+}
@@ -15,6 +15,29 @@ export interface GameAccount {
   nonce: BN;
   bump: number;
   vaultBump: number;
+  bestOf: number;
+  player1Wins: number;
+  player2Wins: number;
+  sidePool: PublicKey;
+  winner: PublicKey;
+  player1Claimable: BN;
+  player2Claimable: BN;
+  feeClaimable: BN;
+  solVaultBump: number;
+  player1Asset: StakeAsset;
+  player2Asset: StakeAsset;
+  assetFee: BN;
+  nftsClaimed: number;
+  player1Basket: BasketLeg[];
+  player2Basket: BasketLeg[];
+  basketPaid: number;
+  basketFeePaid: number;
+  gameType: number;
+  minRating: number;
+  maxRating: number;
+  version: number; // layout version, see GAME_VERSION
+  rentPayer: PublicKey; // Gets the rent back when the game closes
+  crankBountyBps: number;
 
   // Helper methods
   isNativeSOL(): boolean;
@@ -26,7 +49,7 @@ export interface GameAccount {
 export enum GameState {
   Open = 0,
   Ready = 1,
-  Paid = 2,
+  Paid = 2,      // Resolved, winnings claimable
   Canceled = 3,
   Expired = 4,
 }
@@ -39,6 +62,104 @@ export interface CreateGameArgs {
   resolverPubkey?: PublicKey;
   devWallet: PublicKey;
   nonce?: BN;
+  bestOf?: number; // Odd number of rounds, defaults to 1 (single game)
+  player1Asset?: StakeAsset; // Defaults to the fungible wager
+  player2Asset?: StakeAsset; // Defaults to the fungible wager
+  assetFee?: BN; // Fee each side pays in `mint` when NFTs are staked
+  player1Basket?: BasketLeg[]; // Up to MAX_BASKET_LEGS extra token amounts
+  player2Basket?: BasketLeg[];
+  gameType?: number; // Ranked ladder id, 0 (default) for unranked
+  minRating?: number; // Rating band player2 must be in (ranked games)
+  maxRating?: number;
+  useBalance?: boolean; // Stake from the creator's PlayerBalance instead of their wallet
+  sessionOwner?: PublicKey; // Wallet the signer is a session key for (stakes from its balance)
+  payer?: PublicKey; // Relayer paying rent and fees, defaults to the creator
+  relayerFee?: BN; // Reimbursed to the payer from the creator's stake, in `mint`
+  crankBountyBps?: number; // Share of the game's rent paid to crank_expired, defaults to DEFAULT_CRANK_BOUNTY_BPS
+}
+
+export const MAX_SESSION_MINTS = 4;
+
+export interface ApproveSessionArgs {
+  sessionKey: PublicKey;
+  spendCap: BN; // Most the session may stake in total
+  allowedMints: PublicKey[]; // Up to MAX_SESSION_MINTS; PublicKey.default for native SOL
+  expiresAt: BN;
+  resolver: PublicKey; // Every game staked through the session must use this resolver
+  devWallet: PublicKey; // ... and this dev wallet
+  minPayoutBps: number; // ... and pay out at least this much
+}
+
+export const INITIAL_RATING = 1200;
+
+export interface RatingAccount {
+  wallet: PublicKey;
+  gameType: number;
+  rating: number;
+  games: number;
+}
+
+export interface PlayerBalanceAccount {
+  owner: PublicKey;
+  mint: PublicKey;
+  amount: BN; // Available to stake or withdraw
+}
+
+export const MAX_QUEUE_ENTRIES = 16;
+
+export interface MatchQueueAccount {
+  resolver: PublicKey;
+  devWallet: PublicKey;
+  mint: PublicKey;
+  wager: BN;
+  gameType: number;
+  payoutBps: number;
+  gameDuration: BN; // Seconds a matched game has before expiring
+  gamesMatched: BN; // Nonce of the next matched game
+  waiting: PublicKey[]; // Oldest first
+}
+
+export interface CreateQueueArgs {
+  mint: PublicKey;
+  wager: BN;
+  gameType?: number;
+  payoutBps: number;
+  gameDuration: BN;
+  devWallet: PublicKey;
+}
+
+export const MAX_BASKET_LEGS = 4;
+
+export interface BasketLeg {
+  mint: PublicKey;
+  amount: BN;
+}
+
+export enum Side {
+  Player1 = 0,
+  Player2 = 1,
+}
+
+// What `resolveMany` does with a game that fails validation
+export enum BatchPolicy {
+  Atomic = 0, // Fail the whole batch
+  SkipInvalid = 1, // Leave the game untouched and carry on
+}
+
+export interface BatchResolution {
+  gamePda: PublicKey;
+  winner: PublicKey;
+}
+
+export enum AssetKind {
+  Fungible = 0,
+  Nft = 1,
+}
+
+export interface StakeAsset {
+  kind: AssetKind;
+  mint?: PublicKey; // NFT mint; omit to accept any NFT (of the collection)
+  collection?: PublicKey; // Required verified Metaplex collection
 }
 
 export interface GamePDAs {
@@ -46,6 +167,8 @@ export interface GamePDAs {
   gameBump: number;
   vaultPda?: PublicKey;
   vaultBump?: number;
+  solVaultPda: PublicKey;
+  solVaultBump: number;
 }
 
 export interface PayoutAmounts {
@@ -76,14 +199,29 @@ export interface GameCreatedEvent {
   creator: PublicKey;
   mint: PublicKey;
   wager: BN;
+  amount: BN; // Deposited by the creator
   payoutBps: number;
   expiryTs: BN;
+  timestamp: BN;
 }
 
 export interface GameJoinedEvent {
   game: PublicKey;
   player1: PublicKey;
   player2: PublicKey;
+  mint: PublicKey;
+  amount: BN; // Deposited by player2
+  timestamp: BN;
+}
+
+export interface RoundReportedEvent {
+  game: PublicKey;
+  round: number;
+  winner: PublicKey;
+  player1Wins: number;
+  player2Wins: number;
+  mint: PublicKey;
+  timestamp: BN;
 }
 
 export interface GameResolvedEvent {
@@ -91,11 +229,183 @@ export interface GameResolvedEvent {
   winner: PublicKey;
   winnerAmount: BN;
   feeAmount: BN;
+  mint: PublicKey;
+  timestamp: BN;
+}
+
+export interface GameResolutionSkippedEvent {
+  game: PublicKey;
+  winner: PublicKey;
+  timestamp: BN;
+}
+
+export interface BatchResolvedEvent {
+  resolver: PublicKey;
+  resolved: number;
+  skipped: number;
+  timestamp: BN;
+}
+
+export interface ExpiredGamesCrankedEvent {
+  cranker: PublicKey;
+  closed: number;
+  skipped: number;
+  bounty: BN;
+  timestamp: BN;
+}
+
+export interface EscrowSweptEvent {
+  game: PublicKey;
+  creator: PublicKey;
+  mint: PublicKey;
+  lamports: BN; // Above the SOL vault's reserve
+  tokens: BN; // Left in the token vault, in `mint`
+  timestamp: BN;
+}
+
+export interface GameClaimedEvent {
+  game: PublicKey;
+  recipient: PublicKey;
+  amount: BN;
+  mint: PublicKey;
+  timestamp: BN;
+}
+
+export interface BasketLegClaimedEvent {
+  game: PublicKey;
+  recipient: PublicKey;
+  mint: PublicKey;
+  amount: BN;
+  timestamp: BN;
+}
+
+export interface NftClaimedEvent {
+  game: PublicKey;
+  nftMint: PublicKey;
+  recipient: PublicKey;
+  timestamp: BN;
+}
+
+export interface RatingsUpdatedEvent {
+  game: PublicKey;
+  gameType: number;
+  player1Rating: number;
+  player2Rating: number;
+  mint: PublicKey;
+  timestamp: BN;
+}
+
+export interface BalanceDepositedEvent {
+  owner: PublicKey;
+  mint: PublicKey;
+  amount: BN;
+  balance: BN;
+  timestamp: BN;
+}
+
+export interface BalanceWithdrawnEvent {
+  owner: PublicKey;
+  mint: PublicKey;
+  amount: BN;
+  balance: BN;
+  timestamp: BN;
+}
+
+export interface SessionApprovedEvent {
+  owner: PublicKey;
+  sessionKey: PublicKey;
+  spendCap: BN;
+  expiresAt: BN;
+  resolver: PublicKey;
+  devWallet: PublicKey;
+  minPayoutBps: number;
+  timestamp: BN;
+}
+
+export interface SessionRevokedEvent {
+  owner: PublicKey;
+  sessionKey: PublicKey;
+  timestamp: BN;
+}
+
+export interface PlayerQueuedEvent {
+  queue: PublicKey;
+  player: PublicKey;
+  waiting: number;
+  mint: PublicKey;
+  amount: BN;
+  timestamp: BN;
+}
+
+export interface PlayerDequeuedEvent {
+  queue: PublicKey;
+  player: PublicKey;
+  refund: BN;
+  mint: PublicKey;
+  timestamp: BN;
+}
+
+export interface RelayerReimbursedEvent {
+  game: PublicKey;
+  relayer: PublicKey;
+  fee: BN;
+  mint: PublicKey;
+  timestamp: BN;
+}
+
+export const GAME_VERSION = 3;
+
+export const DEFAULT_CRANK_BOUNTY_BPS = 1000;
+
+export interface GameMigratedEvent {
+  game: PublicKey;
+  fromVersion: number;
+  toVersion: number;
+  mint: PublicKey;
+  timestamp: BN;
+}
+
+// Why a game was canceled
+export enum CancelReason {
+  ExpiredOpen = 0, // Expired before a second player joined
+  ExpiredUnresolved = 1, // Expired with both players in but no result
+  Draw = 2, // Resolved as a draw
 }
 
 export interface GameCanceledEvent {
   game: PublicKey;
-  reason: string;
+  canceledBy: PublicKey;
+  reason: CancelReason;
+  mint: PublicKey;
+  player1Refund: BN;
+  player2Refund: BN;
+  timestamp: BN;
+}
+
+export interface ResolverUpdatedEvent {
+  game: PublicKey;
+  oldResolver: PublicKey;
+  newResolver: PublicKey;
+  mint: PublicKey;
+  timestamp: BN;
+}
+
+export interface PlayerStatsAccount {
+  wallet: PublicKey;
+  gamesPlayed: number;
+  wins: number;
+  losses: number;
+  canceled: number; // Refunded after both players staked; not in gamesPlayed
+  draws: number; // Resolved as a draw; in gamesPlayed
+  mintTotals: MintTotals[]; // First MAX_STATS_MINTS mints the wallet played with
+}
+
+export const MAX_STATS_MINTS = 8;
+
+export interface MintTotals {
+  mint: PublicKey; // PublicKey.default for native SOL
+  wagered: BN;
+  won: BN;
 }
 
 // SDK Client configuration
@@ -18,6 +18,7 @@ import BN from 'bn.js';
 import { GamePDAs, TokenInfo, PayoutAmounts } from './types';
 
 export const WAGER_PROGRAM_ID = new PublicKey('Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS');
+export const TOKEN_METADATA_PROGRAM_ID = new PublicKey('metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s');
 
 /**
  * Derive game and vault PDAs
@@ -55,14 +56,162 @@ export function deriveGamePDAs(
     vaultBump = bump;
   }
 
+  // System-owned vault escrowing native SOL
+  const [solVaultPda, solVaultBump] = PublicKey.findProgramAddressSync(
+    [Buffer.from('sol_vault'), gamePda.toBuffer()],
+    programId
+  );
+
   return {
     gamePda,
     gameBump,
     vaultPda,
     vaultBump,
+    solVaultPda,
+    solVaultBump,
   };
 }
 
+/**
+ * Derive the vault escrowing the NFT `staker` put into a game
+ */
+export function deriveNftVaultPDA(
+  gamePda: PublicKey,
+  staker: PublicKey,
+  programId: PublicKey = WAGER_PROGRAM_ID
+): PublicKey {
+  const [nftVault] = PublicKey.findProgramAddressSync(
+    [Buffer.from('nft_vault'), gamePda.toBuffer(), staker.toBuffer()],
+    programId
+  );
+  return nftVault;
+}
+
+/**
+ * Derive a wallet's lifetime stats account
+ */
+export function derivePlayerStatsPDA(
+  wallet: PublicKey,
+  programId: PublicKey = WAGER_PROGRAM_ID
+): PublicKey {
+  const [playerStats] = PublicKey.findProgramAddressSync(
+    [Buffer.from('player_stats'), wallet.toBuffer()],
+    programId
+  );
+  return playerStats;
+}
+
+/**
+ * Derive a wallet's rating account for a game type
+ */
+export function deriveRatingPDA(
+  wallet: PublicKey,
+  gameType: number,
+  programId: PublicKey = WAGER_PROGRAM_ID
+): PublicKey {
+  const gameTypeBytes = Buffer.alloc(4);
+  gameTypeBytes.writeUInt32LE(gameType);
+  const [rating] = PublicKey.findProgramAddressSync(
+    [Buffer.from('rating'), wallet.toBuffer(), gameTypeBytes],
+    programId
+  );
+  return rating;
+}
+
+/**
+ * Derive a wallet's pre-funded balance for a mint and the vault holding its
+ * tokens (native SOL is held on the balance account itself)
+ */
+export function derivePlayerBalancePDAs(
+  owner: PublicKey,
+  mint: PublicKey,
+  programId: PublicKey = WAGER_PROGRAM_ID
+): { balancePda: PublicKey; balanceVaultPda: PublicKey } {
+  const [balancePda] = PublicKey.findProgramAddressSync(
+    [Buffer.from('balance'), owner.toBuffer(), mint.toBuffer()],
+    programId
+  );
+  const [balanceVaultPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from('balance_vault'), owner.toBuffer(), mint.toBuffer()],
+    programId
+  );
+  return { balancePda, balanceVaultPda };
+}
+
+/**
+ * Derive the approval letting `sessionKey` sign for `owner`
+ */
+export function deriveSessionPDA(
+  owner: PublicKey,
+  sessionKey: PublicKey,
+  programId: PublicKey = WAGER_PROGRAM_ID
+): PublicKey {
+  const [session] = PublicKey.findProgramAddressSync(
+    [Buffer.from('session'), owner.toBuffer(), sessionKey.toBuffer()],
+    programId
+  );
+  return session;
+}
+
+/**
+ * Derive the authority that signs the program's `emit_cpi!` self-invocations
+ */
+export function deriveEventAuthorityPDA(programId: PublicKey = WAGER_PROGRAM_ID): PublicKey {
+  const [eventAuthority] = PublicKey.findProgramAddressSync([Buffer.from('__event_authority')], programId);
+  return eventAuthority;
+}
+
+/**
+ * Derive the matchmaking queue a resolver runs for (mint, wager, game type)
+ */
+export function deriveQueuePDA(
+  resolver: PublicKey,
+  mint: PublicKey,
+  wager: BN,
+  gameType: number,
+  programId: PublicKey = WAGER_PROGRAM_ID
+): PublicKey {
+  const gameTypeBytes = Buffer.alloc(4);
+  gameTypeBytes.writeUInt32LE(gameType);
+  const [queue] = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from('queue'),
+      resolver.toBuffer(),
+      mint.toBuffer(),
+      wager.toArrayLike(Buffer, 'le', 8),
+      gameTypeBytes,
+    ],
+    programId
+  );
+  return queue;
+}
+
+/**
+ * Derive the per-mint vault escrowing basket legs of a game
+ */
+export function deriveBasketVaultPDA(
+  gamePda: PublicKey,
+  mint: PublicKey,
+  programId: PublicKey = WAGER_PROGRAM_ID
+): PublicKey {
+  const [basketVault] = PublicKey.findProgramAddressSync(
+    [Buffer.from('basket_vault'), gamePda.toBuffer(), mint.toBuffer()],
+    programId
+  );
+  return basketVault;
+}
+
+/**
+ * Derive the Metaplex metadata account of a mint
+ */
+export function deriveMetadataPDA(mint: PublicKey): PublicKey {
+  const [metadata] = PublicKey.findProgramAddressSync(
+    [Buffer.from('metadata'), TOKEN_METADATA_PROGRAM_ID.toBuffer(), mint.toBuffer()],
+    TOKEN_METADATA_PROGRAM_ID
+  );
+  return metadata;
+}
+
 /**
  * Check if a mint is native SOL
  */
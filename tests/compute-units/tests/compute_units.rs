@@ -0,0 +1,313 @@
+//! Compute units used by each instruction of a native SOL game, compared
+//! against `baseline.json`. Build the program first (`anchor build`), then:
+//!
+//!     cargo test --manifest-path tests/compute-units/Cargo.toml -- --nocapture
+//!
+//! Set `UPDATE_CU_BASELINE=1` to record the current numbers as the baseline.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::{system_program, InstructionData, ToAccountMetas};
+use easy_wager_on_sol::state::{CreateGameArgs, Game};
+use easy_wager_on_sol::{accounts, instruction};
+use litesvm::LiteSVM;
+use serde::{Deserialize, Serialize};
+use solana_sdk::clock::Clock;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::Transaction;
+
+const PROGRAM_ID: Pubkey = easy_wager_on_sol::ID;
+const WAGER: u64 = 1_000_000_000;
+
+// Allowed growth over the baseline before the benchmark fails
+const TOLERANCE_PCT: u64 = 5;
+
+struct Bench {
+    svm: LiteSVM,
+    creator: Keypair,
+    player2: Keypair,
+    dev_wallet: Pubkey,
+    results: BTreeMap<String, u64>,
+}
+
+impl Bench {
+    fn new() -> Self {
+        let program = manifest_dir().join("../../target/deploy/easy_wager_on_sol.so");
+        let mut svm = LiteSVM::new();
+        svm.add_program_from_file(PROGRAM_ID, &program)
+            .unwrap_or_else(|e| panic!("{} not found, run `anchor build` first: {e}", program.display()));
+
+        let creator = Keypair::new();
+        let player2 = Keypair::new();
+        svm.airdrop(&creator.pubkey(), 100 * WAGER).unwrap();
+        svm.airdrop(&player2.pubkey(), 100 * WAGER).unwrap();
+
+        Self { svm, creator, player2, dev_wallet: Pubkey::new_unique(), results: BTreeMap::new() }
+    }
+
+    /// Sends `ix` signed by `signer` and records its compute units under `name`.
+    fn send(&mut self, name: &str, ix: Instruction, signer: &Keypair) {
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&signer.pubkey()),
+            &[signer],
+            self.svm.latest_blockhash(),
+        );
+        let meta = self.svm.send_transaction(tx)
+            .unwrap_or_else(|e| panic!("{name} failed: {:?}\n{:#?}", e.err, e.meta.logs));
+        self.results.insert(name.to_string(), meta.compute_units_consumed);
+        self.svm.expire_blockhash();
+    }
+
+    fn now(&self) -> i64 {
+        self.svm.get_sysvar::<Clock>().unix_timestamp
+    }
+
+    fn warp_to(&mut self, unix_timestamp: i64) {
+        let mut clock = self.svm.get_sysvar::<Clock>();
+        clock.unix_timestamp = unix_timestamp;
+        self.svm.set_sysvar(&clock);
+    }
+
+    fn create_game(&mut self, name: &str, nonce: u64, expiry_ts: i64) -> Pubkey {
+        let creator = self.creator.pubkey();
+        let game = find_pda(&[b"game", creator.as_ref(), &nonce.to_le_bytes()]);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: accounts::CreateGame {
+                creator,
+                payer: creator,
+                relayer_token_account: None,
+                game,
+                vault: None,
+                creator_stats: find_pda(&[b"player_stats", creator.as_ref()]),
+                creator_rating: None,
+                sol_vault: find_pda(&[b"sol_vault", game.as_ref()]),
+                token_mint: None,
+                creator_token_account: None,
+                creator_balance: None,
+                creator_balance_vault: None,
+                nft_mint: None,
+                creator_nft_account: None,
+                nft_vault: None,
+                nft_metadata: None,
+                dev_wallet: self.dev_wallet,
+                session: None,
+                token_program: anchor_spl::token::ID,
+                system_program: system_program::ID,
+                rent: anchor_lang::solana_program::sysvar::rent::ID,
+                event_authority: find_pda(&[b"__event_authority"]),
+                program: PROGRAM_ID,
+            }
+            .to_account_metas(None),
+            data: instruction::CreateGame {
+                args: CreateGameArgs {
+                    mint: Pubkey::default(),
+                    wager: WAGER,
+                    payout_bps: 9_500,
+                    expiry_ts,
+                    resolver: None,
+                    nonce,
+                    best_of: 1,
+                    player1_asset: Default::default(),
+                    player2_asset: Default::default(),
+                    asset_fee: 0,
+                    player1_basket: Default::default(),
+                    player2_basket: Default::default(),
+                    game_type: 0,
+                    min_rating: 0,
+                    max_rating: 0,
+                    relayer_fee: 0,
+                    crank_bounty_bps: Game::DEFAULT_CRANK_BOUNTY_BPS,
+                },
+            }
+            .data(),
+        };
+        let creator = self.creator.insecure_clone();
+        self.send(name, ix, &creator);
+        game
+    }
+
+    fn join_game(&mut self, game: Pubkey) {
+        let player2 = self.player2.pubkey();
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: accounts::JoinGame {
+                player2,
+                payer: player2,
+                relayer_token_account: None,
+                game,
+                player2_token_account: None,
+                player2_balance: None,
+                player2_balance_vault: None,
+                vault: None,
+                nft_mint: None,
+                player2_nft_account: None,
+                nft_vault: None,
+                nft_metadata: None,
+                player2_stats: find_pda(&[b"player_stats", player2.as_ref()]),
+                player2_rating: None,
+                sol_vault: find_pda(&[b"sol_vault", game.as_ref()]),
+                session: None,
+                token_program: anchor_spl::token::ID,
+                system_program: system_program::ID,
+                event_authority: find_pda(&[b"__event_authority"]),
+                program: PROGRAM_ID,
+            }
+            .to_account_metas(None),
+            data: instruction::JoinGame { relayer_fee: 0 }.data(),
+        };
+        let player2 = self.player2.insecure_clone();
+        self.send("join_game", ix, &player2);
+    }
+
+    fn resolve_game(&mut self, game: Pubkey, winner: Pubkey) {
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: accounts::ResolveGame {
+                resolver: self.creator.pubkey(),
+                game,
+                side_pool: None,
+                player1_stats: find_pda(&[b"player_stats", self.creator.pubkey().as_ref()]),
+                player2_stats: find_pda(&[b"player_stats", self.player2.pubkey().as_ref()]),
+                player1_rating: None,
+                player2_rating: None,
+                event_authority: find_pda(&[b"__event_authority"]),
+                program: PROGRAM_ID,
+            }
+            .to_account_metas(None),
+            data: instruction::ResolveGame { winner }.data(),
+        };
+        let creator = self.creator.insecure_clone();
+        self.send("resolve_game", ix, &creator);
+    }
+
+    fn claim(&mut self, name: &str, game: Pubkey, recipient: Pubkey) {
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: accounts::Claim {
+                claimer: self.creator.pubkey(),
+                game,
+                recipient_token_account: None,
+                token_mint: None,
+                vault: None,
+                sol_vault: find_pda(&[b"sol_vault", game.as_ref()]),
+                unwrap_account: None,
+                recipient_account: recipient,
+                recipient_balance: None,
+                recipient_balance_vault: None,
+                token_program: anchor_spl::token::ID,
+                associated_token_program: anchor_spl::associated_token::ID,
+                system_program: system_program::ID,
+                event_authority: find_pda(&[b"__event_authority"]),
+                program: PROGRAM_ID,
+            }
+            .to_account_metas(None),
+            data: instruction::Claim { recipient }.data(),
+        };
+        let creator = self.creator.insecure_clone();
+        self.send(name, ix, &creator);
+    }
+
+    fn cancel_if_expired(&mut self, game: Pubkey) {
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: accounts::CancelGame {
+                authority: self.creator.pubkey(),
+                game,
+                side_pool: None,
+                player1_stats: None,
+                player2_stats: None,
+                event_authority: find_pda(&[b"__event_authority"]),
+                program: PROGRAM_ID,
+            }
+            .to_account_metas(None),
+            data: instruction::CancelIfExpired {}.data(),
+        };
+        let creator = self.creator.insecure_clone();
+        self.send("cancel_if_expired", ix, &creator);
+    }
+}
+
+fn manifest_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+}
+
+fn find_pda(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &PROGRAM_ID).0
+}
+
+#[test]
+fn compute_units_per_instruction() {
+    let mut bench = Bench::new();
+    let expiry_ts = bench.now() + 3_600;
+
+    // Full lifecycle: create, join, resolve, then both sides claim
+    let game = bench.create_game("create_game", 0, expiry_ts);
+    bench.join_game(game);
+    let winner = bench.creator.pubkey();
+    bench.resolve_game(game, winner);
+    bench.claim("claim", game, winner);
+
+    // A second game left open until it expires, then refunded
+    let expired = bench.create_game("create_game_second", 1, expiry_ts);
+    bench.warp_to(expiry_ts + 1);
+    bench.cancel_if_expired(expired);
+    bench.claim("claim_refund", expired, winner);
+
+    report(&bench.results);
+}
+
+/// `baseline.json`: the numbers the program used while `Game` was a Borsh
+/// account, kept for comparison, and the zero-copy numbers regressions are
+/// measured against.
+#[derive(Default, Serialize, Deserialize)]
+struct Baseline {
+    #[serde(default)]
+    borsh: BTreeMap<String, u64>,
+    #[serde(default)]
+    zero_copy: BTreeMap<String, u64>,
+}
+
+/// Prints the numbers next to the baseline and fails on regressions beyond
+/// `TOLERANCE_PCT`. Instructions missing from the baseline are only reported.
+fn report(results: &BTreeMap<String, u64>) {
+    let path = manifest_dir().join("baseline.json");
+    let mut baseline: Baseline = std::fs::read_to_string(&path)
+        .ok()
+        .map(|json| serde_json::from_str(&json).expect("baseline.json is malformed"))
+        .unwrap_or_default();
+
+    if std::env::var_os("UPDATE_CU_BASELINE").is_some() {
+        baseline.zero_copy = results.clone();
+        let json = serde_json::to_string_pretty(&baseline).unwrap();
+        std::fs::write(&path, json + "\n").unwrap();
+        println!("Baseline written to {}", path.display());
+    }
+
+    let mut regressions = Vec::new();
+    println!("{:<20} {:>10} {:>10} {:>10} {:>8}", "instruction", "borsh", "baseline", "current", "delta");
+    for (name, &current) in results {
+        let borsh = baseline.borsh.get(name).map_or("-".to_string(), u64::to_string);
+        match baseline.zero_copy.get(name) {
+            Some(&before) => {
+                let delta = current as i64 - before as i64;
+                println!("{name:<20} {borsh:>10} {before:>10} {current:>10} {delta:>+8}");
+                if current * 100 > before * (100 + TOLERANCE_PCT) {
+                    regressions.push(format!("{name}: {before} -> {current}"));
+                }
+            }
+            None => println!("{name:<20} {borsh:>10} {:>10} {current:>10} {:>8}", "-", "-"),
+        }
+    }
+
+    assert!(
+        regressions.is_empty(),
+        "compute units grew more than {TOLERANCE_PCT}% (rerun with UPDATE_CU_BASELINE=1 if expected):\n{}",
+        regressions.join("\n")
+    );
+}
@@ -0,0 +1,176 @@
+//! LiteSVM with easy_wager_on_sol loaded, shared by the program, client, CLI
+//! and indexer tests.
+//!
+//! The program built by `anchor build` (`target/deploy`, or `SBF_OUT_DIR`) is
+//! loaded when present, so the tests run the real SBF binary. Otherwise the
+//! program runs natively as a builtin: behaviour is the same, but compute
+//! units aren't meaningful, so the compute-unit benchmark always needs the
+//! SBF build.
+
+mod native;
+
+use std::path::PathBuf;
+
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::AccountDeserialize;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::associated_token::{get_associated_token_address, spl_associated_token_account};
+use anchor_spl::token::spl_token;
+use easy_wager_on_sol::error::WagerError;
+use litesvm::types::{FailedTransactionMetadata, TransactionMetadata};
+use litesvm::LiteSVM;
+use solana_sdk::account::Account;
+use solana_sdk::clock::Clock;
+use solana_sdk::instruction::InstructionError;
+use solana_sdk::native_loader;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::{Transaction, TransactionError};
+
+pub use litesvm;
+
+pub const PROGRAM_ID: Pubkey = easy_wager_on_sol::ID;
+
+/// A fresh LiteSVM with the program deployed.
+pub fn svm() -> LiteSVM {
+    let mut svm = LiteSVM::new();
+    match sbf_program() {
+        Some(path) => svm.add_program_from_file(PROGRAM_ID, &path).unwrap(),
+        None => {
+            svm.add_builtin(PROGRAM_ID, native::builtin());
+            // The runtime only dispatches to builtins owned by the native loader
+            let mut program = svm.get_account(&PROGRAM_ID).unwrap();
+            program.owner = native_loader::ID;
+            svm.set_account(PROGRAM_ID, program).unwrap();
+        }
+    }
+    svm
+}
+
+fn sbf_program() -> Option<PathBuf> {
+    let dir = std::env::var_os("SBF_OUT_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../target/deploy"));
+    let path = dir.join("easy_wager_on_sol.so");
+    path.exists().then_some(path)
+}
+
+/// A new keypair holding `lamports`.
+pub fn funded(svm: &mut LiteSVM, lamports: u64) -> Keypair {
+    let keypair = Keypair::new();
+    svm.airdrop(&keypair.pubkey(), lamports).unwrap();
+    keypair
+}
+
+/// Sends `instructions` paid by `payer` and also signed by `signers`. The
+/// blockhash is expired afterwards so identical transactions can be resent.
+pub fn send(svm: &mut LiteSVM, instructions: &[Instruction], payer: &Keypair, signers: &[&Keypair]) -> Result<TransactionMetadata, Box<FailedTransactionMetadata>> {
+    let mut all = vec![payer];
+    all.extend(signers.iter().filter(|signer| signer.pubkey() != payer.pubkey()));
+    let tx = Transaction::new_signed_with_payer(instructions, Some(&payer.pubkey()), &all, svm.latest_blockhash());
+    let result = svm.send_transaction(tx);
+    svm.expire_blockhash();
+    result.map_err(Box::new)
+}
+
+/// Like `send`, panicking with the program logs if the transaction fails.
+pub fn send_ok(svm: &mut LiteSVM, instructions: &[Instruction], payer: &Keypair, signers: &[&Keypair]) -> Vec<String> {
+    match send(svm, instructions, payer, signers) {
+        Ok(meta) => meta.logs,
+        Err(failed) => panic!("transaction failed: {:?}\n{:#?}", failed.err, failed.meta.logs),
+    }
+}
+
+/// Like `send`, asserting the transaction fails with the program's `error`.
+pub fn send_err(svm: &mut LiteSVM, instructions: &[Instruction], payer: &Keypair, signers: &[&Keypair], error: WagerError) {
+    let expected = u32::from(error);
+    match send(svm, instructions, payer, signers) {
+        Ok(meta) => panic!("transaction succeeded, expected error {expected}\n{:#?}", meta.logs),
+        Err(failed) => match failed.err {
+            TransactionError::InstructionError(_, InstructionError::Custom(code)) if code == expected => {}
+            err => panic!("expected error {expected}, got {err:?}\n{:#?}", failed.meta.logs),
+        },
+    }
+}
+
+pub fn find_pda(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &PROGRAM_ID).0
+}
+
+pub fn now(svm: &LiteSVM) -> i64 {
+    svm.get_sysvar::<Clock>().unix_timestamp
+}
+
+pub fn warp_to(svm: &mut LiteSVM, unix_timestamp: i64) {
+    let mut clock = svm.get_sysvar::<Clock>();
+    clock.unix_timestamp = unix_timestamp;
+    svm.set_sysvar(&clock);
+}
+
+pub fn lamports(svm: &LiteSVM, address: &Pubkey) -> u64 {
+    svm.get_account(address).map_or(0, |account| account.lamports)
+}
+
+/// Creates an SPL mint with `authority` as its mint authority and no freeze
+/// authority.
+pub fn create_mint(svm: &mut LiteSVM, authority: &Pubkey, decimals: u8) -> Pubkey {
+    let mint = Pubkey::new_unique();
+    set_mint(svm, mint, COption::Some(*authority), decimals);
+    mint
+}
+
+/// Writes the wrapped SOL mint, which LiteSVM doesn't ship with.
+pub fn native_mint(svm: &mut LiteSVM) -> Pubkey {
+    let mint = spl_token::native_mint::ID;
+    set_mint(svm, mint, COption::None, spl_token::native_mint::DECIMALS);
+    mint
+}
+
+fn set_mint(svm: &mut LiteSVM, mint: Pubkey, mint_authority: COption<Pubkey>, decimals: u8) {
+    let state = spl_token::state::Mint {
+        mint_authority,
+        supply: 0,
+        decimals,
+        is_initialized: true,
+        freeze_authority: COption::None,
+    };
+    let mut data = vec![0; spl_token::state::Mint::LEN];
+    state.pack_into_slice(&mut data);
+    let account = Account {
+        lamports: svm.minimum_balance_for_rent_exemption(data.len()),
+        data,
+        owner: spl_token::ID,
+        executable: false,
+        rent_epoch: 0,
+    };
+    svm.set_account(mint, account).unwrap();
+}
+
+/// Creates `owner`'s associated token account for `mint`.
+pub fn create_ata(svm: &mut LiteSVM, payer: &Keypair, owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    let ix = spl_associated_token_account::instruction::create_associated_token_account(
+        &payer.pubkey(),
+        owner,
+        mint,
+        &spl_token::ID,
+    );
+    send_ok(svm, &[ix], payer, &[]);
+    get_associated_token_address(owner, mint)
+}
+
+pub fn mint_to(svm: &mut LiteSVM, mint: &Pubkey, authority: &Keypair, destination: &Pubkey, amount: u64) {
+    let ix = spl_token::instruction::mint_to(&spl_token::ID, mint, destination, &authority.pubkey(), &[], amount).unwrap();
+    send_ok(svm, &[ix], authority, &[]);
+}
+
+pub fn token_balance(svm: &LiteSVM, account: &Pubkey) -> u64 {
+    svm.get_account(account)
+        .map_or(0, |account| spl_token::state::Account::unpack(&account.data).unwrap().amount)
+}
+
+/// Deserializes an Anchor (Borsh) account, checking its discriminator.
+pub fn account<T: AccountDeserialize>(svm: &LiteSVM, address: &Pubkey) -> T {
+    let account = svm.get_account(address).unwrap_or_else(|| panic!("{address} doesn't exist"));
+    T::try_deserialize(&mut account.data.as_slice()).unwrap()
+}
@@ -0,0 +1,250 @@
+//! Runs the program as a native LiteSVM builtin, for machines without the SBF
+//! toolchain. The program is handed the same serialized accounts a validator
+//! would pass, and its syscalls (CPI, sysvars, logs) are routed back into the
+//! runtime through `SyscallStubs`.
+//!
+//! Adapted from `solana-program-test`, which does the same for `BanksClient`.
+
+use std::cell::Cell;
+use std::collections::{HashMap, HashSet};
+use std::panic::AssertUnwindSafe;
+use std::sync::{Arc, Once};
+
+use anchor_lang::solana_program::account_info::AccountInfo;
+use anchor_lang::solana_program::entrypoint::{deserialize, ProgramResult, SUCCESS};
+use anchor_lang::solana_program::instruction::{Instruction, InstructionError};
+use anchor_lang::solana_program::program_error::{ProgramError, UNSUPPORTED_SYSVAR};
+use anchor_lang::solana_program::program_stubs::{self, SyscallStubs};
+use anchor_lang::solana_program::pubkey::Pubkey;
+use anchor_lang::solana_program::stable_layout::stable_instruction::StableInstruction;
+use anchor_lang::solana_program::sysvar::Sysvar;
+use solana_program_runtime::invoke_context::InvokeContext;
+use solana_program_runtime::serialization::serialize_parameters;
+use solana_program_runtime::{declare_process_instruction, stable_log};
+use solana_timings::ExecuteTimings;
+
+type Entrypoint = for<'a, 'info> fn(&'a Pubkey, &'info [AccountInfo<'info>], &'a [u8]) -> ProgramResult;
+
+const ENTRYPOINT: Entrypoint = easy_wager_on_sol::entry;
+
+declare_process_instruction!(Builtin, 1, |invoke_context| {
+    static STUBS: Once = Once::new();
+    STUBS.call_once(|| {
+        program_stubs::set_syscall_stubs(Box::new(Stubs));
+    });
+    process(invoke_context)
+});
+
+pub(crate) fn builtin() -> solana_program_runtime::invoke_context::BuiltinFunctionWithContext {
+    Builtin::vm
+}
+
+thread_local! {
+    static INVOKE_CONTEXT: Cell<*mut InvokeContext<'static>> = const { Cell::new(std::ptr::null_mut()) };
+}
+
+fn set_invoke_context(invoke_context: &mut InvokeContext) {
+    INVOKE_CONTEXT.set((invoke_context as *mut InvokeContext).cast());
+}
+
+fn invoke_context<'a>() -> &'a mut InvokeContext<'static> {
+    let ptr = INVOKE_CONTEXT.get();
+    assert!(!ptr.is_null(), "no instruction is executing");
+    // SAFETY: set by `process` for the duration of the (single-threaded)
+    // instruction, and refreshed after nested CPIs return here
+    unsafe { &mut *ptr }
+}
+
+fn process(invoke_context: &mut InvokeContext) -> Result<(), InstructionError> {
+    set_invoke_context(invoke_context);
+
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+    let indices: HashSet<_> = (0..instruction_context.get_number_of_instruction_accounts()).collect();
+
+    let mask_out_rent_epoch = invoke_context.get_feature_set().mask_out_rent_epoch_in_vm_serialization;
+    let (mut parameters, _, _) =
+        serialize_parameters(transaction_context, instruction_context, true, mask_out_rent_epoch)?;
+    // SAFETY: `parameters` is laid out exactly as the SBF loader would lay it out
+    let (program_id, account_infos, data) = unsafe { deserialize(&mut parameters.as_slice_mut()[0] as *mut u8) };
+
+    match std::panic::catch_unwind(AssertUnwindSafe(|| ENTRYPOINT(program_id, &account_infos, data))) {
+        Ok(result) => result.map_err(|err| InstructionError::from(u64::from(err)))?,
+        Err(_) => return Err(InstructionError::ProgramFailedToComplete),
+    }
+
+    // Write the program's changes back; a CPI may have replaced the context
+    let by_key: HashMap<_, _> = account_infos.iter().map(|info| (info.key, info)).collect();
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+    for index in indices {
+        let mut account = instruction_context.try_borrow_instruction_account(transaction_context, index)?;
+        if !account.is_writable() {
+            continue;
+        }
+        let Some(info) = by_key.get(account.get_key()) else { continue };
+        if account.get_lamports() != info.lamports() {
+            account.set_lamports(info.lamports())?;
+        }
+        if account.can_data_be_resized(info.data_len()).is_ok() {
+            account.set_data_from_slice(&info.data.borrow())?;
+        }
+        if account.get_owner() != info.owner {
+            account.set_owner(info.owner.as_ref())?;
+        }
+    }
+    Ok(())
+}
+
+fn get_sysvar<T: Sysvar + Clone>(sysvar: Result<Arc<T>, InstructionError>, var_addr: *mut u8) -> u64 {
+    match sysvar {
+        // SAFETY: the program passes a pointer to a `T` it owns
+        Ok(sysvar) => unsafe {
+            *(var_addr as *mut T) = T::clone(&sysvar);
+            SUCCESS
+        },
+        Err(_) => UNSUPPORTED_SYSVAR,
+    }
+}
+
+struct Stubs;
+
+impl SyscallStubs for Stubs {
+    fn sol_log(&self, message: &str) {
+        stable_log::program_log(&invoke_context().get_log_collector(), message);
+    }
+
+    fn sol_log_data(&self, fields: &[&[u8]]) {
+        stable_log::program_data(&invoke_context().get_log_collector(), fields);
+    }
+
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let instruction = StableInstruction::from(instruction.clone());
+        let invoke_context = invoke_context();
+        let transaction_context = &invoke_context.transaction_context;
+        let instruction_context = transaction_context.get_current_instruction_context().unwrap();
+        let caller = *instruction_context.get_last_program_key(transaction_context).unwrap();
+
+        let signers = signers_seeds
+            .iter()
+            .map(|seeds| Pubkey::create_program_address(seeds, &caller).unwrap())
+            .collect::<Vec<_>>();
+        let (instruction_accounts, program_indices) =
+            invoke_context.prepare_instruction(&instruction, &signers).map_err(to_program_error)?;
+
+        // Hand the caller's in-flight changes to the callee
+        let transaction_context = &invoke_context.transaction_context;
+        let instruction_context = transaction_context.get_current_instruction_context().unwrap();
+        let mut writable = Vec::with_capacity(instruction_accounts.len());
+        for instruction_account in &instruction_accounts {
+            let key = transaction_context
+                .get_key_of_account_at_index(instruction_account.index_in_transaction)
+                .unwrap();
+            let info_index = account_infos
+                .iter()
+                .position(|info| info.key == key)
+                .ok_or(ProgramError::NotEnoughAccountKeys)?;
+            let info = &account_infos[info_index];
+            let mut account = instruction_context
+                .try_borrow_instruction_account(transaction_context, instruction_account.index_in_caller)
+                .unwrap();
+            if account.get_lamports() != info.lamports() {
+                account.set_lamports(info.lamports()).map_err(to_program_error)?;
+            }
+            let data = info.try_borrow_data()?;
+            match account.can_data_be_resized(data.len()) {
+                Ok(()) => account.set_data_from_slice(&data).map_err(to_program_error)?,
+                Err(err) if account.get_data() != *data => return Err(to_program_error(err)),
+                Err(_) => {}
+            }
+            if account.get_owner() != info.owner {
+                account.set_owner(info.owner.as_ref()).map_err(to_program_error)?;
+            }
+            if instruction_account.is_writable {
+                writable.push((instruction_account.index_in_caller, info_index));
+            }
+        }
+
+        invoke_context
+            .process_instruction(
+                &instruction.data,
+                &instruction_accounts,
+                &program_indices,
+                &mut 0,
+                &mut ExecuteTimings::default(),
+            )
+            .map_err(to_program_error)?;
+        // The callee may have been this program, which replaced the context
+        set_invoke_context(invoke_context);
+
+        // And hand the callee's changes back to the caller
+        let transaction_context = &invoke_context.transaction_context;
+        let instruction_context = transaction_context.get_current_instruction_context().unwrap();
+        for (index_in_caller, info_index) in writable {
+            let account = instruction_context
+                .try_borrow_instruction_account(transaction_context, index_in_caller)
+                .unwrap();
+            let info = &account_infos[info_index];
+            **info.try_borrow_mut_lamports()? = account.get_lamports();
+            if info.owner != account.get_owner() {
+                // SAFETY: nothing else holds the owner while the CPI returns,
+                // which is how the SBF runtime updates it too
+                #[allow(invalid_reference_casting)]
+                unsafe {
+                    *(info.owner as *const Pubkey as *mut Pubkey) = *account.get_owner();
+                }
+            }
+            let new_data = account.get_data();
+            if info.data_len() != new_data.len() {
+                info.resize(new_data.len())?;
+            }
+            info.try_borrow_mut_data()?.clone_from_slice(new_data);
+        }
+        Ok(())
+    }
+
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        get_sysvar(invoke_context().get_sysvar_cache().get_clock(), var_addr)
+    }
+
+    fn sol_get_epoch_schedule_sysvar(&self, var_addr: *mut u8) -> u64 {
+        get_sysvar(invoke_context().get_sysvar_cache().get_epoch_schedule(), var_addr)
+    }
+
+    fn sol_get_epoch_rewards_sysvar(&self, var_addr: *mut u8) -> u64 {
+        get_sysvar(invoke_context().get_sysvar_cache().get_epoch_rewards(), var_addr)
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        get_sysvar(invoke_context().get_sysvar_cache().get_rent(), var_addr)
+    }
+
+    fn sol_get_last_restart_slot(&self, var_addr: *mut u8) -> u64 {
+        get_sysvar(invoke_context().get_sysvar_cache().get_last_restart_slot(), var_addr)
+    }
+
+    fn sol_get_return_data(&self) -> Option<(Pubkey, Vec<u8>)> {
+        let (program_id, data) = invoke_context().transaction_context.get_return_data();
+        Some((*program_id, data.to_vec()))
+    }
+
+    fn sol_set_return_data(&self, data: &[u8]) {
+        let transaction_context = &mut invoke_context().transaction_context;
+        let instruction_context = transaction_context.get_current_instruction_context().unwrap();
+        let caller = *instruction_context.get_last_program_key(transaction_context).unwrap();
+        transaction_context.set_return_data(caller, data.to_vec()).unwrap();
+    }
+
+    fn sol_get_stack_height(&self) -> u64 {
+        invoke_context().get_stack_height() as u64
+    }
+}
+
+fn to_program_error(err: InstructionError) -> ProgramError {
+    ProgramError::try_from(err).unwrap_or_else(|err| panic!("{err}"))
+}
@@ -0,0 +1,155 @@
+//! `resolve_with_attestation`: settling a game with the resolver's signature
+//! verified by an Ed25519 precompile instruction earlier in the transaction.
+
+use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+use easy_wager_client::instructions::*;
+use easy_wager_client::state::{Game, GameState};
+use easy_wager_client::{decode_game, WagerError};
+use easy_wager_harness::litesvm::LiteSVM;
+use easy_wager_harness::*;
+use solana_sdk::ed25519_instruction::new_ed25519_instruction_with_signature;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+const SOL: Pubkey = Pubkey::new_from_array([0; 32]);
+const WAGER: u64 = LAMPORTS_PER_SOL;
+const PAYOUT: u64 = 2 * WAGER * 9_500 / 10_000;
+
+// Where `new_ed25519_instruction_with_signature` lays out its one signature:
+// the header and offsets, then the public key, signature and message
+const PUBKEY_OFFSET: usize = 16;
+const SIGNATURE_OFFSET: usize = PUBKEY_OFFSET + 32;
+const MESSAGE_OFFSET: usize = SIGNATURE_OFFSET + 64;
+
+struct Setup {
+    svm: LiteSVM,
+    resolver: Keypair,
+    submitter: Keypair,
+    player1: Keypair,
+    player2: Pubkey,
+    game: Pubkey,
+}
+
+/// A ready native SOL game, and a submitter other than the resolver.
+fn setup() -> Setup {
+    let mut svm = svm();
+    let resolver = funded(&mut svm, LAMPORTS_PER_SOL);
+    let submitter = funded(&mut svm, LAMPORTS_PER_SOL);
+    let player1 = funded(&mut svm, 10 * LAMPORTS_PER_SOL);
+    let player2 = funded(&mut svm, 10 * LAMPORTS_PER_SOL);
+
+    let mut args = CreateGameArgs::new(player1.pubkey(), SOL, WAGER, 9_500, now(&svm) + 600, 7, Pubkey::new_unique());
+    args.resolver = Some(resolver.pubkey());
+    send_ok(&mut svm, &[create_game(&args)], &player1, &[]);
+    let game = args.game();
+    let state = game_state(&svm, &game);
+    send_ok(&mut svm, &[join_game(&game, &state, &JoinGameArgs::new(player2.pubkey()))], &player2, &[]);
+
+    Setup { svm, resolver, submitter, player1, player2: player2.pubkey(), game }
+}
+
+fn game_state(svm: &LiteSVM, game: &Pubkey) -> Game {
+    decode_game(&svm.get_account(game).unwrap().data).unwrap()
+}
+
+/// What the resolver signs: game || winner || nonce (LE).
+fn message(game: &Pubkey, winner: &Pubkey, nonce: u64) -> Vec<u8> {
+    [game.as_ref(), winner.as_ref(), &nonce.to_le_bytes()].concat()
+}
+
+fn ed25519(signer: &Keypair, message: &[u8]) -> Instruction {
+    let signature = <[u8; 64]>::from(signer.sign_message(message));
+    new_ed25519_instruction_with_signature(message, &signature, &signer.pubkey().to_bytes())
+}
+
+fn resolve(setup: &Setup, winner: &Pubkey) -> Instruction {
+    let state = game_state(&setup.svm, &setup.game);
+    resolve_with_attestation(&setup.submitter.pubkey(), &setup.game, &state, winner)
+}
+
+fn submit(setup: &mut Setup, instructions: &[Instruction], error: WagerError) {
+    send_err(&mut setup.svm, instructions, &setup.submitter, &[], error);
+    assert!(game_state(&setup.svm, &setup.game).state().unwrap() == GameState::Ready);
+}
+
+#[test]
+fn valid_attestation_pays_the_winner() {
+    let mut setup = setup();
+    let winner = setup.player1.pubkey();
+    let signed = ed25519(&setup.resolver, &message(&setup.game, &winner, 7));
+    let ix = resolve(&setup, &winner);
+    send_ok(&mut setup.svm, &[signed, ix], &setup.submitter, &[]);
+
+    let state = game_state(&setup.svm, &setup.game);
+    assert!(state.state().unwrap() == GameState::Paid);
+    assert_eq!(state.winner, winner);
+    let before = lamports(&setup.svm, &winner);
+    let ix = claim(&setup.submitter.pubkey(), &setup.game, &state, &winner, ClaimTo::Wallet);
+    send_ok(&mut setup.svm, &[ix], &setup.submitter, &[]);
+    assert_eq!(lamports(&setup.svm, &winner), before + PAYOUT);
+}
+
+#[test]
+fn attestation_by_another_key_is_rejected() {
+    let mut setup = setup();
+    let winner = setup.player1.pubkey();
+    let impostor = Keypair::new();
+    let signed = ed25519(&impostor, &message(&setup.game, &winner, 7));
+    let ix = resolve(&setup, &winner);
+    submit(&mut setup, &[signed, ix], WagerError::InvalidAttestation);
+
+    // The program instruction alone carries no signature at all
+    let ix = resolve(&setup, &winner);
+    submit(&mut setup, &[ix], WagerError::InvalidAttestation);
+}
+
+#[test]
+fn attestation_for_another_game_winner_or_nonce_is_rejected() {
+    let mut setup = setup();
+    let winner = setup.player1.pubkey();
+    let other_messages = [
+        message(&Pubkey::new_unique(), &winner, 7),
+        message(&setup.game, &setup.player2, 7),
+        message(&setup.game, &winner, 8),
+    ];
+    for other in other_messages {
+        let signed = ed25519(&setup.resolver, &other);
+        let ix = resolve(&setup, &winner);
+        submit(&mut setup, &[signed, ix], WagerError::InvalidAttestation);
+    }
+}
+
+#[test]
+fn signature_data_in_another_instruction_is_rejected() {
+    let mut setup = setup();
+    let winner = setup.player1.pubkey();
+    let message = message(&setup.game, &winner, 7);
+
+    // The precompile verifies the impostor's signature in the first
+    // instruction, while the second instruction's own bytes at the same
+    // offsets hold the resolver's key and the right message
+    let impostor = Keypair::new();
+    let verified = ed25519(&impostor, &message);
+    let mut decoy = verified.clone();
+    for index_at in [4, 8, 14] {
+        decoy.data[index_at..index_at + 2].copy_from_slice(&0u16.to_le_bytes());
+    }
+    decoy.data[PUBKEY_OFFSET..SIGNATURE_OFFSET].copy_from_slice(setup.resolver.pubkey().as_ref());
+    assert_eq!(&decoy.data[MESSAGE_OFFSET..], message.as_slice());
+
+    let ix = resolve(&setup, &winner);
+    submit(&mut setup, &[verified, decoy, ix], WagerError::InvalidAttestation);
+}
+
+#[test]
+fn replayed_attestation_is_rejected() {
+    let mut setup = setup();
+    let winner = setup.player1.pubkey();
+    let signed = ed25519(&setup.resolver, &message(&setup.game, &winner, 7));
+    let ix = resolve(&setup, &winner);
+    send_ok(&mut setup.svm, &[signed.clone(), ix.clone()], &setup.submitter, &[]);
+
+    send_err(&mut setup.svm, &[signed, ix], &setup.submitter, &[], WagerError::GameNotReady);
+    assert!(game_state(&setup.svm, &setup.game).state().unwrap() == GameState::Paid);
+}
@@ -0,0 +1,223 @@
+//! Multi-asset baskets: up to `Basket::MAX_LEGS` token amounts per side,
+//! escrowed in per-mint vaults through `remaining_accounts` and paid out leg
+//! by leg with the fee taken from each.
+
+use anchor_lang::solana_program::instruction::AccountMeta;
+use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+use anchor_spl::associated_token::get_associated_token_address;
+use easy_wager_client::instructions::*;
+use easy_wager_client::state::{Basket, BasketLeg, Game, GameState};
+use easy_wager_client::*;
+use easy_wager_harness::litesvm::LiteSVM;
+use easy_wager_harness::*;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+const SOL: Pubkey = Pubkey::new_from_array([0; 32]);
+const AMOUNT: u64 = 1_000_000;
+
+struct Setup {
+    svm: LiteSVM,
+    creator: Keypair,
+    player2: Keypair,
+    resolver: Keypair,
+    dev: Pubkey,
+    // One more mint than a basket holds, each held by both players
+    mints: Vec<Pubkey>,
+}
+
+fn setup() -> Setup {
+    let mut svm = svm();
+    let creator = funded(&mut svm, 10 * LAMPORTS_PER_SOL);
+    let player2 = funded(&mut svm, 10 * LAMPORTS_PER_SOL);
+    let resolver = funded(&mut svm, LAMPORTS_PER_SOL);
+    let mint_authority = funded(&mut svm, LAMPORTS_PER_SOL);
+    let mints: Vec<Pubkey> = (0..=Basket::MAX_LEGS).map(|_| create_mint(&mut svm, &mint_authority.pubkey(), 6)).collect();
+    for mint in &mints {
+        for player in [&creator, &player2] {
+            let ata = create_ata(&mut svm, player, &player.pubkey(), mint);
+            mint_to(&mut svm, mint, &mint_authority, &ata, 10 * AMOUNT);
+        }
+    }
+    Setup { svm, creator, player2, resolver, dev: Pubkey::new_unique(), mints }
+}
+
+fn basket(legs: &[(Pubkey, u64)]) -> Basket {
+    let mut basket = Basket::default();
+    for (slot, &(mint, amount)) in basket.legs.iter_mut().zip(legs) {
+        *slot = BasketLeg { mint, amount };
+    }
+    basket
+}
+
+fn game_state(svm: &LiteSVM, game: &Pubkey) -> Game {
+    decode_game(&svm.get_account(game).unwrap().data).unwrap()
+}
+
+/// Baskets-only terms with nothing staked in SOL.
+fn basket_game(setup: &Setup, player1_basket: Basket, player2_basket: Basket) -> CreateGameArgs {
+    let mut args = CreateGameArgs::new(setup.creator.pubkey(), SOL, 0, 9_500, now(&setup.svm) + 600, 0, setup.dev);
+    args.resolver = Some(setup.resolver.pubkey());
+    args.player1_basket = player1_basket;
+    args.player2_basket = player2_basket;
+    args
+}
+
+fn tokens(svm: &LiteSVM, owner: &Pubkey, mint: &Pubkey) -> u64 {
+    token_balance(svm, &get_associated_token_address(owner, mint))
+}
+
+#[test]
+fn full_baskets_are_escrowed_and_paid_out_per_mint() {
+    let mut setup = setup();
+    let creator = setup.creator.pubkey();
+    let player2 = setup.player2.pubkey();
+    let mints = setup.mints.clone();
+    let player1_legs: Vec<(Pubkey, u64)> = mints[..Basket::MAX_LEGS].iter().map(|&mint| (mint, AMOUNT)).collect();
+    // Player2's leg shares its mint, and so its vault, with player1's first
+    let args = basket_game(&setup, basket(&player1_legs), basket(&[(mints[0], 2 * AMOUNT)]));
+    send_ok(&mut setup.svm, &[create_game(&args)], &setup.creator, &[]);
+    let game = args.game();
+    let state = game_state(&setup.svm, &game);
+    send_ok(&mut setup.svm, &[join_game(&game, &state, &JoinGameArgs::new(player2))], &setup.player2, &[]);
+
+    assert_eq!(token_balance(&setup.svm, &find_basket_vault_pda(&game, &mints[0]).0), 3 * AMOUNT);
+    for mint in &mints[1..Basket::MAX_LEGS] {
+        assert_eq!(token_balance(&setup.svm, &find_basket_vault_pda(&game, mint).0), AMOUNT);
+    }
+
+    let state = game_state(&setup.svm, &game);
+    send_ok(&mut setup.svm, &[resolve_game(&setup.resolver.pubkey(), &game, &state, &player2)], &setup.resolver, &[]);
+    let claimer = funded(&mut setup.svm, LAMPORTS_PER_SOL);
+    for mint in &mints[..Basket::MAX_LEGS] {
+        create_ata(&mut setup.svm, &claimer, &setup.dev, mint);
+    }
+    let state = game_state(&setup.svm, &game);
+    for recipient in [player2, setup.dev] {
+        send_ok(&mut setup.svm, &[claim_basket(&claimer.pubkey(), &game, &state, &recipient)], &claimer, &[]);
+    }
+
+    // Every leg splits 95/5, the shared vault once per leg
+    let (won, fee) = (AMOUNT * 9_500 / 10_000, AMOUNT * 500 / 10_000);
+    assert_eq!(tokens(&setup.svm, &player2, &mints[0]), 8 * AMOUNT + 3 * won);
+    assert_eq!(tokens(&setup.svm, &setup.dev, &mints[0]), 3 * fee);
+    for mint in &mints[1..Basket::MAX_LEGS] {
+        assert_eq!(tokens(&setup.svm, &player2, mint), 10 * AMOUNT + won);
+        assert_eq!(tokens(&setup.svm, &setup.dev, mint), fee);
+        assert_eq!(tokens(&setup.svm, &creator, mint), 9 * AMOUNT);
+    }
+    for mint in &mints[..Basket::MAX_LEGS] {
+        assert_eq!(token_balance(&setup.svm, &find_basket_vault_pda(&game, mint).0), 0);
+    }
+
+    let state = game_state(&setup.svm, &game);
+    let ix = claim_basket(&claimer.pubkey(), &game, &state, &player2);
+    send_err(&mut setup.svm, &[ix], &claimer, &[], WagerError::NothingToClaim);
+}
+
+#[test]
+fn canceled_games_refund_each_leg_to_its_staker() {
+    let mut setup = setup();
+    let creator = setup.creator.pubkey();
+    let mints = setup.mints.clone();
+    let args = basket_game(&setup, basket(&[(mints[0], AMOUNT), (mints[1], 2 * AMOUNT)]), basket(&[(mints[2], AMOUNT)]));
+    send_ok(&mut setup.svm, &[create_game(&args)], &setup.creator, &[]);
+    let game = args.game();
+
+    warp_to(&mut setup.svm, args.expiry_ts);
+    let state = game_state(&setup.svm, &game);
+    send_ok(&mut setup.svm, &[cancel_if_expired(&creator, &game, &state)], &setup.creator, &[]);
+    let state = game_state(&setup.svm, &game);
+    assert!(state.state().unwrap() == GameState::Canceled);
+
+    let claimer = funded(&mut setup.svm, LAMPORTS_PER_SOL);
+    send_ok(&mut setup.svm, &[claim_basket(&claimer.pubkey(), &game, &state, &creator)], &claimer, &[]);
+    assert_eq!(tokens(&setup.svm, &creator, &mints[0]), 10 * AMOUNT);
+    assert_eq!(tokens(&setup.svm, &creator, &mints[1]), 10 * AMOUNT);
+}
+
+#[test]
+fn deposits_need_exactly_the_basket_legs_accounts() {
+    let mut setup = setup();
+    let creator = setup.creator.pubkey();
+    let mints = setup.mints.clone();
+    let legs: Vec<(Pubkey, u64)> = mints[..Basket::MAX_LEGS].iter().map(|&mint| (mint, AMOUNT)).collect();
+    let args = basket_game(&setup, basket(&legs), basket(&[(mints[0], AMOUNT)]));
+    let game = args.game();
+    let leg_accounts = |mint: &Pubkey| {
+        [
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(get_associated_token_address(&creator, mint), false),
+            AccountMeta::new(find_basket_vault_pda(&game, mint).0, false),
+        ]
+    };
+
+    // A basket holds at most `MAX_LEGS` mints, so accounts for a fifth leg
+    // don't match it
+    let mut ix = create_game(&args);
+    ix.accounts.extend(leg_accounts(&mints[Basket::MAX_LEGS]));
+    send_err(&mut setup.svm, &[ix], &setup.creator, &[], WagerError::BasketAccountMismatch);
+
+    // Nor do missing accounts
+    let mut ix = create_game(&args);
+    ix.accounts.truncate(ix.accounts.len() - 3);
+    send_err(&mut setup.svm, &[ix], &setup.creator, &[], WagerError::BasketAccountMismatch);
+
+    // ... or legs passed out of order
+    let mut ix = create_game(&args);
+    let first_leg = ix.accounts.len() - 3 * Basket::MAX_LEGS;
+    ix.accounts[first_leg..first_leg + 6].rotate_left(3);
+    send_err(&mut setup.svm, &[ix], &setup.creator, &[], WagerError::BasketAccountMismatch);
+
+    // ... or a vault that isn't the leg's
+    let mut ix = create_game(&args);
+    ix.accounts[first_leg + 2] = AccountMeta::new(find_basket_vault_pda(&game, &mints[Basket::MAX_LEGS]).0, false);
+    send_err(&mut setup.svm, &[ix], &setup.creator, &[], WagerError::BasketAccountMismatch);
+
+    send_ok(&mut setup.svm, &[create_game(&args)], &setup.creator, &[]);
+}
+
+#[test]
+fn claims_need_the_recipients_own_token_accounts() {
+    let mut setup = setup();
+    let player2 = setup.player2.pubkey();
+    let mints = setup.mints.clone();
+    let args = basket_game(&setup, basket(&[(mints[0], AMOUNT)]), basket(&[(mints[1], AMOUNT)]));
+    send_ok(&mut setup.svm, &[create_game(&args)], &setup.creator, &[]);
+    let game = args.game();
+    let state = game_state(&setup.svm, &game);
+    send_ok(&mut setup.svm, &[join_game(&game, &state, &JoinGameArgs::new(player2))], &setup.player2, &[]);
+    let state = game_state(&setup.svm, &game);
+    send_ok(&mut setup.svm, &[resolve_game(&setup.resolver.pubkey(), &game, &state, &player2)], &setup.resolver, &[]);
+    let state = game_state(&setup.svm, &game);
+    let claimer = funded(&mut setup.svm, LAMPORTS_PER_SOL);
+
+    // Paying player2's winnings into the creator's token account
+    let mut ix = claim_basket(&claimer.pubkey(), &game, &state, &player2);
+    let first_leg = ix.accounts.len() - 4;
+    ix.accounts[first_leg + 1] = AccountMeta::new(get_associated_token_address(&setup.creator.pubkey(), &mints[0]), false);
+    send_err(&mut setup.svm, &[ix], &claimer, &[], WagerError::BasketAccountMismatch);
+
+    // Leaving a leg's accounts out
+    let mut ix = claim_basket(&claimer.pubkey(), &game, &state, &player2);
+    ix.accounts.truncate(ix.accounts.len() - 2);
+    send_err(&mut setup.svm, &[ix], &claimer, &[], WagerError::BasketAccountMismatch);
+
+    // Each leg's vault for the other's
+    let mut ix = claim_basket(&claimer.pubkey(), &game, &state, &player2);
+    ix.accounts.swap(first_leg, first_leg + 2);
+    send_err(&mut setup.svm, &[ix], &claimer, &[], WagerError::BasketAccountMismatch);
+
+    send_ok(&mut setup.svm, &[claim_basket(&claimer.pubkey(), &game, &state, &player2)], &claimer, &[]);
+    assert_eq!(tokens(&setup.svm, &player2, &mints[0]), 10 * AMOUNT + AMOUNT * 9_500 / 10_000);
+}
+
+#[test]
+fn legs_need_both_a_mint_and_an_amount() {
+    let mut setup = setup();
+    let mints = setup.mints.clone();
+    for invalid in [basket(&[(mints[0], 0)]), basket(&[(SOL, AMOUNT)])] {
+        let args = basket_game(&setup, invalid, Basket::default());
+        send_err(&mut setup.svm, &[create_game(&args)], &setup.creator, &[], WagerError::InvalidBasket);
+    }
+}
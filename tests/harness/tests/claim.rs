@@ -0,0 +1,206 @@
+//! `claim`: where a resolved game's payout ends up, and who may send it there.
+
+use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::spl_token;
+use easy_wager_client::instructions::*;
+use easy_wager_client::state::{Game, PlayerBalance};
+use easy_wager_client::*;
+use easy_wager_harness::litesvm::LiteSVM;
+use easy_wager_harness::*;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+const SOL: Pubkey = Pubkey::new_from_array([0; 32]);
+const WAGER: u64 = LAMPORTS_PER_SOL;
+const PAYOUT: u64 = 2 * WAGER * 9_500 / 10_000;
+
+struct Setup {
+    svm: LiteSVM,
+    winner: Keypair,
+    // Mint authority of SPL games
+    mint_authority: Keypair,
+    mint: Pubkey,
+    game: Pubkey,
+}
+
+/// A game in `mint` won by player2. SPL players start with exactly their
+/// stake in their associated token account; wrapped SOL is wrapped from
+/// their SOL.
+fn setup(mint: impl FnOnce(&mut LiteSVM, &Keypair) -> Pubkey) -> Setup {
+    let mut svm = svm();
+    let mint_authority = funded(&mut svm, LAMPORTS_PER_SOL);
+    let mint = mint(&mut svm, &mint_authority);
+    let creator = funded(&mut svm, 10 * LAMPORTS_PER_SOL);
+    let winner = funded(&mut svm, 10 * LAMPORTS_PER_SOL);
+    let resolver = funded(&mut svm, LAMPORTS_PER_SOL);
+    if mint != SOL && mint != spl_token::native_mint::ID {
+        for player in [&creator, &winner] {
+            let ata = create_ata(&mut svm, player, &player.pubkey(), &mint);
+            mint_to(&mut svm, &mint, &mint_authority, &ata, WAGER);
+        }
+    }
+
+    let mut args = CreateGameArgs::new(creator.pubkey(), mint, WAGER, 9_500, now(&svm) + 600, 0, Pubkey::new_unique());
+    args.resolver = Some(resolver.pubkey());
+    send_ok(&mut svm, &[create_game(&args)], &creator, &[]);
+    let game = args.game();
+    let state = game_state(&svm, &game);
+    send_ok(&mut svm, &[join_game(&game, &state, &JoinGameArgs::new(winner.pubkey()))], &winner, &[]);
+    let state = game_state(&svm, &game);
+    send_ok(&mut svm, &[resolve_game(&resolver.pubkey(), &game, &state, &winner.pubkey())], &resolver, &[]);
+
+    Setup { svm, winner, mint_authority, mint, game }
+}
+
+fn sol_game() -> Setup {
+    setup(|_, _| SOL)
+}
+
+fn spl_game() -> Setup {
+    setup(|svm, mint_authority| create_mint(svm, &mint_authority.pubkey(), 6))
+}
+
+fn game_state(svm: &LiteSVM, game: &Pubkey) -> Game {
+    decode_game(&svm.get_account(game).unwrap().data).unwrap()
+}
+
+/// Opens the winner's player balance with a first deposit of one unit.
+fn open_balance(setup: &mut Setup) -> Pubkey {
+    let winner = setup.winner.pubkey();
+    if setup.mint != SOL {
+        let ata = get_associated_token_address(&winner, &setup.mint);
+        mint_to(&mut setup.svm, &setup.mint, &setup.mint_authority, &ata, 1);
+    }
+    send_ok(&mut setup.svm, &[deposit_balance(&winner, &setup.mint, 1)], &setup.winner, &[]);
+    find_balance_pda(&winner, &setup.mint).0
+}
+
+#[test]
+fn winner_may_claim_into_their_sol_balance() {
+    let mut setup = sol_game();
+    let balance = open_balance(&mut setup);
+    let held = lamports(&setup.svm, &balance);
+
+    let winner = setup.winner.pubkey();
+    let state = game_state(&setup.svm, &setup.game);
+    let before = lamports(&setup.svm, &winner);
+    send_ok(&mut setup.svm, &[claim(&winner, &setup.game, &state, &winner, ClaimTo::Balance)], &setup.winner, &[]);
+
+    assert_eq!(account::<PlayerBalance>(&setup.svm, &balance).amount, 1 + PAYOUT);
+    assert_eq!(lamports(&setup.svm, &balance), held + PAYOUT);
+    // Only the transaction fee left the wallet
+    assert_eq!(lamports(&setup.svm, &winner), before - 5_000);
+}
+
+#[test]
+fn winner_may_claim_into_their_token_balance() {
+    let mut setup = spl_game();
+    let balance = open_balance(&mut setup);
+
+    let winner = setup.winner.pubkey();
+    let state = game_state(&setup.svm, &setup.game);
+    send_ok(&mut setup.svm, &[claim(&winner, &setup.game, &state, &winner, ClaimTo::Balance)], &setup.winner, &[]);
+
+    assert_eq!(account::<PlayerBalance>(&setup.svm, &balance).amount, 1 + PAYOUT);
+    assert_eq!(token_balance(&setup.svm, &find_balance_vault_pda(&winner, &setup.mint).0), 1 + PAYOUT);
+    assert_eq!(token_balance(&setup.svm, &get_associated_token_address(&winner, &setup.mint)), 0);
+}
+
+#[test]
+fn only_the_recipient_may_claim_into_their_balance() {
+    for mut setup in [sol_game(), spl_game()] {
+        let balance = open_balance(&mut setup);
+        let winner = setup.winner.pubkey();
+        let state = game_state(&setup.svm, &setup.game);
+
+        let claimer = funded(&mut setup.svm, LAMPORTS_PER_SOL);
+        let ix = claim(&claimer.pubkey(), &setup.game, &state, &winner, ClaimTo::Balance);
+        send_err(&mut setup.svm, &[ix], &claimer, &[], WagerError::UnauthorizedBalanceCredit);
+        assert_eq!(account::<PlayerBalance>(&setup.svm, &balance).amount, 1);
+        assert_eq!(game_state(&setup.svm, &setup.game).claimable_by(&winner).unwrap(), PAYOUT);
+
+        // ... though they may still push it to the winner's wallet
+        send_ok(&mut setup.svm, &[claim(&claimer.pubkey(), &setup.game, &state, &winner, ClaimTo::Wallet)], &claimer, &[]);
+        assert_eq!(game_state(&setup.svm, &setup.game).claimable_by(&winner).unwrap(), 0);
+    }
+}
+
+#[test]
+fn claimer_pays_for_a_missing_token_account() {
+    let mut setup = spl_game();
+    let winner = setup.winner.pubkey();
+    let ata = get_associated_token_address(&winner, &setup.mint);
+    // The stake emptied the winner's token account; they closed it since
+    let close = spl_token::instruction::close_account(&spl_token::ID, &ata, &winner, &winner, &[]).unwrap();
+    send_ok(&mut setup.svm, &[close], &setup.winner, &[]);
+    assert!(setup.svm.get_account(&ata).is_none_or(|account| account.lamports == 0));
+
+    let claimer = funded(&mut setup.svm, LAMPORTS_PER_SOL);
+    let state = game_state(&setup.svm, &setup.game);
+    let before = lamports(&setup.svm, &winner);
+    send_ok(&mut setup.svm, &[claim(&claimer.pubkey(), &setup.game, &state, &winner, ClaimTo::Wallet)], &claimer, &[]);
+
+    assert_eq!(token_balance(&setup.svm, &ata), PAYOUT);
+    let rent = setup.svm.minimum_balance_for_rent_exemption(spl_token::state::Account::LEN);
+    assert_eq!(lamports(&setup.svm, &ata), rent);
+    assert_eq!(lamports(&setup.svm, &claimer.pubkey()), LAMPORTS_PER_SOL - 5_000 - rent);
+    assert_eq!(lamports(&setup.svm, &winner), before);
+}
+
+#[test]
+fn wrapped_sol_payout_is_unwrapped_to_the_wallet() {
+    let mut setup = setup(|svm, _| native_mint(svm));
+    let winner = setup.winner.pubkey();
+    let unwrap_account = find_unwrap_pda(&setup.game).0;
+    let sol_vault = find_sol_vault_pda(&setup.game).0;
+    let reserve = lamports(&setup.svm, &sol_vault);
+
+    let claimer = funded(&mut setup.svm, LAMPORTS_PER_SOL);
+    let state = game_state(&setup.svm, &setup.game);
+    let before = lamports(&setup.svm, &winner);
+    send_ok(&mut setup.svm, &[claim(&claimer.pubkey(), &setup.game, &state, &winner, ClaimTo::Wallet)], &claimer, &[]);
+
+    assert_eq!(lamports(&setup.svm, &winner), before + PAYOUT);
+    assert_eq!(token_balance(&setup.svm, &find_vault_pda(&setup.game, &setup.mint).0), 2 * WAGER - PAYOUT);
+    // The temporary account is closed and its rent refunded to the claimer,
+    // with nothing left behind in the SOL vault
+    assert!(setup.svm.get_account(&unwrap_account).is_none_or(|account| account.lamports == 0));
+    assert_eq!(lamports(&setup.svm, &claimer.pubkey()), LAMPORTS_PER_SOL - 5_000);
+    assert_eq!(lamports(&setup.svm, &sol_vault), reserve);
+    assert_eq!(token_balance(&setup.svm, &get_associated_token_address(&winner, &setup.mint)), 0);
+}
+
+#[test]
+fn sol_stakes_are_escrowed_in_the_sol_vault_until_claimed() {
+    let mut svm = svm();
+    let creator = funded(&mut svm, 10 * LAMPORTS_PER_SOL);
+    let player2 = funded(&mut svm, 10 * LAMPORTS_PER_SOL);
+    let resolver = funded(&mut svm, LAMPORTS_PER_SOL);
+    let dev = Pubkey::new_unique();
+    let mut args = CreateGameArgs::new(creator.pubkey(), SOL, WAGER, 9_500, now(&svm) + 600, 0, dev);
+    args.resolver = Some(resolver.pubkey());
+    let game = args.game();
+    let sol_vault = find_sol_vault_pda(&game).0;
+    let reserve = svm.minimum_balance_for_rent_exemption(0);
+
+    send_ok(&mut svm, &[create_game(&args)], &creator, &[]);
+    assert_eq!(lamports(&svm, &sol_vault), reserve + WAGER);
+    let rent = lamports(&svm, &game);
+    let state = game_state(&svm, &game);
+    send_ok(&mut svm, &[join_game(&game, &state, &JoinGameArgs::new(player2.pubkey()))], &player2, &[]);
+    assert_eq!(lamports(&svm, &sol_vault), reserve + 2 * WAGER);
+    // The game account itself only ever holds its rent
+    assert_eq!(lamports(&svm, &game), rent);
+
+    let state = game_state(&svm, &game);
+    send_ok(&mut svm, &[resolve_game(&resolver.pubkey(), &game, &state, &creator.pubkey())], &resolver, &[]);
+    let state = game_state(&svm, &game);
+    for recipient in [creator.pubkey(), dev] {
+        send_ok(&mut svm, &[claim(&resolver.pubkey(), &game, &state, &recipient, ClaimTo::Wallet)], &resolver, &[]);
+    }
+    assert_eq!(lamports(&svm, &dev), 2 * WAGER - PAYOUT);
+    assert_eq!(lamports(&svm, &sol_vault), reserve);
+    assert_eq!(lamports(&svm, &game), rent);
+}
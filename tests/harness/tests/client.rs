@@ -0,0 +1,155 @@
+//! The client's PDA helpers and instruction builders against the program:
+//! every address a builder derives must be the one the program checks, for
+//! native SOL, SPL and wrapped SOL games alike.
+
+use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+use anchor_spl::associated_token::get_associated_token_address;
+use easy_wager_client::instructions::*;
+use easy_wager_client::state::{Game, GameState, PlayerBalance, PlayerStats, Rating};
+use easy_wager_client::*;
+use easy_wager_harness::litesvm::LiteSVM;
+use easy_wager_harness::*;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+const SOL: Pubkey = Pubkey::new_from_array([0; 32]);
+const WAGER: u64 = LAMPORTS_PER_SOL;
+const PAYOUT: u64 = 2 * WAGER * 9_500 / 10_000;
+
+struct Players {
+    creator: Keypair,
+    player2: Keypair,
+    resolver: Keypair,
+}
+
+fn players(svm: &mut LiteSVM) -> Players {
+    Players {
+        creator: funded(svm, 10 * LAMPORTS_PER_SOL),
+        player2: funded(svm, 10 * LAMPORTS_PER_SOL),
+        resolver: funded(svm, LAMPORTS_PER_SOL),
+    }
+}
+
+fn game_state(svm: &LiteSVM, game: &Pubkey) -> Game {
+    decode_game(&svm.get_account(game).unwrap().data).unwrap()
+}
+
+/// Creates, joins and resolves a game in `mint` with the builders, player2
+/// winning, and returns the game's address.
+fn play(svm: &mut LiteSVM, players: &Players, mint: Pubkey, game_type: u32) -> Pubkey {
+    let mut args = CreateGameArgs::new(players.creator.pubkey(), mint, WAGER, 9_500, now(svm) + 600, 0, Pubkey::new_unique());
+    args.resolver = Some(players.resolver.pubkey());
+    args.game_type = game_type;
+    send_ok(svm, &[create_game(&args)], &players.creator, &[]);
+    let game = args.game();
+
+    let state = game_state(svm, &game);
+    send_ok(svm, &[join_game(&game, &state, &JoinGameArgs::new(players.player2.pubkey()))], &players.player2, &[]);
+    let state = game_state(svm, &game);
+    let resolve = resolve_game(&players.resolver.pubkey(), &game, &state, &players.player2.pubkey());
+    send_ok(svm, &[resolve], &players.resolver, &[]);
+    game
+}
+
+fn claim_payout(svm: &mut LiteSVM, players: &Players, game: &Pubkey, to: ClaimTo) {
+    let state = game_state(svm, game);
+    let winner = players.player2.pubkey();
+    send_ok(svm, &[claim(&winner, game, &state, &winner, to)], &players.player2, &[]);
+}
+
+#[test]
+fn pda_helpers_derive_the_addresses_the_program_stores() {
+    let mut svm = svm();
+    let mint_authority = funded(&mut svm, LAMPORTS_PER_SOL);
+    let mint = create_mint(&mut svm, &mint_authority.pubkey(), 6);
+    let players = players(&mut svm);
+    for player in [&players.creator, &players.player2] {
+        let ata = create_ata(&mut svm, player, &player.pubkey(), &mint);
+        mint_to(&mut svm, &mint, &mint_authority, &ata, WAGER);
+    }
+
+    let game = play(&mut svm, &players, mint, 7);
+    let (address, bump) = find_game_pda(&players.creator.pubkey(), 0);
+    assert_eq!(game, address);
+    let state = game_state(&svm, &game);
+    assert_eq!(state.bump, bump);
+    assert_eq!(state.vault_bump, find_vault_pda(&game, &mint).1);
+    assert_eq!(state.sol_vault_bump, find_sol_vault_pda(&game).1);
+
+    for player in [&players.creator, &players.player2] {
+        let (address, bump) = find_player_stats_pda(&player.pubkey());
+        assert_eq!(account::<PlayerStats>(&svm, &address).bump, bump);
+        let (address, bump) = find_rating_pda(&player.pubkey(), 7);
+        let rating = account::<Rating>(&svm, &address);
+        assert_eq!((rating.bump, rating.game_type), (bump, 7));
+    }
+
+    let owner = &players.creator;
+    mint_to(&mut svm, &mint, &mint_authority, &get_associated_token_address(&owner.pubkey(), &mint), WAGER);
+    send_ok(&mut svm, &[deposit_balance(&owner.pubkey(), &mint, WAGER)], owner, &[]);
+    let (address, bump) = find_balance_pda(&owner.pubkey(), &mint);
+    let balance = account::<PlayerBalance>(&svm, &address);
+    assert_eq!(balance.bump, bump);
+    let (vault, vault_bump) = find_balance_vault_pda(&owner.pubkey(), &mint);
+    assert_eq!(balance.vault_bump, vault_bump);
+    assert_eq!(token_balance(&svm, &vault), WAGER);
+}
+
+#[test]
+fn native_sol_game_runs_on_the_builders() {
+    let mut svm = svm();
+    let players = players(&mut svm);
+    let game = play(&mut svm, &players, SOL, 0);
+
+    let before = lamports(&svm, &players.player2.pubkey());
+    claim_payout(&mut svm, &players, &game, ClaimTo::Wallet);
+    assert_eq!(lamports(&svm, &players.player2.pubkey()), before + PAYOUT - 5_000);
+    assert!(game_state(&svm, &game).state().unwrap() == GameState::Paid);
+}
+
+#[test]
+fn spl_game_runs_on_the_builders() {
+    let mut svm = svm();
+    let mint_authority = funded(&mut svm, LAMPORTS_PER_SOL);
+    let mint = create_mint(&mut svm, &mint_authority.pubkey(), 6);
+    let players = players(&mut svm);
+    for player in [&players.creator, &players.player2] {
+        let ata = create_ata(&mut svm, player, &player.pubkey(), &mint);
+        mint_to(&mut svm, &mint, &mint_authority, &ata, WAGER);
+    }
+    let game = play(&mut svm, &players, mint, 0);
+    assert_eq!(token_balance(&svm, &find_vault_pda(&game, &mint).0), 2 * WAGER);
+
+    claim_payout(&mut svm, &players, &game, ClaimTo::Wallet);
+    let winner_tokens = get_associated_token_address(&players.player2.pubkey(), &mint);
+    assert_eq!(token_balance(&svm, &winner_tokens), PAYOUT);
+}
+
+#[test]
+fn wrapped_sol_game_runs_on_the_builders() {
+    let mut svm = svm();
+    let mint = native_mint(&mut svm);
+    let players = players(&mut svm);
+    // Both stakes are wrapped from the players' SOL
+    let game = play(&mut svm, &players, mint, 0);
+    assert_eq!(token_balance(&svm, &find_vault_pda(&game, &mint).0), 2 * WAGER);
+
+    // ... and the payout unwrapped back to SOL
+    let before = lamports(&svm, &players.player2.pubkey());
+    claim_payout(&mut svm, &players, &game, ClaimTo::Wallet);
+    assert_eq!(lamports(&svm, &players.player2.pubkey()), before + PAYOUT - 5_000);
+    assert_eq!(lamports(&svm, &find_unwrap_pda(&game).0), 0);
+}
+
+#[test]
+fn wrapped_sol_payout_can_stay_wrapped() {
+    let mut svm = svm();
+    let mint = native_mint(&mut svm);
+    let players = players(&mut svm);
+    let game = play(&mut svm, &players, mint, 0);
+
+    create_ata(&mut svm, &players.player2, &players.player2.pubkey(), &mint);
+    claim_payout(&mut svm, &players, &game, ClaimTo::TokenAccount);
+    let winner_tokens = get_associated_token_address(&players.player2.pubkey(), &mint);
+    assert_eq!(token_balance(&svm, &winner_tokens), PAYOUT);
+}
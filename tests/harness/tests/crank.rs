@@ -0,0 +1,204 @@
+//! `crank_expired` refunding and closing expired games: the bounty, where the
+//! rent and leftover escrow go, and skipping games that can't be closed.
+
+use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+use easy_wager_client::instructions::*;
+use easy_wager_client::state::{Game, GameState};
+use easy_wager_client::state::EscrowSwept;
+use easy_wager_client::{decode_cpi_event, decode_game, find_sol_vault_pda, find_vault_pda, WagerError, WagerEvent};
+use easy_wager_harness::litesvm::types::TransactionMetadata;
+use easy_wager_harness::litesvm::LiteSVM;
+use easy_wager_harness::*;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+const SOL: Pubkey = Pubkey::new_from_array([0; 32]);
+const WAGER: u64 = LAMPORTS_PER_SOL;
+
+fn game_state(svm: &LiteSVM, game: &Pubkey) -> Game {
+    decode_game(&svm.get_account(game).unwrap().data).unwrap()
+}
+
+/// Opens a game expiring in a minute.
+fn open_game(svm: &mut LiteSVM, creator: &Keypair, mint: Pubkey, nonce: u64) -> Pubkey {
+    let args = CreateGameArgs::new(creator.pubkey(), mint, WAGER, 9_500, now(svm) + 60, nonce, Pubkey::new_unique());
+    send_ok(svm, &[create_game(&args)], creator, &[]);
+    args.game()
+}
+
+/// Moves the clock past `game`'s expiry.
+fn expire(svm: &mut LiteSVM, game: &Pubkey) {
+    let expiry_ts = game_state(svm, game).expiry_ts;
+    warp_to(svm, expiry_ts + 1);
+}
+
+/// Cranks `games`, signed by `cranker` with the fee paid by someone else so
+/// the cranker's balance only moves by the bounty.
+fn crank(svm: &mut LiteSVM, cranker: &Keypair, games: &[Pubkey]) -> TransactionMetadata {
+    let states: Vec<Game> = games.iter().map(|game| game_state(svm, game)).collect();
+    let entries: Vec<(Pubkey, &Game)> = games.iter().copied().zip(&states).collect();
+    let fee_payer = funded(svm, LAMPORTS_PER_SOL);
+    send(svm, &[crank_expired(&cranker.pubkey(), &entries)], &fee_payer, &[cranker]).unwrap()
+}
+
+fn sweeps(meta: &TransactionMetadata) -> Vec<EscrowSwept> {
+    meta.inner_instructions
+        .iter()
+        .flatten()
+        .filter_map(|inner| match decode_cpi_event(&inner.instruction.data) {
+            Some(WagerEvent::EscrowSwept(event)) => Some(event),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn rent_returns_to_the_relayer_minus_the_creators_bounty() {
+    let mut svm = svm();
+    let creator = funded(&mut svm, 10 * LAMPORTS_PER_SOL);
+    let relayer = funded(&mut svm, LAMPORTS_PER_SOL);
+    let cranker = funded(&mut svm, LAMPORTS_PER_SOL);
+    let mut args = CreateGameArgs::new(creator.pubkey(), SOL, WAGER, 9_500, now(&svm) + 60, 0, Pubkey::new_unique());
+    args.payer = relayer.pubkey();
+    args.crank_bounty_bps = 2_500;
+    send_ok(&mut svm, &[create_game(&args)], &relayer, &[&creator]);
+    let game = args.game();
+
+    let state = game_state(&svm, &game);
+    assert_eq!(state.rent_payer, relayer.pubkey());
+    assert_eq!(state.crank_bounty_bps, 2_500);
+
+    let game_rent = lamports(&svm, &game);
+    let reserve = svm.minimum_balance_for_rent_exemption(0);
+    let bounty = game_rent * 2_500 / 10_000;
+    let creator_before = lamports(&svm, &creator.pubkey());
+    let relayer_before = lamports(&svm, &relayer.pubkey());
+    let cranker_before = lamports(&svm, &cranker.pubkey());
+
+    expire(&mut svm, &game);
+    let meta = crank(&mut svm, &cranker, &[game]);
+    // Nothing was left beyond the refund
+    assert!(sweeps(&meta).is_empty());
+
+    assert!(svm.get_account(&game).is_none_or(|account| account.lamports == 0));
+    assert_eq!(lamports(&svm, &find_sol_vault_pda(&game).0), 0);
+    assert_eq!(lamports(&svm, &creator.pubkey()), creator_before + WAGER);
+    assert_eq!(lamports(&svm, &cranker.pubkey()), cranker_before + bounty);
+    assert_eq!(lamports(&svm, &relayer.pubkey()), relayer_before + game_rent - bounty + reserve);
+}
+
+#[test]
+fn games_that_fail_a_check_are_skipped() {
+    let mut svm = svm();
+    let alice = funded(&mut svm, 10 * LAMPORTS_PER_SOL);
+    let bob = funded(&mut svm, 10 * LAMPORTS_PER_SOL);
+    let cranker = funded(&mut svm, LAMPORTS_PER_SOL);
+
+    let open = open_game(&mut svm, &alice, SOL, 0);
+    let ready = open_game(&mut svm, &bob, SOL, 0);
+    let state = game_state(&svm, &ready);
+    send_ok(&mut svm, &[join_game(&ready, &state, &JoinGameArgs::new(alice.pubkey()))], &alice, &[]);
+    // Still running when the others expire
+    let args = CreateGameArgs::new(alice.pubkey(), SOL, WAGER, 9_500, now(&svm) + 600, 1, Pubkey::new_unique());
+    send_ok(&mut svm, &[create_game(&args)], &alice, &[]);
+    let running = args.game();
+
+    expire(&mut svm, &open);
+    let bob_before = lamports(&svm, &bob.pubkey());
+    let ready_rent = lamports(&svm, &ready);
+    let bounty = ready_rent * u64::from(Game::DEFAULT_CRANK_BOUNTY_BPS) / 10_000;
+    crank(&mut svm, &cranker, &[open, running, ready]);
+
+    for game in [open, ready] {
+        assert!(svm.get_account(&game).is_none_or(|account| account.lamports == 0));
+    }
+    assert!(game_state(&svm, &running).state().unwrap() == GameState::Open);
+    assert_eq!(lamports(&svm, &find_sol_vault_pda(&running).0), svm.minimum_balance_for_rent_exemption(0) + WAGER);
+    // Bob created the ready game, so its rent goes back to Bob too
+    let reserve = svm.minimum_balance_for_rent_exemption(0);
+    assert_eq!(lamports(&svm, &bob.pubkey()), bob_before + WAGER + ready_rent - bounty + reserve);
+
+    // A batch of nothing but skipped games fails
+    let state = game_state(&svm, &running);
+    let fee_payer = funded(&mut svm, LAMPORTS_PER_SOL);
+    let ix = crank_expired(&cranker.pubkey(), &[(running, &state)]);
+    send_err(&mut svm, &[ix], &fee_payer, &[&cranker], WagerError::GameNotCrankable);
+}
+
+#[test]
+fn games_passed_twice_are_closed_once() {
+    let mut svm = svm();
+    let creator = funded(&mut svm, 10 * LAMPORTS_PER_SOL);
+    let cranker = funded(&mut svm, LAMPORTS_PER_SOL);
+    let game = open_game(&mut svm, &creator, SOL, 0);
+
+    let game_rent = lamports(&svm, &game);
+    let reserve = svm.minimum_balance_for_rent_exemption(0);
+    let bounty = game_rent * u64::from(Game::DEFAULT_CRANK_BOUNTY_BPS) / 10_000;
+    let creator_before = lamports(&svm, &creator.pubkey());
+    let cranker_before = lamports(&svm, &cranker.pubkey());
+
+    expire(&mut svm, &game);
+    let meta = crank(&mut svm, &cranker, &[game, game]);
+
+    assert_eq!(lamports(&svm, &cranker.pubkey()), cranker_before + bounty);
+    assert_eq!(lamports(&svm, &creator.pubkey()), creator_before + WAGER + game_rent - bounty + reserve);
+    let cranked = meta
+        .inner_instructions
+        .iter()
+        .flatten()
+        .find_map(|inner| match decode_cpi_event(&inner.instruction.data) {
+            Some(WagerEvent::ExpiredGamesCranked(event)) => Some(event),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!((cranked.closed, cranked.skipped, cranked.bounty), (1, 1, bounty));
+}
+
+#[test]
+fn leftover_lamports_go_to_the_creator() {
+    let mut svm = svm();
+    let creator = funded(&mut svm, 10 * LAMPORTS_PER_SOL);
+    let cranker = funded(&mut svm, LAMPORTS_PER_SOL);
+    let game = open_game(&mut svm, &creator, SOL, 0);
+    // Sent to the SOL vault by mistake
+    svm.airdrop(&find_sol_vault_pda(&game).0, 1_000).unwrap();
+
+    let game_rent = lamports(&svm, &game);
+    let reserve = svm.minimum_balance_for_rent_exemption(0);
+    let bounty = game_rent * u64::from(Game::DEFAULT_CRANK_BOUNTY_BPS) / 10_000;
+    let before = lamports(&svm, &creator.pubkey());
+
+    expire(&mut svm, &game);
+    let meta = crank(&mut svm, &cranker, &[game]);
+
+    assert_eq!(lamports(&svm, &creator.pubkey()), before + WAGER + 1_000 + game_rent - bounty + reserve);
+    let sweeps = sweeps(&meta);
+    assert_eq!(sweeps.len(), 1);
+    assert_eq!((sweeps[0].game, sweeps[0].creator), (game, creator.pubkey()));
+    assert_eq!((sweeps[0].lamports, sweeps[0].tokens), (1_000, 0));
+}
+
+#[test]
+fn leftover_tokens_go_to_the_creators_token_account() {
+    let mut svm = svm();
+    let mint_authority = funded(&mut svm, LAMPORTS_PER_SOL);
+    let mint = create_mint(&mut svm, &mint_authority.pubkey(), 6);
+    let creator = funded(&mut svm, 10 * LAMPORTS_PER_SOL);
+    let cranker = funded(&mut svm, LAMPORTS_PER_SOL);
+    let creator_tokens = create_ata(&mut svm, &creator, &creator.pubkey(), &mint);
+    mint_to(&mut svm, &mint, &mint_authority, &creator_tokens, WAGER);
+
+    let game = open_game(&mut svm, &creator, mint, 0);
+    let vault = find_vault_pda(&game, &mint).0;
+    mint_to(&mut svm, &mint, &mint_authority, &vault, 500);
+    assert_eq!(token_balance(&svm, &creator_tokens), 0);
+
+    expire(&mut svm, &game);
+    let meta = crank(&mut svm, &cranker, &[game]);
+
+    assert_eq!(token_balance(&svm, &creator_tokens), WAGER + 500);
+    let sweeps = sweeps(&meta);
+    assert_eq!((sweeps[0].mint, sweeps[0].lamports, sweeps[0].tokens), (mint, 0, 500));
+    assert!(svm.get_account(&vault).is_none_or(|account| account.lamports == 0));
+}
@@ -0,0 +1,211 @@
+//! `migrate_game` on Borsh game accounts: the first release's layout
+//! ("version 0", ending at `vault_bump`) and the last Borsh layout (version 1).
+
+use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::Discriminator;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::spl_token;
+use easy_wager_client::instructions::*;
+use easy_wager_client::state::{Game, GameState};
+use easy_wager_client::{decode_game, find_game_pda, find_sol_vault_pda, find_vault_pda, WagerError};
+use easy_wager_harness::litesvm::LiteSVM;
+use easy_wager_harness::*;
+use solana_sdk::account::Account;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+const SOL: Pubkey = Pubkey::new_from_array([0; 32]);
+const WAGER: u64 = LAMPORTS_PER_SOL;
+const V0_LEN: usize = 229;
+const V1_LEN: usize = 859;
+
+struct Legacy {
+    game: Pubkey,
+    creator: Keypair,
+    player2: Keypair,
+    resolver: Keypair,
+}
+
+/// Writes a legacy `Game` holding `escrowed_on_account` lamports above its rent,
+/// in the version 0 layout, or the version 1 layout when `v1` is set.
+fn legacy_game(svm: &mut LiteSVM, mint: Pubkey, state: GameState, v1: bool, escrowed_on_account: u64) -> Legacy {
+    let creator = funded(svm, 10 * LAMPORTS_PER_SOL);
+    let player2 = funded(svm, 10 * LAMPORTS_PER_SOL);
+    let resolver = funded(svm, LAMPORTS_PER_SOL);
+    let (game, bump) = find_game_pda(&creator.pubkey(), 0);
+    let vault_bump = if mint == SOL { 0 } else { find_vault_pda(&game, &mint).1 };
+    let joined = if state == GameState::Open { Pubkey::default() } else { player2.pubkey() };
+
+    let mut data = Game::DISCRIMINATOR.to_vec();
+    for key in [creator.pubkey(), creator.pubkey(), joined, resolver.pubkey(), Pubkey::new_unique(), mint] {
+        data.extend_from_slice(key.as_ref());
+    }
+    data.extend_from_slice(&WAGER.to_le_bytes());
+    data.extend_from_slice(&9_000u16.to_le_bytes());
+    data.push(state as u8);
+    data.extend_from_slice(&(now(svm) + 600).to_le_bytes());
+    data.extend_from_slice(&0u64.to_le_bytes());
+    data.extend_from_slice(&[bump, vault_bump]);
+    assert_eq!(data.len(), V0_LEN);
+
+    if v1 {
+        data.extend_from_slice(&[1, 0, 0]); // best_of, wins
+        data.resize(data.len() + 32 + 32 + 8 * 3, 0); // side_pool, winner, claimables
+        data.push(find_sol_vault_pda(&game).1);
+        // Fungible stakes and empty baskets are all zeros
+        data.resize(V1_LEN - 64 - 1 - 4 * 3, 0);
+        data.extend_from_slice(&0u32.to_le_bytes()); // game_type
+        data.extend_from_slice(&0u32.to_le_bytes()); // min_rating
+        data.extend_from_slice(&u32::MAX.to_le_bytes()); // max_rating
+        data.push(1); // version
+        data.resize(V1_LEN, 0);
+    }
+
+    let account = Account {
+        lamports: svm.minimum_balance_for_rent_exemption(data.len()) + escrowed_on_account,
+        data,
+        owner: PROGRAM_ID,
+        executable: false,
+        rent_epoch: 0,
+    };
+    svm.set_account(game, account).unwrap();
+    Legacy { game, creator, player2, resolver }
+}
+
+/// Funds the SOL vault of `game` with its reserve plus `escrowed`.
+fn fund_sol_vault(svm: &mut LiteSVM, game: &Pubkey, escrowed: u64) {
+    let sol_vault = find_sol_vault_pda(game).0;
+    let reserve = svm.minimum_balance_for_rent_exemption(0);
+    svm.airdrop(&sol_vault, reserve + escrowed).unwrap();
+}
+
+/// Writes the token vault of `game` holding `amount` of `mint`.
+fn token_vault(svm: &mut LiteSVM, game: &Pubkey, mint: &Pubkey, amount: u64) {
+    let state = spl_token::state::Account {
+        mint: *mint,
+        owner: *game,
+        amount,
+        delegate: COption::None,
+        state: spl_token::state::AccountState::Initialized,
+        is_native: COption::None,
+        delegated_amount: 0,
+        close_authority: COption::None,
+    };
+    let mut data = vec![0; spl_token::state::Account::LEN];
+    state.pack_into_slice(&mut data);
+    let account = Account {
+        lamports: svm.minimum_balance_for_rent_exemption(data.len()),
+        data,
+        owner: spl_token::ID,
+        executable: false,
+        rent_epoch: 0,
+    };
+    svm.set_account(find_vault_pda(game, mint).0, account).unwrap();
+}
+
+fn game(svm: &LiteSVM, game: &Pubkey) -> Game {
+    decode_game(&svm.get_account(game).unwrap().data).unwrap()
+}
+
+#[test]
+fn first_release_sol_stake_moves_to_the_sol_vault_and_is_refunded() {
+    let mut svm = svm();
+    // The first release escrowed only player2's wager, on the game account
+    let legacy = legacy_game(&mut svm, SOL, GameState::Ready, false, WAGER);
+    let payer = funded(&mut svm, LAMPORTS_PER_SOL);
+
+    send_ok(&mut svm, &[migrate_game(&payer.pubkey(), &legacy.game, &SOL)], &payer, &[]);
+
+    let account = svm.get_account(&legacy.game).unwrap();
+    assert_eq!(account.data.len(), Game::LEN);
+    assert_eq!(account.lamports, svm.minimum_balance_for_rent_exemption(Game::LEN));
+    let (sol_vault, sol_vault_bump) = find_sol_vault_pda(&legacy.game);
+    assert_eq!(lamports(&svm, &sol_vault), svm.minimum_balance_for_rent_exemption(0) + WAGER);
+
+    let state = game(&svm, &legacy.game);
+    assert_eq!(state.version, Game::VERSION);
+    assert_eq!(state.best_of, 1);
+    assert_eq!(state.sol_vault_bump, sol_vault_bump);
+    assert!(state.state().unwrap() == GameState::Canceled);
+    assert_eq!((state.player1_claimable, state.player2_claimable), (0, WAGER));
+
+    let player2 = legacy.player2.pubkey();
+    let before = lamports(&svm, &player2);
+    send_ok(&mut svm, &[claim(&player2, &legacy.game, &state, &player2, ClaimTo::Wallet)], &payer, &[&legacy.player2]);
+    assert_eq!(lamports(&svm, &player2), before + WAGER);
+}
+
+#[test]
+fn first_release_open_game_is_canceled_with_nothing_owed() {
+    let mut svm = svm();
+    let legacy = legacy_game(&mut svm, SOL, GameState::Open, false, 0);
+    let payer = funded(&mut svm, LAMPORTS_PER_SOL);
+
+    send_ok(&mut svm, &[migrate_game(&payer.pubkey(), &legacy.game, &SOL)], &payer, &[]);
+
+    let state = game(&svm, &legacy.game);
+    assert!(state.state().unwrap() == GameState::Canceled);
+    assert_eq!((state.player1_claimable, state.player2_claimable), (0, 0));
+}
+
+#[test]
+fn first_release_spl_game_refunds_player2_from_the_vault() {
+    let mut svm = svm();
+    let mint_authority = funded(&mut svm, LAMPORTS_PER_SOL);
+    let mint = create_mint(&mut svm, &mint_authority.pubkey(), 6);
+    let legacy = legacy_game(&mut svm, mint, GameState::Ready, false, 0);
+    token_vault(&mut svm, &legacy.game, &mint, WAGER);
+    let payer = funded(&mut svm, LAMPORTS_PER_SOL);
+
+    send_ok(&mut svm, &[migrate_game(&payer.pubkey(), &legacy.game, &mint)], &payer, &[]);
+
+    let state = game(&svm, &legacy.game);
+    assert!(state.state().unwrap() == GameState::Canceled);
+    assert_eq!((state.player1_claimable, state.player2_claimable), (0, WAGER));
+
+    let player2 = legacy.player2.pubkey();
+    send_ok(&mut svm, &[claim(&payer.pubkey(), &legacy.game, &state, &player2, ClaimTo::Wallet)], &payer, &[]);
+    assert_eq!(token_balance(&svm, &get_associated_token_address(&player2, &mint)), WAGER);
+}
+
+#[test]
+fn version_1_game_keeps_its_escrow_and_resolves() {
+    let mut svm = svm();
+    let legacy = legacy_game(&mut svm, SOL, GameState::Ready, true, 0);
+    fund_sol_vault(&mut svm, &legacy.game, 2 * WAGER);
+    let payer = funded(&mut svm, LAMPORTS_PER_SOL);
+    // Version 1 games opened their players' stats when they were created and joined
+    for (nonce, player) in [(1, &legacy.creator), (2, &legacy.player2)] {
+        let args = CreateGameArgs::new(player.pubkey(), SOL, 1_000, 9_000, now(&svm) + 600, nonce, Pubkey::new_unique());
+        send_ok(&mut svm, &[create_game(&args)], player, &[]);
+    }
+
+    send_ok(&mut svm, &[migrate_game(&payer.pubkey(), &legacy.game, &SOL)], &payer, &[]);
+    assert_eq!(svm.get_account(&legacy.game).unwrap().data.len(), Game::LEN);
+    let state = game(&svm, &legacy.game);
+    assert!(state.state().unwrap() == GameState::Ready);
+    assert_eq!(state.version, Game::VERSION);
+    assert_eq!(state.max_rating, u32::MAX);
+
+    let resolver = legacy.resolver.pubkey();
+    let winner = legacy.player2.pubkey();
+    send_ok(&mut svm, &[resolve_game(&resolver, &legacy.game, &state, &winner)], &legacy.resolver, &[]);
+    let state = game(&svm, &legacy.game);
+    let before = lamports(&svm, &winner);
+    send_ok(&mut svm, &[claim(&payer.pubkey(), &legacy.game, &state, &winner, ClaimTo::Wallet)], &payer, &[]);
+    assert_eq!(lamports(&svm, &winner), before + 2 * WAGER * 9_000 / 10_000);
+}
+
+#[test]
+fn migrated_games_cannot_be_migrated_again() {
+    let mut svm = svm();
+    let legacy = legacy_game(&mut svm, SOL, GameState::Ready, true, 0);
+    fund_sol_vault(&mut svm, &legacy.game, 2 * WAGER);
+    let payer = funded(&mut svm, LAMPORTS_PER_SOL);
+
+    let migrate = [migrate_game(&payer.pubkey(), &legacy.game, &SOL)];
+    send_ok(&mut svm, &migrate, &payer, &[]);
+    send_err(&mut svm, &migrate, &payer, &[], WagerError::GameAlreadyMigrated);
+}
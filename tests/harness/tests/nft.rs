@@ -0,0 +1,246 @@
+//! NFT stakes: escrowed on create/join, only when nobody can mint more or
+//! freeze them, and claimed by the winner (or returned if the game is
+//! canceled) alongside a fungible fee per side.
+
+use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::metadata::mpl_token_metadata::accounts::MasterEdition;
+use anchor_spl::token::spl_token;
+use easy_wager_client::instructions::*;
+use easy_wager_client::state::{AssetKind, Game, GameState, Side, StakeAsset};
+use easy_wager_client::*;
+use easy_wager_harness::litesvm::LiteSVM;
+use easy_wager_harness::*;
+use solana_sdk::account::Account;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+const SOL: Pubkey = Pubkey::new_from_array([0; 32]);
+const WAGER: u64 = LAMPORTS_PER_SOL;
+const ASSET_FEE: u64 = LAMPORTS_PER_SOL / 10;
+const NFT: StakeAsset = StakeAsset { kind: AssetKind::Nft as u8, mint: SOL, collection: SOL };
+
+struct Setup {
+    svm: LiteSVM,
+    creator: Keypair,
+    player2: Keypair,
+    resolver: Keypair,
+    dev: Pubkey,
+}
+
+fn setup() -> Setup {
+    let mut svm = svm();
+    Setup {
+        creator: funded(&mut svm, 10 * LAMPORTS_PER_SOL),
+        player2: funded(&mut svm, 10 * LAMPORTS_PER_SOL),
+        resolver: funded(&mut svm, LAMPORTS_PER_SOL),
+        dev: Pubkey::new_unique(),
+        svm,
+    }
+}
+
+/// Writes an NFT mint of `supply` with the given authorities, all of it held
+/// in `owner`'s associated token account.
+fn nft(svm: &mut LiteSVM, owner: &Pubkey, authorities: impl FnOnce(Pubkey) -> [COption<Pubkey>; 2], supply: u64) -> Pubkey {
+    let mint = Pubkey::new_unique();
+    let [mint_authority, freeze_authority] = authorities(mint);
+    let state = spl_token::state::Mint { mint_authority, supply, decimals: 0, is_initialized: true, freeze_authority };
+    let mut data = vec![0; spl_token::state::Mint::LEN];
+    state.pack_into_slice(&mut data);
+    set_token_program_account(svm, mint, data);
+
+    let state = spl_token::state::Account {
+        mint,
+        owner: *owner,
+        amount: supply,
+        state: spl_token::state::AccountState::Initialized,
+        ..Default::default()
+    };
+    let mut data = vec![0; spl_token::state::Account::LEN];
+    state.pack_into_slice(&mut data);
+    set_token_program_account(svm, get_associated_token_address(owner, &mint), data);
+    mint
+}
+
+/// A locked NFT, as Metaplex leaves it: both authorities on the master edition.
+fn locked_nft(svm: &mut LiteSVM, owner: &Pubkey) -> Pubkey {
+    nft(svm, owner, |mint| [COption::Some(MasterEdition::find_pda(&mint).0); 2], 1)
+}
+
+fn set_token_program_account(svm: &mut LiteSVM, address: Pubkey, data: Vec<u8>) {
+    let account = Account {
+        lamports: svm.minimum_balance_for_rent_exemption(data.len()),
+        data,
+        owner: spl_token::ID,
+        executable: false,
+        rent_epoch: 0,
+    };
+    svm.set_account(address, account).unwrap();
+}
+
+fn game_state(svm: &LiteSVM, game: &Pubkey) -> Game {
+    decode_game(&svm.get_account(game).unwrap().data).unwrap()
+}
+
+/// Native SOL terms with the creator staking `nft` against player2's wager.
+fn nft_game(setup: &Setup, nft: Pubkey) -> CreateGameArgs {
+    let mut args = CreateGameArgs::new(setup.creator.pubkey(), SOL, WAGER, 9_500, now(&setup.svm) + 600, 0, setup.dev);
+    args.resolver = Some(setup.resolver.pubkey());
+    args.player1_asset = NFT;
+    args.asset_fee = ASSET_FEE;
+    args.nft_mint = Some(nft);
+    args
+}
+
+fn nft_balance(svm: &LiteSVM, owner: &Pubkey, nft: &Pubkey) -> u64 {
+    token_balance(svm, &get_associated_token_address(owner, nft))
+}
+
+#[test]
+fn winner_claims_both_stakes_and_the_dev_both_fees() {
+    let mut setup = setup();
+    let creator = setup.creator.pubkey();
+    let player2 = setup.player2.pubkey();
+    let creator_nft = locked_nft(&mut setup.svm, &creator);
+    let player2_nft = locked_nft(&mut setup.svm, &player2);
+
+    let mut args = nft_game(&setup, creator_nft);
+    args.player2_asset = NFT;
+    send_ok(&mut setup.svm, &[create_game(&args)], &setup.creator, &[]);
+    let game = args.game();
+    let state = game_state(&setup.svm, &game);
+    let join = JoinGameArgs { nft_mint: Some(player2_nft), ..JoinGameArgs::new(player2) };
+    send_ok(&mut setup.svm, &[join_game(&game, &state, &join)], &setup.player2, &[]);
+
+    // Both NFTs are escrowed, and with neither side staking the wager only
+    // the fees are left in the SOL vault
+    assert_eq!(token_balance(&setup.svm, &find_nft_vault_pda(&game, &creator).0), 1);
+    assert_eq!(token_balance(&setup.svm, &find_nft_vault_pda(&game, &player2).0), 1);
+    let reserve = setup.svm.minimum_balance_for_rent_exemption(0);
+    assert_eq!(lamports(&setup.svm, &find_sol_vault_pda(&game).0), reserve + 2 * ASSET_FEE);
+
+    let state = game_state(&setup.svm, &game);
+    send_ok(&mut setup.svm, &[resolve_game(&setup.resolver.pubkey(), &game, &state, &player2)], &setup.resolver, &[]);
+    let state = game_state(&setup.svm, &game);
+    let claimer = funded(&mut setup.svm, LAMPORTS_PER_SOL);
+    for side in [Side::Player1, Side::Player2] {
+        send_ok(&mut setup.svm, &[claim_nft(&claimer.pubkey(), &game, &state, side, &player2)], &claimer, &[]);
+    }
+    send_ok(&mut setup.svm, &[claim(&claimer.pubkey(), &game, &state, &setup.dev, ClaimTo::Wallet)], &claimer, &[]);
+
+    assert_eq!(nft_balance(&setup.svm, &player2, &creator_nft), 1);
+    assert_eq!(nft_balance(&setup.svm, &player2, &player2_nft), 1);
+    assert_eq!(nft_balance(&setup.svm, &creator, &creator_nft), 0);
+    assert_eq!(lamports(&setup.svm, &setup.dev), 2 * ASSET_FEE);
+    assert_eq!(lamports(&setup.svm, &find_sol_vault_pda(&game).0), reserve);
+    // The emptied vaults are closed
+    for staker in [creator, player2] {
+        assert!(setup.svm.get_account(&find_nft_vault_pda(&game, &staker).0).is_none_or(|account| account.lamports == 0));
+    }
+
+    // ... so neither NFT can be claimed twice
+    let state = game_state(&setup.svm, &game);
+    assert!(state.nft_claimed(Side::Player1) && state.nft_claimed(Side::Player2));
+    let ix = claim_nft(&claimer.pubkey(), &game, &state, Side::Player1, &player2);
+    assert!(send(&mut setup.svm, &[ix], &claimer, &[]).is_err());
+}
+
+#[test]
+fn winner_takes_the_nft_and_the_wager_staked_against_it() {
+    let mut setup = setup();
+    let creator = setup.creator.pubkey();
+    let player2 = setup.player2.pubkey();
+    let creator_nft = locked_nft(&mut setup.svm, &creator);
+
+    let args = nft_game(&setup, creator_nft);
+    send_ok(&mut setup.svm, &[create_game(&args)], &setup.creator, &[]);
+    let game = args.game();
+    let state = game_state(&setup.svm, &game);
+    send_ok(&mut setup.svm, &[join_game(&game, &state, &JoinGameArgs::new(player2))], &setup.player2, &[]);
+    let reserve = setup.svm.minimum_balance_for_rent_exemption(0);
+    assert_eq!(lamports(&setup.svm, &find_sol_vault_pda(&game).0), reserve + WAGER + 2 * ASSET_FEE);
+
+    let state = game_state(&setup.svm, &game);
+    send_ok(&mut setup.svm, &[resolve_game(&setup.resolver.pubkey(), &game, &state, &creator)], &setup.resolver, &[]);
+    let state = game_state(&setup.svm, &game);
+    // The NFT can't be split, so the winner takes the whole fungible pot
+    assert_eq!(state.claimable_by(&creator).unwrap(), WAGER);
+    assert_eq!(state.claimable_by(&setup.dev).unwrap(), 2 * ASSET_FEE);
+
+    // Nobody but the winner may receive the NFT
+    let claimer = funded(&mut setup.svm, LAMPORTS_PER_SOL);
+    let ix = claim_nft(&claimer.pubkey(), &game, &state, Side::Player1, &player2);
+    send_err(&mut setup.svm, &[ix], &claimer, &[], WagerError::InvalidWinner);
+    send_ok(&mut setup.svm, &[claim_nft(&claimer.pubkey(), &game, &state, Side::Player1, &creator)], &claimer, &[]);
+    assert_eq!(nft_balance(&setup.svm, &creator, &creator_nft), 1);
+}
+
+#[test]
+fn canceled_games_return_each_nft_to_its_staker() {
+    let mut setup = setup();
+    let creator = setup.creator.pubkey();
+    let creator_nft = locked_nft(&mut setup.svm, &creator);
+    let args = nft_game(&setup, creator_nft);
+    send_ok(&mut setup.svm, &[create_game(&args)], &setup.creator, &[]);
+    let game = args.game();
+    assert_eq!(nft_balance(&setup.svm, &creator, &creator_nft), 0);
+
+    // Not before the game is decided
+    let claimer = funded(&mut setup.svm, LAMPORTS_PER_SOL);
+    let state = game_state(&setup.svm, &game);
+    let ix = claim_nft(&claimer.pubkey(), &game, &state, Side::Player1, &creator);
+    send_err(&mut setup.svm, &[ix], &claimer, &[], WagerError::GameNotReady);
+
+    warp_to(&mut setup.svm, args.expiry_ts);
+    send_ok(&mut setup.svm, &[cancel_if_expired(&creator, &game, &state)], &setup.creator, &[]);
+    let state = game_state(&setup.svm, &game);
+    assert!(state.state().unwrap() == GameState::Canceled);
+    send_ok(&mut setup.svm, &[claim_nft(&claimer.pubkey(), &game, &state, Side::Player1, &creator)], &claimer, &[]);
+    assert_eq!(nft_balance(&setup.svm, &creator, &creator_nft), 1);
+}
+
+#[test]
+fn nfts_someone_could_mint_or_freeze_are_rejected() {
+    let mut setup = setup();
+    let creator = setup.creator.pubkey();
+    let someone = COption::Some(Pubkey::new_unique());
+    let unlocked = [
+        // A live mint authority could mint a second copy
+        nft(&mut setup.svm, &creator, |_| [someone, COption::None], 1),
+        // ... and a live freeze authority could freeze the escrow
+        nft(&mut setup.svm, &creator, |_| [COption::None, someone], 1),
+        nft(&mut setup.svm, &creator, |mint| [COption::Some(MasterEdition::find_pda(&mint).0), someone], 1),
+        // Not a single token
+        nft(&mut setup.svm, &creator, |_| [COption::None; 2], 2),
+    ];
+    for nft in unlocked {
+        let args = nft_game(&setup, nft);
+        send_err(&mut setup.svm, &[create_game(&args)], &setup.creator, &[], WagerError::InvalidNft);
+    }
+
+    // Authorities that are gone or held by the master edition are fine
+    let renounced = nft(&mut setup.svm, &creator, |_| [COption::None; 2], 1);
+    let args = nft_game(&setup, renounced);
+    send_ok(&mut setup.svm, &[create_game(&args)], &setup.creator, &[]);
+    let locked = locked_nft(&mut setup.svm, &creator);
+    let args = CreateGameArgs { nonce: 1, ..nft_game(&setup, locked) };
+    send_ok(&mut setup.svm, &[create_game(&args)], &setup.creator, &[]);
+}
+
+#[test]
+fn asset_fee_needs_an_nft_and_stays_bounded() {
+    let mut setup = setup();
+    let creator = setup.creator.pubkey();
+    let nft = locked_nft(&mut setup.svm, &creator);
+
+    // Both sides' fees must add up without overflowing
+    let args = CreateGameArgs { asset_fee: Game::MAX_ASSET_FEE + 1, ..nft_game(&setup, nft) };
+    send_err(&mut setup.svm, &[create_game(&args)], &setup.creator, &[], WagerError::InvalidAssetFee);
+
+    // Fungible games have no asset fee
+    let mut args = CreateGameArgs::new(creator, SOL, WAGER, 9_500, now(&setup.svm) + 600, 0, setup.dev);
+    args.asset_fee = ASSET_FEE;
+    send_err(&mut setup.svm, &[create_game(&args)], &setup.creator, &[], WagerError::InvalidAssetFee);
+}
@@ -0,0 +1,170 @@
+//! Matchmaking queues: stakes escrowed on enqueue, the two longest-waiting
+//! players paired into a ready game, and refunds for players leaving early.
+
+use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+use anchor_spl::associated_token::get_associated_token_address;
+use easy_wager_client::instructions::*;
+use easy_wager_client::state::{Game, GameState, MatchQueue};
+use easy_wager_client::*;
+use easy_wager_harness::litesvm::LiteSVM;
+use easy_wager_harness::*;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+const SOL: Pubkey = Pubkey::new_from_array([0; 32]);
+const WAGER: u64 = LAMPORTS_PER_SOL;
+
+struct Setup {
+    svm: LiteSVM,
+    resolver: Keypair,
+    cranker: Keypair,
+}
+
+fn setup() -> Setup {
+    let mut svm = svm();
+    Setup {
+        resolver: funded(&mut svm, LAMPORTS_PER_SOL),
+        cranker: funded(&mut svm, LAMPORTS_PER_SOL),
+        svm,
+    }
+}
+
+/// Opens an unranked queue for `wager` in `mint` and returns its address.
+fn open_queue(setup: &mut Setup, mint: &Pubkey, wager: u64) -> Pubkey {
+    let resolver = setup.resolver.pubkey();
+    let ix = create_queue(&resolver, mint, wager, 0, 9_500, 600, &Pubkey::new_unique());
+    send_ok(&mut setup.svm, &[ix], &setup.resolver, &[]);
+    find_queue_pda(&resolver, mint, wager, 0).0
+}
+
+fn join(setup: &mut Setup, queue: &Pubkey, player: &Keypair) {
+    let state: MatchQueue = account(&setup.svm, queue);
+    send_ok(&mut setup.svm, &[enqueue(&player.pubkey(), queue, &state)], player, &[]);
+}
+
+/// Cranks the queue and returns the matched game.
+fn crank(setup: &mut Setup, queue: &Pubkey) -> Game {
+    let state: MatchQueue = account(&setup.svm, queue);
+    let game = find_matched_game_pda(queue, state.games_matched).0;
+    send_ok(&mut setup.svm, &[match_players(&setup.cranker.pubkey(), queue, &state)], &setup.cranker, &[]);
+    decode_game(&setup.svm.get_account(&game).unwrap().data).unwrap()
+}
+
+fn waiting(svm: &LiteSVM, queue: &Pubkey) -> Vec<Pubkey> {
+    account::<MatchQueue>(svm, queue).waiting().to_vec()
+}
+
+#[test]
+fn players_are_matched_in_arrival_order() {
+    let mut setup = setup();
+    let queue = open_queue(&mut setup, &SOL, WAGER);
+    let players: Vec<Keypair> = (0..4).map(|_| funded(&mut setup.svm, 10 * LAMPORTS_PER_SOL)).collect();
+    for player in &players[..3] {
+        join(&mut setup, &queue, player);
+    }
+    let queue_vault = find_sol_vault_pda(&queue).0;
+    let reserve = setup.svm.minimum_balance_for_rent_exemption(0);
+    assert_eq!(lamports(&setup.svm, &queue_vault), reserve + 3 * WAGER);
+
+    let game = crank(&mut setup, &queue);
+    assert_eq!((game.player1, game.player2), (players[0].pubkey(), players[1].pubkey()));
+    assert!(game.state().unwrap() == GameState::Ready);
+    assert_eq!(game.resolver, setup.resolver.pubkey());
+    let game_address = find_matched_game_pda(&queue, 0).0;
+    assert_eq!(lamports(&setup.svm, &find_sol_vault_pda(&game_address).0), reserve + 2 * WAGER);
+    assert_eq!(lamports(&setup.svm, &queue_vault), reserve + WAGER);
+    assert_eq!(waiting(&setup.svm, &queue), [players[2].pubkey()]);
+
+    // The player left over is matched first next time
+    join(&mut setup, &queue, &players[3]);
+    let game = crank(&mut setup, &queue);
+    assert_eq!((game.player1, game.player2), (players[2].pubkey(), players[3].pubkey()));
+    assert_eq!(game.nonce, 1);
+    assert!(waiting(&setup.svm, &queue).is_empty());
+}
+
+#[test]
+fn leaving_refunds_the_stake_and_keeps_the_others_in_order() {
+    let mut setup = setup();
+    let queue = open_queue(&mut setup, &SOL, WAGER);
+    let players: Vec<Keypair> = (0..3).map(|_| funded(&mut setup.svm, 10 * LAMPORTS_PER_SOL)).collect();
+    for player in &players {
+        join(&mut setup, &queue, player);
+    }
+
+    let leaving = &players[1];
+    let before = lamports(&setup.svm, &leaving.pubkey());
+    let state: MatchQueue = account(&setup.svm, &queue);
+    send_ok(&mut setup.svm, &[dequeue(&leaving.pubkey(), &queue, &state)], leaving, &[]);
+    assert_eq!(lamports(&setup.svm, &leaving.pubkey()), before + WAGER - 5_000);
+    assert_eq!(waiting(&setup.svm, &queue), [players[0].pubkey(), players[2].pubkey()]);
+
+    let game = crank(&mut setup, &queue);
+    assert_eq!((game.player1, game.player2), (players[0].pubkey(), players[2].pubkey()));
+}
+
+#[test]
+fn only_waiting_players_may_leave() {
+    let mut setup = setup();
+    let queue = open_queue(&mut setup, &SOL, WAGER);
+    let member = funded(&mut setup.svm, 10 * LAMPORTS_PER_SOL);
+    join(&mut setup, &queue, &member);
+    let queue_vault = find_sol_vault_pda(&queue).0;
+    let escrowed = lamports(&setup.svm, &queue_vault);
+
+    let stranger = funded(&mut setup.svm, LAMPORTS_PER_SOL);
+    let state: MatchQueue = account(&setup.svm, &queue);
+    send_err(&mut setup.svm, &[dequeue(&stranger.pubkey(), &queue, &state)], &stranger, &[], WagerError::NotQueued);
+    assert_eq!(lamports(&setup.svm, &queue_vault), escrowed);
+    assert_eq!(waiting(&setup.svm, &queue), [member.pubkey()]);
+
+    // Nobody waits twice, and leaves only once
+    send_err(&mut setup.svm, &[enqueue(&member.pubkey(), &queue, &state)], &member, &[], WagerError::AlreadyQueued);
+    send_ok(&mut setup.svm, &[dequeue(&member.pubkey(), &queue, &state)], &member, &[]);
+    send_err(&mut setup.svm, &[dequeue(&member.pubkey(), &queue, &state)], &member, &[], WagerError::NotQueued);
+}
+
+#[test]
+fn players_at_different_stakes_are_never_paired() {
+    let mut setup = setup();
+    let low = open_queue(&mut setup, &SOL, WAGER);
+    let high = open_queue(&mut setup, &SOL, 2 * WAGER);
+    let players = [funded(&mut setup.svm, 10 * LAMPORTS_PER_SOL), funded(&mut setup.svm, 10 * LAMPORTS_PER_SOL)];
+    join(&mut setup, &low, &players[0]);
+    join(&mut setup, &high, &players[1]);
+    let reserve = setup.svm.minimum_balance_for_rent_exemption(0);
+    assert_eq!(lamports(&setup.svm, &find_sol_vault_pda(&high).0), reserve + 2 * WAGER);
+
+    for queue in [low, high] {
+        let state: MatchQueue = account(&setup.svm, &queue);
+        let ix = match_players(&setup.cranker.pubkey(), &queue, &state);
+        send_err(&mut setup.svm, &[ix], &setup.cranker, &[], WagerError::NotEnoughQueued);
+    }
+}
+
+#[test]
+fn spl_queues_only_take_their_own_mint() {
+    let mut setup = setup();
+    let mint_authority = funded(&mut setup.svm, LAMPORTS_PER_SOL);
+    let mint = create_mint(&mut setup.svm, &mint_authority.pubkey(), 6);
+    let other_mint = create_mint(&mut setup.svm, &mint_authority.pubkey(), 6);
+    let queue = open_queue(&mut setup, &mint, WAGER);
+    let player = funded(&mut setup.svm, LAMPORTS_PER_SOL);
+    for mint in [mint, other_mint] {
+        let ata = create_ata(&mut setup.svm, &player, &player.pubkey(), &mint);
+        mint_to(&mut setup.svm, &mint, &mint_authority, &ata, WAGER);
+    }
+
+    // Staking from a token account in another mint
+    let state: MatchQueue = account(&setup.svm, &queue);
+    let mut ix = enqueue(&player.pubkey(), &queue, &state);
+    let own_account = get_associated_token_address(&player.pubkey(), &mint);
+    let slot = ix.accounts.iter().position(|meta| meta.pubkey == own_account).unwrap();
+    ix.accounts[slot].pubkey = get_associated_token_address(&player.pubkey(), &other_mint);
+    assert!(send(&mut setup.svm, &[ix], &player, &[]).is_err());
+    assert!(waiting(&setup.svm, &queue).is_empty());
+
+    join(&mut setup, &queue, &player);
+    assert_eq!(token_balance(&setup.svm, &find_vault_pda(&queue, &mint).0), WAGER);
+    assert_eq!(token_balance(&setup.svm, &own_account), 0);
+}
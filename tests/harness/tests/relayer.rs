@@ -0,0 +1,59 @@
+//! Relayers sponsoring `create_game` / `join_game` and charging for it.
+
+use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+use easy_wager_client::instructions::*;
+use easy_wager_client::{decode_cpi_event, decode_game, WagerError, WagerEvent};
+use easy_wager_harness::*;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+
+const SOL: Pubkey = Pubkey::new_from_array([0; 32]);
+const WAGER: u64 = LAMPORTS_PER_SOL;
+const FEE: u64 = 10_000;
+
+#[test]
+fn relayer_is_reimbursed_from_the_player_stake() {
+    let mut svm = svm();
+    let player = funded(&mut svm, 10 * LAMPORTS_PER_SOL);
+    let relayer = funded(&mut svm, LAMPORTS_PER_SOL);
+    let mut args = CreateGameArgs::new(player.pubkey(), SOL, WAGER, 9_500, now(&svm) + 600, 0, Pubkey::new_unique());
+    args.payer = relayer.pubkey();
+    args.relayer_fee = FEE;
+
+    let player_before = lamports(&svm, &player.pubkey());
+    let meta = send(&mut svm, &[create_game(&args)], &relayer, &[&player]).unwrap();
+
+    assert_eq!(lamports(&svm, &player.pubkey()), player_before - WAGER - FEE);
+    let state = decode_game(&svm.get_account(&args.game()).unwrap().data).unwrap();
+    assert_eq!(state.player1, player.pubkey());
+
+    // The deposit `GameCreated` reports is the stake, without the fee
+    let created = meta
+        .inner_instructions
+        .iter()
+        .flatten()
+        .find_map(|inner| match decode_cpi_event(&inner.instruction.data) {
+            Some(WagerEvent::GameCreated(event)) => Some(event),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!((created.wager, created.amount), (WAGER, WAGER));
+}
+
+#[test]
+fn players_paying_for_themselves_cannot_charge_a_fee() {
+    let mut svm = svm();
+    let creator = funded(&mut svm, 10 * LAMPORTS_PER_SOL);
+    let player2 = funded(&mut svm, 10 * LAMPORTS_PER_SOL);
+
+    let mut args = CreateGameArgs::new(creator.pubkey(), SOL, WAGER, 9_500, now(&svm) + 600, 0, Pubkey::new_unique());
+    args.relayer_fee = FEE;
+    send_err(&mut svm, &[create_game(&args)], &creator, &[], WagerError::RelayerFeeNotAllowed);
+
+    args.relayer_fee = 0;
+    send_ok(&mut svm, &[create_game(&args)], &creator, &[]);
+    let game = args.game();
+    let state = decode_game(&svm.get_account(&game).unwrap().data).unwrap();
+    let join = JoinGameArgs { relayer_fee: FEE, ..JoinGameArgs::new(player2.pubkey()) };
+    send_err(&mut svm, &[join_game(&game, &state, &join)], &player2, &[], WagerError::RelayerFeeNotAllowed);
+}
@@ -0,0 +1,182 @@
+//! `resolve_many` settling several games at once, atomically or skipping the
+//! games that can't be resolved.
+
+use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+use easy_wager_client::instructions::*;
+use easy_wager_client::state::{BatchPolicy, Game, GameState, PlayerStats};
+use easy_wager_client::{decode_game, find_player_stats_pda, WagerError};
+use easy_wager_harness::litesvm::LiteSVM;
+use easy_wager_harness::*;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+const SOL: Pubkey = Pubkey::new_from_array([0; 32]);
+const WAGER: u64 = LAMPORTS_PER_SOL;
+const PAYOUT: u64 = 2 * WAGER * 9_500 / 10_000;
+
+struct Setup {
+    svm: LiteSVM,
+    resolver: Keypair,
+    alice: Keypair,
+    bob: Keypair,
+    carol: Keypair,
+    // Alice against Bob, then Carol against Alice
+    games: [Pubkey; 2],
+}
+
+/// Two ready games sharing Alice, player1 in the first and player2 in the
+/// second, both resolved by the same resolver.
+fn setup() -> Setup {
+    let mut svm = svm();
+    let resolver = funded(&mut svm, LAMPORTS_PER_SOL);
+    let alice = funded(&mut svm, 10 * LAMPORTS_PER_SOL);
+    let bob = funded(&mut svm, 10 * LAMPORTS_PER_SOL);
+    let carol = funded(&mut svm, 10 * LAMPORTS_PER_SOL);
+
+    let first = open_game(&mut svm, &alice, 0, &resolver.pubkey());
+    join(&mut svm, &first, &bob);
+    let second = open_game(&mut svm, &carol, 0, &resolver.pubkey());
+    join(&mut svm, &second, &alice);
+
+    Setup { svm, resolver, alice, bob, carol, games: [first, second] }
+}
+
+fn open_game(svm: &mut LiteSVM, creator: &Keypair, nonce: u64, resolver: &Pubkey) -> Pubkey {
+    let mut args = CreateGameArgs::new(creator.pubkey(), SOL, WAGER, 9_500, now(svm) + 600, nonce, Pubkey::new_unique());
+    args.resolver = Some(*resolver);
+    send_ok(svm, &[create_game(&args)], creator, &[]);
+    args.game()
+}
+
+fn join(svm: &mut LiteSVM, game: &Pubkey, player2: &Keypair) {
+    let state = game_state(svm, game);
+    send_ok(svm, &[join_game(game, &state, &JoinGameArgs::new(player2.pubkey()))], player2, &[]);
+}
+
+fn game_state(svm: &LiteSVM, game: &Pubkey) -> Game {
+    decode_game(&svm.get_account(game).unwrap().data).unwrap()
+}
+
+fn stats(svm: &LiteSVM, wallet: &Pubkey) -> PlayerStats {
+    account(svm, &find_player_stats_pda(wallet).0)
+}
+
+/// Resolves the games of `setup` with the given winners in one instruction.
+fn resolve(setup: &Setup, winners: [Pubkey; 2], policy: BatchPolicy) -> solana_sdk::instruction::Instruction {
+    let states = setup.games.map(|game| game_state(&setup.svm, &game));
+    let games = [
+        (setup.games[0], &states[0], winners[0]),
+        (setup.games[1], &states[1], winners[1]),
+    ];
+    resolve_many(&setup.resolver.pubkey(), &games, policy)
+}
+
+#[test]
+fn atomic_batch_settles_games_sharing_a_player() {
+    let mut setup = setup();
+    let alice = setup.alice.pubkey();
+
+    let ix = resolve(&setup, [alice, alice], BatchPolicy::Atomic);
+    send_ok(&mut setup.svm, &[ix], &setup.resolver, &[]);
+
+    for game in setup.games {
+        let state = game_state(&setup.svm, &game);
+        assert!(state.state().unwrap() == GameState::Paid);
+        assert_eq!(state.winner, alice);
+        assert_eq!(state.claimable_by(&alice).unwrap(), PAYOUT);
+    }
+    // Alice's stats saw both games, one after the other
+    let alice_stats = stats(&setup.svm, &alice);
+    assert_eq!((alice_stats.games_played, alice_stats.wins, alice_stats.losses), (2, 2, 0));
+    assert_eq!(alice_stats.mint_totals[0].wagered, 2 * WAGER);
+    assert_eq!(alice_stats.mint_totals[0].won, 2 * PAYOUT);
+    for loser in [setup.bob.pubkey(), setup.carol.pubkey()] {
+        let loser_stats = stats(&setup.svm, &loser);
+        assert_eq!((loser_stats.games_played, loser_stats.wins, loser_stats.losses), (1, 0, 1));
+    }
+}
+
+#[test]
+fn atomic_batch_fails_as_a_whole_on_one_invalid_game() {
+    let mut setup = setup();
+    let alice = setup.alice.pubkey();
+
+    // Bob isn't playing the second game
+    let ix = resolve(&setup, [alice, setup.bob.pubkey()], BatchPolicy::Atomic);
+    send_err(&mut setup.svm, &[ix], &setup.resolver, &[], WagerError::InvalidWinner);
+
+    for game in setup.games {
+        assert!(game_state(&setup.svm, &game).state().unwrap() == GameState::Ready);
+    }
+    assert_eq!(stats(&setup.svm, &alice).games_played, 0);
+}
+
+#[test]
+fn skip_invalid_batch_settles_the_other_games() {
+    let mut setup = setup();
+    let alice = setup.alice.pubkey();
+
+    let ix = resolve(&setup, [alice, setup.bob.pubkey()], BatchPolicy::SkipInvalid);
+    send_ok(&mut setup.svm, &[ix], &setup.resolver, &[]);
+
+    let [first, second] = setup.games.map(|game| game_state(&setup.svm, &game));
+    assert!(first.state().unwrap() == GameState::Paid);
+    assert_eq!(first.claimable_by(&alice).unwrap(), PAYOUT);
+    // The skipped game and its players are left as they were
+    assert!(second.state().unwrap() == GameState::Ready);
+    assert_eq!(second.winner, Pubkey::default());
+    let alice_stats = stats(&setup.svm, &alice);
+    assert_eq!((alice_stats.games_played, alice_stats.wins), (1, 1));
+    assert_eq!(stats(&setup.svm, &setup.carol.pubkey()).games_played, 0);
+}
+
+#[test]
+fn skip_invalid_batch_skips_games_of_another_resolver_or_not_ready() {
+    let mut setup = setup();
+    let alice = setup.alice.pubkey();
+    let other_resolver = Pubkey::new_unique();
+    let Setup { svm, resolver, bob, carol, .. } = &mut setup;
+    let foreign = open_game(svm, bob, 0, &other_resolver);
+    join(svm, &foreign, carol);
+    let open = open_game(svm, carol, 1, &resolver.pubkey());
+
+    let states = [setup.games[0], foreign, open].map(|game| game_state(&setup.svm, &game));
+    let bob = setup.bob.pubkey();
+    let games = [(setup.games[0], &states[0], alice), (foreign, &states[1], bob), (open, &states[2], bob)];
+
+    // Atomic batches stop at the first game the resolver can't settle
+    let atomic = resolve_many(&setup.resolver.pubkey(), &games, BatchPolicy::Atomic);
+    send_err(&mut setup.svm, &[atomic], &setup.resolver, &[], WagerError::UnauthorizedResolver);
+
+    let skip = resolve_many(&setup.resolver.pubkey(), &games, BatchPolicy::SkipInvalid);
+    send_ok(&mut setup.svm, &[skip], &setup.resolver, &[]);
+    assert!(game_state(&setup.svm, &setup.games[0]).state().unwrap() == GameState::Paid);
+    assert!(game_state(&setup.svm, &foreign).state().unwrap() == GameState::Ready);
+    assert!(game_state(&setup.svm, &open).state().unwrap() == GameState::Open);
+}
+
+#[test]
+fn draw_refunds_both_players_and_counts_a_draw() {
+    let mut setup = setup();
+    let (alice, bob) = (setup.alice.pubkey(), setup.bob.pubkey());
+
+    let ix = resolve(&setup, [Game::DRAW, alice], BatchPolicy::Atomic);
+    send_ok(&mut setup.svm, &[ix], &setup.resolver, &[]);
+
+    let drawn = game_state(&setup.svm, &setup.games[0]);
+    assert!(drawn.state().unwrap() == GameState::Canceled);
+    assert_eq!(drawn.claimable_by(&alice).unwrap(), WAGER);
+    assert_eq!(drawn.claimable_by(&bob).unwrap(), WAGER);
+    let alice_stats = stats(&setup.svm, &alice);
+    assert_eq!((alice_stats.games_played, alice_stats.wins, alice_stats.draws), (2, 1, 1));
+    let bob_stats = stats(&setup.svm, &bob);
+    assert_eq!((bob_stats.games_played, bob_stats.wins, bob_stats.losses, bob_stats.draws), (1, 0, 0, 1));
+
+    // Both stakes come back through the usual refund claim
+    for player in [&setup.alice, &setup.bob] {
+        let before = lamports(&setup.svm, &player.pubkey());
+        let ix = claim(&player.pubkey(), &setup.games[0], &drawn, &player.pubkey(), ClaimTo::Wallet);
+        send_ok(&mut setup.svm, &[ix], player, &[]);
+        assert!(lamports(&setup.svm, &player.pubkey()) > before + WAGER - 10_000);
+    }
+}
@@ -0,0 +1,108 @@
+//! Best-of-N series: rounds reported one at a time, settling the game once a
+//! player has won a majority.
+
+use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+use easy_wager_client::instructions::*;
+use easy_wager_client::state::{Game, GameState};
+use easy_wager_client::{decode_game, WagerError};
+use easy_wager_harness::litesvm::LiteSVM;
+use easy_wager_harness::*;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+const SOL: Pubkey = Pubkey::new_from_array([0; 32]);
+const WAGER: u64 = LAMPORTS_PER_SOL;
+
+struct Setup {
+    svm: LiteSVM,
+    resolver: Keypair,
+    player1: Pubkey,
+    player2: Pubkey,
+    game: Pubkey,
+}
+
+/// A ready best-of-`best_of` native SOL game.
+fn setup(best_of: u8) -> Setup {
+    let mut svm = svm();
+    let resolver = funded(&mut svm, LAMPORTS_PER_SOL);
+    let player1 = funded(&mut svm, 10 * LAMPORTS_PER_SOL);
+    let player2 = funded(&mut svm, 10 * LAMPORTS_PER_SOL);
+
+    let mut args = CreateGameArgs::new(player1.pubkey(), SOL, WAGER, 9_500, now(&svm) + 600, 0, Pubkey::new_unique());
+    args.resolver = Some(resolver.pubkey());
+    args.best_of = best_of;
+    send_ok(&mut svm, &[create_game(&args)], &player1, &[]);
+    let game = args.game();
+    let state = game_state(&svm, &game);
+    send_ok(&mut svm, &[join_game(&game, &state, &JoinGameArgs::new(player2.pubkey()))], &player2, &[]);
+
+    Setup { svm, resolver, player1: player1.pubkey(), player2: player2.pubkey(), game }
+}
+
+fn game_state(svm: &LiteSVM, game: &Pubkey) -> Game {
+    decode_game(&svm.get_account(game).unwrap().data).unwrap()
+}
+
+fn report(setup: &mut Setup, winner: &Pubkey) {
+    let state = game_state(&setup.svm, &setup.game);
+    let ix = report_round(&setup.resolver.pubkey(), &setup.game, &state, winner);
+    send_ok(&mut setup.svm, &[ix], &setup.resolver, &[]);
+}
+
+#[test]
+fn rounds_are_counted_until_a_player_reaches_the_threshold() {
+    let mut setup = setup(5);
+    let (player1, player2) = (setup.player1, setup.player2);
+
+    for (winner, score) in [(player1, (1, 0)), (player2, (1, 1)), (player1, (2, 1)), (player2, (2, 2))] {
+        report(&mut setup, &winner);
+        let state = game_state(&setup.svm, &setup.game);
+        assert_eq!((state.player1_wins, state.player2_wins), score);
+        assert!(state.state().unwrap() == GameState::Ready);
+    }
+
+    // Three wins out of five take the series
+    report(&mut setup, &player2);
+    let state = game_state(&setup.svm, &setup.game);
+    assert_eq!((state.player1_wins, state.player2_wins), (2, 3));
+    assert!(state.state().unwrap() == GameState::Paid);
+    assert_eq!(state.winner, player2);
+}
+
+#[test]
+fn rounds_are_rejected_once_the_series_is_decided() {
+    let mut setup = setup(3);
+    let player1 = setup.player1;
+    report(&mut setup, &player1);
+    report(&mut setup, &player1);
+    assert!(game_state(&setup.svm, &setup.game).state().unwrap() == GameState::Paid);
+
+    let state = game_state(&setup.svm, &setup.game);
+    let ix = report_round(&setup.resolver.pubkey(), &setup.game, &state, &player1);
+    send_err(&mut setup.svm, &[ix], &setup.resolver, &[], WagerError::GameNotReady);
+    let state = game_state(&setup.svm, &setup.game);
+    assert_eq!((state.player1_wins, state.player2_wins), (2, 0));
+}
+
+#[test]
+fn rounds_need_the_resolver_and_a_player() {
+    let mut setup = setup(3);
+    let state = game_state(&setup.svm, &setup.game);
+
+    let impostor = funded(&mut setup.svm, LAMPORTS_PER_SOL);
+    let ix = report_round(&impostor.pubkey(), &setup.game, &state, &setup.player1);
+    send_err(&mut setup.svm, &[ix], &impostor, &[], WagerError::UnauthorizedResolver);
+
+    let ix = report_round(&setup.resolver.pubkey(), &setup.game, &state, &Pubkey::new_unique());
+    send_err(&mut setup.svm, &[ix], &setup.resolver, &[], WagerError::InvalidWinner);
+    let state = game_state(&setup.svm, &setup.game);
+    assert_eq!((state.player1_wins, state.player2_wins), (0, 0));
+}
+
+#[test]
+fn single_games_have_no_rounds() {
+    let mut setup = setup(1);
+    let state = game_state(&setup.svm, &setup.game);
+    let ix = report_round(&setup.resolver.pubkey(), &setup.game, &state, &setup.player1);
+    send_err(&mut setup.svm, &[ix], &setup.resolver, &[], WagerError::NotASeries);
+}
@@ -0,0 +1,187 @@
+//! Session keys staking from their owner's player balance.
+
+use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+use easy_wager_client::instructions::*;
+use easy_wager_client::state::{PlayerBalance, SessionAuthority};
+use easy_wager_client::{decode_game, find_balance_pda, find_session_pda, WagerError};
+use easy_wager_harness::litesvm::LiteSVM;
+use easy_wager_harness::*;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+const SOL: Pubkey = Pubkey::new_from_array([0; 32]);
+const WAGER: u64 = LAMPORTS_PER_SOL;
+
+struct Setup {
+    svm: LiteSVM,
+    owner: Keypair,
+    session_key: Keypair,
+    resolver: Keypair,
+    dev_wallet: Pubkey,
+}
+
+/// An owner with 5 SOL in their balance and a session key allowed to stake up
+/// to `spend_cap` on games resolved by `resolver` paying at least 90%.
+fn setup(spend_cap: u64) -> Setup {
+    let mut svm = svm();
+    let owner = funded(&mut svm, 10 * LAMPORTS_PER_SOL);
+    let session_key = funded(&mut svm, LAMPORTS_PER_SOL);
+    let resolver = funded(&mut svm, LAMPORTS_PER_SOL);
+    let dev_wallet = Pubkey::new_unique();
+
+    send_ok(&mut svm, &[deposit_balance(&owner.pubkey(), &SOL, 5 * WAGER)], &owner, &[]);
+    let approve = approve_session(&ApproveSessionArgs {
+        owner: owner.pubkey(),
+        session_key: session_key.pubkey(),
+        spend_cap,
+        allowed_mints: vec![SOL],
+        expires_at: now(&svm) + 300,
+        resolver: resolver.pubkey(),
+        dev_wallet,
+        min_payout_bps: 9_000,
+    });
+    send_ok(&mut svm, &[approve], &owner, &[]);
+
+    Setup { svm, owner, session_key, resolver, dev_wallet }
+}
+
+/// Opens a SOL game from a fresh creator, returning its address.
+fn open_game(setup: &mut Setup, resolver: Pubkey, dev_wallet: Pubkey, payout_bps: u16) -> Pubkey {
+    let creator = funded(&mut setup.svm, 10 * LAMPORTS_PER_SOL);
+    let mut args = CreateGameArgs::new(creator.pubkey(), SOL, WAGER, payout_bps, now(&setup.svm) + 600, 0, dev_wallet);
+    args.resolver = Some(resolver);
+    send_ok(&mut setup.svm, &[create_game(&args)], &creator, &[]);
+    args.game()
+}
+
+/// Opens a SOL game on the terms the session was approved for.
+fn approved_game(setup: &mut Setup) -> Pubkey {
+    let (resolver, dev_wallet) = (setup.resolver.pubkey(), setup.dev_wallet);
+    open_game(setup, resolver, dev_wallet, 9_500)
+}
+
+fn join_as_session(setup: &Setup, game: &Pubkey) -> Vec<solana_sdk::instruction::Instruction> {
+    let state = decode_game(&setup.svm.get_account(game).unwrap().data).unwrap();
+    let args = JoinGameArgs {
+        session_owner: Some(setup.owner.pubkey()),
+        use_balance: true,
+        ..JoinGameArgs::new(setup.session_key.pubkey())
+    };
+    vec![join_game(game, &state, &args)]
+}
+
+fn session(setup: &Setup) -> SessionAuthority {
+    account(&setup.svm, &find_session_pda(&setup.owner.pubkey(), &setup.session_key.pubkey()).0)
+}
+
+fn balance(setup: &Setup) -> u64 {
+    account::<PlayerBalance>(&setup.svm, &find_balance_pda(&setup.owner.pubkey(), &SOL).0).amount
+}
+
+#[test]
+fn session_key_joins_for_its_owner() {
+    let mut setup = setup(2 * WAGER);
+    let game = approved_game(&mut setup);
+
+    let ixs = join_as_session(&setup, &game);
+    send_ok(&mut setup.svm, &ixs, &setup.session_key, &[]);
+
+    let state = decode_game(&setup.svm.get_account(&game).unwrap().data).unwrap();
+    assert_eq!(state.player2, setup.owner.pubkey());
+    assert_eq!(balance(&setup), 4 * WAGER);
+    assert_eq!(session(&setup).spent, WAGER);
+}
+
+#[test]
+fn session_key_creates_for_its_owner_with_the_approved_resolver() {
+    let mut setup = setup(2 * WAGER);
+    let mut args = CreateGameArgs::new(setup.session_key.pubkey(), SOL, WAGER, 9_500, now(&setup.svm) + 600, 0, setup.dev_wallet);
+    args.session_owner = Some(setup.owner.pubkey());
+    args.use_balance = true;
+
+    // Without a resolver the game would be resolved by the owner, which the
+    // owner didn't approve
+    send_err(&mut setup.svm, &[create_game(&args)], &setup.session_key, &[], WagerError::SessionTermsNotAllowed);
+
+    args.resolver = Some(setup.resolver.pubkey());
+    send_ok(&mut setup.svm, &[create_game(&args)], &setup.session_key, &[]);
+
+    let state = decode_game(&setup.svm.get_account(&args.game()).unwrap().data).unwrap();
+    assert_eq!(state.creator, setup.owner.pubkey());
+    assert_eq!(state.resolver, setup.resolver.pubkey());
+    assert_eq!(balance(&setup), 4 * WAGER);
+}
+
+#[test]
+fn session_key_cannot_pick_the_game_terms() {
+    let mut setup = setup(5 * WAGER);
+    let (resolver, dev_wallet, session_key) = (setup.resolver.pubkey(), setup.dev_wallet, setup.session_key.pubkey());
+    let own_resolver = open_game(&mut setup, session_key, dev_wallet, 9_500);
+    let own_dev_wallet = open_game(&mut setup, resolver, session_key, 9_500);
+    let low_payout = open_game(&mut setup, resolver, dev_wallet, 8_000);
+
+    for game in [own_resolver, own_dev_wallet, low_payout] {
+        let ixs = join_as_session(&setup, &game);
+        send_err(&mut setup.svm, &ixs, &setup.session_key, &[], WagerError::SessionTermsNotAllowed);
+    }
+    assert_eq!(balance(&setup), 5 * WAGER);
+}
+
+#[test]
+fn stakes_beyond_the_spend_cap_fail() {
+    let mut setup = setup(WAGER + WAGER / 2);
+    let first = approved_game(&mut setup);
+    let second = approved_game(&mut setup);
+
+    let ixs = join_as_session(&setup, &first);
+    send_ok(&mut setup.svm, &ixs, &setup.session_key, &[]);
+    let ixs = join_as_session(&setup, &second);
+    send_err(&mut setup.svm, &ixs, &setup.session_key, &[], WagerError::SessionCapExceeded);
+    assert_eq!(session(&setup).spent, WAGER);
+}
+
+#[test]
+fn expired_and_revoked_sessions_cannot_stake() {
+    let mut setup = setup(2 * WAGER);
+    let game = approved_game(&mut setup);
+    let ixs = join_as_session(&setup, &game);
+
+    let expires_at = session(&setup).expires_at;
+    warp_to(&mut setup.svm, expires_at);
+    send_err(&mut setup.svm, &ixs, &setup.session_key, &[], WagerError::SessionExpired);
+
+    warp_to(&mut setup.svm, expires_at - 60);
+    let revoke = revoke_session(&setup.owner.pubkey(), &setup.session_key.pubkey());
+    send_ok(&mut setup.svm, &[revoke], &setup.owner, &[]);
+    assert!(send(&mut setup.svm, &ixs, &setup.session_key, &[]).is_err());
+    assert_eq!(balance(&setup), 5 * WAGER);
+}
+
+#[test]
+fn session_key_only_stakes_from_the_balance() {
+    let mut setup = setup(2 * WAGER);
+    let game = approved_game(&mut setup);
+    let state = decode_game(&setup.svm.get_account(&game).unwrap().data).unwrap();
+    let args = JoinGameArgs { session_owner: Some(setup.owner.pubkey()), ..JoinGameArgs::new(setup.session_key.pubkey()) };
+
+    send_err(&mut setup.svm, &[join_game(&game, &state, &args)], &setup.session_key, &[], WagerError::SessionStakeNotAllowed);
+}
+
+#[test]
+fn session_key_cannot_pay_a_relayer_fee() {
+    let mut setup = setup(2 * WAGER);
+    let game = approved_game(&mut setup);
+    let relayer = funded(&mut setup.svm, LAMPORTS_PER_SOL);
+    let state = decode_game(&setup.svm.get_account(&game).unwrap().data).unwrap();
+    let args = JoinGameArgs {
+        payer: relayer.pubkey(),
+        relayer_fee: 10_000,
+        session_owner: Some(setup.owner.pubkey()),
+        use_balance: true,
+        ..JoinGameArgs::new(setup.session_key.pubkey())
+    };
+
+    let ix = join_game(&game, &state, &args);
+    send_err(&mut setup.svm, &[ix], &relayer, &[&setup.session_key], WagerError::RelayerFeeNotAllowed);
+    assert_eq!(session(&setup).spent, 0);
+}
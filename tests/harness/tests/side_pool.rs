@@ -0,0 +1,130 @@
+//! Spectator side pools: bets escrowed in the pool's SOL vault, paid out pro
+//! rata, and the pool closed with its rounding dust once everyone has claimed.
+
+use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+use easy_wager_client::instructions::*;
+use easy_wager_client::state::{Game, Side, SidePool};
+use easy_wager_client::{decode_game, find_side_pool_pda, find_sol_vault_pda, WagerError};
+use easy_wager_harness::litesvm::LiteSVM;
+use easy_wager_harness::*;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+const SOL: Pubkey = Pubkey::new_from_array([0; 32]);
+const WAGER: u64 = LAMPORTS_PER_SOL;
+const FEE_BPS: u16 = 100;
+
+struct Setup {
+    svm: LiteSVM,
+    creator: Keypair,
+    player2: Keypair,
+    resolver: Keypair,
+    dev: Pubkey,
+    game: Pubkey,
+    side_pool: Pubkey,
+}
+
+/// An open native SOL game with a side pool taking a 1% fee.
+fn setup() -> Setup {
+    let mut svm = svm();
+    let creator = funded(&mut svm, 10 * LAMPORTS_PER_SOL);
+    let player2 = funded(&mut svm, 10 * LAMPORTS_PER_SOL);
+    let resolver = funded(&mut svm, LAMPORTS_PER_SOL);
+    let dev = Pubkey::new_unique();
+
+    let mut args = CreateGameArgs::new(creator.pubkey(), SOL, WAGER, 9_500, now(&svm) + 600, 0, dev);
+    args.resolver = Some(resolver.pubkey());
+    send_ok(&mut svm, &[create_game(&args)], &creator, &[]);
+    let game = args.game();
+    let state = game_state(&svm, &game);
+    send_ok(&mut svm, &[create_side_pool(&creator.pubkey(), &game, &state, FEE_BPS, 0)], &creator, &[]);
+
+    Setup { svm, creator, player2, resolver, dev, game, side_pool: find_side_pool_pda(&game).0 }
+}
+
+fn game_state(svm: &LiteSVM, game: &Pubkey) -> Game {
+    decode_game(&svm.get_account(game).unwrap().data).unwrap()
+}
+
+fn bet(setup: &mut Setup, side: Side, amount: u64) -> Keypair {
+    let bettor = funded(&mut setup.svm, 5 * LAMPORTS_PER_SOL);
+    let pool: SidePool = account(&setup.svm, &setup.side_pool);
+    send_ok(&mut setup.svm, &[place_side_bet(&bettor.pubkey(), &setup.game, &pool, side, amount)], &bettor, &[]);
+    bettor
+}
+
+/// Player2 joins and the creator wins.
+fn play(setup: &mut Setup) {
+    let Setup { svm, player2, resolver, creator, game, .. } = setup;
+    let state = game_state(svm, game);
+    send_ok(svm, &[join_game(game, &state, &JoinGameArgs::new(player2.pubkey()))], player2, &[]);
+    let state = game_state(svm, game);
+    send_ok(svm, &[resolve_game(&resolver.pubkey(), game, &state, &creator.pubkey())], resolver, &[]);
+}
+
+#[test]
+fn bets_are_escrowed_in_the_sol_vault() {
+    let mut setup = setup();
+    let sol_vault = find_sol_vault_pda(&setup.side_pool).0;
+    let reserve = setup.svm.minimum_balance_for_rent_exemption(0);
+    assert_eq!(lamports(&setup.svm, &sol_vault), reserve);
+    let pool_rent = lamports(&setup.svm, &setup.side_pool);
+
+    bet(&mut setup, Side::Player1, WAGER);
+    bet(&mut setup, Side::Player2, 2 * WAGER);
+
+    assert_eq!(lamports(&setup.svm, &sol_vault), reserve + 3 * WAGER);
+    // The pool account itself only ever holds its rent
+    assert_eq!(lamports(&setup.svm, &setup.side_pool), pool_rent);
+    let pool: SidePool = account(&setup.svm, &setup.side_pool);
+    assert_eq!((pool.total_player1, pool.total_player2, pool.open_bets), (WAGER, 2 * WAGER, 2));
+}
+
+#[test]
+fn close_sweeps_the_rounding_dust_to_the_creator() {
+    let mut setup = setup();
+    let sol_vault = find_sol_vault_pda(&setup.side_pool).0;
+    // The losing side's 1_000_000_001 less the 1% fee splits 1:2 between
+    // the winners, rounding each share down and leaving one lamport over
+    let winners = [bet(&mut setup, Side::Player1, WAGER), bet(&mut setup, Side::Player1, 2 * WAGER)];
+    let loser = bet(&mut setup, Side::Player2, WAGER + 1);
+    play(&mut setup);
+
+    let pool: SidePool = account(&setup.svm, &setup.side_pool);
+    let closer = funded(&mut setup.svm, LAMPORTS_PER_SOL);
+    let state = game_state(&setup.svm, &setup.game);
+    let close = close_side_pool(&closer.pubkey(), &setup.game, &state);
+    // Nothing has been claimed yet
+    send_err(&mut setup.svm, std::slice::from_ref(&close), &closer, &[], WagerError::SidePoolNotEmpty);
+
+    for bettor in winners.iter().chain([&loser]) {
+        send_ok(&mut setup.svm, &[claim_side_bet(&closer.pubkey(), &pool, &bettor.pubkey())], &closer, &[]);
+    }
+    // Each winner's stake came back with their share, less the fee for
+    // placing the bet
+    assert_eq!(lamports(&setup.svm, &winners[0].pubkey()), 5 * LAMPORTS_PER_SOL - 5_000 + 330_000_000);
+    assert_eq!(lamports(&setup.svm, &winners[1].pubkey()), 5 * LAMPORTS_PER_SOL - 5_000 + 660_000_000);
+    // The fee is still owed
+    send_err(&mut setup.svm, std::slice::from_ref(&close), &closer, &[], WagerError::SidePoolNotEmpty);
+    send_ok(&mut setup.svm, &[claim_side_pool_fee(&closer.pubkey(), &setup.game, &state)], &closer, &[]);
+    assert_eq!(lamports(&setup.svm, &setup.dev), 10_000_000);
+
+    let reserve = setup.svm.minimum_balance_for_rent_exemption(0);
+    assert_eq!(lamports(&setup.svm, &sol_vault), reserve + 1);
+    let pool_rent = lamports(&setup.svm, &setup.side_pool);
+    let creator_before = lamports(&setup.svm, &setup.creator.pubkey());
+    send_ok(&mut setup.svm, &[close], &closer, &[]);
+
+    assert_eq!(lamports(&setup.svm, &setup.creator.pubkey()), creator_before + 1 + reserve + pool_rent);
+    assert_eq!(lamports(&setup.svm, &sol_vault), 0);
+    assert!(setup.svm.get_account(&setup.side_pool).is_none_or(|account| account.lamports == 0));
+}
+
+#[test]
+fn only_settled_pools_close() {
+    let mut setup = setup();
+    let closer = funded(&mut setup.svm, LAMPORTS_PER_SOL);
+    let state = game_state(&setup.svm, &setup.game);
+    let close = close_side_pool(&closer.pubkey(), &setup.game, &state);
+    send_err(&mut setup.svm, &[close], &closer, &[], WagerError::SidePoolNotSettled);
+}
@@ -0,0 +1,101 @@
+//! Native SOL tournaments: entry fees escrowed in the tournament's SOL vault
+//! and paid out of it as prizes, the dev fee or refunds.
+
+use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+use easy_wager_client::instructions::*;
+use easy_wager_client::state::Tournament;
+use easy_wager_client::{find_sol_vault_pda, find_tournament_pda};
+use easy_wager_harness::litesvm::LiteSVM;
+use easy_wager_harness::*;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+const SOL: Pubkey = Pubkey::new_from_array([0; 32]);
+const ENTRY_FEE: u64 = LAMPORTS_PER_SOL;
+
+struct Setup {
+    svm: LiteSVM,
+    organizer: Keypair,
+    dev: Pubkey,
+    tournament: Pubkey,
+    players: [Keypair; 2],
+}
+
+/// A two-player native SOL tournament with a 5% fee and both players in.
+fn setup() -> Setup {
+    let mut svm = svm();
+    let organizer = funded(&mut svm, 10 * LAMPORTS_PER_SOL);
+    let dev = Pubkey::new_unique();
+    let args = CreateTournamentArgs {
+        organizer: organizer.pubkey(),
+        mint: SOL,
+        entry_fee: ENTRY_FEE,
+        fee_bps: 500,
+        max_players: 2,
+        registration_end_ts: now(&svm) + 600,
+        payout_table: vec![10_000],
+        resolver: None,
+        nonce: 0,
+        dev_wallet: dev,
+    };
+    send_ok(&mut svm, &[create_tournament(&args)], &organizer, &[]);
+    let tournament = find_tournament_pda(&organizer.pubkey(), 0).0;
+
+    let players = [funded(&mut svm, 10 * LAMPORTS_PER_SOL), funded(&mut svm, 10 * LAMPORTS_PER_SOL)];
+    for player in &players {
+        let state: Tournament = account(&svm, &tournament);
+        send_ok(&mut svm, &[register_tournament(&player.pubkey(), &tournament, &state)], player, &[]);
+    }
+
+    Setup { svm, organizer, dev, tournament, players }
+}
+
+fn claim(setup: &mut Setup, recipient: &Pubkey) {
+    let claimer = funded(&mut setup.svm, LAMPORTS_PER_SOL);
+    let state: Tournament = account(&setup.svm, &setup.tournament);
+    let ix = claim_tournament_prize(&claimer.pubkey(), &setup.tournament, &state, recipient);
+    send_ok(&mut setup.svm, &[ix], &claimer, &[]);
+}
+
+#[test]
+fn prizes_and_fee_are_paid_from_the_sol_vault() {
+    let mut setup = setup();
+    let sol_vault = find_sol_vault_pda(&setup.tournament).0;
+    let reserve = setup.svm.minimum_balance_for_rent_exemption(0);
+    let rent = lamports(&setup.svm, &setup.tournament);
+    assert_eq!(lamports(&setup.svm, &sol_vault), reserve + 2 * ENTRY_FEE);
+
+    let organizer = setup.organizer.pubkey();
+    let champion = setup.players[0].pubkey();
+    let Setup { svm, organizer: organizer_keypair, tournament, .. } = &mut setup;
+    send_ok(svm, &[start_tournament(&organizer, tournament, vec![0, 1])], organizer_keypair, &[]);
+    send_ok(svm, &[report_match(&organizer, tournament, 0, &champion)], organizer_keypair, &[]);
+
+    let before = lamports(&setup.svm, &champion);
+    claim(&mut setup, &champion);
+    let dev = setup.dev;
+    claim(&mut setup, &dev);
+
+    assert_eq!(lamports(&setup.svm, &champion), before + 2 * ENTRY_FEE * 9_500 / 10_000);
+    assert_eq!(lamports(&setup.svm, &dev), 2 * ENTRY_FEE * 500 / 10_000);
+    // Only the reserve is left, and the tournament account keeps its rent
+    assert_eq!(lamports(&setup.svm, &sol_vault), reserve);
+    assert_eq!(lamports(&setup.svm, &setup.tournament), rent);
+}
+
+#[test]
+fn canceled_tournaments_refund_from_the_sol_vault() {
+    let mut setup = setup();
+    let sol_vault = find_sol_vault_pda(&setup.tournament).0;
+    let reserve = setup.svm.minimum_balance_for_rent_exemption(0);
+    let organizer = setup.organizer.pubkey();
+    send_ok(&mut setup.svm, &[cancel_tournament(&organizer, &setup.tournament)], &setup.organizer, &[]);
+
+    for index in 0..2 {
+        let player = setup.players[index].pubkey();
+        let before = lamports(&setup.svm, &player);
+        claim(&mut setup, &player);
+        assert_eq!(lamports(&setup.svm, &player), before + ENTRY_FEE);
+    }
+    assert_eq!(lamports(&setup.svm, &sol_vault), reserve);
+}
@@ -22,6 +22,7 @@ import {
   calculatePayouts,
   formatTokenAmount,
   parseTokenAmount,
+  deriveGamePDAs,
 } from '../sdk';
 
 describe('Wager System Tests', () => {
@@ -182,6 +183,12 @@ describe('Wager System Tests', () => {
       expect(gameAccount.player2.toString()).to.equal(player2.publicKey.toString());
       expect(gameAccount.canJoin()).to.be.false;
       expect(gameAccount.canResolve()).to.be.true;
+
+      // Both stakes sit in the SOL vault on top of its rent reserve
+      const { solVaultPda } = deriveGamePDAs(creator.publicKey, gameAccount.nonce, PublicKey.default);
+      const reserve = await connection.getMinimumBalanceForRentExemption(0);
+      const solVaultBalance = await connection.getBalance(solVaultPda);
+      expect(solVaultBalance).to.equal(reserve + WAGER_AMOUNT.muln(2).toNumber());
     });
     
     it('should resolve the game and distribute payouts', async function() {
@@ -207,9 +214,15 @@ describe('Wager System Tests', () => {
       expect(result.payouts.winnerAmount.toString()).to.equal(expectedPayouts.winnerAmount.toString());
       expect(result.payouts.feeAmount.toString()).to.equal(expectedPayouts.feeAmount.toString());
       
-      // Verify game state
+      // Verify game state and recorded entitlements
       const gameAccount = await wagerClient.getGame(gamePda);
       expect(gameAccount.state).to.equal(GameState.Paid);
+      expect(gameAccount.player2Claimable.toString()).to.equal(expectedPayouts.winnerAmount.toString());
+      expect(gameAccount.feeClaimable.toString()).to.equal(expectedPayouts.feeAmount.toString());
+      
+      // Winner and dev wallet withdraw their shares
+      await wagerClient.claim(player2, gamePda);
+      await wagerClient.claim(resolver, gamePda, devWallet.publicKey);
       
       // Verify balances changed (approximately, accounting for transaction fees)
       const winnerBalanceAfter = await connection.getBalance(player2.publicKey);